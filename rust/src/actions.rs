@@ -2,10 +2,14 @@ use crate::path_utils::normalize_path_for_display;
 #[cfg(target_os = "windows")]
 use crate::path_utils::normalize_windows_shell_path;
 use anyhow::{Context, Result};
+use std::fs;
 use std::path::Path;
 use std::process::Command;
 #[cfg(target_os = "windows")]
-use std::{ffi::OsStr, os::windows::ffi::OsStrExt, ptr};
+use std::{ffi::OsStr, os::windows::ffi::OsStrExt, os::windows::process::CommandExt, ptr};
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
@@ -190,6 +194,262 @@ pub fn open_text_file_with_default_or_editor(path: &Path) -> Result<()> {
     open_text_file_with_handlers(path, open_with_default, open_text_editor)
 }
 
+pub fn spawn_instance_at_root(root: &Path) -> Result<()> {
+    let exe = std::env::current_exe().context("failed to resolve current executable")?;
+    Command::new(exe)
+        .arg("--root")
+        .arg(root)
+        .spawn()
+        .with_context(|| {
+            format!(
+                "failed to open new window for {}",
+                normalize_action_path_for_display(root)
+            )
+        })?;
+    Ok(())
+}
+
+/// template を空白で引数に分ける。`"` か `'` で囲んだ部分は空白を含めて 1 引数の一部になる。
+/// Windows path を壊さないよう `\` は escape として扱わない。
+fn split_template_args(template: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote = None;
+    for ch in template.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote = Some(ch);
+                in_arg = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// 行番号が無いときに `{line}` 部分を取り除く。`{path}` と同じ引数なら直前の区切り文字
+/// (`{path}:{line}` の `:` など) ごと消し、`+{line}` のような行だけの引数は引数ごと落とす。
+fn omit_line_placeholder(arg: String) -> Option<String> {
+    const LINE: &str = "{line}";
+    if !arg.contains(LINE) {
+        return Some(arg);
+    }
+    if !arg.contains("{path}") {
+        return None;
+    }
+    let mut arg = arg;
+    while let Some(at) = arg.find(LINE) {
+        let start = arg[..at]
+            .char_indices()
+            .next_back()
+            .filter(|(_, ch)| matches!(ch, ':' | ',' | '+'))
+            .map_or(at, |(index, _)| index);
+        arg.replace_range(start..at + LINE.len(), "");
+    }
+    Some(arg)
+}
+
+/// editor command template を引数に分け、各引数の `{path}` / `{line}` を置換する。
+/// 置換は引数単位なので、空白を含む path も 1 引数のまま渡る。行が `None` なら `{line}` 部分を省く。
+/// 空の template は `None`。
+pub fn editor_command_args(
+    template: &str,
+    path: &Path,
+    line: Option<usize>,
+) -> Option<Vec<String>> {
+    let path = path.to_string_lossy();
+    let args = split_template_args(template)
+        .into_iter()
+        .filter_map(|arg| match line {
+            Some(line) => Some(arg.replace("{line}", &line.to_string())),
+            None => omit_line_placeholder(arg),
+        })
+        .map(|arg| arg.replace("{path}", &path))
+        .collect::<Vec<_>>();
+    (!args.is_empty()).then_some(args)
+}
+
+/// editor command template (`code -g {path}:{line}` など) で path を開く。行が分かれば指定行で開く。
+pub fn open_at_line(path: &Path, line: Option<usize>, editor_cmd_template: &str) -> Result<()> {
+    let args = editor_command_args(editor_cmd_template, path, line)
+        .ok_or_else(|| anyhow::anyhow!("editor command template is empty"))?;
+    let mut command = Command::new(&args[0]);
+    command.args(&args[1..]);
+    #[cfg(target_os = "windows")]
+    command.creation_flags(CREATE_NO_WINDOW);
+    command.spawn().with_context(|| {
+        format!(
+            "failed to open {} with {}",
+            normalize_action_path_for_display(path),
+            args[0]
+        )
+    })?;
+    Ok(())
+}
+
+fn is_cross_device_error(err: &std::io::Error) -> bool {
+    // ERROR_NOT_SAME_DEVICE on Windows, EXDEV on Unix.
+    #[cfg(target_os = "windows")]
+    {
+        err.raw_os_error() == Some(17)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        err.raw_os_error() == Some(18)
+    }
+}
+
+/// symlink は参照先を辿らず link 自体を作り直す。Windows では link の再作成に権限が要るため拒否する。
+fn copy_symlink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(fs::read_link(src)?, dst)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = dst;
+        Err(std::io::Error::other(format!(
+            "cannot move symbolic link {} across drives",
+            normalize_action_path_for_display(src)
+        )))
+    }
+}
+
+fn copy_path_recursively(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let file_type = fs::symlink_metadata(src)?.file_type();
+    if file_type.is_symlink() {
+        copy_symlink(src, dst)
+    } else if file_type.is_dir() {
+        fs::create_dir(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            copy_path_recursively(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(src, dst).map(|_| ())
+    }
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// `src` を `dst` へ移動する。既存の `dst` は上書きしない。
+/// 別 filesystem への移動で rename できないときだけ copy してから元を削除する。
+/// copy に失敗したら途中まで作った `dst` を消し、元は残す。
+pub fn move_path(src: &Path, dst: &Path) -> Result<()> {
+    if fs::symlink_metadata(dst).is_ok() {
+        anyhow::bail!("{} already exists", normalize_action_path_for_display(dst));
+    }
+    let context = || {
+        format!(
+            "failed to move {} to {}",
+            normalize_action_path_for_display(src),
+            normalize_action_path_for_display(dst)
+        )
+    };
+    match fs::rename(src, dst) {
+        Ok(()) => Ok(()),
+        Err(err) if is_cross_device_error(&err) => copy_then_remove(src, dst, context),
+        Err(err) => Err(err).with_context(context),
+    }
+}
+
+fn copy_then_remove(src: &Path, dst: &Path, context: impl Fn() -> String) -> Result<()> {
+    if let Err(err) = copy_path_recursively(src, dst) {
+        if fs::symlink_metadata(dst).is_ok() {
+            let _ = remove_path(dst);
+        }
+        return Err(err).with_context(context);
+    }
+    remove_path(src).with_context(|| {
+        format!(
+            "copied {} but failed to remove the original",
+            normalize_action_path_for_display(src)
+        )
+    })
+}
+
+fn trash_command(path: &Path) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        // The path goes through the environment so PowerShell never parses it as script text.
+        let mut command = Command::new("powershell");
+        command
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "Add-Type -AssemblyName Microsoft.VisualBasic; \
+                 $p = $env:FLISTWALKER_TRASH_PATH; \
+                 if (Test-Path -LiteralPath $p -PathType Container) { \
+                 [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteDirectory($p, 'OnlyErrorDialogs', 'SendToRecycleBin') \
+                 } else { \
+                 [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile($p, 'OnlyErrorDialogs', 'SendToRecycleBin') }",
+            ])
+            .env("FLISTWALKER_TRASH_PATH", normalize_windows_shell_path(path))
+            .creation_flags(CREATE_NO_WINDOW);
+        command
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let mut command = Command::new("osascript");
+        command
+            .args([
+                "-e",
+                "on run argv",
+                "-e",
+                "tell application \"Finder\" to delete POSIX file (item 1 of argv)",
+                "-e",
+                "end run",
+            ])
+            .arg(path);
+        command
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        let mut command = Command::new("gio");
+        command.args(["trash", "--"]).arg(path);
+        command
+    }
+}
+
+/// path を OS の trash (Windows はごみ箱) へ移す。完全削除はしない。
+pub fn trash_path(path: &Path) -> Result<()> {
+    let context = || {
+        format!(
+            "failed to move {} to trash",
+            normalize_action_path_for_display(path)
+        )
+    };
+    let output = trash_command(path).output().with_context(context)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("{}", stderr.trim())).with_context(context);
+    }
+    Ok(())
+}
+
 pub fn open_with_default(path: &Path) -> Result<()> {
     #[cfg(target_os = "windows")]
     {
@@ -225,6 +485,130 @@ mod tests {
     #[cfg(target_os = "windows")]
     use std::path::PathBuf;
 
+    #[test]
+    fn editor_command_args_substitute_placeholders_per_argument() {
+        let path = Path::new("/tmp/my project/main.rs");
+
+        assert_eq!(
+            editor_command_args("code -g {path}:{line}", path, Some(42)),
+            Some(vec![
+                "code".to_string(),
+                "-g".to_string(),
+                "/tmp/my project/main.rs:42".to_string(),
+            ])
+        );
+        assert_eq!(
+            editor_command_args("vim +{line} {path}", path, Some(7)),
+            Some(vec![
+                "vim".to_string(),
+                "+7".to_string(),
+                "/tmp/my project/main.rs".to_string(),
+            ])
+        );
+        assert_eq!(editor_command_args("   ", path, Some(1)), None);
+    }
+
+    #[test]
+    fn editor_command_args_leave_out_line_part_when_line_is_unknown() {
+        let path = Path::new("/tmp/my project/main.rs");
+
+        assert_eq!(
+            editor_command_args("code -g {path}:{line}", path, None),
+            Some(vec![
+                "code".to_string(),
+                "-g".to_string(),
+                "/tmp/my project/main.rs".to_string(),
+            ])
+        );
+        assert_eq!(
+            editor_command_args("vim +{line} {path}", path, None),
+            Some(vec![
+                "vim".to_string(),
+                "/tmp/my project/main.rs".to_string()
+            ])
+        );
+        assert_eq!(
+            editor_command_args("subl {path}", path, None),
+            Some(vec![
+                "subl".to_string(),
+                "/tmp/my project/main.rs".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn editor_command_args_keep_quoted_program_path_as_one_argument() {
+        let path = Path::new(r"C:\src\main.rs");
+
+        assert_eq!(
+            editor_command_args(
+                r#""C:\Program Files\Microsoft VS Code\Code.exe" --goto {path}:{line}"#,
+                path,
+                Some(3)
+            ),
+            Some(vec![
+                r"C:\Program Files\Microsoft VS Code\Code.exe".to_string(),
+                "--goto".to_string(),
+                r"C:\src\main.rs:3".to_string(),
+            ])
+        );
+        assert_eq!(
+            editor_command_args("'my editor' --title 'a b'{line} \"\"", path, Some(9)),
+            Some(vec![
+                "my editor".to_string(),
+                "--title".to_string(),
+                "a b9".to_string(),
+                String::new(),
+            ])
+        );
+    }
+
+    #[test]
+    fn move_path_renames_and_refuses_to_overwrite_existing_target() {
+        let root = std::env::temp_dir().join("fff-rs-actions-move");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("dest")).expect("create dest");
+        let src = root.join("note.txt");
+        fs::write(&src, "x").expect("write src");
+        fs::write(root.join("dest").join("taken.txt"), "y").expect("write taken");
+
+        move_path(&src, &root.join("dest").join("note.txt")).expect("move file");
+        assert!(!src.exists());
+        assert!(root.join("dest").join("note.txt").exists());
+
+        let err = move_path(
+            &root.join("dest").join("note.txt"),
+            &root.join("dest").join("taken.txt"),
+        )
+        .expect_err("collision");
+        assert!(err.to_string().contains("already exists"));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_then_remove_keeps_symlinks_as_links() {
+        let root = std::env::temp_dir().join("fff-rs-actions-copy-link");
+        let _ = fs::remove_dir_all(&root);
+        let src = root.join("src");
+        fs::create_dir_all(&src).expect("create src");
+        fs::write(root.join("outside.txt"), "x").expect("write outside");
+        std::os::unix::fs::symlink(root.join("outside.txt"), src.join("link.txt"))
+            .expect("create link");
+
+        copy_then_remove(&src, &root.join("dst"), String::new).expect("copy then remove");
+
+        let copied = root.join("dst").join("link.txt");
+        assert!(fs::symlink_metadata(&copied)
+            .expect("copied link")
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_link(&copied).ok(), Some(root.join("outside.txt")));
+        assert!(!src.exists());
+        assert!(root.join("outside.txt").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn directory_is_open_action() {
         let dir = std::env::temp_dir();
@@ -15,13 +15,13 @@ pub(crate) enum ActionPathPrecheck {
 pub(crate) struct AuthorizedActionTarget {
     pub(crate) display_path: PathBuf,
     pub(crate) execution_path: PathBuf,
-    source_paths: Vec<PathBuf>,
+    /// 選択された path と、その path を含む root の実体。
+    sources: Vec<(PathBuf, PathBuf)>,
     open_parent_for_files: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct AuthorizedActionBatch {
-    pub(crate) canonical_root: PathBuf,
     pub(crate) targets: Vec<AuthorizedActionTarget>,
 }
 
@@ -179,48 +179,95 @@ fn resolve_within_root(
     Ok(resolved)
 }
 
+/// path ごとの root を実体へ解決する。同じ root は 1 度だけ解決し、解決できない root は `None` にする。
+pub(crate) fn canonicalize_action_roots(roots: &[PathBuf]) -> Vec<Option<PathBuf>> {
+    let mut resolved: HashMap<&Path, Option<PathBuf>> = HashMap::new();
+    roots
+        .iter()
+        .map(|root| {
+            resolved
+                .entry(root.as_path())
+                .or_insert_with(|| root.canonicalize().ok())
+                .clone()
+        })
+        .collect()
+}
+
+/// 各 path を、`roots` の同じ位置にあるその path 自身の root 内か確かめて起動対象にまとめる。
 pub(crate) fn authorize_action_targets(
-    root: &Path,
+    roots: &[PathBuf],
     paths: &[PathBuf],
     open_parent_for_files: bool,
 ) -> Result<AuthorizedActionBatch, ActionAuthorizationFailure> {
-    let canonical_root = root.canonicalize().map_err(|_| {
-        ActionAuthorizationFailure::new(
+    let root_unresolved = |path: &PathBuf| {
+        ActionAuthorizationFailure::new(Some(path.clone()), "current root could not be resolved")
+    };
+    if roots.len() != paths.len() {
+        return Err(ActionAuthorizationFailure::new(
             paths.first().cloned(),
             "current root could not be resolved",
-        )
-    })?;
+        ));
+    }
     let mut target_indices: HashMap<PathBuf, usize> = HashMap::with_capacity(paths.len());
     let mut targets: Vec<AuthorizedActionTarget> = Vec::with_capacity(paths.len());
 
-    for source_path in paths {
+    for (source_path, canonical_root) in paths.iter().zip(canonicalize_action_roots(roots)) {
+        let canonical_root = canonical_root.ok_or_else(|| root_unresolved(source_path))?;
         let raw_path = raw_action_target(source_path, open_parent_for_files)?;
         let execution_path = resolve_within_root(&canonical_root, &raw_path)?;
         if let Some(index) = target_indices.get(&execution_path).copied() {
-            targets[index].source_paths.push(source_path.clone());
+            targets[index]
+                .sources
+                .push((source_path.clone(), canonical_root));
             continue;
         }
         target_indices.insert(execution_path.clone(), targets.len());
         targets.push(AuthorizedActionTarget {
             display_path: raw_path,
             execution_path,
-            source_paths: vec![source_path.clone()],
+            sources: vec![(source_path.clone(), canonical_root)],
             open_parent_for_files,
         });
     }
 
-    Ok(AuthorizedActionBatch {
-        canonical_root,
-        targets,
-    })
+    Ok(AuthorizedActionBatch { targets })
 }
 
-pub(crate) fn reauthorize_action_target(
+/// 移動などの file 操作元を root 内か確かめる。symlink は辿らず、親 folder の実体で判定する。
+pub(crate) fn authorize_file_operation_source(
     canonical_root: &Path,
+    path: &Path,
+) -> Result<(), ActionAuthorizationFailure> {
+    fs::symlink_metadata(path).map_err(|_| {
+        ActionAuthorizationFailure::new(Some(path.to_path_buf()), "selected path is unavailable")
+    })?;
+    authorize_file_operation_parent(canonical_root, path)
+}
+
+/// file 操作で path を置く親 folder の実体が root 内か確かめる。path 自体は存在しなくてよい。
+pub(crate) fn authorize_file_operation_parent(
+    canonical_root: &Path,
+    path: &Path,
+) -> Result<(), ActionAuthorizationFailure> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .ok_or_else(|| {
+            ActionAuthorizationFailure::new(
+                Some(path.to_path_buf()),
+                "containing folder could not be determined",
+            )
+        })?;
+    resolve_within_root(canonical_root, parent)
+        .map(|_| ())
+        .map_err(|err| ActionAuthorizationFailure::new(Some(path.to_path_buf()), err.message))
+}
+
+pub(crate) fn reauthorize_action_target(
     target: &AuthorizedActionTarget,
 ) -> Result<PathBuf, ActionAuthorizationFailure> {
     let mut final_execution_path = None;
-    for source_path in &target.source_paths {
+    for (source_path, canonical_root) in &target.sources {
         let raw_path = raw_action_target(source_path, target.open_parent_for_files)?;
         let execution_path = resolve_within_root(canonical_root, &raw_path)?;
         if execution_path != target.execution_path {
@@ -32,6 +32,8 @@ pub(super) struct AdaptiveWalkerMetrics {
     pub(super) adaptive_limit_avg: f64,
     pub(super) read_dir_total_us: u128,
     pub(super) read_dir_max_us: u128,
+    /// 1 folder あたりの上限に達して読み切らなかった folder 数。
+    pub(super) capped_dirs: usize,
 }
 
 struct SharedState {
@@ -45,6 +47,7 @@ struct Shared {
     stop: AtomicBool,
     limit: AtomicUsize,
     max_workers: usize,
+    max_entries_per_dir: Option<usize>,
     sample_size: usize,
     control: Mutex<LimitControlState>,
     metrics: AdaptiveAtomicMetrics,
@@ -89,6 +92,7 @@ struct AdaptiveAtomicMetrics {
     limit_sample_count: AtomicUsize,
     read_dir_total_us: AtomicU64,
     read_dir_max_us: AtomicU64,
+    capped_dirs: AtomicUsize,
 }
 
 impl AdaptiveAtomicMetrics {
@@ -135,6 +139,7 @@ impl AdaptiveAtomicMetrics {
             adaptive_limit_avg: control_snapshot.average_limit,
             read_dir_total_us: self.read_dir_total_us.load(Ordering::Relaxed) as u128,
             read_dir_max_us: self.read_dir_max_us.load(Ordering::Relaxed) as u128,
+            capped_dirs: self.capped_dirs.load(Ordering::Relaxed),
         }
     }
 }
@@ -335,6 +340,7 @@ fn worker_loop(shared: Arc<Shared>, tx: SyncSender<AdaptiveWalkerEntry>) {
 
         let started = Instant::now();
         let mut child_dirs = Vec::new();
+        let mut emitted = 0usize;
         match fs::read_dir(&dir) {
             Ok(read_dir) => {
                 for child in read_dir {
@@ -359,6 +365,11 @@ fn worker_loop(shared: Arc<Shared>, tx: SyncSender<AdaptiveWalkerEntry>) {
                     if policy.skip {
                         continue;
                     }
+                    if directory_cap_reached(shared.max_entries_per_dir, emitted) {
+                        shared.metrics.capped_dirs.fetch_add(1, Ordering::Relaxed);
+                        break;
+                    }
+                    emitted = emitted.saturating_add(1);
                     let path = child.path();
                     if file_type.is_dir() && policy.recurse {
                         child_dirs.push(path.clone());
@@ -392,6 +403,12 @@ fn worker_loop(shared: Arc<Shared>, tx: SyncSender<AdaptiveWalkerEntry>) {
     }
 }
 
+/// 1 folder から渡した entry 数が上限に達したかを返す。
+/// 達した folder は残りを読まないため、分類・canonicalize も配下の走査もしない。
+fn directory_cap_reached(max_entries_per_dir: Option<usize>, emitted: usize) -> bool {
+    max_entries_per_dir.is_some_and(|limit| emitted >= limit)
+}
+
 struct AdaptiveEntryPolicy {
     skip: bool,
     recurse: bool,
@@ -441,16 +458,18 @@ fn adaptive_entry_policy_from_attrs(windows_attrs: Option<u32>) -> AdaptiveEntry
     }
 }
 
+/// `max_entries_per_dir` を指定すると、各 folder からその件数だけ読んで残りは読まずに次へ進む。
 pub(super) fn walk_adaptive(
     root: &Path,
     max_workers: usize,
     initial_limit: usize,
+    max_entries_per_dir: Option<usize>,
     mut on_entry: impl FnMut(AdaptiveWalkerEntry) -> bool,
     should_stop: impl Fn() -> bool,
 ) -> AdaptiveWalkerMetrics {
     let max_workers = max_workers.max(1);
     if max_workers == 1 {
-        return walk_adaptive_serial(root, on_entry, should_stop);
+        return walk_adaptive_serial(root, max_entries_per_dir, on_entry, should_stop);
     }
     let initial_limit = initial_limit.clamp(1, max_workers);
     let shared = Arc::new(Shared {
@@ -462,6 +481,7 @@ pub(super) fn walk_adaptive(
         stop: AtomicBool::new(false),
         limit: AtomicUsize::new(initial_limit),
         max_workers,
+        max_entries_per_dir,
         sample_size: CONTROL_SAMPLE_SIZE,
         control: Mutex::new(LimitControlState {
             window_started_at: Instant::now(),
@@ -522,6 +542,7 @@ pub(super) fn walk_adaptive(
 
 fn walk_adaptive_serial(
     root: &Path,
+    max_entries_per_dir: Option<usize>,
     mut on_entry: impl FnMut(AdaptiveWalkerEntry) -> bool,
     should_stop: impl Fn() -> bool,
 ) -> AdaptiveWalkerMetrics {
@@ -542,6 +563,7 @@ fn walk_adaptive_serial(
         metrics.max_inflight_read_dirs = metrics.max_inflight_read_dirs.max(1);
         let started = Instant::now();
         let mut stop = false;
+        let mut emitted = 0usize;
         match fs::read_dir(&dir) {
             Ok(read_dir) => {
                 for child in read_dir {
@@ -561,6 +583,11 @@ fn walk_adaptive_serial(
                     if policy.skip {
                         continue;
                     }
+                    if directory_cap_reached(max_entries_per_dir, emitted) {
+                        metrics.capped_dirs = metrics.capped_dirs.saturating_add(1);
+                        break;
+                    }
+                    emitted = emitted.saturating_add(1);
                     let path = child.path();
                     if file_type.is_dir() && policy.recurse {
                         queue.push_back(path.clone());
@@ -644,6 +671,7 @@ mod tests {
             &root,
             1,
             1,
+            None,
             |entry| {
                 paths.push(entry.path);
                 true
@@ -2,23 +2,26 @@ use super::{
     spawn_action_worker, spawn_filelist_worker, spawn_index_worker, spawn_kind_resolver_worker,
     spawn_preview_worker, spawn_search_worker, spawn_sort_metadata_worker, spawn_update_worker,
     ActionWorkerBus, AppRuntimeState, AppShellState, CacheStateBundle, EntryKindCacheState,
-    FeatureStateBundle, FileListManager, FileListWorkerBus, FlistWalkerApp, HashSet,
-    HighlightCacheState, IgnoreMatcherCacheState, IndexBuildResult, IndexCoordinator, IndexRequest,
-    IndexResponse, IndexSource, KindWorkerBus, LaunchSettings, PreviewCacheState, PreviewWorkerBus,
-    QueryState, Receiver, ResultSortMode, ResultSortScope, RootBrowserState, RuntimeUiState,
+    EntrySizeCacheState, FeatureStateBundle, FileListManager, FileListWorkerBus, FlistWalkerApp,
+    FolderActivation, HashSet, HighlightCacheState, IgnoreMatcherCacheState, IndexBuildResult,
+    IndexCoordinator, IndexRequest, IndexResponse, IndexSource, KindWorkerBus, LaunchSettings,
+    MarkdownPreviewCacheState, PreviewCacheState, PreviewWorkerBus, QueryState, Receiver,
+    ResultKindGrouping, ResultSortMode, ResultSortScope, RootBrowserState, RuntimeUiState,
     SavedTabState, SearchCoordinator, SearchRequest, SearchResponse, Sender,
-    SortMetadataCacheState, SortWorkerBus, TabSessionState, UpdateWorkerBus, WorkerBus,
-    WorkerRuntime,
+    SortMetadataCacheState, SortWorkerBus, SourceFilterMemory, TabSessionState, UpdateWorkerBus,
+    WorkerBus, WorkerRuntime,
 };
 use crate::app::state::{UpdateManager, UpdateState};
 use crate::app::worker_channel::BoundedSender;
 use crate::ignore_list::load_ignore_terms_from_current_exe;
 use crate::path_utils::normalize_windows_path_buf;
-use crate::runtime_config::current_runtime_config;
+use crate::runtime_config::{current_runtime_config, settings_base_dir};
 use std::collections::{HashMap, VecDeque};
+use std::ffi::OsString;
 use std::path::PathBuf;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 type WorkerBootstrapParts = (
     Sender<SearchRequest>,
@@ -40,7 +43,9 @@ type LaunchSeedParts = (
     Option<PathBuf>,
     bool,
     bool,
+    bool,
     f32,
+    SourceFilterMemory,
     Arc<Vec<String>>,
     UpdateState,
 );
@@ -65,7 +70,9 @@ pub(super) struct AppLaunchSeed {
     default_root: Option<PathBuf>,
     show_preview: bool,
     ignore_list_enabled: bool,
+    render_markdown_preview: bool,
     preview_panel_width: f32,
+    source_filter_memory: SourceFilterMemory,
     ignore_list_terms: Arc<Vec<String>>,
     update_state: UpdateState,
 }
@@ -96,7 +103,9 @@ impl AppLaunchSeed {
             self.default_root,
             self.show_preview,
             self.ignore_list_enabled,
+            self.render_markdown_preview,
             self.preview_panel_width,
+            self.source_filter_memory,
             self.ignore_list_terms,
             self.update_state,
         )
@@ -108,18 +117,65 @@ impl FlistWalkerApp {
         let launch = LaunchSettings {
             show_preview: true,
             ignore_list_enabled: true,
+            render_markdown_preview: true,
             preview_panel_width: Self::DEFAULT_PREVIEW_PANEL_WIDTH,
             ..LaunchSettings::default()
         };
         Self::new_with_launch(root, limit, query, launch, None)
     }
 
+    /// 明示 query が無く、tab 復元もしない起動で `restore_last_query` が有効なら保存済みの最後の query を使う。
+    /// 復元する tab はそれぞれの query を持つため、その場合は上書きしない。
+    pub(super) fn startup_query(
+        query: String,
+        last_query: Option<&str>,
+        restoring_tabs: bool,
+        restore_last_query: bool,
+    ) -> String {
+        match last_query {
+            Some(last_query)
+                if restore_last_query && !restoring_tabs && query.trim().is_empty() =>
+            {
+                last_query.to_string()
+            }
+            _ => query,
+        }
+    }
+
+    /// `FLISTWALKER_ROOT` の値を起動 root として解決する。未設定/空は `Ok(None)`。
+    pub(super) fn startup_root_from_env_value(
+        value: Option<OsString>,
+    ) -> Result<Option<PathBuf>, String> {
+        let Some(value) = value.filter(|value| !value.is_empty()) else {
+            return Ok(None);
+        };
+        let path = PathBuf::from(&value);
+        match path.canonicalize() {
+            Ok(root) if root.is_dir() => Ok(Some(normalize_windows_path_buf(root))),
+            _ => Err(format!(
+                "Ignored {}: not a directory: {}",
+                Self::STARTUP_ROOT_ENV,
+                path.display()
+            )),
+        }
+    }
+
     pub(super) fn build_from_launch(
         root: PathBuf,
         limit: usize,
         query: String,
         root_explicit: bool,
     ) -> Self {
+        // FLISTWALKER_ROOT pins the start location like `--root`, but an explicit flag still wins.
+        let (root, root_explicit, env_root_notice) = if root_explicit {
+            (root, true, None)
+        } else {
+            match Self::startup_root_from_env_value(std::env::var_os(Self::STARTUP_ROOT_ENV)) {
+                Ok(Some(env_root)) => (env_root, true, None),
+                Ok(None) => (root, false, None),
+                Err(notice) => (root, false, Some(notice)),
+            }
+        };
         let launch = Self::load_launch_settings();
         let restore_tabs_enabled = Self::restore_tabs_enabled();
         let saved_last_root = launch.last_root.clone().map(normalize_windows_path_buf);
@@ -129,6 +185,12 @@ impl FlistWalkerApp {
         } else {
             None
         };
+        let query = Self::startup_query(
+            query,
+            launch.last_query.as_deref(),
+            restore_session.is_some(),
+            current_runtime_config().restore_last_query,
+        );
         let chosen_root = Self::choose_startup_root(
             root,
             root_explicit,
@@ -138,6 +200,11 @@ impl FlistWalkerApp {
             saved_default,
         );
         let mut app = Self::new_with_launch(chosen_root, limit, query, launch, restore_session);
+        if let Some(notice) = env_root_notice {
+            app.set_notice(notice);
+        } else if settings_base_dir().is_none() {
+            app.set_notice(Self::NO_SETTINGS_DIR_NOTICE);
+        }
         app.request_startup_update_check();
         app
     }
@@ -148,8 +215,11 @@ impl FlistWalkerApp {
         let (search_tx, search_rx, search_handle) =
             spawn_search_worker(Arc::clone(&worker_shutdown));
         worker_runtime.push("search", search_handle);
-        let (preview_tx, preview_rx, preview_handle) =
-            spawn_preview_worker(Arc::clone(&worker_shutdown));
+        let latest_preview_request_id = Arc::new(AtomicU64::new(0));
+        let (preview_tx, preview_rx, preview_handle) = spawn_preview_worker(
+            Arc::clone(&worker_shutdown),
+            Arc::clone(&latest_preview_request_id),
+        );
         worker_runtime.push("preview", preview_handle);
         let (action_tx, action_rx, action_handles) =
             spawn_action_worker(Arc::clone(&worker_shutdown));
@@ -159,6 +229,9 @@ impl FlistWalkerApp {
         let (sort_tx, sort_rx, sort_handle) =
             spawn_sort_metadata_worker(Arc::clone(&worker_shutdown));
         worker_runtime.push("sort-metadata", sort_handle);
+        let (size_tx, size_rx, size_handle) =
+            spawn_sort_metadata_worker(Arc::clone(&worker_shutdown));
+        worker_runtime.push("size-metadata", size_handle);
         let latest_kind_epochs = Arc::new(Mutex::new(HashMap::new()));
         let (kind_tx, kind_rx, kind_handle) = spawn_kind_resolver_worker(
             Arc::clone(&worker_shutdown),
@@ -190,6 +263,7 @@ impl FlistWalkerApp {
                     next_request_id: 1,
                     pending_request_id: None,
                     in_progress: false,
+                    latest_request_id: latest_preview_request_id,
                 },
                 action: ActionWorkerBus {
                     tx: action_tx,
@@ -205,6 +279,13 @@ impl FlistWalkerApp {
                     pending_request_id: None,
                     in_progress: false,
                 },
+                size: SortWorkerBus {
+                    tx: size_tx,
+                    rx: size_rx,
+                    next_request_id: 1,
+                    pending_request_id: None,
+                    in_progress: false,
+                },
                 kind: KindWorkerBus {
                     tx: kind_tx,
                     rx: kind_rx,
@@ -241,9 +322,11 @@ impl FlistWalkerApp {
             default_root: launch.default_root.clone(),
             show_preview: launch.show_preview,
             ignore_list_enabled: launch.ignore_list_enabled,
+            render_markdown_preview: launch.render_markdown_preview,
             preview_panel_width: launch
                 .preview_panel_width
                 .max(Self::MIN_PREVIEW_PANEL_WIDTH),
+            source_filter_memory: launch.source_filters,
             ignore_list_terms: Arc::new(load_ignore_terms_from_current_exe()),
             update_state: UpdateState {
                 skipped_target_version: launch.skipped_update_target_version.clone(),
@@ -279,13 +362,50 @@ impl FlistWalkerApp {
             default_root,
             show_preview,
             ignore_list_enabled,
+            render_markdown_preview,
             preview_panel_width,
+            source_filter_memory,
             ignore_list_terms,
             update_state,
         ) = Self::launch_seed(root, limit, query, &launch).into_parts();
         let runtime_config = current_runtime_config();
         let emacs_keybindings_enabled = runtime_config.emacs_keybindings_enabled;
         let tab_pin_moves_to_next_row = runtime_config.tab_pin_moves_to_next_row;
+        let action_confirm_threshold = runtime_config.action_confirm_threshold;
+        let folder_activation =
+            FolderActivation::from_config_value(&runtime_config.shift_enter_folder_action);
+        let result_kind_grouping =
+            ResultKindGrouping::from_config_value(&runtime_config.result_kind_grouping);
+        let remember_filters_per_source = runtime_config.remember_filters_per_source;
+        let walker_throttle = (runtime_config.walker_throttle_ms > 0)
+            .then(|| Duration::from_millis(runtime_config.walker_throttle_ms));
+        let empty_query_recent_first = runtime_config.empty_query_recent_first;
+        let sticky_results_scroll = runtime_config.sticky_results_scroll;
+        let preview_max_file_bytes = (runtime_config.preview_max_file_mb > 0).then(|| {
+            runtime_config
+                .preview_max_file_mb
+                .saturating_mul(1024 * 1024)
+        });
+        let preview_dir_depth = runtime_config.preview_dir_depth.clamp(1, 2);
+        let preview_skip_binary = runtime_config.preview_skip_binary;
+        let preview_metadata_details = runtime_config.preview_metadata_details;
+        let filelist_header_comment = runtime_config.filelist_header_comment;
+        let max_results_per_directory = runtime_config.max_results_per_directory;
+        let dir_trailing_slash = runtime_config.dir_trailing_slash;
+        let fold_diacritics = runtime_config.fold_diacritics;
+        let prefix_match = runtime_config.prefix_match;
+        let match_tail_components = (runtime_config.match_tail_components > 0)
+            .then_some(runtime_config.match_tail_components);
+        let eager_partial_search = runtime_config.eager_partial_search;
+        let match_full_path = runtime_config.match_full_path;
+        let editor_command_template = runtime_config.editor_command_template.trim().to_string();
+        let min_query_length = runtime_config.min_query_length.clamp(1, 8);
+        let wrap_navigation = runtime_config.wrap_navigation;
+        let extension_filter_keeps_dirs = runtime_config.extension_filter_keeps_dirs;
+        let walker_canonicalize_entries = runtime_config.walker_canonicalize_entries;
+        let query_path_prefix_filter = runtime_config.query_path_prefix_filter;
+        let result_type_icons = runtime_config.result_type_icons;
+        let rank_by_filename = runtime_config.rank_by_filename;
         let mut app = Self {
             shell: AppShellState {
                 runtime: AppRuntimeState {
@@ -298,6 +418,10 @@ impl FlistWalkerApp {
                     ignore_list_terms,
                     include_files: true,
                     include_dirs: true,
+                    size_filter_min_bytes: None,
+                    size_filter_max_bytes: None,
+                    hide_root_dotfiles: false,
+                    hide_nested_dotfiles: false,
                     index: IndexBuildResult {
                         entries: Vec::new(),
                         source: IndexSource::None,
@@ -313,8 +437,49 @@ impl FlistWalkerApp {
                     current_row: Some(0),
                     emacs_keybindings_enabled,
                     tab_pin_moves_to_next_row,
+                    action_confirm_threshold,
+                    folder_activation,
+                    result_kind_grouping,
+                    max_results_per_directory,
+                    dir_trailing_slash,
+                    remember_filters_per_source,
+                    walker_throttle,
+                    empty_query_recent_first,
+                    sticky_results_scroll,
+                    preview_max_file_bytes,
+                    preview_dir_depth,
+                    preview_skip_binary,
+                    preview_metadata_details,
+                    filelist_header_comment,
+                    query_path_prefix_filter,
+                    result_type_icons,
+                    rank_by_filename,
+                    fold_diacritics,
+                    prefix_match,
+                    match_tail_components,
+                    match_any_term: false,
+                    eager_partial_search,
+                    match_full_path,
+                    editor_command_template,
+                    min_query_length,
+                    wrap_navigation,
+                    extension_filter_keeps_dirs,
+                    walker_canonicalize_entries,
+                    search_all_saved_roots: false,
+                    display_base: None,
+                    hide_pinned_results: false,
+                    recent_view: false,
+                    last_action: None,
+                    last_operation: None,
+                    pending_undo: None,
+                    basket: Vec::new(),
+                    basket_enter_mode: false,
+                    query_error: None,
+                    git_tracked_only: false,
                     preview: String::new(),
+                    preview_path: None,
                     notice: String::new(),
+                    log_messages: VecDeque::new(),
                     status_line: "Initializing...".to_string(),
                 },
                 search: SearchCoordinator::new(search_tx, search_rx),
@@ -325,13 +490,21 @@ impl FlistWalkerApp {
                     latest_index_request_ids,
                     latest_kind_epochs,
                 ),
-                ui: RuntimeUiState::new(show_preview, ignore_list_enabled, preview_panel_width),
+                ui: RuntimeUiState::new(
+                    show_preview,
+                    ignore_list_enabled,
+                    render_markdown_preview,
+                    preview_panel_width,
+                    source_filter_memory,
+                ),
                 cache: CacheStateBundle {
                     preview: PreviewCacheState::default(),
+                    markdown_preview: MarkdownPreviewCacheState::default(),
                     highlight: HighlightCacheState::with_scope_ignore_case(true),
                     ignore_matcher: IgnoreMatcherCacheState::default(),
                     entry_kind: EntryKindCacheState::default(),
                     sort_metadata: SortMetadataCacheState::default(),
+                    entry_size: EntrySizeCacheState::default(),
                 },
                 tabs: TabSessionState::default(),
                 features: FeatureStateBundle {
@@ -350,6 +523,7 @@ impl FlistWalkerApp {
                 worker_runtime: Some(worker_runtime),
             },
         };
+        app.shell.ui.smart_filters = Self::load_smart_filters();
         if let Some(path) = Self::window_trace_path() {
             Self::append_window_trace("app_initialized", &format!("path={}", path.display()));
         }
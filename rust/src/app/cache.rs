@@ -1,6 +1,7 @@
 use super::{EntryKind, HighlightCacheKey, SortMetadata};
 use crate::path_utils::path_key;
 use crate::query::{CompiledIgnoreTerms, CompiledQuery, QueryOptions};
+use crate::ui_model::{parse_markdown_preview, MarkdownPreviewLine};
 use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -12,18 +13,38 @@ pub(super) struct PreviewCacheState {
     total_bytes: usize,
 }
 
+/// 描画中の markdown preview の解析結果。preview 元の path と本文が変わるまで再解析しない。
+#[derive(Default)]
+pub(super) struct MarkdownPreviewCacheState {
+    path: PathBuf,
+    source: String,
+    lines: Arc<Vec<MarkdownPreviewLine>>,
+}
+
+/// highlight cache が有効な照合条件。query と root 以外はこの値同士で比較する。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct HighlightScope {
+    pub(super) query: QueryOptions,
+    pub(super) prefer_relative: bool,
+}
+
 #[derive(Default)]
 pub(super) struct HighlightCacheState {
     scope_query: String,
     scope_root: PathBuf,
-    scope_use_regex: bool,
-    scope_ignore_case: bool,
-    scope_prefer_relative: bool,
+    scope: HighlightScope,
     compiled: Option<Arc<CompiledQuery>>,
-    entries: HashMap<HighlightCacheKey, Arc<Vec<u16>>>,
+    entries: HashMap<HighlightCacheKey, Arc<HighlightedDisplay>>,
     order: VecDeque<HighlightCacheKey>,
 }
 
+/// 結果行 1 件分の表示文字列と highlight 位置。
+#[derive(Debug)]
+pub(super) struct HighlightedDisplay {
+    pub(super) display: String,
+    pub(super) positions: Vec<u16>,
+}
+
 #[derive(Default)]
 pub(super) struct IgnoreMatcherCacheState {
     scope_terms: Vec<String>,
@@ -37,12 +58,50 @@ pub(super) struct SortMetadataCacheState {
     order: VecDeque<PathBuf>,
 }
 
+/// Size filter 用に worker が調べた entry の大きさ。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum EntrySize {
+    File(u64),
+    /// folder など大きさを持たない entry。範囲に関係なく表示する。
+    NotFile,
+    /// metadata を読めなかった entry。範囲に入らないものとして除外する。
+    Unreadable,
+}
+
+impl From<SortMetadata> for EntrySize {
+    fn from(metadata: SortMetadata) -> Self {
+        match metadata.size_bytes {
+            Some(size) => Self::File(size),
+            // The worker leaves every field empty only when metadata itself failed.
+            None if metadata.modified.is_some() || metadata.created.is_some() => Self::NotFile,
+            None => Self::Unreadable,
+        }
+    }
+}
+
+/// Size filter が参照する entry size の cache。index 全体を対象にするため上限は設けない。
+#[derive(Debug, Default)]
+pub(super) struct EntrySizeCacheState {
+    entries: HashMap<PathBuf, EntrySize>,
+}
+
 #[derive(Debug, Default)]
 #[cfg_attr(test, derive(Clone))]
 pub(super) struct EntryKindCacheState {
     pub(super) entries: HashMap<PathBuf, EntryKind>,
 }
 
+impl MarkdownPreviewCacheState {
+    pub(super) fn lines(&mut self, path: &Path, preview: &str) -> Arc<Vec<MarkdownPreviewLine>> {
+        if self.path != path || self.source != preview {
+            self.path = path.to_path_buf();
+            self.source = preview.to_string();
+            self.lines = Arc::new(parse_markdown_preview(preview));
+        }
+        Arc::clone(&self.lines)
+    }
+}
+
 impl PreviewCacheState {
     pub(super) fn clear(&mut self) {
         self.entries.clear();
@@ -97,9 +156,15 @@ impl PreviewCacheState {
 }
 
 impl HighlightCacheState {
-    pub(super) fn with_scope_ignore_case(scope_ignore_case: bool) -> Self {
+    pub(super) fn with_scope_ignore_case(ignore_case: bool) -> Self {
         Self {
-            scope_ignore_case,
+            scope: HighlightScope {
+                query: QueryOptions {
+                    ignore_case,
+                    ..QueryOptions::default()
+                },
+                prefer_relative: false,
+            },
             ..Self::default()
         }
     }
@@ -109,43 +174,19 @@ impl HighlightCacheState {
         self.order.clear();
     }
 
-    pub(super) fn matches_scope(
-        &self,
-        query: &str,
-        root: &Path,
-        use_regex: bool,
-        ignore_case: bool,
-        prefer_relative: bool,
-    ) -> bool {
+    pub(super) fn matches_scope(&self, query: &str, root: &Path, scope: HighlightScope) -> bool {
         self.scope_query == query
             && path_key(&self.scope_root) == path_key(root)
-            && self.scope_use_regex == use_regex
-            && self.scope_ignore_case == ignore_case
-            && self.scope_prefer_relative == prefer_relative
+            && self.scope == scope
     }
 
-    pub(super) fn reset_scope(
-        &mut self,
-        query: String,
-        root: PathBuf,
-        use_regex: bool,
-        ignore_case: bool,
-        prefer_relative: bool,
-    ) {
-        let compiled = CompiledQuery::compile(
-            &query,
-            QueryOptions {
-                use_regex,
-                ignore_case,
-            },
-        )
-        .ok()
-        .map(Arc::new);
+    pub(super) fn reset_scope(&mut self, query: String, root: PathBuf, scope: HighlightScope) {
+        let compiled = CompiledQuery::compile(&query, scope.query)
+            .ok()
+            .map(Arc::new);
         self.scope_query = query;
         self.scope_root = root;
-        self.scope_use_regex = use_regex;
-        self.scope_ignore_case = ignore_case;
-        self.scope_prefer_relative = prefer_relative;
+        self.scope = scope;
         self.compiled = compiled;
         self.clear();
     }
@@ -154,20 +195,20 @@ impl HighlightCacheState {
         self.compiled.as_ref().map(Arc::clone)
     }
 
-    pub(super) fn get(&self, key: &HighlightCacheKey) -> Option<Arc<Vec<u16>>> {
+    pub(super) fn get(&self, key: &HighlightCacheKey) -> Option<Arc<HighlightedDisplay>> {
         self.entries.get(key).cloned()
     }
 
     pub(super) fn insert_bounded(
         &mut self,
         key: HighlightCacheKey,
-        positions: Vec<u16>,
+        highlighted: Arc<HighlightedDisplay>,
         max_entries: usize,
     ) {
         if !self.entries.contains_key(&key) {
             self.order.push_back(key.clone());
         }
-        self.entries.insert(key, Arc::new(positions));
+        self.entries.insert(key, highlighted);
         while self.order.len() > max_entries {
             if let Some(oldest) = self.order.pop_front() {
                 self.entries.remove(&oldest);
@@ -248,6 +289,20 @@ impl SortMetadataCacheState {
     }
 }
 
+impl EntrySizeCacheState {
+    pub(super) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(super) fn get(&self, path: &Path) -> Option<EntrySize> {
+        self.entries.get(path).copied()
+    }
+
+    pub(super) fn set(&mut self, path: PathBuf, size: EntrySize) {
+        self.entries.insert(path, size);
+    }
+}
+
 impl EntryKindCacheState {
     pub(super) fn clear(&mut self) {
         self.entries.clear();
@@ -13,10 +13,18 @@ impl FlistWalkerApp {
         Duration::from_millis(1500);
     pub(super) const INCREMENTAL_SEARCH_MIN_DELTA_DURING_INDEX: usize = 2048;
     pub(super) const PAGE_MOVE_ROWS: isize = 10;
+    pub(super) const TYPE_AHEAD_IDLE_RESET: Duration = Duration::from_millis(1000);
+    pub(super) const EXTENSION_BREAKDOWN_TOP: usize = 5;
+    pub(super) const LOG_PANEL_MAX_MESSAGES: usize = 200;
+    pub(super) const QUICK_LOOK_MAX_LINES: usize = 400;
+    pub(super) const QUICK_LOOK_MAX_BYTES: usize = 1024 * 1024;
     pub(super) const DEFAULT_PREVIEW_PANEL_WIDTH: f32 = 440.0;
     pub(super) const MIN_RESULTS_PANEL_WIDTH: f32 = 220.0;
     pub(super) const MIN_PREVIEW_PANEL_WIDTH: f32 = 220.0;
     pub(super) const ROOT_SELECTOR_POPUP_ID: &'static str = "root-selector-popup";
+    pub(super) const STARTUP_ROOT_ENV: &'static str = "FLISTWALKER_ROOT";
+    pub(super) const NO_SETTINGS_DIR_NOTICE: &'static str =
+        "Settings won't be saved: no home/config directory (set FLISTWALKER_CONFIG_DIR)";
     pub(super) const INDEX_MAX_CONCURRENT: usize = 2;
     pub(super) const INDEX_MAX_QUEUE: usize = 4;
     pub(super) const UI_STATE_SAVE_INTERVAL: Duration = Duration::from_millis(500);
@@ -1,10 +1,11 @@
 use super::{normalize_windows_path_buf, FlistWalkerApp};
 use crate::indexer::IndexSource;
-use crate::ui_model::normalize_path_for_display;
+use crate::ui_model::{display_path_with_mode, format_system_time, normalize_path_for_display};
 use eframe::egui;
 use memory_stats::memory_stats;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 
 #[derive(Debug, Clone)]
 pub(super) struct StatusLineContext<'a> {
@@ -12,9 +13,11 @@ pub(super) struct StatusLineContext<'a> {
     pub(super) tab_count: usize,
     pub(super) indexed_count: usize,
     pub(super) results_len: usize,
+    pub(super) current_row: Option<usize>,
     pub(super) total_match_count: usize,
     pub(super) limit: usize,
     pub(super) pinned_paths_len: usize,
+    pub(super) basket_len: usize,
     pub(super) search_in_progress: bool,
     pub(super) indexing_in_progress: bool,
     pub(super) action_in_progress: bool,
@@ -22,6 +25,7 @@ pub(super) struct StatusLineContext<'a> {
     pub(super) filelist_cancel_requested: bool,
     pub(super) update_in_progress: bool,
     pub(super) sort_in_progress: bool,
+    pub(super) size_in_progress: bool,
     pub(super) history_search_active: bool,
     pub(super) history_search_results_len: usize,
     pub(super) query_history_len: usize,
@@ -42,11 +46,25 @@ pub(super) fn build_status_line(ctx: StatusLineContext<'_>) -> String {
     } else {
         String::new()
     };
+    let row_position = match ctx.current_row {
+        Some(row) if row < ctx.results_len => format!(
+            " | Row {}/{} ({}%)",
+            row + 1,
+            ctx.results_len,
+            (row + 1) * 100 / ctx.results_len
+        ),
+        _ => String::new(),
+    };
     let pinned = if ctx.pinned_paths_len == 0 {
         String::new()
     } else {
         format!(" | Pinned: {}", ctx.pinned_paths_len)
     };
+    let basket = if ctx.basket_len == 0 {
+        String::new()
+    } else {
+        format!(" | Basket: {}", ctx.basket_len)
+    };
     let searching = if ctx.search_in_progress {
         " | Searching..."
     } else {
@@ -78,6 +96,8 @@ pub(super) fn build_status_line(ctx: StatusLineContext<'_>) -> String {
     };
     let sorting = if ctx.sort_in_progress {
         " | Sorting..."
+    } else if ctx.size_in_progress {
+        " | Reading sizes..."
     } else {
         ""
     };
@@ -100,12 +120,14 @@ pub(super) fn build_status_line(ctx: StatusLineContext<'_>) -> String {
     };
 
     format!(
-        "{} | Entries: {} | Results: {}{}{}{}{}{}{}{}{}{}{}{}",
+        "{} | Entries: {} | Results: {}{}{}{}{}{}{}{}{}{}{}{}{}{}",
         tab_label,
         ctx.indexed_count,
         ctx.results_len,
         clip_text,
+        row_position,
         pinned,
+        basket,
         searching,
         indexing,
         executing,
@@ -137,33 +159,58 @@ impl FlistWalkerApp {
         self.status_line_text_with_memory_sample(false)
     }
 
-    fn status_line_text_with_memory_sample(&mut self, sample_memory: bool) -> String {
-        let indexed_count =
-            if self.shell.indexing.in_progress || self.shell.indexing.pending_finish.is_some() {
-                let active_indexed_count = self
-                    .shell
-                    .runtime
-                    .index
-                    .entries
-                    .len()
-                    .saturating_add(self.shell.indexing.pending_entries.len());
-                if active_indexed_count == 0 {
-                    self.shell.runtime.all_entries.len()
-                } else {
-                    active_indexed_count
-                }
-            } else {
+    fn indexed_count(&self) -> usize {
+        if self.shell.indexing.in_progress || self.shell.indexing.pending_finish.is_some() {
+            let active_indexed_count = self
+                .shell
+                .runtime
+                .index
+                .entries
+                .len()
+                .saturating_add(self.shell.indexing.pending_entries.len());
+            if active_indexed_count == 0 {
                 self.shell.runtime.all_entries.len()
-            };
+            } else {
+                active_indexed_count
+            }
+        } else {
+            self.shell.runtime.all_entries.len()
+        }
+    }
+
+    /// indexing 中なら進捗率を返す。総数が不明な walker では `Some(None)` を返す。
+    pub(super) fn index_progress_fraction(&self) -> Option<Option<f32>> {
+        if !self.shell.indexing.in_progress {
+            return None;
+        }
+        let total = self
+            .shell
+            .indexing
+            .expected_total
+            .filter(|total| *total > 0);
+        let indexed = self
+            .shell
+            .runtime
+            .index
+            .entries
+            .len()
+            .saturating_add(self.shell.indexing.pending_entries.len());
+        Some(total.map(|total| (indexed as f32 / total as f32).min(1.0)))
+    }
+
+    fn status_line_text_with_memory_sample(&mut self, sample_memory: bool) -> String {
+        let indexed_count = self.indexed_count();
         let memory = self.memory_usage_text(sample_memory);
         let status_line = build_status_line(StatusLineContext {
             active_tab: self.shell.tabs.active_tab_index(),
             tab_count: self.shell.tabs.len(),
             indexed_count,
             results_len: self.shell.runtime.results.len(),
+            current_row: self.shell.runtime.current_row,
             total_match_count: self.shell.runtime.total_match_count,
             limit: self.shell.runtime.limit,
             pinned_paths_len: self.shell.runtime.pinned_paths.len(),
+            basket_len: self.shell.runtime.basket.len(),
             search_in_progress: self.shell.search.in_progress(),
             indexing_in_progress: self.shell.indexing.in_progress,
             action_in_progress: self.shell.worker_bus.action.in_progress,
@@ -171,6 +218,7 @@ impl FlistWalkerApp {
             filelist_cancel_requested: self.shell.features.filelist.workflow.cancel_requested,
             update_in_progress: self.shell.features.update.state.in_progress,
             sort_in_progress: self.shell.worker_bus.sort.in_progress,
+            size_in_progress: self.shell.worker_bus.size.in_progress,
             history_search_active: self.shell.runtime.query_state.history_search_active,
             history_search_results_len: self.shell.runtime.query_state.history_search_results.len(),
             query_history_len: self.shell.runtime.query_state.query_history.len(),
@@ -206,9 +254,35 @@ impl FlistWalkerApp {
     /// notice を更新し status line と同期する。
     pub(super) fn set_notice(&mut self, notice: impl Into<String>) {
         self.shell.runtime.notice = notice.into();
+        if !self.shell.runtime.notice.is_empty() {
+            let message = self.shell.runtime.notice.clone();
+            self.append_log_message(message);
+        }
         self.refresh_status_line();
     }
 
+    /// Log panel へ時刻付きで追記する。status line には影響しない。
+    pub(super) fn append_log_message(&mut self, message: impl Into<String>) {
+        let log = &mut self.shell.runtime.log_messages;
+        log.push_back((SystemTime::now(), message.into()));
+        while log.len() > Self::LOG_PANEL_MAX_MESSAGES {
+            log.pop_front();
+        }
+    }
+
+    /// Log panel 表示用に `時刻  message` 形式の行へ整形する。
+    pub(super) fn log_panel_lines(&self) -> Vec<String> {
+        self.shell
+            .runtime
+            .log_messages
+            .iter()
+            .map(|(time, message)| {
+                let time = format_system_time(*time).unwrap_or_default();
+                format!("{time}  {message}")
+            })
+            .collect()
+    }
+
     /// notice を消去し status line を再計算する。
     pub(super) fn clear_notice(&mut self) {
         self.shell.runtime.notice.clear();
@@ -302,6 +376,121 @@ impl FlistWalkerApp {
             .join("\n")
     }
 
+    /// shell へ貼り付ける前提で各 path を quote し、空白区切りの 1 行にまとめる。
+    pub(super) fn shell_quoted_paths_text(paths: &[PathBuf]) -> String {
+        paths
+            .iter()
+            .map(|p| Self::shell_quote(&normalize_path_for_display(p), cfg!(windows)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// root 配下の path を、区切りを `/` にして先頭へ `/` を付けた site 相対 URL へ変換する。
+    /// root 外の path は `None` を返す。
+    pub(super) fn site_relative_url(path: &Path, root: &Path) -> Option<String> {
+        let relative = display_path_with_mode(path, root, true);
+        if relative == display_path_with_mode(path, root, false) {
+            return None;
+        }
+        Some(format!(
+            "/{}",
+            relative.replace(std::path::MAIN_SEPARATOR, "/")
+        ))
+    }
+
+    /// Unix は single quote (内部の `'` は `'\''`)、Windows は PowerShell の single quote (内部の `'` は `''`) で囲む。
+    /// Windows で double quote を使うと `$` や `` ` `` が展開されるため使わない。
+    pub(super) fn shell_quote(text: &str, windows: bool) -> String {
+        if windows {
+            format!("'{}'", text.replace('\'', "''"))
+        } else {
+            format!("'{}'", text.replace('\'', "'\\''"))
+        }
+    }
+
+    /// 結果 path の拡張子を小文字で集計し、件数の多い順に上位 `top` 件を `rs: 42, md: 10` 形式で返す。
+    /// 拡張子の無い path は `(none)` にまとめ、上位外の種類は `+N more` として末尾に添える。
+    pub(super) fn extension_breakdown_text<'a>(
+        paths: impl IntoIterator<Item = &'a Path>,
+        top: usize,
+    ) -> Option<String> {
+        let mut counts = HashMap::<String, usize>::new();
+        for path in paths {
+            let key = path
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .unwrap_or_else(|| "(none)".to_string());
+            *counts.entry(key).or_default() += 1;
+        }
+        if counts.is_empty() {
+            return None;
+        }
+        let mut ranked = counts.into_iter().collect::<Vec<_>>();
+        ranked.sort_by(|(a_ext, a_count), (b_ext, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_ext.cmp(b_ext))
+        });
+        let rest = ranked.len().saturating_sub(top);
+        let mut text = ranked
+            .iter()
+            .take(top)
+            .map(|(ext, count)| format!("{ext}: {count}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if rest > 0 {
+            text.push_str(&format!(", +{rest} more"));
+        }
+        Some(text)
+    }
+
+    /// root 基準の相対 path を `tree` コマンド風の階層テキストへ整形する。
+    pub(super) fn results_tree_text(root: &Path, paths: &[PathBuf]) -> String {
+        #[derive(Default)]
+        struct TreeNode {
+            children: BTreeMap<String, TreeNode>,
+        }
+
+        let mut tree = TreeNode::default();
+        for path in paths {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            let mut node = &mut tree;
+            for component in relative.components() {
+                let name = component.as_os_str().to_string_lossy().to_string();
+                node = node.children.entry(name).or_default();
+            }
+        }
+
+        let root_label = normalize_windows_path_buf(root.to_path_buf())
+            .to_string_lossy()
+            .to_string();
+        let mut out = root_label;
+        // Iterative walk keeps very deep hierarchies from growing the call stack.
+        let mut stack = vec![(
+            tree.children.iter().collect::<Vec<_>>(),
+            0usize,
+            String::new(),
+        )];
+        while let Some((siblings, index, prefix)) = stack.last_mut() {
+            let Some((name, node)) = siblings.get(*index).copied() else {
+                stack.pop();
+                continue;
+            };
+            *index += 1;
+            let is_last = *index == siblings.len();
+            let branch = if is_last { "└── " } else { "├── " };
+            let suffix = if node.children.is_empty() { "" } else { "/" };
+            out.push('\n');
+            out.push_str(prefix);
+            out.push_str(branch);
+            out.push_str(name);
+            out.push_str(suffix);
+            if !node.children.is_empty() {
+                let child_prefix = format!("{prefix}{}", if is_last { "    " } else { "│   " });
+                stack.push((node.children.iter().collect(), 0, child_prefix));
+            }
+        }
+        out
+    }
+
     /// 現在の index source を status 向け文言へ整形する。
     pub(super) fn source_text(&self) -> String {
         match &self.shell.runtime.index.source {
@@ -312,6 +501,7 @@ impl FlistWalkerApp {
                     .unwrap_or("FileList.txt")
             ),
             IndexSource::Walker => "Source: Walker".to_string(),
+            IndexSource::Git => "Source: Git".to_string(),
             IndexSource::None => "Source: None".to_string(),
         }
     }
@@ -357,9 +547,11 @@ mod tests {
             tab_count: 3,
             indexed_count: 42,
             results_len: 7,
+            current_row: Some(2),
             total_match_count: 12,
             limit: 10,
             pinned_paths_len: 2,
+            basket_len: 3,
             search_in_progress: true,
             indexing_in_progress: true,
             action_in_progress: false,
@@ -367,6 +559,7 @@ mod tests {
             filelist_cancel_requested: true,
             update_in_progress: false,
             sort_in_progress: true,
+            size_in_progress: false,
             history_search_active: true,
             history_search_results_len: 4,
             query_history_len: 12,
@@ -378,7 +571,9 @@ mod tests {
         assert!(status.contains("Entries: 42"));
         assert!(status.contains("Results: 7"));
         assert!(status.contains("of 12 shown"));
+        assert!(status.contains("Row 3/7 (42%)"));
         assert!(status.contains("Pinned: 2"));
+        assert!(status.contains("Basket: 3"));
         assert!(status.contains("Searching..."));
         assert!(status.contains("Indexing..."));
         assert!(status.contains("Canceling FileList..."));
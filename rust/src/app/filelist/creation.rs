@@ -35,6 +35,7 @@ impl FlistWalkerApp {
             root,
             entries,
             propagate_to_ancestors,
+            self.shell.runtime.filelist_header_comment,
         );
         self.dispatch_filelist_commands(commands);
     }
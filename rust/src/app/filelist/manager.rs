@@ -14,6 +14,7 @@ impl FileListManager {
         root: PathBuf,
         entries: Vec<PathBuf>,
         propagate_to_ancestors: bool,
+        header_comment: bool,
     ) -> Vec<FileListCommand> {
         let cancel = Arc::new(AtomicBool::new(false));
         let request_id = self.begin_request(tab_id, root.clone(), Arc::clone(&cancel));
@@ -23,6 +24,7 @@ impl FileListManager {
             root,
             entries,
             propagate_to_ancestors,
+            header_comment,
             cancel,
         };
         vec![
@@ -26,6 +26,7 @@ pub(super) struct IndexCoordinator {
     pub(super) pending_queue: VecDeque<IndexRequest>,
     pub(super) inflight_requests: HashSet<u64>,
     pub(super) in_progress: bool,
+    pub(super) expected_total: Option<usize>,
     pub(super) incremental_filtered_entries: Vec<Entry>,
     pub(super) pending_entries: VecDeque<IndexEntry>,
     pub(super) pending_entries_request_id: Option<u64>,
@@ -42,6 +43,8 @@ pub(super) struct IndexCoordinator {
     pub(super) search_rerun_pending: bool,
     pub(super) request_tabs: HashMap<u64, u64>,
     pub(super) background_states: HashMap<u64, BackgroundIndexState>,
+    pub(super) frozen: bool,
+    pub(super) frozen_responses: VecDeque<IndexResponse>,
 }
 
 impl IndexCoordinator {
@@ -61,6 +64,7 @@ impl IndexCoordinator {
             pending_queue: VecDeque::new(),
             inflight_requests: HashSet::new(),
             in_progress: false,
+            expected_total: None,
             incremental_filtered_entries: Vec::new(),
             pending_entries: VecDeque::new(),
             pending_entries_request_id: None,
@@ -77,9 +81,21 @@ impl IndexCoordinator {
             search_rerun_pending: false,
             request_tabs: HashMap::new(),
             background_states: HashMap::new(),
+            frozen: false,
+            frozen_responses: VecDeque::new(),
         }
     }
 
+    /// 凍結解除後は退避済みの active 応答を受信順に先に返し、尽きたら channel から読む。
+    pub(super) fn next_response(&mut self) -> Option<IndexResponse> {
+        if !self.frozen {
+            if let Some(msg) = self.frozen_responses.pop_front() {
+                return Some(msg);
+            }
+        }
+        self.rx.try_recv().ok()
+    }
+
     pub(super) fn clear_for_tab(&mut self, tab_id: u64) {
         self.request_tabs.retain(|_, id| *id != tab_id);
         self.pending_queue.retain(|req| req.tab_id != tab_id);
@@ -108,6 +124,7 @@ impl IndexCoordinator {
     pub(super) fn begin_active_refresh(&mut self, request_id: u64, query_non_empty: bool) {
         self.pending_request_id = Some(request_id);
         self.in_progress = true;
+        self.expected_total = None;
         self.search_resume_pending = query_non_empty;
         self.search_rerun_pending = false;
     }
@@ -192,7 +209,8 @@ impl IndexCoordinator {
             | IndexResponse::Finished { request_id, .. }
             | IndexResponse::Failed { request_id, .. }
             | IndexResponse::Canceled { request_id }
-            | IndexResponse::Truncated { request_id, .. } => *request_id,
+            | IndexResponse::Truncated { request_id, .. }
+            | IndexResponse::DirectoriesCapped { request_id, .. } => *request_id,
         }
     }
 
@@ -5,11 +5,14 @@ use super::worker_channel::{
 use super::worker_protocol::{IndexEntry, IndexRequest, IndexResponse};
 use crate::entry::EntryKind;
 use crate::indexer::{
-    apply_filelist_hierarchy_overrides, find_filelist_in_first_level, parse_filelist_stream,
-    IndexSource,
+    apply_filelist_hierarchy_overrides, find_filelist_in_first_level, git_tracked_dirs,
+    git_tracked_files, parse_filelist_stream, parse_filelist_stream_with_total, IndexSource,
 };
+use crate::path_utils::{normalize_windows_path_buf, path_key};
 use crate::runtime_config::{current_runtime_config, RuntimeConfig};
-use std::collections::HashMap;
+use anyhow::anyhow;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use std::fs::FileType;
 use std::fs::OpenOptions;
 use std::io::Write;
@@ -25,7 +28,6 @@ const ADAPTIVE_WALKER_MAX_LIMIT_CAP: usize = 64;
 const ADAPTIVE_WALKER_MAX_LIMIT_DEFAULT_CAP: usize = 8;
 const FILELIST_BATCH_SIZE: usize = 1024;
 const WALKER_BATCH_SIZE: usize = 256;
-const INDEX_BATCH_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum WalkerBackend {
@@ -35,11 +37,15 @@ enum WalkerBackend {
 #[derive(Debug)]
 struct WalkerRuntimeSettings {
     max_entries: usize,
+    max_entries_per_dir: Option<usize>,
+    batch_size: usize,
+    flush_interval: Duration,
     adaptive_initial_limit: usize,
     adaptive_max_limit: usize,
     backend: WalkerBackend,
     metrics_enabled: bool,
     metrics_log_path: String,
+    exclude_root_filelist: bool,
 }
 
 fn walker_runtime_settings(config: &RuntimeConfig) -> WalkerRuntimeSettings {
@@ -57,14 +63,32 @@ fn walker_runtime_settings(config: &RuntimeConfig) -> WalkerRuntimeSettings {
 
     WalkerRuntimeSettings {
         max_entries: config.walker_max_entries.max(1),
+        max_entries_per_dir: (config.walker_max_entries_per_dir > 0)
+            .then_some(config.walker_max_entries_per_dir),
+        batch_size: index_batch_size(config, WALKER_BATCH_SIZE),
+        flush_interval: index_flush_interval(config),
         adaptive_initial_limit,
         adaptive_max_limit,
         backend: WalkerBackend::Adaptive,
         metrics_enabled: config.developer.walker_metrics,
         metrics_log_path: config.developer.walker_metrics_log_path.clone(),
+        exclude_root_filelist: config.exclude_root_filelist,
     }
 }
 
+/// `index_batch_size` が 0 のときは source ごとの既定 batch size を使う。
+fn index_batch_size(config: &RuntimeConfig, default: usize) -> usize {
+    if config.index_batch_size > 0 {
+        config.index_batch_size
+    } else {
+        default
+    }
+}
+
+fn index_flush_interval(config: &RuntimeConfig) -> Duration {
+    Duration::from_millis(config.index_flush_interval_ms.max(1))
+}
+
 fn default_adaptive_max_limit() -> usize {
     let logical_cores = std::thread::available_parallelism()
         .map(|value| value.get())
@@ -342,6 +366,15 @@ fn flush_walker_batch(
     ok
 }
 
+/// root 直下の FileList（`FileList.txt` / `filelist.txt`、大小文字無視）かを判定する。
+fn is_root_filelist_entry(path: &Path, root: &Path) -> bool {
+    path.parent() == Some(root)
+        && path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .is_some_and(|name| name.eq_ignore_ascii_case("filelist.txt"))
+}
+
 fn is_nested_filelist_candidate(path: &Path, root_filelist: &Path, root: &Path) -> bool {
     if path == root_filelist || !path.starts_with(root) {
         return false;
@@ -355,6 +388,7 @@ fn index_source_kind(source: &IndexSource) -> &'static str {
     match source {
         IndexSource::None => "none",
         IndexSource::Walker => "walker",
+        IndexSource::Git => "git",
         IndexSource::FileList(_) => "filelist",
     }
 }
@@ -398,13 +432,20 @@ fn stream_filelist_index(
         filelist = %filelist.display(),
         "worker request started"
     );
-    if tx_res
-        .send(IndexResponse::Started {
-            request_id: req.request_id,
-            source: source.clone(),
-        })
-        .is_err()
-    {
+    // The total comes from the reader's validation pass, so Started is sent once it finishes.
+    let started = Cell::new(false);
+    let send_started = |total: Option<usize>| -> bool {
+        started.set(true);
+        if tx_res
+            .send(IndexResponse::Started {
+                request_id: req.request_id,
+                source: source.clone(),
+                total,
+            })
+            .is_ok()
+        {
+            return true;
+        }
         warn!(
             flow = "index",
             source_kind = "filelist",
@@ -412,16 +453,19 @@ fn stream_filelist_index(
             request_id = req.request_id,
             "worker response receiver closed before start"
         );
-        return Err("index receiver closed".to_string());
-    }
+        false
+    };
 
+    let config = current_runtime_config();
+    let batch_size = index_batch_size(&config, FILELIST_BATCH_SIZE);
+    let flush_interval = index_flush_interval(&config);
     let mut buffer: Vec<IndexEntry> = Vec::new();
     let mut streamed_entries_for_nested: Option<Vec<PathBuf>> = None;
     let mut can_reuse_streamed_entries_for_nested = true;
     let mut last_flush = Instant::now();
     let mut stream_err: Option<String> = None;
     let mut has_nested_filelist_candidate = false;
-    parse_filelist_stream(
+    let streamed = parse_filelist_stream_with_total(
         &filelist,
         root,
         req.include_files,
@@ -436,6 +480,13 @@ fn stream_filelist_index(
                 .and_then(|m| m.get(&req.tab_id).copied())
                 != Some(req.request_id)
         },
+        |total| {
+            if send_started(total) {
+                Ok(())
+            } else {
+                Err(anyhow!("index receiver closed"))
+            }
+        },
         |path, is_dir| {
             if stream_err.is_some() {
                 return;
@@ -463,9 +514,7 @@ fn stream_filelist_index(
                 }),
                 kind_known: is_dir.is_some(),
             });
-            if buffer.len() >= FILELIST_BATCH_SIZE
-                || last_flush.elapsed() >= INDEX_BATCH_FLUSH_INTERVAL
-            {
+            if buffer.len() >= batch_size || last_flush.elapsed() >= flush_interval {
                 if !has_nested_filelist_candidate {
                     can_reuse_streamed_entries_for_nested = false;
                 }
@@ -476,8 +525,14 @@ fn stream_filelist_index(
                 last_flush = Instant::now();
             }
         },
-    )
-    .map_err(|err| err.to_string())?;
+    );
+    if let Err(err) = streamed {
+        // Validation failures still report the source before the error, as before.
+        if !started.get() && !send_started(None) {
+            return Err("index receiver closed".to_string());
+        }
+        return Err(err.to_string());
+    }
     if let Some(err) = stream_err {
         return Err(err);
     }
@@ -570,6 +625,7 @@ fn stream_walker_index(
     tx_res: &Sender<IndexResponse>,
     req: &IndexRequest,
     root: &Path,
+    announce_started: bool,
     shutdown: &AtomicBool,
     latest_request_ids: &Mutex<HashMap<u64, u64>>,
 ) -> std::result::Result<IndexSource, String> {
@@ -585,12 +641,14 @@ fn stream_walker_index(
         include_dirs = req.include_dirs,
         "worker request started"
     );
-    if tx_res
-        .send(IndexResponse::Started {
-            request_id: req.request_id,
-            source: source.clone(),
-        })
-        .is_err()
+    if announce_started
+        && tx_res
+            .send(IndexResponse::Started {
+                request_id: req.request_id,
+                source: source.clone(),
+                total: None,
+            })
+            .is_err()
     {
         warn!(
             flow = "index",
@@ -621,6 +679,7 @@ fn stream_walker_index(
             != Some(req.request_id)
     };
     let mut stream_err: Option<String> = None;
+    let mut canonical_seen: HashSet<PathBuf> = HashSet::new();
 
     let mut handle_entry = |path: PathBuf, file_type: FileType| -> bool {
         cancel_check_budget = cancel_check_budget.saturating_add(1);
@@ -631,11 +690,28 @@ fn stream_walker_index(
                 return false;
             }
         }
+        // The FileList generated at the root is our own output, not a search target.
+        if settings.exclude_root_filelist && is_root_filelist_entry(&path, root) {
+            return true;
+        }
         let Some((kind, kind_known)) =
             classify_walker_entry(&path, file_type, req.include_files, req.include_dirs)
         else {
             return true;
         };
+        let path = if req.canonicalize_entries {
+            // Collapse symlinked aliases onto one resolved path; unresolvable entries are dropped.
+            let Ok(canonical) = path.canonicalize() else {
+                return true;
+            };
+            let canonical = normalize_windows_path_buf(canonical);
+            if !canonical_seen.insert(canonical.clone()) {
+                return true;
+            }
+            canonical
+        } else {
+            path
+        };
         if emitted_entries >= max_entries {
             truncated = true;
             return false;
@@ -647,11 +723,18 @@ fn stream_walker_index(
         });
         emitted_entries = emitted_entries.saturating_add(1);
         metrics.entries_emitted = emitted_entries;
-        if buffer.len() >= WALKER_BATCH_SIZE || last_flush.elapsed() >= INDEX_BATCH_FLUSH_INTERVAL {
+        if buffer.len() >= settings.batch_size || last_flush.elapsed() >= settings.flush_interval {
             if !flush_walker_batch(tx_res, req.request_id, &mut buffer, &mut metrics) {
                 stream_err = Some("index receiver closed".to_string());
                 return false;
             }
+            if let Some(throttle) = req.throttle {
+                thread::sleep(throttle);
+                if should_cancel() {
+                    stream_err = Some("superseded".to_string());
+                    return false;
+                }
+            }
             last_flush = Instant::now();
         }
         true
@@ -671,9 +754,11 @@ fn stream_walker_index(
         root,
         settings.adaptive_max_limit,
         settings.adaptive_initial_limit,
+        settings.max_entries_per_dir,
         |entry: AdaptiveWalkerEntry| handle_entry(entry.path, entry.file_type),
         should_cancel_for_walk,
     );
+    let capped_dirs = adaptive_metrics.capped_dirs;
     metrics.record_adaptive(adaptive_metrics);
 
     if stream_err.is_none() && should_cancel() {
@@ -693,6 +778,21 @@ fn stream_walker_index(
         }
         return Err("index receiver closed".to_string());
     }
+    if let Some(limit) = settings.max_entries_per_dir.filter(|_| capped_dirs > 0) {
+        if tx_res
+            .send(IndexResponse::DirectoriesCapped {
+                request_id: req.request_id,
+                limit,
+                directories: capped_dirs,
+            })
+            .is_err()
+        {
+            if settings.metrics_enabled {
+                log_walker_metrics(req, &metrics, "receiver_closed", &settings.metrics_log_path);
+            }
+            return Err("index receiver closed".to_string());
+        }
+    }
     if truncated
         && tx_res
             .send(IndexResponse::Truncated {
@@ -733,6 +833,182 @@ fn stream_walker_index(
     Ok(source)
 }
 
+/// `git ls-files` で得た tracked file (と導出した中間 directory) を Git source として流す。
+fn stream_git_index(
+    tx_res: &Sender<IndexResponse>,
+    req: &IndexRequest,
+    root: &Path,
+    files: Vec<PathBuf>,
+    shutdown: &AtomicBool,
+    latest_request_ids: &Mutex<HashMap<u64, u64>>,
+) -> std::result::Result<IndexSource, String> {
+    let source = IndexSource::Git;
+    let dirs = if req.include_dirs {
+        git_tracked_dirs(root, &files)
+    } else {
+        Vec::new()
+    };
+    let files = if req.include_files { files } else { Vec::new() };
+    let total = files.len().saturating_add(dirs.len());
+    info!(
+        flow = "index",
+        source_kind = "git",
+        event = "started",
+        request_id = req.request_id,
+        tab_id = req.tab_id,
+        root = %root.display(),
+        total,
+        "worker request started"
+    );
+    if tx_res
+        .send(IndexResponse::Started {
+            request_id: req.request_id,
+            source: source.clone(),
+            total: Some(total),
+        })
+        .is_err()
+    {
+        warn!(
+            flow = "index",
+            source_kind = "git",
+            event = "receiver_closed",
+            request_id = req.request_id,
+            "worker response receiver closed before start"
+        );
+        return Err("index receiver closed".to_string());
+    }
+
+    let should_cancel = || {
+        if shutdown.load(Ordering::Relaxed) {
+            return true;
+        }
+        latest_request_ids
+            .lock()
+            .ok()
+            .and_then(|m| m.get(&req.tab_id).copied())
+            != Some(req.request_id)
+    };
+    let batch_size = index_batch_size(&current_runtime_config(), FILELIST_BATCH_SIZE);
+    let entries = dirs
+        .into_iter()
+        .map(|path| (path, EntryKind::dir()))
+        .chain(files.into_iter().map(|path| (path, EntryKind::file())));
+    let mut buffer: Vec<IndexEntry> = Vec::with_capacity(batch_size);
+    for (path, kind) in entries {
+        buffer.push(IndexEntry {
+            path,
+            kind,
+            kind_known: true,
+        });
+        if buffer.len() >= batch_size {
+            if should_cancel() {
+                return Err("superseded".to_string());
+            }
+            if !flush_batch(tx_res, req.request_id, &mut buffer) {
+                return Err("index receiver closed".to_string());
+            }
+        }
+    }
+    if !flush_batch(tx_res, req.request_id, &mut buffer) {
+        return Err("index receiver closed".to_string());
+    }
+    info!(
+        flow = "index",
+        source_kind = "git",
+        event = "finished",
+        request_id = req.request_id,
+        total,
+        "worker request finished"
+    );
+    Ok(source)
+}
+
+/// 全保存 root 検索用に追加 root の entry を同じ request の Batch として流す。
+/// 入れ子判定用に root を実体 path へ解決し、OS の大文字小文字規則に揃えた key にする。
+fn root_cover_key(root: &Path) -> PathBuf {
+    let resolved = root
+        .canonicalize()
+        .map(normalize_windows_path_buf)
+        .unwrap_or_else(|_| root.to_path_buf());
+    PathBuf::from(path_key(&resolved))
+}
+
+fn stream_extra_roots_index(
+    tx_res: &Sender<IndexResponse>,
+    req: &IndexRequest,
+    primary_root: &Path,
+    shutdown: &AtomicBool,
+    latest_request_ids: &Mutex<HashMap<u64, u64>>,
+) -> std::result::Result<(), String> {
+    let should_cancel = || {
+        if shutdown.load(Ordering::Relaxed) {
+            return true;
+        }
+        latest_request_ids
+            .lock()
+            .ok()
+            .and_then(|m| m.get(&req.tab_id).copied())
+            != Some(req.request_id)
+    };
+    // A root inside one that is already walked would only index the same entries twice.
+    let mut walked = vec![root_cover_key(primary_root), root_cover_key(&req.root)];
+    for root in &req.extra_roots {
+        if !root.is_dir() {
+            continue;
+        }
+        let key = root_cover_key(root);
+        if walked
+            .iter()
+            .any(|walked_root| key.starts_with(walked_root))
+        {
+            continue;
+        }
+        walked.push(key);
+        if should_cancel() {
+            return Err("superseded".to_string());
+        }
+        let filelist = req
+            .use_filelist
+            .then(|| find_filelist_in_first_level(root))
+            .flatten();
+        let Some(filelist) = filelist else {
+            stream_walker_index(tx_res, req, root, false, shutdown, latest_request_ids)?;
+            continue;
+        };
+        let mut entries = collect_filelist_entries_with_cancel(
+            &filelist,
+            root,
+            req.include_files,
+            req.include_dirs,
+            should_cancel,
+        )?;
+        apply_filelist_hierarchy_overrides(
+            &filelist,
+            root,
+            &mut entries,
+            req.include_files,
+            req.include_dirs,
+            should_cancel,
+        )
+        .map_err(|err| err.to_string())?;
+        let batch_size = index_batch_size(&current_runtime_config(), FILELIST_BATCH_SIZE);
+        for chunk in entries.chunks(batch_size) {
+            let mut buffer = chunk
+                .iter()
+                .map(|path| IndexEntry {
+                    path: path.clone(),
+                    kind: EntryKind::file(),
+                    kind_known: false,
+                })
+                .collect::<Vec<_>>();
+            if !flush_batch(tx_res, req.request_id, &mut buffer) {
+                return Err("index receiver closed".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
 pub(super) fn spawn_index_worker(
     shutdown: Arc<AtomicBool>,
     latest_request_ids: Arc<Mutex<HashMap<u64, u64>>>,
@@ -834,6 +1110,7 @@ fn spawn_index_worker_with(
                         .send(IndexResponse::Started {
                             request_id: req.request_id,
                             source: IndexSource::None,
+                            total: Some(0),
                         })
                         .is_err()
                     {
@@ -878,7 +1155,36 @@ fn spawn_index_worker_with(
                 }
 
                 let root = resolve_root_worker(&req.root);
-                let result = if req.use_filelist {
+                let git_files = req
+                    .git_tracked_only
+                    .then(|| git_tracked_files(&root))
+                    .flatten();
+                let result = if let Some(files) = git_files {
+                    stream_git_index(
+                        &tx_res_worker,
+                        &req,
+                        &root,
+                        files,
+                        shutdown_worker.as_ref(),
+                        latest_request_ids_worker.as_ref(),
+                    )
+                } else if req.filelist_only {
+                    // Reload FileList must not silently fall back to walking the root.
+                    match find_filelist_in_first_level(&root) {
+                        Some(filelist) => stream_filelist_index(
+                            &tx_res_worker,
+                            &req,
+                            &root,
+                            filelist,
+                            shutdown_worker.as_ref(),
+                            latest_request_ids_worker.as_ref(),
+                        ),
+                        None => Err(format!(
+                            "no FileList.txt or filelist.txt in {}",
+                            root.display()
+                        )),
+                    }
+                } else if req.use_filelist {
                     if let Some(filelist) = find_filelist_in_first_level(&root) {
                         stream_filelist_index(
                             &tx_res_worker,
@@ -893,6 +1199,7 @@ fn spawn_index_worker_with(
                             &tx_res_worker,
                             &req,
                             &root,
+                            true,
                             shutdown_worker.as_ref(),
                             latest_request_ids_worker.as_ref(),
                         )
@@ -902,10 +1209,21 @@ fn spawn_index_worker_with(
                         &tx_res_worker,
                         &req,
                         &root,
+                        true,
                         shutdown_worker.as_ref(),
                         latest_request_ids_worker.as_ref(),
                     )
                 };
+                let result = result.and_then(|source| {
+                    stream_extra_roots_index(
+                        &tx_res_worker,
+                        &req,
+                        &root,
+                        shutdown_worker.as_ref(),
+                        latest_request_ids_worker.as_ref(),
+                    )
+                    .map(|()| source)
+                });
 
                 match result {
                     Ok(source) => {
@@ -160,6 +160,28 @@ fn walker_runtime_settings_use_adaptive_by_default() {
     assert!(settings.metrics_enabled);
 }
 
+#[test]
+fn walker_runtime_settings_use_configured_batch_size_and_flush_interval() {
+    let defaults = walker_runtime_settings(&RuntimeConfig::default());
+    assert_eq!(defaults.batch_size, WALKER_BATCH_SIZE);
+    assert_eq!(defaults.flush_interval, Duration::from_millis(100));
+
+    let config = RuntimeConfig {
+        index_batch_size: 4096,
+        index_flush_interval_ms: 250,
+        ..RuntimeConfig::default()
+    };
+    let settings = walker_runtime_settings(&config);
+
+    assert_eq!(settings.batch_size, 4096);
+    assert_eq!(settings.flush_interval, Duration::from_millis(250));
+    assert_eq!(index_batch_size(&config, FILELIST_BATCH_SIZE), 4096);
+    assert_eq!(
+        index_batch_size(&RuntimeConfig::default(), FILELIST_BATCH_SIZE),
+        FILELIST_BATCH_SIZE
+    );
+}
+
 #[test]
 fn walker_runtime_settings_always_uses_adaptive_backend() {
     let config = RuntimeConfig {
@@ -171,6 +193,85 @@ fn walker_runtime_settings_always_uses_adaptive_backend() {
     assert_eq!(settings.backend, WalkerBackend::Adaptive);
 }
 
+#[test]
+fn directory_entry_cap_stops_reading_each_folder_and_skips_unread_subfolders() {
+    let root = test_root("dir-cap");
+    let _ = std::fs::remove_dir_all(&root);
+    let huge = root.join("huge");
+    for i in 0..5usize {
+        let dir = huge.join(format!("d{i}"));
+        std::fs::create_dir_all(&dir).expect("create child dir");
+        std::fs::write(dir.join("inner.txt"), "x").expect("write inner");
+    }
+    std::fs::create_dir_all(root.join("small")).expect("create small");
+    std::fs::write(root.join("small").join("a.txt"), "x").expect("write small");
+
+    for max_workers in [1, 2] {
+        let mut paths = Vec::new();
+        let metrics = walk_adaptive(
+            &root,
+            max_workers,
+            max_workers,
+            Some(2),
+            |entry| {
+                paths.push(entry.path);
+                true
+            },
+            || false,
+        );
+
+        let read_dirs = paths
+            .iter()
+            .filter(|path| path.parent() == Some(huge.as_path()))
+            .collect::<Vec<_>>();
+        assert_eq!(read_dirs.len(), 2);
+        // Only the admitted subfolders are walked; the unread ones contribute nothing.
+        let inner = paths
+            .iter()
+            .filter(|path| path.ends_with("inner.txt"))
+            .collect::<Vec<_>>();
+        assert_eq!(inner.len(), 2);
+        assert!(inner.iter().all(|path| read_dirs
+            .iter()
+            .any(|dir| path.parent() == Some(dir.as_path()))));
+        assert!(paths.contains(&root.join("small").join("a.txt")));
+        assert_eq!(metrics.capped_dirs, 1);
+    }
+    let _ = std::fs::remove_dir_all(&root);
+
+    assert_eq!(
+        walker_runtime_settings(&RuntimeConfig::default()).max_entries_per_dir,
+        None
+    );
+    let config = RuntimeConfig {
+        walker_max_entries_per_dir: 10_000,
+        ..RuntimeConfig::default()
+    };
+    assert_eq!(
+        walker_runtime_settings(&config).max_entries_per_dir,
+        Some(10_000)
+    );
+}
+
+#[test]
+fn walker_excludes_only_root_filelist_when_enabled() {
+    let root = PathBuf::from("/tmp/flist-root");
+    assert!(is_root_filelist_entry(&root.join("FileList.txt"), &root));
+    assert!(is_root_filelist_entry(&root.join("FILELIST.TXT"), &root));
+    assert!(!is_root_filelist_entry(
+        &root.join("sub").join("FileList.txt"),
+        &root
+    ));
+    assert!(!is_root_filelist_entry(&root.join("FileList.md"), &root));
+
+    assert!(walker_runtime_settings(&RuntimeConfig::default()).exclude_root_filelist);
+    let config = RuntimeConfig {
+        exclude_root_filelist: false,
+        ..RuntimeConfig::default()
+    };
+    assert!(!walker_runtime_settings(&config).exclude_root_filelist);
+}
+
 #[test]
 fn walker_metrics_summary_can_be_written_to_file() {
     let root = test_root("metrics-log");
@@ -184,6 +285,11 @@ fn walker_metrics_summary_can_be_written_to_file() {
         use_filelist: false,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     let mut metrics = WalkerMetrics::new(WalkerBackend::Adaptive);
     metrics.entries_emitted = 11;
@@ -339,6 +445,7 @@ fn adaptive_walker_emits_entries_and_records_control_metrics() {
         &root,
         2,
         2,
+        None,
         |entry| {
             paths.push(entry.path);
             true
@@ -372,6 +479,7 @@ fn adaptive_walker_can_stop_from_consumer_callback() {
         &root,
         2,
         2,
+        None,
         |_entry| {
             count = count.saturating_add(1);
             count < 3
@@ -407,11 +515,16 @@ fn adaptive_walker_returns_superseded_when_canceled_before_entry() {
         use_filelist: false,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     let shutdown = AtomicBool::new(false);
     let latest_request_ids = Mutex::new(HashMap::from([(req.tab_id, req.request_id + 1)]));
 
-    let result = stream_walker_index(&tx_res, &req, &root, &shutdown, &latest_request_ids);
+    let result = stream_walker_index(&tx_res, &req, &root, true, &shutdown, &latest_request_ids);
 
     assert_eq!(result, Err("superseded".to_string()));
     set_process_runtime_config(RuntimeConfig::default());
@@ -434,6 +547,11 @@ fn filelist_stream_returns_superseded_when_canceled_before_entry() {
         use_filelist: true,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     let shutdown = AtomicBool::new(false);
     let latest_request_ids = Mutex::new(HashMap::from([(req.tab_id, req.request_id + 1)]));
@@ -470,6 +588,11 @@ fn filelist_stream_uses_larger_batches() {
         use_filelist: true,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     let shutdown = AtomicBool::new(false);
     let latest_request_ids = Mutex::new(HashMap::from([(req.tab_id, req.request_id)]));
@@ -490,6 +613,7 @@ fn filelist_stream_uses_larger_batches() {
         Some(IndexResponse::Started {
             request_id: 22,
             source: IndexSource::FileList(_),
+            total: Some(1025),
         })
     ));
     let batches = responses
@@ -504,6 +628,56 @@ fn filelist_stream_uses_larger_batches() {
     let _ = std::fs::remove_dir_all(&root);
 }
 
+#[test]
+fn filelist_stream_total_skips_comment_lines_and_follows_delimiter() {
+    let root = test_root("filelist-total-rules");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).expect("create root");
+    let filelist = root.join("FileList.txt");
+    let request = |include_dirs: bool| IndexRequest {
+        request_id: 23,
+        tab_id: 5,
+        root: root.clone(),
+        use_filelist: true,
+        include_files: true,
+        include_dirs,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
+    };
+    let shutdown = AtomicBool::new(false);
+    let latest_request_ids = Mutex::new(HashMap::from([(5, 23)]));
+    let started_total = |text: &[u8], include_dirs: bool| {
+        std::fs::write(&filelist, text).expect("write filelist");
+        let (tx_res, rx_res) = mpsc::channel();
+        stream_filelist_index(
+            &tx_res,
+            &request(include_dirs),
+            &root,
+            filelist.clone(),
+            &shutdown,
+            &latest_request_ids,
+        )
+        .expect("stream filelist");
+        match rx_res.try_iter().next() {
+            Some(IndexResponse::Started { total, .. }) => total,
+            _ => panic!("expected Started first"),
+        }
+    };
+
+    assert_eq!(
+        started_total(b"# comment\n\n  a.txt\n  # indented\nb.txt\n", true),
+        Some(2)
+    );
+    assert_eq!(started_total(b"a.txt\0b.txt\0c.txt\0", true), Some(3));
+    assert_eq!(started_total(b"a.txt\n!b.txt\nb.txt\n", true), None);
+    assert_eq!(started_total(b"a.txt\nb.txt\n", false), None);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
 #[test]
 fn filelist_stream_applies_nested_override_after_initial_batches() {
     let root = test_root("filelist-nested-replace");
@@ -530,6 +704,11 @@ fn filelist_stream_applies_nested_override_after_initial_batches() {
         use_filelist: true,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     let shutdown = AtomicBool::new(false);
     let latest_request_ids = Mutex::new(HashMap::from([(req.tab_id, req.request_id)]));
@@ -564,6 +743,105 @@ fn filelist_stream_applies_nested_override_after_initial_batches() {
     let _ = std::fs::remove_dir_all(&root);
 }
 
+#[cfg(unix)]
+#[test]
+fn walker_stream_canonicalizes_symlinked_entries_when_requested() {
+    let root = test_root("walker-canonicalize");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).expect("create root");
+    std::fs::write(root.join("main.rs"), "fn main() {}").expect("write file");
+    std::os::unix::fs::symlink(root.join("main.rs"), root.join("alias.rs")).expect("symlink");
+    std::os::unix::fs::symlink(root.join("missing.rs"), root.join("dangling.rs"))
+        .expect("dangling symlink");
+
+    let (tx_res, rx_res) = mpsc::channel();
+    let req = IndexRequest {
+        request_id: 24,
+        tab_id: 6,
+        root: root.clone(),
+        use_filelist: false,
+        include_files: true,
+        include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: true,
+        git_tracked_only: false,
+        filelist_only: false,
+    };
+    let shutdown = AtomicBool::new(false);
+    let latest_request_ids = Mutex::new(HashMap::from([(req.tab_id, req.request_id)]));
+
+    let result = stream_walker_index(&tx_res, &req, &root, true, &shutdown, &latest_request_ids);
+
+    assert_eq!(result, Ok(IndexSource::Walker));
+    let paths = rx_res
+        .try_iter()
+        .filter_map(|response| match response {
+            IndexResponse::Batch { entries, .. } => Some(entries),
+            _ => None,
+        })
+        .flatten()
+        .map(|entry| entry.path)
+        .collect::<Vec<_>>();
+    let canonical_main = root.join("main.rs").canonicalize().expect("canonical main");
+    assert_eq!(paths, vec![canonical_main]);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn git_stream_emits_tracked_files_and_derived_dirs_with_known_kinds() {
+    let root = PathBuf::from("repo");
+    let files = vec![root.join("README.md"), root.join("src").join("main.rs")];
+
+    let (tx_res, rx_res) = mpsc::channel();
+    let req = IndexRequest {
+        request_id: 25,
+        tab_id: 7,
+        root: root.clone(),
+        use_filelist: true,
+        include_files: true,
+        include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: true,
+        filelist_only: false,
+    };
+    let shutdown = AtomicBool::new(false);
+    let latest_request_ids = Mutex::new(HashMap::from([(req.tab_id, req.request_id)]));
+
+    let result = stream_git_index(&tx_res, &req, &root, files, &shutdown, &latest_request_ids);
+
+    assert_eq!(result, Ok(IndexSource::Git));
+    let responses = rx_res.try_iter().collect::<Vec<_>>();
+    assert!(matches!(
+        responses.first(),
+        Some(IndexResponse::Started {
+            source: IndexSource::Git,
+            total: Some(3),
+            ..
+        })
+    ));
+    let entries = responses
+        .into_iter()
+        .filter_map(|response| match response {
+            IndexResponse::Batch { entries, .. } => Some(entries),
+            _ => None,
+        })
+        .flatten()
+        .map(|entry| (entry.path, entry.kind, entry.kind_known))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            (root.join("src"), EntryKind::dir(), true),
+            (root.join("README.md"), EntryKind::file(), true),
+            (root.join("src").join("main.rs"), EntryKind::file(), true),
+        ]
+    );
+}
+
 #[test]
 fn tc_152_stale_index_request_cancels_before_root_resolution() {
     let shutdown = Arc::new(AtomicBool::new(false));
@@ -585,6 +863,11 @@ fn tc_152_stale_index_request_cancels_before_root_resolution() {
         use_filelist: false,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     })
     .expect("send stale index request");
     assert!(matches!(
@@ -633,6 +916,11 @@ fn tc_152_index_workers_bound_total_to_four() {
         use_filelist: false,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     tx.send(request(1)).expect("send first index request");
     tx.send(request(2)).expect("send second index request");
@@ -686,6 +974,11 @@ fn tc_153_index_shutdown_drains_accepted_queue_with_terminal_cancellation() {
             use_filelist: false,
             include_files: true,
             include_dirs: true,
+            throttle: None,
+            extra_roots: Vec::new(),
+            canonicalize_entries: false,
+            git_tracked_only: false,
+            filelist_only: false,
         })
         .expect("accept index request before channel close");
     }
@@ -735,6 +1028,11 @@ fn index_worker_trace_smoke_emits_canonical_fields() {
             use_filelist: false,
             include_files: true,
             include_dirs: true,
+            throttle: None,
+            extra_roots: Vec::new(),
+            canonicalize_entries: false,
+            git_tracked_only: false,
+            filelist_only: false,
         })
         .expect("send request");
 
@@ -743,6 +1041,7 @@ fn index_worker_trace_smoke_emits_canonical_fields() {
         IndexResponse::Started {
             request_id: 41,
             source: IndexSource::Walker,
+            total: None,
         }
     ));
     assert!(matches!(
@@ -765,6 +1064,98 @@ fn index_worker_trace_smoke_emits_canonical_fields() {
     let _ = std::fs::remove_dir_all(&root);
 }
 
+#[test]
+fn index_worker_appends_extra_roots_to_same_request() {
+    let base = test_root("extra-roots");
+    let _ = std::fs::remove_dir_all(&base);
+    let primary = base.join("primary");
+    let walked = base.join("walked");
+    let listed = base.join("listed");
+    for dir in [&primary, &walked, &listed] {
+        std::fs::create_dir_all(dir).expect("create dir");
+    }
+    std::fs::write(primary.join("main.rs"), "fn main() {}").expect("write main");
+    std::fs::write(walked.join("lib.rs"), "pub fn lib() {}").expect("write lib");
+    std::fs::write(listed.join("notes.txt"), "notes").expect("write notes");
+    std::fs::write(listed.join("hidden.txt"), "hidden").expect("write hidden");
+    std::fs::write(listed.join("FileList.txt"), "notes.txt\n").expect("write filelist");
+    std::fs::create_dir_all(primary.join("sub")).expect("create nested primary dir");
+    std::fs::write(primary.join("sub").join("sub.rs"), "").expect("write sub");
+    std::fs::create_dir_all(walked.join("inner")).expect("create nested walked dir");
+    std::fs::write(walked.join("inner").join("inner.rs"), "").expect("write inner");
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let latest_request_ids = Arc::new(Mutex::new(HashMap::new()));
+    latest_request_ids
+        .lock()
+        .expect("latest ids lock")
+        .insert(2, 51);
+    let (tx_req, rx_res, handles) = spawn_index_worker(shutdown.clone(), latest_request_ids);
+    tx_req
+        .send(IndexRequest {
+            request_id: 51,
+            tab_id: 2,
+            root: primary.clone(),
+            use_filelist: true,
+            include_files: true,
+            include_dirs: true,
+            throttle: None,
+            extra_roots: vec![
+                primary.clone(),
+                walked.clone(),
+                listed.clone(),
+                primary.join("sub"),
+                walked.join("..").join("walked").join("inner"),
+            ],
+            canonicalize_entries: false,
+            git_tracked_only: false,
+            filelist_only: false,
+        })
+        .expect("send request");
+
+    let mut names = Vec::new();
+    let mut started = 0usize;
+    loop {
+        match rx_res.recv().expect("index response") {
+            IndexResponse::Started { .. } => started += 1,
+            IndexResponse::Batch { entries, .. } => {
+                names.extend(entries.into_iter().map(|entry| {
+                    entry
+                        .path
+                        .file_name()
+                        .expect("file name")
+                        .to_string_lossy()
+                        .to_string()
+                }))
+            }
+            IndexResponse::Finished { .. } => break,
+            _ => panic!("unexpected index response"),
+        }
+    }
+    names.sort();
+
+    assert_eq!(started, 1);
+    // Nested roots are already covered by their walked parents and add no duplicates.
+    assert_eq!(
+        names,
+        vec![
+            "inner",
+            "inner.rs",
+            "lib.rs",
+            "main.rs",
+            "notes.txt",
+            "sub",
+            "sub.rs"
+        ]
+    );
+    drop(tx_req);
+    shutdown.store(true, Ordering::Relaxed);
+    for handle in handles {
+        handle.join().expect("join index worker");
+    }
+    let _ = std::fs::remove_dir_all(&base);
+}
+
 #[test]
 fn adaptive_walker_matches_std_read_dir_count_on_basic_tree() {
     let root = test_root("adaptive-std-count");
@@ -782,6 +1173,7 @@ fn adaptive_walker_matches_std_read_dir_count_on_basic_tree() {
         &root,
         2,
         2,
+        None,
         |entry| {
             if classify_walker_entry(&entry.path, entry.file_type, true, true).is_some() {
                 adaptive_count = adaptive_count.saturating_add(1);
@@ -819,6 +1211,7 @@ fn perf_adaptive_walker_reports_local_dataset_metrics() {
         &root,
         2,
         2,
+        None,
         |entry| {
             if classify_walker_entry(&entry.path, entry.file_type, true, true).is_some() {
                 adaptive_count = adaptive_count.saturating_add(1);
@@ -956,3 +1349,45 @@ fn visit_std_walker_entries(root: &Path, on_entry: &mut impl FnMut(&Path, FileTy
         }
     }
 }
+
+#[test]
+fn walker_stream_with_throttle_pauses_between_batches_and_emits_all_entries() {
+    let root = test_root("walker-throttle");
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).expect("create root");
+    for i in 0..(WALKER_BATCH_SIZE + 4) {
+        std::fs::write(root.join(format!("entry-{i}.txt")), "").expect("write file");
+    }
+
+    let (tx_res, rx_res) = mpsc::channel();
+    let req = IndexRequest {
+        request_id: 31,
+        tab_id: 5,
+        root: root.clone(),
+        use_filelist: false,
+        include_files: true,
+        include_dirs: true,
+        throttle: Some(Duration::from_millis(40)),
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
+    };
+    let shutdown = AtomicBool::new(false);
+    let latest_request_ids = Mutex::new(HashMap::from([(req.tab_id, req.request_id)]));
+
+    let started = Instant::now();
+    let result = stream_walker_index(&tx_res, &req, &root, true, &shutdown, &latest_request_ids);
+
+    assert_eq!(result, Ok(IndexSource::Walker));
+    assert!(started.elapsed() >= Duration::from_millis(40));
+    let emitted = rx_res
+        .try_iter()
+        .map(|response| match response {
+            IndexResponse::Batch { entries, .. } => entries.len(),
+            _ => 0,
+        })
+        .sum::<usize>();
+    assert_eq!(emitted, WALKER_BATCH_SIZE + 4);
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -1,10 +1,16 @@
-use super::super::{normalize_path_for_display, ActionRequest, FlistWalkerApp};
+use super::super::{
+    normalize_path_for_display, number_preview_lines, ActionKind, ActionOperation, ActionRequest,
+    FlistWalkerApp, FolderActivation, PendingActionConfirmation,
+};
+use crate::actions::spawn_instance_at_root;
+use crate::indexer::build_filelist_header;
 use eframe::egui;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 impl FlistWalkerApp {
     /// pinned selection 優先で action 対象 path を列挙する。
-    fn selected_paths(&self) -> Vec<PathBuf> {
+    pub(in crate::app) fn selected_paths(&self) -> Vec<PathBuf> {
         if !self.shell.runtime.pinned_paths.is_empty() {
             let mut out: Vec<PathBuf> = self.shell.runtime.pinned_paths.iter().cloned().collect();
             out.sort();
@@ -23,24 +29,110 @@ impl FlistWalkerApp {
             .unwrap_or_default()
     }
 
+    /// 右クリックした行の action 対象 path を返す。pin 済みの行なら pin 全件、そうでなければその行だけ。
+    pub(in crate::app) fn menu_target_paths(&self, row: usize) -> Vec<PathBuf> {
+        let Some((path, _)) = self.shell.runtime.results.get(row) else {
+            return Vec::new();
+        };
+        if self.shell.runtime.pinned_paths.contains(path) {
+            self.selected_paths()
+        } else {
+            vec![path.clone()]
+        }
+    }
+
     /// 既定動作で選択 path を実行またはオープンする。
     pub(in crate::app) fn execute_selected(&mut self) {
-        self.execute_selected_with_options(false);
+        let paths = self.selected_paths();
+        self.execute_paths(paths);
     }
 
-    /// Enter 系アクション用に file は親フォルダオープンへ切り替えられる実行入口。
-    pub(in crate::app) fn execute_selected_for_activation(&mut self, open_parent_for_files: bool) {
-        self.execute_selected_with_options(open_parent_for_files);
+    /// 既定動作で指定 path 群を実行またはオープンし、`.` で繰り返す action として記録する。
+    pub(in crate::app) fn execute_paths(&mut self, paths: Vec<PathBuf>) {
+        if !paths.is_empty() {
+            self.shell.runtime.last_action = Some(ActionKind::Execute);
+        }
+        self.execute_paths_with_options(paths, false);
     }
 
-    /// 選択項目の格納フォルダを開く。
+    /// 選択項目の格納フォルダを開く。単一 folder 選択時は設定に応じて root 切替や別 window 起動へ切り替える。
     pub(in crate::app) fn execute_selected_open_folder(&mut self) {
-        self.execute_selected_for_activation(true);
+        let paths = self.selected_paths();
+        self.open_folder_for_paths(paths);
     }
 
-    /// worker dispatch と root 外 path ガードを含めて action を起動する。
-    pub(in crate::app) fn execute_selected_with_options(&mut self, open_parent_for_files: bool) {
-        let paths = self.selected_paths();
+    /// 指定 path 群の格納フォルダを開く。単一 folder なら設定に応じて root 切替や別 window 起動へ切り替える。
+    pub(in crate::app) fn open_folder_for_paths(&mut self, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        self.shell.runtime.last_action = Some(ActionKind::OpenFolder);
+        let activation = self.shell.runtime.folder_activation;
+        if activation != FolderActivation::Open {
+            if let Some(dir) = self.single_folder(&paths) {
+                match activation {
+                    FolderActivation::SetRoot => self.apply_root_change(dir),
+                    FolderActivation::NewWindow => self.open_folder_in_new_window(&dir),
+                    FolderActivation::Open => {}
+                }
+                return;
+            }
+        }
+        self.execute_paths_with_options(paths, true);
+    }
+
+    /// 直前に実行した action を現在の選択へもう一度適用する。
+    pub(in crate::app) fn repeat_last_action(&mut self, ctx: &egui::Context) {
+        let Some(kind) = self.shell.runtime.last_action else {
+            self.set_notice("No action to repeat yet");
+            return;
+        };
+        match kind {
+            ActionKind::Execute => self.execute_selected(),
+            ActionKind::OpenFolder => self.execute_selected_open_folder(),
+            ActionKind::CopyPath => self.copy_selected_paths(ctx),
+            ActionKind::CopyPathForShell => self.copy_selected_paths_for_shell(ctx),
+            ActionKind::CopySiteUrl => self.copy_selected_site_urls(ctx),
+            ActionKind::CopyName => self.copy_current_name(ctx),
+        }
+    }
+
+    /// path が 1 件だけで folder のとき、その path を返す。
+    fn single_folder(&self, paths: &[PathBuf]) -> Option<PathBuf> {
+        let [path] = paths else {
+            return None;
+        };
+        (self.find_entry_kind(path).and_then(|kind| kind.is_dir) == Some(true))
+            .then(|| path.clone())
+    }
+
+    /// folder を root にした新しい FlistWalker process を起動する。
+    fn open_folder_in_new_window(&mut self, dir: &Path) {
+        match spawn_instance_at_root(dir) {
+            Ok(()) => self.set_notice(format!(
+                "Opened new window: {}",
+                normalize_path_for_display(dir)
+            )),
+            Err(err) => self.set_notice(format!("Action failed: {err:#}")),
+        }
+    }
+
+    /// 指定 path 群を root 外ガードと大量実行確認を通して起動する。
+    pub(in crate::app) fn execute_paths_with_options(
+        &mut self,
+        paths: Vec<PathBuf>,
+        open_parent_for_files: bool,
+    ) {
+        self.request_action_paths(paths, open_parent_for_files, ActionOperation::Launch);
+    }
+
+    /// 指定 path 群への操作を root 外ガードと大量実行確認を通して action worker へ送る。
+    pub(in crate::app) fn request_action_paths(
+        &mut self,
+        paths: Vec<PathBuf>,
+        open_parent_for_files: bool,
+        operation: ActionOperation,
+    ) {
         if paths.is_empty() {
             return;
         }
@@ -52,14 +144,100 @@ impl FlistWalkerApp {
             ));
             return;
         }
+        let threshold = self.shell.runtime.action_confirm_threshold;
+        if threshold > 0 && paths.len() > threshold {
+            self.set_notice(match operation {
+                ActionOperation::Launch | ActionOperation::OpenInEditor { .. } => {
+                    "Confirm opening pinned items or cancel first"
+                }
+                ActionOperation::Move { .. } | ActionOperation::UndoMove { .. } => {
+                    "Confirm moving items or cancel first"
+                }
+                ActionOperation::Trash => "Confirm moving items to trash or cancel first",
+            });
+            self.shell.ui.pending_action_confirmation = Some(PendingActionConfirmation {
+                tab_id: self.current_tab_id().unwrap_or_default(),
+                paths,
+                open_parent_for_files,
+                operation,
+            });
+            self.shell.ui.action_dialog_button = 0;
+            return;
+        }
+        let _ = self.dispatch_action_paths(paths, open_parent_for_files, operation);
+    }
 
+    /// 確認済みの大量アクションを起動する。
+    pub(in crate::app) fn confirm_pending_action(&mut self) {
+        let Some(pending) = self.shell.ui.pending_action_confirmation.take() else {
+            return;
+        };
+        self.shell.ui.action_dialog_button = 0;
+        if let Some(blocked) = self.first_action_path_outside_root(&pending.paths) {
+            self.shell.worker_bus.action.clear_request();
+            self.set_notice(format!(
+                "Action blocked: path is outside current root: {}",
+                normalize_path_for_display(&blocked)
+            ));
+            return;
+        }
+        let _ = self.dispatch_action_paths(
+            pending.paths,
+            pending.open_parent_for_files,
+            pending.operation,
+        );
+    }
+
+    /// 大量アクションの確認を取り消す。
+    pub(in crate::app) fn cancel_pending_action(&mut self) {
+        if let Some(pending) = self.shell.ui.pending_action_confirmation.take() {
+            self.shell.ui.action_dialog_button = 0;
+            self.set_notice(match pending.operation {
+                ActionOperation::Launch | ActionOperation::OpenInEditor { .. } => {
+                    "Canceled opening pinned items"
+                }
+                ActionOperation::Move { .. } | ActionOperation::UndoMove { .. } => {
+                    "Canceled moving items"
+                }
+                ActionOperation::Trash => "Canceled moving items to trash",
+            });
+        }
+    }
+
+    /// action worker へ path 群を送り、受理結果を notice へ反映する。受理されたときだけ request id を返す。
+    pub(in crate::app) fn dispatch_action_paths(
+        &mut self,
+        paths: Vec<PathBuf>,
+        open_parent_for_files: bool,
+        operation: ActionOperation,
+    ) -> Option<u64> {
         let request_id = self.shell.worker_bus.action.allocate_request_id();
         let tab_id = self.current_tab_id();
+        let file_operation_notice = match &operation {
+            ActionOperation::Launch => None,
+            ActionOperation::OpenInEditor { .. } => Some(format!(
+                "Opening in editor: {}",
+                paths
+                    .first()
+                    .map(|path| normalize_path_for_display(path))
+                    .unwrap_or_default()
+            )),
+            ActionOperation::Move { .. } => Some(format!("Moving {} item(s)...", paths.len())),
+            ActionOperation::UndoMove { .. } => {
+                Some(format!("Undoing move of {} item(s)...", paths.len()))
+            }
+            ActionOperation::Trash => Some(format!("Moving {} item(s) to trash...", paths.len())),
+        };
+        let roots = match &operation {
+            ActionOperation::UndoMove { targets } => self.action_roots_for_paths(targets),
+            _ => self.action_roots_for_paths(&paths),
+        };
         let req = ActionRequest {
             request_id,
-            root: self.shell.runtime.root.clone(),
+            roots,
             paths: paths.clone(),
             open_parent_for_files,
+            operation,
         };
         match self.shell.worker_bus.action.tx.try_send(req) {
             Ok(()) => {
@@ -77,7 +255,9 @@ impl FlistWalkerApp {
                 );
                 self.shell.worker_bus.action.accept_request(request_id);
                 self.bind_action_request_to_current_tab(request_id);
-                if paths.len() == 1 {
+                if let Some(notice) = file_operation_notice {
+                    self.set_notice(notice);
+                } else if paths.len() == 1 {
                     if open_parent_for_files {
                         self.set_notice(format!(
                             "Action: open containing folder for {}",
@@ -97,6 +277,7 @@ impl FlistWalkerApp {
                 } else {
                     self.set_notice(format!("Action: launched {} items", paths.len()));
                 }
+                Some(request_id)
             }
             Err(std::sync::mpsc::TrySendError::Full(_)) => {
                 super::super::worker_channel::trace_worker_load(
@@ -112,6 +293,7 @@ impl FlistWalkerApp {
                     },
                 );
                 self.set_notice("Action worker is busy");
+                None
             }
             Err(std::sync::mpsc::TrySendError::Disconnected(_)) => {
                 super::super::worker_channel::trace_worker_load(
@@ -128,6 +310,7 @@ impl FlistWalkerApp {
                 );
                 self.clear_all_action_request_state();
                 self.set_notice("Action worker is unavailable");
+                None
             }
         }
     }
@@ -135,9 +318,15 @@ impl FlistWalkerApp {
     /// 選択 path を clipboard 用文字列へ変換して UI 出力へ流す。
     pub(in crate::app) fn copy_selected_paths(&mut self, ctx: &egui::Context) {
         let paths = self.selected_paths();
+        self.copy_paths(ctx, paths);
+    }
+
+    /// 指定 path 群を clipboard 用文字列へ変換して UI 出力へ流す。
+    pub(in crate::app) fn copy_paths(&mut self, ctx: &egui::Context, paths: Vec<PathBuf>) {
         if paths.is_empty() {
             return;
         }
+        self.shell.runtime.last_action = Some(ActionKind::CopyPath);
         let text = Self::clipboard_paths_text(&paths);
         ctx.copy_text(text);
         if paths.len() == 1 {
@@ -150,9 +339,237 @@ impl FlistWalkerApp {
         }
     }
 
+    /// 選択 path を shell 向けに quote して clipboard へ出力する。
+    pub(in crate::app) fn copy_selected_paths_for_shell(&mut self, ctx: &egui::Context) {
+        let paths = self.selected_paths();
+        self.copy_paths_for_shell(ctx, paths);
+    }
+
+    /// 指定 path 群を shell 向けに quote して clipboard へ出力する。
+    pub(in crate::app) fn copy_paths_for_shell(
+        &mut self,
+        ctx: &egui::Context,
+        paths: Vec<PathBuf>,
+    ) {
+        if paths.is_empty() {
+            return;
+        }
+        self.shell.runtime.last_action = Some(ActionKind::CopyPathForShell);
+        ctx.copy_text(Self::shell_quoted_paths_text(&paths));
+        if paths.len() == 1 {
+            self.set_notice("Copied path for shell");
+        } else {
+            self.set_notice(format!("Copied {} paths for shell", paths.len()));
+        }
+    }
+
+    /// 選択 path を root 基準の site 相対 URL (`/assets/logo.png`) に変換して clipboard へ出力する。
+    pub(in crate::app) fn copy_selected_site_urls(&mut self, ctx: &egui::Context) {
+        let paths = self.selected_paths();
+        self.copy_site_urls(ctx, paths);
+    }
+
+    /// 指定 path 群を site 相対 URL に変換して clipboard へ出力する。
+    /// root 外の path は URL にできないため除外する。
+    pub(in crate::app) fn copy_site_urls(&mut self, ctx: &egui::Context, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        self.shell.runtime.last_action = Some(ActionKind::CopySiteUrl);
+        let urls = paths
+            .iter()
+            .filter_map(|path| Self::site_relative_url(path, &self.shell.runtime.root))
+            .collect::<Vec<_>>();
+        let skipped = paths.len() - urls.len();
+        if urls.is_empty() {
+            self.set_notice("Selected paths are outside the root; no site URL to copy");
+            return;
+        }
+        ctx.copy_text(urls.join("\n"));
+        let copied = if urls.len() == 1 {
+            format!("Copied site URL: {}", urls[0])
+        } else {
+            format!("Copied {} site URLs", urls.len())
+        };
+        if skipped > 0 {
+            self.set_notice(format!("{copied} (skipped {skipped} outside root)"));
+        } else {
+            self.set_notice(copied);
+        }
+    }
+
+    /// 表示中の結果を root 基準の tree 表記で clipboard へ出力する。
+    pub(in crate::app) fn copy_results_as_tree(&mut self, ctx: &egui::Context) {
+        if self.shell.runtime.results.is_empty() {
+            return;
+        }
+        let paths = self
+            .shell
+            .runtime
+            .results
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        ctx.copy_text(Self::results_tree_text(&self.shell.runtime.root, &paths));
+        self.set_notice(format!("Copied {} results as tree", paths.len()));
+    }
+
+    /// 表示中の全結果を pin 状態に関係なく表示用 path の改行区切りで clipboard へ出力する。
+    pub(in crate::app) fn copy_all_results(&mut self, ctx: &egui::Context) {
+        if self.shell.runtime.results.is_empty() {
+            return;
+        }
+        ctx.copy_text(self.all_results_text(SystemTime::now()));
+        self.set_notice(format!(
+            "Copied {} results to clipboard",
+            self.shell.runtime.results.len()
+        ));
+    }
+
+    /// `Copy All Results` の本文。`filelist_header_comment` が有効なら FileList 作成と同じ header を先頭に付ける。
+    pub(in crate::app) fn all_results_text(&self, created_at: SystemTime) -> String {
+        let paths = self
+            .shell
+            .runtime
+            .results
+            .iter()
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        let text = Self::clipboard_paths_text(&paths);
+        if !self.shell.runtime.filelist_header_comment {
+            return text;
+        }
+        let header = build_filelist_header(
+            &self.shell.runtime.root,
+            &self.shell.runtime.query_state.query,
+            paths.len(),
+            created_at,
+        );
+        format!("{header}{text}")
+    }
+
+    /// preview 本文を clipboard へ出力する。必要なら本文行へ行番号を付ける。
+    pub(in crate::app) fn copy_preview(&mut self, ctx: &egui::Context) {
+        if self.shell.runtime.preview.is_empty() {
+            return;
+        }
+        if self.shell.ui.copy_preview_line_numbers {
+            ctx.copy_text(number_preview_lines(&self.shell.runtime.preview));
+            self.set_notice("Copied preview with line numbers");
+        } else {
+            ctx.copy_text(self.shell.runtime.preview.clone());
+            self.set_notice("Copied preview");
+        }
+    }
+
+    /// current row の file/folder 名だけを clipboard へ出力する。
+    pub(in crate::app) fn copy_current_name(&mut self, ctx: &egui::Context) {
+        let Some(name) = self
+            .shell
+            .runtime
+            .current_row
+            .and_then(|row| self.shell.runtime.results.get(row))
+            .and_then(|(path, _)| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            return;
+        };
+        self.shell.runtime.last_action = Some(ActionKind::CopyName);
+        ctx.copy_text(name.clone());
+        self.set_notice(format!("Copied name: {name}"));
+    }
+
+    /// current row の file を runtime config の `editor_command_template` で開くよう action worker へ送る。
+    /// 行番号を持つ結果はまだないため、`{line}` 部分は省いて開く。
+    pub(in crate::app) fn open_current_in_editor(&mut self) {
+        let Some(path) = self
+            .shell
+            .runtime
+            .current_row
+            .and_then(|row| self.shell.runtime.results.get(row))
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+        if self.shell.runtime.editor_command_template.is_empty() {
+            self.set_notice(
+                "Set editor_command_template in the runtime config to open in an editor",
+            );
+            return;
+        }
+        if let Some(blocked) = self.first_action_path_outside_root(std::slice::from_ref(&path)) {
+            self.set_notice(format!(
+                "Action blocked: path is outside current root: {}",
+                normalize_path_for_display(&blocked)
+            ));
+            return;
+        }
+        let template = self.shell.runtime.editor_command_template.clone();
+        let _ = self.dispatch_action_paths(
+            vec![path],
+            false,
+            ActionOperation::OpenInEditor {
+                template,
+                line: None,
+            },
+        );
+    }
+
+    /// current row が folder のとき、その folder を現在 tab の root にする。
+    pub(in crate::app) fn set_current_as_root(&mut self) {
+        let Some(path) = self
+            .shell
+            .runtime
+            .current_row
+            .and_then(|row| self.shell.runtime.results.get(row))
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+        if self.find_entry_kind(&path).and_then(|kind| kind.is_dir) != Some(true) {
+            self.set_notice("Set as Root requires a folder");
+            return;
+        }
+        self.apply_root_change(path);
+    }
+
+    /// current row の folder を相対表示の基準にする。highlight は新しい表示文字列で再計算する。
+    pub(in crate::app) fn set_current_as_display_base(&mut self) {
+        let Some(path) = self
+            .shell
+            .runtime
+            .current_row
+            .and_then(|row| self.shell.runtime.results.get(row))
+            .map(|(path, _)| path.clone())
+        else {
+            return;
+        };
+        if self.find_entry_kind(&path).and_then(|kind| kind.is_dir) != Some(true) {
+            self.set_notice("Set as Display Base requires a folder");
+            return;
+        }
+        self.set_notice(format!(
+            "Display base: {}",
+            normalize_path_for_display(&path)
+        ));
+        self.shell.runtime.display_base = Some(path);
+        self.clear_highlight_cache();
+    }
+
+    /// 相対表示の基準を root へ戻す。
+    pub(in crate::app) fn clear_display_base(&mut self) {
+        if self.shell.runtime.display_base.take().is_some() {
+            self.clear_highlight_cache();
+            self.set_notice("Display base reset to root");
+        }
+    }
+
     /// pinned selection を全解除する。
     pub(in crate::app) fn clear_pinned(&mut self) {
         self.shell.runtime.pinned_paths.clear();
+        if self.shell.runtime.hide_pinned_results {
+            self.update_results();
+        }
         self.set_notice("Cleared pinned selections");
     }
 
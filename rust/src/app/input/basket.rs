@@ -0,0 +1,72 @@
+use super::super::FlistWalkerApp;
+use eframe::egui;
+
+impl FlistWalkerApp {
+    /// 現在の選択（pinned 優先）を basket へ追加する。basket は query / root / tab を跨いで保持する。
+    pub(in crate::app) fn add_selection_to_basket(&mut self) {
+        let paths = self.selected_paths();
+        if paths.is_empty() {
+            return;
+        }
+        let mut added = 0usize;
+        for path in paths {
+            if !self.shell.runtime.basket.contains(&path) {
+                self.shell.runtime.basket.push(path);
+                added += 1;
+            }
+        }
+        self.set_notice(format!(
+            "Added {added} item(s) to basket ({} total)",
+            self.shell.runtime.basket.len()
+        ));
+    }
+
+    /// Enter 系の実行入口。basket mode 中は実行せず basket へ追加する。
+    pub(in crate::app) fn execute_or_add_to_basket(&mut self) {
+        if self.shell.runtime.basket_enter_mode {
+            self.add_selection_to_basket();
+        } else {
+            self.execute_selected();
+        }
+    }
+
+    /// basket の 1 項目を取り除く。
+    pub(in crate::app) fn remove_basket_item(&mut self, index: usize) {
+        if index < self.shell.runtime.basket.len() {
+            self.shell.runtime.basket.remove(index);
+            self.refresh_status_line();
+        }
+    }
+
+    /// basket を空にする。
+    pub(in crate::app) fn clear_basket(&mut self) {
+        if self.shell.runtime.basket.is_empty() {
+            return;
+        }
+        self.shell.runtime.basket.clear();
+        self.set_notice("Cleared basket");
+    }
+
+    /// basket の全項目を通常の実行経路（root 外ガードと確認 dialog 付き）で開く。
+    pub(in crate::app) fn open_basket_items(&mut self) {
+        let paths = self.shell.runtime.basket.clone();
+        self.execute_paths_with_options(paths, false);
+    }
+
+    /// basket の全項目の path を clipboard へ出力する。
+    pub(in crate::app) fn copy_basket_paths(&mut self, ctx: &egui::Context) {
+        if self.shell.runtime.basket.is_empty() {
+            return;
+        }
+        ctx.copy_text(Self::clipboard_paths_text(&self.shell.runtime.basket));
+        self.set_notice(format!(
+            "Copied {} basket paths to clipboard",
+            self.shell.runtime.basket.len()
+        ));
+    }
+
+    /// basket window の表示を切り替える。
+    pub(in crate::app) fn toggle_basket_window(&mut self) {
+        self.shell.ui.show_basket = !self.shell.ui.show_basket;
+    }
+}
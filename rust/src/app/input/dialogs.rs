@@ -106,6 +106,72 @@ impl FlistWalkerApp {
             (current + delta).rem_euclid(count) as usize;
     }
 
+    pub(in crate::app) fn handle_action_dialog_shortcuts(&mut self, ctx: &egui::Context) -> bool {
+        let current_tab_id = self.current_tab_id().unwrap_or_default();
+        if self
+            .shell
+            .ui
+            .pending_action_confirmation
+            .as_ref()
+            .is_none_or(|pending| pending.tab_id != current_tab_id)
+        {
+            return false;
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
+            self.cancel_pending_action();
+            return true;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowLeft))
+            || ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowRight))
+            || ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp))
+            || ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown))
+            || ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Tab))
+            || ctx.input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, egui::Key::Tab))
+        {
+            self.shell.ui.action_dialog_button = (self.shell.ui.action_dialog_button + 1) % 2;
+            return true;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter))
+            || ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Space))
+        {
+            if self.shell.ui.action_dialog_button == 0 {
+                self.confirm_pending_action();
+            } else {
+                self.cancel_pending_action();
+            }
+            return true;
+        }
+        true
+    }
+
+    pub(in crate::app) fn handle_goto_row_shortcuts(&mut self, ctx: &egui::Context) -> bool {
+        if self.shell.ui.goto_row_input.is_none() {
+            return false;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
+            self.cancel_goto_row();
+        } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)) {
+            self.apply_goto_row();
+        }
+        true
+    }
+
+    pub(in crate::app) fn handle_save_smart_filter_shortcuts(
+        &mut self,
+        ctx: &egui::Context,
+    ) -> bool {
+        if self.shell.ui.smart_filter_name_input.is_none() {
+            return false;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape)) {
+            self.cancel_save_smart_filter();
+        } else if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)) {
+            self.confirm_save_smart_filter();
+        }
+        true
+    }
+
     pub(in crate::app) fn handle_filelist_dialog_shortcuts(&mut self, ctx: &egui::Context) -> bool {
         let Some(kind) = self.current_filelist_dialog_kind() else {
             self.clear_filelist_dialog_selection();
@@ -0,0 +1,194 @@
+use super::super::{ActionOperation, FileOperation, FlistWalkerApp};
+use crate::entry::{Entry, EntryKind};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// file 操作で変わった (元の path, 移動先) の組。移動先が `None` の path は trash などで消えた。
+type PathChanges = [(PathBuf, Option<PathBuf>)];
+
+/// 変化した path (folder なら配下も) の行き先を返す。操作と無関係なら `None`、消えた path なら `Some(None)`。
+fn changed_path(changes: &PathChanges, path: &Path) -> Option<Option<PathBuf>> {
+    changes.iter().find_map(|(from, to)| {
+        let rest = path.strip_prefix(from).ok()?;
+        Some(to.as_ref().map(|to| {
+            if rest.as_os_str().is_empty() {
+                to.clone()
+            } else {
+                to.join(rest)
+            }
+        }))
+    })
+}
+
+/// 操作後の path を返す。消えた path は `None`。
+fn apply_change(changes: &PathChanges, path: PathBuf) -> Option<PathBuf> {
+    changed_path(changes, &path).unwrap_or(Some(path))
+}
+
+fn remap_entries(changes: &PathChanges, entries: &[Entry]) -> Vec<Entry> {
+    entries
+        .iter()
+        .filter_map(|entry| match changed_path(changes, &entry.path) {
+            Some(path) => path.map(|path| Entry::new(path, entry.kind)),
+            None => Some(entry.clone()),
+        })
+        .collect()
+}
+
+fn remap_results(changes: &PathChanges, results: &mut Vec<(PathBuf, f64)>) {
+    *results = results
+        .drain(..)
+        .filter_map(|(path, score)| Some((apply_change(changes, path)?, score)))
+        .collect();
+}
+
+fn remap_path_set(changes: &PathChanges, paths: &mut HashSet<PathBuf>) {
+    *paths = paths
+        .drain()
+        .filter_map(|path| apply_change(changes, path))
+        .collect();
+}
+
+fn remap_kinds(changes: &PathChanges, kinds: &mut HashMap<PathBuf, EntryKind>) {
+    *kinds = kinds
+        .drain()
+        .filter_map(|(path, kind)| Some((apply_change(changes, path)?, kind)))
+        .collect();
+}
+
+/// 結果が減ったときに current row を範囲内へ戻す。
+fn clamp_row(row: Option<usize>, len: usize) -> Option<usize> {
+    row.filter(|_| len > 0).map(|row| row.min(len - 1))
+}
+
+impl FlistWalkerApp {
+    /// dialog で選んだ folder へ指定 path 群を移動する。
+    pub(in crate::app) fn move_paths_via_dialog(&mut self, paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        let dialog_root = Self::browse_dialog_start_location(&self.shell.runtime.root);
+        match self.select_root_via_dialog(&dialog_root) {
+            Ok(Some(dest)) => self.move_paths_to_directory(paths, &dest),
+            Ok(None) => {}
+            Err(err) => self.set_notice(format!("Move failed: {err}")),
+        }
+    }
+
+    /// path 群を `dest` 直下へ移動する request を、root 外ガードと大量実行確認を通して action worker へ送る。
+    pub(in crate::app) fn move_paths_to_directory(&mut self, paths: Vec<PathBuf>, dest: &Path) {
+        let (paths, targets): (Vec<_>, Vec<_>) = paths
+            .into_iter()
+            .filter_map(|path| {
+                let target = dest.join(path.file_name()?);
+                Some((path, target))
+            })
+            .unzip();
+        self.request_action_paths(paths, false, ActionOperation::Move { targets });
+    }
+
+    /// path 群を OS の trash へ移す request を、root 外ガードと大量実行確認を通して action worker へ送る。
+    pub(in crate::app) fn trash_paths(&mut self, paths: Vec<PathBuf>) {
+        self.request_action_paths(paths, false, ActionOperation::Trash);
+    }
+
+    /// 直前の file 操作を取り消す。移動は元の場所へ戻す request を action worker へ送る。
+    /// trash は OS の trash から戻す手段がないため、取り消せないことだけを伝える。
+    pub(in crate::app) fn undo_last_operation(&mut self) {
+        match &self.shell.runtime.last_operation {
+            Some(FileOperation::Move(moves)) => {
+                let moves = moves.clone();
+                let (targets, paths): (Vec<_>, Vec<_>) = moves.iter().cloned().unzip();
+                // Keep the move undoable until the worker actually takes the request.
+                if let Some(request_id) =
+                    self.dispatch_action_paths(paths, false, ActionOperation::UndoMove { targets })
+                {
+                    self.shell.runtime.last_operation = None;
+                    self.shell.runtime.pending_undo = Some((request_id, moves));
+                }
+            }
+            Some(FileOperation::Trash(_)) => {
+                self.set_notice("Trash cannot be undone here; restore the items from the trash")
+            }
+            Some(FileOperation::UndoMove(_)) | None => self.set_notice("Nothing to undo"),
+        }
+    }
+
+    /// 取り消し要求の応答を受けたら、戻せなかった移動を再び取り消せるよう last_operation へ戻す。
+    /// 応答待ちの間に別の file 操作が完了していれば、そちらを優先して残す。
+    pub(in crate::app) fn settle_pending_undo(
+        &mut self,
+        request_id: u64,
+        undone: Option<&FileOperation>,
+    ) {
+        let Some((pending_id, moves)) = self.shell.runtime.pending_undo.take() else {
+            return;
+        };
+        if pending_id != request_id {
+            self.shell.runtime.pending_undo = Some((pending_id, moves));
+            return;
+        }
+        let undone = match undone {
+            Some(FileOperation::UndoMove(undone)) => undone.as_slice(),
+            _ => &[],
+        };
+        let remaining = moves
+            .into_iter()
+            .filter(|(from, to)| {
+                !undone
+                    .iter()
+                    .any(|(back_from, back_to)| back_from == to && back_to == from)
+            })
+            .collect::<Vec<_>>();
+        if !remaining.is_empty() && self.shell.runtime.last_operation.is_none() {
+            self.shell.runtime.last_operation = Some(FileOperation::Move(remaining));
+        }
+    }
+
+    /// 完了した file 操作を全 tab の index・結果・pin・種別 cache と basket へ反映し、取り消し用に記録する。
+    pub(in crate::app) fn apply_file_operation(&mut self, operation: FileOperation) {
+        let changes = match &operation {
+            FileOperation::Move(moves) | FileOperation::UndoMove(moves) => moves
+                .iter()
+                .map(|(from, to)| (from.clone(), Some(to.clone())))
+                .collect::<Vec<_>>(),
+            FileOperation::Trash(paths) => paths.iter().map(|path| (path.clone(), None)).collect(),
+        };
+        if !matches!(operation, FileOperation::UndoMove(_)) {
+            self.shell.runtime.last_operation = Some(operation);
+        }
+        let active_tab = self.shell.tabs.active_tab_index();
+        for (index, tab) in self.shell.tabs.iter_mut().enumerate() {
+            if index == active_tab {
+                continue;
+            }
+            let index_state = &mut tab.index_state;
+            index_state.index.entries = remap_entries(&changes, &index_state.index.entries);
+            index_state.all_entries = Arc::new(remap_entries(&changes, &index_state.all_entries));
+            index_state.entries = Arc::new(remap_entries(&changes, &index_state.entries));
+            let result_state = &mut tab.result_state;
+            remap_results(&changes, &mut result_state.results);
+            remap_results(&changes, &mut result_state.base_results);
+            result_state.current_row =
+                clamp_row(result_state.current_row, result_state.results.len());
+            remap_path_set(&changes, &mut result_state.pinned_paths);
+            remap_kinds(&changes, &mut tab.entry_kind_cache.entries);
+        }
+        let runtime = &mut self.shell.runtime;
+        runtime.index.entries = remap_entries(&changes, &runtime.index.entries);
+        runtime.all_entries = Arc::new(remap_entries(&changes, &runtime.all_entries));
+        runtime.entries = Arc::new(remap_entries(&changes, &runtime.entries));
+        remap_results(&changes, &mut runtime.results);
+        remap_results(&changes, &mut runtime.base_results);
+        runtime.current_row = clamp_row(runtime.current_row, runtime.results.len());
+        remap_path_set(&changes, &mut runtime.pinned_paths);
+        runtime.basket = runtime
+            .basket
+            .drain(..)
+            .filter_map(|path| apply_change(&changes, path))
+            .collect();
+        remap_kinds(&changes, &mut self.shell.cache.entry_kind.entries);
+        self.request_preview_for_current();
+    }
+}
@@ -138,9 +138,12 @@ impl FlistWalkerApp {
     }
 
     pub(in crate::app) fn mark_query_edited(&mut self) {
+        // Typing returns from the Recent view to normal search.
+        self.shell.runtime.recent_view = false;
         self.reset_query_history_navigation();
         self.set_query_history_dirty_since(Some(Instant::now()));
         self.invalidate_result_sort(true);
+        self.mark_ui_state_dirty();
     }
 
     pub(in crate::app) fn commit_query_history_if_needed(&mut self, force: bool) {
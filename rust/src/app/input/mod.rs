@@ -1,5 +1,7 @@
 mod actions;
+mod basket;
 mod dialogs;
+mod file_ops;
 mod history;
 mod ime;
 mod navigation;
@@ -1,4 +1,42 @@
 use super::super::FlistWalkerApp;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// type-ahead 文字列に最も合う行を返す。ファイル名の前方一致 (大文字小文字無視) を結果順で優先し、
+/// 無ければファイル名への fuzzy score が最大の行 (同点は上の行) を選ぶ。
+fn type_ahead_best_row(results: &[(PathBuf, f64)], typed: &str) -> Option<usize> {
+    let typed_lower = typed.to_lowercase();
+    let name_of = |path: &PathBuf| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string())
+    };
+    if let Some(row) = results
+        .iter()
+        .position(|(path, _)| name_of(path).to_lowercase().starts_with(&typed_lower))
+    {
+        return Some(row);
+    }
+    let matcher = SkimMatcherV2::default();
+    results
+        .iter()
+        .enumerate()
+        .filter_map(|(row, (path, _))| {
+            matcher
+                .fuzzy_match(&name_of(path), typed)
+                .map(|score| (row, score))
+        })
+        .fold(
+            None,
+            |best: Option<(usize, i64)>, (row, score)| match best {
+                Some((_, best_score)) if best_score >= score => best,
+                _ => Some((row, score)),
+            },
+        )
+        .map(|(row, _)| row)
+}
 
 impl FlistWalkerApp {
     /// ページ単位のカーソル移動を行う。
@@ -6,14 +44,23 @@ impl FlistWalkerApp {
         self.move_row(direction.saturating_mul(Self::PAGE_MOVE_ROWS));
     }
 
-    /// 結果一覧内の current row を相対移動する。
+    /// 結果一覧内の current row を相対移動する。`wrap_navigation` 有効時は端の行から外へ出ると反対端へ回り込む。
     pub(in crate::app) fn move_row(&mut self, delta: isize) {
         self.commit_query_history_if_needed(true);
         if self.shell.runtime.results.is_empty() {
             return;
         }
         let row = self.shell.runtime.current_row.unwrap_or(0) as isize;
-        let next = (row + delta).clamp(0, self.shell.runtime.results.len() as isize - 1) as usize;
+        let len = self.shell.runtime.results.len() as isize;
+        let target = row + delta;
+        // wrap_navigation only wraps from the edge row, so a page move first stops at the end.
+        let next = if self.shell.runtime.wrap_navigation && target >= len && row == len - 1 {
+            0
+        } else if self.shell.runtime.wrap_navigation && target < 0 && row == 0 {
+            len as usize - 1
+        } else {
+            target.clamp(0, len - 1) as usize
+        };
         self.set_current_row(Some(next));
         self.request_scroll_to_current();
         self.request_preview_for_current();
@@ -29,20 +76,164 @@ impl FlistWalkerApp {
                     self.shell.runtime.pinned_paths.remove(&path);
                 } else {
                     self.shell.runtime.pinned_paths.insert(path);
+                    if self.shell.runtime.hide_pinned_results {
+                        self.hide_pinned_row(row);
+                    }
                 }
                 self.refresh_status_line();
             }
         }
     }
 
+    /// current row 以降で current row と同じ拡張子を持つ結果をまとめて pin する。
+    pub(in crate::app) fn pin_same_extension_below(&mut self) {
+        let Some(row) = self.shell.runtime.current_row else {
+            return;
+        };
+        let Some(extension) = self
+            .shell
+            .runtime
+            .results
+            .get(row)
+            .and_then(|(path, _)| path.extension())
+            .map(|extension| extension.to_string_lossy().to_string())
+        else {
+            self.set_notice("Current row has no extension");
+            return;
+        };
+        let matching: Vec<_> = self.shell.runtime.results[row..]
+            .iter()
+            .filter(|(path, _)| {
+                path.extension()
+                    .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case(&extension))
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        let mut pinned = 0usize;
+        for path in matching {
+            if self.shell.runtime.pinned_paths.insert(path) {
+                pinned += 1;
+            }
+        }
+        self.set_notice(format!(
+            "Pinned {pinned} .{extension} result(s) from current row"
+        ));
+        if pinned > 0 && self.shell.runtime.hide_pinned_results {
+            self.update_results();
+        } else {
+            self.refresh_status_line();
+        }
+    }
+
+    /// Hide pinned 中に pin した行を一覧から外し、同じ位置の次行を current row にする。
+    fn hide_pinned_row(&mut self, row: usize) {
+        self.shell.runtime.results.remove(row);
+        if self.shell.runtime.results.is_empty() {
+            self.set_current_row(None);
+            self.shell.runtime.preview.clear();
+            return;
+        }
+        self.set_current_row(Some(row.min(self.shell.runtime.results.len() - 1)));
+        self.request_scroll_to_current();
+        self.request_preview_for_current();
+    }
+
+    /// pin 済み結果の非表示を切り替える。解除時は再検索して pin 済み行を戻す。
+    pub(in crate::app) fn set_hide_pinned_results(&mut self, hide: bool) {
+        if self.shell.runtime.hide_pinned_results == hide {
+            return;
+        }
+        self.shell.runtime.hide_pinned_results = hide;
+        self.update_results();
+    }
+
     pub(in crate::app) fn toggle_pin_current_from_tab(&mut self) {
         let before = self.shell.runtime.current_row;
+        let results_len = self.shell.runtime.results.len();
         self.toggle_pin_current();
-        if self.shell.runtime.tab_pin_moves_to_next_row && before.is_some() {
+        // A row hidden by Hide pinned already leaves the cursor on the next row.
+        let row_hidden = self.shell.runtime.results.len() < results_len;
+        if self.shell.runtime.tab_pin_moves_to_next_row && before.is_some() && !row_hidden {
             self.move_row(1);
         }
     }
 
+    /// 行番号入力を開く。
+    pub(in crate::app) fn open_goto_row(&mut self) {
+        if self.shell.runtime.results.is_empty() {
+            return;
+        }
+        self.shell.ui.goto_row_input = Some(String::new());
+    }
+
+    pub(in crate::app) fn cancel_goto_row(&mut self) {
+        self.shell.ui.goto_row_input = None;
+        self.request_focus_query();
+    }
+
+    /// 入力された 1-based 行番号へ current row を移動する。
+    pub(in crate::app) fn apply_goto_row(&mut self) {
+        let Some(input) = self.shell.ui.goto_row_input.take() else {
+            return;
+        };
+        self.request_focus_query();
+        match input.trim().parse::<usize>() {
+            Ok(row) => self.go_to_row(row),
+            Err(_) => self.set_notice(format!("Invalid row number: {}", input.trim())),
+        }
+    }
+
+    pub(in crate::app) fn go_to_row(&mut self, row: usize) {
+        self.commit_query_history_if_needed(true);
+        let len = self.shell.runtime.results.len();
+        if len == 0 {
+            return;
+        }
+        self.set_current_row(Some(row.clamp(1, len) - 1));
+        self.request_scroll_to_current();
+        self.request_preview_for_current();
+        self.refresh_status_line();
+    }
+
+    /// 入力中の type-ahead 文字列を返す。最後の入力から一定時間経つと `None` になる。
+    pub(in crate::app) fn active_type_ahead(&self, now: Instant) -> Option<&str> {
+        let last = self.shell.ui.last_type_ahead_input?;
+        if self.shell.ui.type_ahead_buffer.is_empty()
+            || now.saturating_duration_since(last) >= Self::TYPE_AHEAD_IDLE_RESET
+        {
+            return None;
+        }
+        Some(&self.shell.ui.type_ahead_buffer)
+    }
+
+    /// 結果リスト操作中に打たれた文字を type-ahead 文字列へ足し、最も合う行へ移動する。
+    /// 空白と制御文字は無視し、先頭の `.` は繰り返し操作に譲る。文字を取り込んだら true を返す。
+    pub(in crate::app) fn type_ahead_select(&mut self, text: &str, now: Instant) -> bool {
+        if self.active_type_ahead(now).is_none() {
+            self.shell.ui.type_ahead_buffer.clear();
+        }
+        let buffer = &mut self.shell.ui.type_ahead_buffer;
+        let before = buffer.len();
+        for ch in text.chars() {
+            if ch.is_whitespace() || ch.is_control() || (ch == '.' && buffer.is_empty()) {
+                continue;
+            }
+            buffer.push(ch);
+        }
+        if buffer.len() == before {
+            return false;
+        }
+        self.shell.ui.last_type_ahead_input = Some(now);
+        let typed = self.shell.ui.type_ahead_buffer.clone();
+        if let Some(row) = type_ahead_best_row(&self.shell.runtime.results, &typed) {
+            self.set_current_row(Some(row));
+            self.request_scroll_to_current();
+            self.request_preview_for_current();
+            self.refresh_status_line();
+        }
+        true
+    }
+
     /// 先頭行へ移動し preview を更新する。
     pub(in crate::app) fn move_to_first_row(&mut self) {
         self.commit_query_history_if_needed(true);
@@ -1,5 +1,6 @@
 use super::super::FlistWalkerApp;
 use eframe::egui;
+use std::time::Instant;
 
 impl FlistWalkerApp {
     pub(in crate::app) fn primary_shortcut_label() -> &'static str {
@@ -14,9 +15,18 @@ impl FlistWalkerApp {
     }
 
     pub(in crate::app) fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if self.handle_action_dialog_shortcuts(ctx) {
+            return;
+        }
         if self.handle_filelist_dialog_shortcuts(ctx) {
             return;
         }
+        if self.handle_goto_row_shortcuts(ctx) {
+            return;
+        }
+        if self.handle_save_smart_filter_shortcuts(ctx) {
+            return;
+        }
         let query_focused = ctx.memory(|m| m.has_focus(self.shell.ui.query_input_id));
         self.handle_shortcuts_with_focus(ctx, query_focused);
     }
@@ -54,6 +64,22 @@ impl FlistWalkerApp {
         }
     }
 
+    /// 修飾キー無しで打たれた文字を type-ahead 用に集める。
+    fn collect_type_ahead_text(ctx: &egui::Context) -> String {
+        ctx.input(|i| {
+            if i.modifiers.ctrl || i.modifiers.alt || i.modifiers.command || i.modifiers.mac_cmd {
+                return String::new();
+            }
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect()
+        })
+    }
+
     fn consume_copy_event_shortcut(ctx: &egui::Context) -> bool {
         let modifiers = ctx.input(|i| i.modifiers);
         #[cfg(target_os = "macos")]
@@ -117,6 +143,32 @@ impl FlistWalkerApp {
         ctx.input_mut(|i| i.consume_key(mods, key))
     }
 
+    /// key repeat ではない新しい押下だけを消費する。押しっぱなしで届く repeat は残す。
+    fn consume_fresh_key_press(
+        ctx: &egui::Context,
+        modifiers: egui::Modifiers,
+        key: egui::Key,
+    ) -> bool {
+        ctx.input_mut(|i| {
+            let Some(index) = i.events.iter().position(|event| {
+                matches!(
+                    event,
+                    egui::Event::Key {
+                        key: event_key,
+                        pressed: true,
+                        repeat: false,
+                        modifiers: event_modifiers,
+                        ..
+                    } if *event_key == key && event_modifiers.matches_logically(modifiers)
+                )
+            }) else {
+                return false;
+            };
+            i.events.remove(index);
+            true
+        })
+    }
+
     pub(in crate::app) fn consume_emacs_shortcut(
         &self,
         ctx: &egui::Context,
@@ -184,6 +236,28 @@ impl FlistWalkerApp {
                 self.close_root_dropdown(ctx);
                 return;
             }
+            // 開いている間の残りのキー入力は dropdown の絞り込み欄へ渡す。
+            return;
+        }
+
+        if self.shell.ui.quick_look.is_some()
+            && ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Escape))
+        {
+            self.close_quick_look();
+            return;
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::ALT, egui::Key::G)) {
+            self.open_goto_row();
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::ALT, egui::Key::E)) {
+            self.cycle_entry_kind_filter();
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::ALT, egui::Key::M)) {
+            self.cycle_match_mode();
+            return;
         }
 
         if Self::consume_gui_shortcut(ctx, egui::Key::T, true) {
@@ -240,6 +314,10 @@ impl FlistWalkerApp {
             self.browse_for_root();
             return;
         }
+        if Self::consume_gui_shortcut(ctx, egui::Key::A, true) {
+            self.copy_all_results(ctx);
+            return;
+        }
 
         if self.shell.runtime.query_state.is_history_search_active() {
             if self.consume_emacs_shortcut(ctx, egui::Key::N, false) {
@@ -318,32 +396,72 @@ impl FlistWalkerApp {
         if self.consume_emacs_shortcut(ctx, egui::Key::I, false) {
             self.toggle_pin_current_from_tab();
         }
+        // Ctrl+Space always pins in place, even when tab_pin_moves_to_next_row advances Tab.
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::CTRL, egui::Key::Space)) {
+            self.toggle_pin_current();
+        }
         if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown)) {
             self.move_row(1);
         }
         if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp)) {
             self.move_row(-1);
         }
+        // Ctrl+Home/End jumps even while the query box keeps plain Home/End for caret movement.
+        if Self::consume_gui_shortcut(ctx, egui::Key::Home, false) {
+            self.move_to_first_row();
+        }
+        if Self::consume_gui_shortcut(ctx, egui::Key::End, false) {
+            self.move_to_last_row();
+        }
+        // Shift+PageUp/PageDown scroll the preview while the results cursor stays put.
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, egui::Key::PageUp)) {
+            self.scroll_preview_page(-1);
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, egui::Key::PageDown)) {
+            self.scroll_preview_page(1);
+        }
         if self.consume_emacs_shortcut(ctx, egui::Key::J, false)
             || self.consume_emacs_shortcut(ctx, egui::Key::M, false)
         {
-            self.execute_selected();
+            self.execute_or_add_to_basket();
         }
         if ctx.input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, egui::Key::Enter)) {
             self.execute_selected_open_folder();
         }
         if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)) {
-            self.execute_selected();
+            self.execute_or_add_to_basket();
+        }
+        if Self::consume_gui_shortcut(ctx, egui::Key::B, true) {
+            self.add_selection_to_basket();
+        }
+        // The query box keeps Ctrl+Z for text undo unless a file operation can be undone.
+        if (!query_focused || self.shell.runtime.last_operation.is_some())
+            && Self::consume_gui_shortcut(ctx, egui::Key::Z, false)
+        {
+            self.undo_last_operation();
         }
 
         if self.shell.ui.ime_composition_active {
             return;
         }
+        // Backspace only re-roots once the query is empty so normal deletion keeps working.
+        // Key repeat from holding Backspace while deleting the query must not move the root up.
+        if self.shell.runtime.query_state.query.is_empty()
+            && Self::consume_fresh_key_press(ctx, egui::Modifiers::NONE, egui::Key::Backspace)
+        {
+            self.apply_root_change_to_parent();
+        }
         // Regression guard: query focus must not disable row movement/pin toggle/execute shortcuts.
         if query_focused {
             return;
         }
 
+        let typed = Self::collect_type_ahead_text(ctx);
+        if !typed.is_empty() && self.type_ahead_select(&typed, Instant::now()) {
+            // A `.` inside a file name must not also repeat the last action.
+            ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Period));
+        }
+
         if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Home)) {
             self.move_to_first_row();
         }
@@ -364,5 +482,11 @@ impl FlistWalkerApp {
         {
             self.move_page(-1);
         }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Space)) {
+            self.toggle_quick_look();
+        }
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Period)) {
+            self.repeat_last_action(ctx);
+        }
     }
 }
@@ -263,6 +263,9 @@ impl FlistWalkerApp {
                 anchor = cursor;
                 text_changed = true;
                 cursor_changed = true;
+            } else {
+                // Empty kill buffer: yank from the system clipboard via the focused TextEdit.
+                ctx.send_viewport_cmd(egui::ViewportCommand::RequestPaste);
             }
         } else if pressed(egui::Key::U) && cursor > 0 {
             Self::remove_char_range(&mut self.shell.runtime.query_state.query, 0, cursor);
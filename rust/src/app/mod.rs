@@ -3,6 +3,7 @@ use crate::indexer::{IndexBuildResult, IndexSource};
 use crate::path_utils::normalize_windows_path_buf;
 use crate::ui_model::{
     display_path_with_mode, match_positions_for_path_with_compiled, normalize_path_for_display,
+    number_preview_lines,
 };
 use crate::updater::{
     forced_update_check_failure_message, self_update_disabled, should_skip_update_prompt,
@@ -56,7 +57,8 @@ mod workers;
 
 use action_authorization::{lexical_action_path_precheck, ActionPathPrecheck};
 use cache::{
-    EntryKindCacheState, HighlightCacheState, IgnoreMatcherCacheState, PreviewCacheState,
+    EntryKindCacheState, EntrySize, EntrySizeCacheState, HighlightCacheState, HighlightScope,
+    HighlightedDisplay, IgnoreMatcherCacheState, MarkdownPreviewCacheState, PreviewCacheState,
     SortMetadataCacheState,
 };
 use coordinator::normalized_compare_key;
@@ -65,14 +67,18 @@ use index_worker::spawn_index_worker;
 use pipeline_owner::PipelineOwner;
 use query_state::QueryState;
 use search_coordinator::SearchCoordinator;
-use session::{LaunchSettings, SavedTabState, SavedWindowGeometry, TabAccentColor};
+use session::{
+    LaunchSettings, SavedFilterToggles, SavedTabState, SavedWindowGeometry, SmartFilter,
+    SmartFilterMode, SourceFilterMemory, TabAccentColor,
+};
 use state::{
-    AppRuntimeState, AppShellState, BackgroundIndexState, CacheStateBundle, ClosedTabState,
-    FeatureStateBundle, FileListDialogKind, FileListManager, HighlightCacheKey,
-    PendingActiveIndexFinish, PendingFileListAfterIndex, PendingFileListAncestorConfirmation,
-    PendingFileListConfirmation, PendingFileListUseWalkerConfirmation, ResultSortMode,
-    ResultSortScope, RootBrowserState, SortMetadata, TabAccentPalette, TabDragState,
-    TabSessionState,
+    ActionKind, AppRuntimeState, AppShellState, BackgroundIndexState, CacheStateBundle,
+    ClosedTabState, FeatureStateBundle, FileListDialogKind, FileListManager, FolderActivation,
+    HighlightCacheKey, PendingActionConfirmation, PendingActiveIndexFinish,
+    PendingFileListAfterIndex, PendingFileListAncestorConfirmation, PendingFileListConfirmation,
+    PendingFileListUseWalkerConfirmation, QuickLookState, ResetSettingsDialogState,
+    ResultKindGrouping, ResultSortMode, ResultSortScope, RootBrowserState, SortMetadata,
+    TabAccentPalette, TabDragState, TabSessionState,
 };
 use tab_state::AppTabState;
 use ui_state::RuntimeUiState;
@@ -81,10 +87,10 @@ use worker_bus::{
     UpdateWorkerBus, WorkerBus,
 };
 use worker_protocol::{
-    ActionRequest, ActionResponse, FileListRequest, FileListResponse, IndexEntry, IndexRequest,
-    IndexResponse, KindResolveRequest, PreviewRequest, PreviewResponse, SearchRequest,
-    SearchResponse, SortMetadataRequest, SortMetadataResponse, UpdateRequest, UpdateRequestKind,
-    UpdateResponse,
+    ActionOperation, ActionRequest, ActionResponse, FileListRequest, FileListResponse,
+    FileOperation, IndexEntry, IndexRequest, IndexResponse, KindResolveRequest, PreviewRequest,
+    PreviewResponse, SearchRequest, SearchResponse, SortMetadataRequest, SortMetadataResponse,
+    UpdateRequest, UpdateRequestKind, UpdateResponse,
 };
 use worker_runtime::WorkerRuntime;
 use workers::{
@@ -103,6 +109,12 @@ fn walker_truncated_notice(limit: usize) -> String {
     )
 }
 
+fn walker_directory_cap_notice(limit: usize, directories: usize) -> String {
+    format!(
+        "Walker took only the first {limit} entries from {directories} large folder(s) (set walker_max_entries_per_dir in the config file to adjust)"
+    )
+}
+
 impl TabAccentColor {
     pub(super) const ALL: [Self; 8] = [
         Self::Teal,
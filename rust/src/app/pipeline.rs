@@ -1,10 +1,13 @@
 use super::{
-    walker_truncated_notice, AppTabState, Entry, FlistWalkerApp, IndexCoordinator, IndexEntry,
-    IndexRequest, IndexResponse, IndexSource, PendingActiveIndexFinish, PipelineOwner,
+    walker_directory_cap_notice, walker_truncated_notice, AppTabState, Entry, FlistWalkerApp,
+    IndexCoordinator, IndexEntry, IndexRequest, IndexResponse, IndexSource,
+    PendingActiveIndexFinish, PipelineOwner, ResultSortMode, ResultSortScope, SavedFilterToggles,
+    SmartFilterMode,
 };
 use crate::app::index_coordinator::IndexResponseRoute;
 use crate::app::tabs::BackgroundIndexResponseEffect;
-use crate::path_utils::path_key;
+use crate::indexer::find_filelist_in_first_level;
+use crate::path_utils::{normalize_path_for_display, path_key};
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -68,6 +71,8 @@ impl FlistWalkerApp {
         reset_kind_resolution: bool,
         mark_inflight: bool,
     ) {
+        // A new index request replaces the frozen snapshot, so refreshing also unfreezes.
+        self.shell.indexing.frozen = false;
         let query_non_empty = !self.shell.runtime.query_state.query.trim().is_empty();
         if mark_inflight {
             self.shell
@@ -84,6 +89,19 @@ impl FlistWalkerApp {
     }
 
     pub(super) fn request_index_refresh(&mut self) {
+        self.request_index_refresh_with(false);
+    }
+
+    /// 現在 root の FileList だけを読み直す。FileList が無ければ Walker へ戻さず notice を出す。
+    pub(super) fn reload_filelist(&mut self) {
+        if find_filelist_in_first_level(&self.shell.runtime.root).is_none() {
+            self.set_notice("No FileList.txt or filelist.txt in the current root to reload");
+            return;
+        }
+        self.request_index_refresh_with(true);
+    }
+
+    fn request_index_refresh_with(&mut self, filelist_only: bool) {
         self.ensure_entry_filters();
         self.invalidate_result_sort(true);
         self.clear_sort_metadata_cache();
@@ -99,9 +117,14 @@ impl FlistWalkerApp {
             request_id,
             tab_id: tab_id.unwrap_or_default(),
             root: self.shell.runtime.root.clone(),
-            use_filelist: self.shell.runtime.use_filelist,
+            use_filelist: self.shell.runtime.use_filelist || filelist_only,
             include_files: self.shell.runtime.include_files,
             include_dirs: self.shell.runtime.include_dirs,
+            throttle: self.shell.runtime.walker_throttle,
+            extra_roots: self.extra_index_roots(),
+            canonicalize_entries: self.shell.runtime.walker_canonicalize_entries,
+            git_tracked_only: self.shell.runtime.git_tracked_only && !filelist_only,
+            filelist_only,
         };
         self.enqueue_index_request(req);
         self.dispatch_index_queue();
@@ -122,6 +145,11 @@ impl FlistWalkerApp {
             use_filelist: false,
             include_files: self.shell.runtime.include_files,
             include_dirs: self.shell.runtime.include_dirs,
+            throttle: self.shell.runtime.walker_throttle,
+            extra_roots: Vec::new(),
+            canonicalize_entries: self.shell.runtime.walker_canonicalize_entries,
+            git_tracked_only: false,
+            filelist_only: false,
         };
         self.enqueue_index_request(req);
         self.dispatch_index_queue();
@@ -148,6 +176,11 @@ impl FlistWalkerApp {
             use_filelist: tab.use_filelist,
             include_files: tab.include_files,
             include_dirs: tab.include_dirs,
+            throttle: shell.runtime.walker_throttle,
+            extra_roots: Vec::new(),
+            canonicalize_entries: shell.runtime.walker_canonicalize_entries,
+            git_tracked_only: false,
+            filelist_only: false,
         };
         self.enqueue_index_request(req);
         self.dispatch_index_queue();
@@ -196,6 +229,9 @@ impl FlistWalkerApp {
         files_changed: bool,
         dirs_changed: bool,
     ) {
+        if use_filelist_changed && self.shell.runtime.remember_filters_per_source {
+            self.swap_source_filter_toggles();
+        }
         let mut reindex = use_filelist_changed;
         reindex |= files_changed || dirs_changed;
         if self.use_filelist_requires_locked_filters()
@@ -211,6 +247,139 @@ impl FlistWalkerApp {
         }
     }
 
+    /// Files+Folders → Files only → Folders only の順に種別フィルタを切り替える。
+    pub(super) fn cycle_entry_kind_filter(&mut self) {
+        if self.use_filelist_requires_locked_filters() {
+            self.set_notice("Files/Folders filter is locked while using FileList");
+            return;
+        }
+        let include_files = self.shell.runtime.include_files;
+        let include_dirs = self.shell.runtime.include_dirs;
+        let (next_files, next_dirs, label) = match (include_files, include_dirs) {
+            (true, true) => (true, false, "files only"),
+            (true, false) => (false, true, "folders only"),
+            _ => (true, true, "files and folders"),
+        };
+        self.shell.runtime.include_files = next_files;
+        self.shell.runtime.include_dirs = next_dirs;
+        self.set_notice(format!("Showing {label}"));
+        self.maybe_reindex_from_filter_toggles(
+            false,
+            include_files != next_files,
+            include_dirs != next_dirs,
+        );
+    }
+
+    /// fuzzy と regex の match mode を切り替えて再検索する。
+    pub(super) fn cycle_match_mode(&mut self) {
+        self.shell.runtime.use_regex = !self.shell.runtime.use_regex;
+        let label = if self.shell.runtime.use_regex {
+            "regex"
+        } else {
+            "fuzzy"
+        };
+        self.set_notice(format!("Match mode: {label}"));
+        self.invalidate_result_sort(true);
+        self.update_results();
+    }
+
+    /// 保存済み smart filter の query・match mode・種別フィルタを現在タブへ適用して再検索する。
+    pub(super) fn apply_smart_filter(&mut self, index: usize) {
+        let Some(filter) = self.shell.ui.smart_filters.get(index).cloned() else {
+            return;
+        };
+        let include_files = self.shell.runtime.include_files;
+        let include_dirs = self.shell.runtime.include_dirs;
+        // Both kinds off would hide everything; treat it like the default Files+Folders.
+        let (next_files, next_dirs) = if filter.include_files || filter.include_dirs {
+            (filter.include_files, filter.include_dirs)
+        } else {
+            (true, true)
+        };
+        self.shell.runtime.query_state.query = filter.query;
+        self.shell.runtime.use_regex = filter.mode == SmartFilterMode::Regex;
+        self.shell.runtime.include_files = next_files;
+        self.shell.runtime.include_dirs = next_dirs;
+        self.mark_query_edited();
+        self.maybe_reindex_from_filter_toggles(
+            false,
+            include_files != next_files,
+            include_dirs != next_dirs,
+        );
+        self.update_results();
+        self.request_focus_query();
+        self.set_notice(format!("Applied filter: {}", filter.name));
+    }
+
+    /// 全保存 root 検索が有効なら現在 root 以外の保存 root を返す。
+    fn extra_index_roots(&self) -> Vec<PathBuf> {
+        if !self.shell.runtime.search_all_saved_roots {
+            return Vec::new();
+        }
+        self.shell
+            .features
+            .root_browser
+            .saved_roots()
+            .iter()
+            .filter(|root| **root != self.shell.runtime.root)
+            .cloned()
+            .collect()
+    }
+
+    pub(super) fn set_search_all_saved_roots(&mut self, enabled: bool) {
+        if self.shell.runtime.search_all_saved_roots == enabled {
+            return;
+        }
+        self.shell.runtime.search_all_saved_roots = enabled;
+        self.request_index_refresh();
+    }
+
+    pub(super) fn set_git_tracked_only(&mut self, enabled: bool) {
+        if self.shell.runtime.git_tracked_only == enabled {
+            return;
+        }
+        self.shell.runtime.git_tracked_only = enabled;
+        self.request_index_refresh();
+    }
+
+    /// 凍結中は active tab の index 応答を適用せず退避し、現在の entries を固定する。
+    /// 解除すると退避した応答を受信順に適用する。手動 refresh でも解除される。
+    pub(super) fn set_index_frozen(&mut self, frozen: bool) {
+        if self.shell.indexing.frozen == frozen {
+            return;
+        }
+        self.shell.indexing.frozen = frozen;
+        if frozen {
+            self.set_notice("Index frozen; new entries are held until unfrozen or refreshed");
+        } else {
+            let held = self.shell.indexing.frozen_responses.len();
+            self.set_notice(format!("Index unfrozen; applying {held} held update(s)"));
+        }
+    }
+
+    fn swap_source_filter_toggles(&mut self) {
+        let use_filelist = self.shell.runtime.use_filelist;
+        let current = SavedFilterToggles {
+            include_files: self.shell.runtime.include_files,
+            include_dirs: self.shell.runtime.include_dirs,
+            use_regex: self.shell.runtime.use_regex,
+        };
+        self.shell
+            .ui
+            .source_filter_memory
+            .set(!use_filelist, current);
+        self.mark_ui_state_dirty();
+        let Some(restored) = self.shell.ui.source_filter_memory.get(use_filelist) else {
+            return;
+        };
+        self.shell.runtime.include_files = restored.include_files;
+        self.shell.runtime.include_dirs = restored.include_dirs;
+        if self.shell.runtime.use_regex != restored.use_regex {
+            self.shell.runtime.use_regex = restored.use_regex;
+            self.invalidate_result_sort(true);
+        }
+    }
+
     fn enqueue_index_request(&mut self, req: IndexRequest) {
         let active_tab_id = self.current_tab_id().unwrap_or_default();
         let stale_inflight: Vec<u64> = self
@@ -434,6 +603,13 @@ impl FlistWalkerApp {
     }
 
     fn handle_background_index_response(&mut self, tab_index: usize, msg: IndexResponse) {
+        // Background tabs keep their own notice, so log failures here or they never surface.
+        if let IndexResponse::Failed { error, .. } = &msg {
+            if let Some(tab) = self.shell.tabs.get(tab_index) {
+                let root = normalize_path_for_display(&tab.root);
+                self.append_log_message(format!("Indexing failed ({root}): {error}"));
+            }
+        }
         let BackgroundIndexResponseEffect {
             trigger_search,
             cleanup_request_id,
@@ -465,7 +641,7 @@ impl FlistWalkerApp {
         let mut processed = 0usize;
         let mut has_index_progress = false;
         let mut finished_current_request = false;
-        while let Ok(msg) = self.shell.indexing.rx.try_recv() {
+        while let Some(msg) = self.shell.indexing.next_response() {
             let request_id = IndexCoordinator::response_request_id(&msg);
             match self.shell.indexing.route_response(request_id) {
                 IndexResponseRoute::Background(tab_id) => {
@@ -487,12 +663,17 @@ impl FlistWalkerApp {
                         .cleanup_stale_terminal_response(request_id);
                     continue;
                 }
+                IndexResponseRoute::Active if self.shell.indexing.frozen => {
+                    self.shell.indexing.frozen_responses.push_back(msg);
+                    continue;
+                }
                 IndexResponseRoute::Active => {}
             }
 
             match msg {
-                IndexResponse::Started { source, .. } => {
+                IndexResponse::Started { source, total, .. } => {
                     self.shell.runtime.index.source = source;
+                    self.shell.indexing.expected_total = total;
                     self.refresh_status_line();
                 }
                 IndexResponse::Batch {
@@ -532,6 +713,11 @@ impl FlistWalkerApp {
                 IndexResponse::Truncated { limit, .. } => {
                     self.set_notice(walker_truncated_notice(limit));
                 }
+                IndexResponse::DirectoriesCapped {
+                    limit, directories, ..
+                } => {
+                    self.set_notice(walker_directory_cap_notice(limit, directories));
+                }
             }
 
             processed = processed.saturating_add(1);
@@ -540,6 +726,11 @@ impl FlistWalkerApp {
             }
         }
 
+        if self.shell.indexing.frozen {
+            self.dispatch_index_queue();
+            return;
+        }
+
         if let Some(request_id) = self.shell.indexing.pending_request_id {
             let remaining_budget = FRAME_BUDGET.saturating_sub(frame_start.elapsed());
             let consumed = if remaining_budget.is_zero() {
@@ -579,7 +770,8 @@ impl FlistWalkerApp {
             return;
         }
 
-        if self.shell.runtime.query_state.query.trim().is_empty() {
+        if self.shell.runtime.query_state.query.trim().is_empty() && !self.shell.runtime.recent_view
+        {
             self.apply_incremental_empty_query_results();
         } else {
             self.maybe_refresh_incremental_search();
@@ -702,6 +894,8 @@ impl FlistWalkerApp {
             || !self.shell.runtime.include_dirs
             || (self.shell.ui.ignore_list_enabled
                 && !self.shell.runtime.ignore_list_terms.is_empty())
+            || self.size_filter_active()
+            || self.dotfile_filter_active()
     }
 
     fn try_finish_active_index_after_pending_drain(&mut self) -> bool {
@@ -727,12 +921,16 @@ impl FlistWalkerApp {
         let needs_filtering = !self.shell.runtime.include_files
             || !self.shell.runtime.include_dirs
             || (self.shell.ui.ignore_list_enabled
-                && !self.shell.runtime.ignore_list_terms.is_empty());
+                && !self.shell.runtime.ignore_list_terms.is_empty())
+            || self.size_filter_active()
+            || self.dotfile_filter_active();
         let has_incremental_filter_snapshot = needs_filtering
             && (!self.shell.indexing.incremental_filtered_entries.is_empty()
                 || !self.shell.runtime.index.entries.is_empty());
         self.shell.indexing.settle_active_terminal_state();
         if needs_filtering {
+            // Entries streamed before their size was known are still pending in the snapshot.
+            self.request_unknown_entry_sizes_for_active_entries();
             if has_incremental_filter_snapshot {
                 self.shell.runtime.entries = Arc::new(std::mem::take(
                     &mut self.shell.indexing.incremental_filtered_entries,
@@ -818,12 +1016,36 @@ impl FlistWalkerApp {
         self.pipeline_owner().maybe_refresh_incremental_search();
     }
 
+    /// Recent view を切り替える。query は変えず、結果だけを index 全体の更新日時降順へ差し替える。
+    pub(super) fn set_recent_view(&mut self, enabled: bool) {
+        if self.shell.runtime.recent_view == enabled {
+            return;
+        }
+        self.shell.runtime.recent_view = enabled;
+        self.invalidate_result_sort(true);
+        self.update_results();
+    }
+
+    /// Recent view 中、または空 query かつ既定 sort のとき、更新日時降順の一覧へ差し替えるかを返す。
+    pub(super) fn empty_query_recent_first_active(&self) -> bool {
+        self.shell.runtime.recent_view
+            || (self.shell.runtime.empty_query_recent_first
+                && self.shell.runtime.query_state.query.trim().is_empty()
+                && self.shell.runtime.result_sort_mode == ResultSortMode::Score
+                && self.shell.runtime.result_sort_scope == ResultSortScope::ShownResults)
+    }
+
     pub(super) fn should_refresh_incremental_search(&self) -> bool {
         let current_len = self.shell.indexing.incremental_filtered_entries.len();
         let delta = current_len.saturating_sub(self.shell.indexing.last_search_snapshot_len);
         if delta == 0 {
             return false;
         }
+        // eager_partial_search は CPU を余分に使う代わりに間隔・件数の間引きを外し、
+        // 検索中なら search_rerun_pending 側で次回へ回す。
+        if self.shell.runtime.eager_partial_search {
+            return true;
+        }
         if self.shell.indexing.in_progress {
             if delta < Self::INCREMENTAL_SEARCH_MIN_DELTA_DURING_INDEX {
                 return false;
@@ -3,6 +3,8 @@ use super::{
     SearchRequest,
 };
 use crate::app::search_coordinator::SearchResponseRoute;
+use crate::query::QueryOptions;
+use crate::search::validate_query;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
@@ -45,6 +47,13 @@ impl<'a> PipelineOwner<'a> {
     }
 
     pub(super) fn enqueue_search_request(&mut self) {
+        if self.app.query_below_min_length() {
+            // Skip the near-match-all pass; the results pane shows a hint instead.
+            self.app.shell.search.clear_active_request_state();
+            self.app.shell.runtime.total_match_count = 0;
+            self.app.replace_results_snapshot(Vec::new(), false);
+            return;
+        }
         self.app.commit_query_history_if_needed(false);
         let current_tab_id = self.app.current_tab_id();
         let request_id = self.app.shell.search.begin_active_request(current_tab_id);
@@ -72,20 +81,30 @@ impl<'a> PipelineOwner<'a> {
     }
 
     pub(super) fn update_results(&mut self) {
+        if self.app.shell.runtime.recent_view {
+            // The query is left untouched while the Recent view ignores it.
+            self.app.shell.runtime.query_error = None;
+            self.enqueue_search_request();
+            return;
+        }
+        self.app.shell.runtime.query_error = validate_query(
+            &self.app.shell.runtime.query_state.query,
+            self.app.shell.runtime.use_regex,
+        )
+        .err();
+        if let Some(error) = self.app.shell.runtime.query_error.clone() {
+            // Keep the previous results instead of enqueueing a search that can only fail.
+            self.app.shell.search.clear_active_request_state();
+            self.app.set_notice(format!("Invalid query: {error}"));
+            return;
+        }
         if self.app.shell.runtime.query_state.query.trim().is_empty()
             && !self.search_worker_needed_for_empty_query_sort()
         {
             self.app.shell.search.clear_active_request_state();
             let results = self
                 .app
-                .shell
-                .runtime
-                .entries
-                .iter()
-                .take(self.app.shell.runtime.limit)
-                .cloned()
-                .map(|entry| (entry.path, 0.0))
-                .collect();
+                .empty_query_results(&self.app.shell.runtime.entries);
             self.app.shell.runtime.total_match_count = self.app.shell.runtime.entries.len();
             self.app.replace_results_snapshot(results, false);
             return;
@@ -101,6 +120,7 @@ impl<'a> PipelineOwner<'a> {
         {
             self.app.reset_kind_resolution_state();
         }
+        self.app.request_unknown_entry_sizes_for_active_entries();
 
         let compiled_ignore_terms = self.app.compiled_ignore_terms();
         let source_is_all_entries =
@@ -112,7 +132,9 @@ impl<'a> PipelineOwner<'a> {
         };
         let needs_filtering = !self.app.shell.runtime.include_files
             || !self.app.shell.runtime.include_dirs
-            || self.ignore_list_filter_active();
+            || self.ignore_list_filter_active()
+            || self.app.size_filter_active()
+            || self.app.dotfile_filter_active();
         if self.app.shell.indexing.in_progress
             && !source_is_all_entries
             && !needs_filtering
@@ -167,14 +189,7 @@ impl<'a> PipelineOwner<'a> {
             self.app.shell.search.clear_active_request_state();
             let results = self
                 .app
-                .shell
-                .runtime
-                .entries
-                .iter()
-                .take(self.app.shell.runtime.limit)
-                .cloned()
-                .map(|entry| (entry.path, 0.0))
-                .collect();
+                .empty_query_results(&self.app.shell.runtime.entries);
             self.app.shell.runtime.total_match_count = self.app.shell.runtime.entries.len();
             self.app
                 .replace_results_snapshot(results, keep_scroll_position);
@@ -186,16 +201,13 @@ impl<'a> PipelineOwner<'a> {
     pub(super) fn apply_incremental_empty_query_results(&mut self) {
         let needs_filtering = !self.app.shell.runtime.include_files
             || !self.app.shell.runtime.include_dirs
-            || self.ignore_list_filter_active();
+            || self.ignore_list_filter_active()
+            || self.app.size_filter_active()
+            || self.app.dotfile_filter_active();
         if self.app.shell.indexing.in_progress && !needs_filtering {
             self.app.shell.search.clear_active_request_state();
             let source = self.app.shell.runtime.index.entries.as_slice();
-            let results = source
-                .iter()
-                .take(self.app.shell.runtime.limit)
-                .cloned()
-                .map(|entry| (entry.path, 0.0))
-                .collect();
+            let results = self.app.empty_query_results(source);
             self.app.shell.runtime.total_match_count = source.len();
             self.app.shell.indexing.last_search_snapshot_len = source.len();
             self.app.shell.indexing.last_incremental_results_refresh = Instant::now();
@@ -206,20 +218,15 @@ impl<'a> PipelineOwner<'a> {
         self.app.shell.search.clear_active_request_state();
         let results = self
             .app
-            .shell
-            .runtime
-            .entries
-            .iter()
-            .take(self.app.shell.runtime.limit)
-            .cloned()
-            .map(|entry| (entry.path, 0.0))
-            .collect();
+            .empty_query_results(&self.app.shell.runtime.entries);
         self.app.shell.runtime.total_match_count = self.app.shell.runtime.entries.len();
         self.app.replace_results_snapshot(results, true);
     }
 
     pub(super) fn maybe_refresh_incremental_search(&mut self) {
-        if self.app.shell.runtime.query_state.query.trim().is_empty() {
+        if self.app.shell.runtime.query_state.query.trim().is_empty()
+            && !self.app.shell.runtime.recent_view
+        {
             return;
         }
 
@@ -253,41 +260,84 @@ impl<'a> PipelineOwner<'a> {
         tab: &AppTabState,
         request_id: u64,
         limit: usize,
+        path_prefix_filter: bool,
+        query_options: QueryOptions,
+        match_full_path: bool,
+        max_results_per_directory: usize,
     ) -> SearchRequest {
         SearchRequest {
             request_id,
             query: tab.query_state.query.clone(),
             entries: Arc::clone(&tab.index_state.entries),
             limit,
-            use_regex: tab.use_regex,
-            ignore_case: tab.ignore_case,
+            query_options: QueryOptions {
+                use_regex: tab.use_regex,
+                ignore_case: tab.ignore_case,
+                ..query_options
+            },
             root: tab.root.clone(),
-            prefer_relative: FlistWalkerApp::prefer_relative_display_for(
-                &tab.index_state.index.source,
-            ),
+            prefer_relative: !match_full_path
+                && FlistWalkerApp::prefer_relative_display_for(&tab.index_state.index.source),
             sort_mode: tab.result_state.result_sort_mode,
             sort_scope: tab.result_state.result_sort_scope,
+            path_prefix_filter,
+            max_results_per_directory,
         }
     }
 
     fn build_active_search_request(&self, request_id: u64) -> SearchRequest {
+        let (sort_mode, sort_scope) = if self.app.empty_query_recent_first_active() {
+            (ResultSortMode::ModifiedDesc, ResultSortScope::AllMatches)
+        } else {
+            (
+                self.app.shell.runtime.result_sort_mode,
+                self.app.shell.runtime.result_sort_scope,
+            )
+        };
+        if self.app.shell.runtime.recent_view {
+            return SearchRequest {
+                query: String::new(),
+                entries: Arc::new(Self::recent_view_entries(&self.app.shell.runtime.entries)),
+                ..self.build_query_search_request(request_id, sort_mode, sort_scope)
+            };
+        }
+        self.build_query_search_request(request_id, sort_mode, sort_scope)
+    }
+
+    fn build_query_search_request(
+        &self,
+        request_id: u64,
+        sort_mode: ResultSortMode,
+        sort_scope: ResultSortScope,
+    ) -> SearchRequest {
         SearchRequest {
             request_id,
             query: self.app.shell.runtime.query_state.query.clone(),
             entries: Arc::clone(&self.app.shell.runtime.entries),
             limit: self.app.shell.runtime.limit,
-            use_regex: self.app.shell.runtime.use_regex,
-            ignore_case: self.app.shell.runtime.ignore_case,
+            query_options: self.app.query_options(),
             root: self.app.shell.runtime.root.clone(),
-            prefer_relative: self.app.prefer_relative_display(),
-            sort_mode: self.app.shell.runtime.result_sort_mode,
-            sort_scope: self.app.shell.runtime.result_sort_scope,
+            prefer_relative: self.app.prefer_relative_search(),
+            sort_mode,
+            sort_scope,
+            path_prefix_filter: self.app.shell.runtime.query_path_prefix_filter,
+            max_results_per_directory: self.app.shell.runtime.max_results_per_directory,
         }
     }
 
+    /// Recent view の対象として、folder を除いた entry だけを返す。未解決 kind は file として扱う。
+    fn recent_view_entries(entries: &[Entry]) -> Vec<Entry> {
+        entries
+            .iter()
+            .filter(|entry| entry.kind.and_then(|kind| kind.is_dir) != Some(true))
+            .cloned()
+            .collect()
+    }
+
     fn search_worker_needed_for_empty_query_sort(&self) -> bool {
-        self.app.shell.runtime.result_sort_scope == ResultSortScope::AllMatches
-            && self.app.shell.runtime.result_sort_mode != ResultSortMode::Score
+        (self.app.shell.runtime.result_sort_scope == ResultSortScope::AllMatches
+            && self.app.shell.runtime.result_sort_mode != ResultSortMode::Score)
+            || self.app.empty_query_recent_first_active()
     }
 
     fn filtered_entries(
@@ -314,6 +364,10 @@ impl<'a> PipelineOwner<'a> {
 
     pub(super) fn enqueue_search_request_for_tab_index(&mut self, tab_index: usize) {
         let limit = self.app.shell.runtime.limit;
+        let path_prefix_filter = self.app.shell.runtime.query_path_prefix_filter;
+        let query_options = self.app.query_options();
+        let match_full_path = self.app.shell.runtime.match_full_path;
+        let max_results_per_directory = self.app.shell.runtime.max_results_per_directory;
         let (request_id, req) = {
             let shell = &mut self.app.shell;
             let (tabs, search) = (&mut shell.tabs, &mut shell.search);
@@ -321,7 +375,15 @@ impl<'a> PipelineOwner<'a> {
                 return;
             };
             let request_id = search.begin_tab_request(tab);
-            let req = Self::build_search_request_for_tab(tab, request_id, limit);
+            let req = Self::build_search_request_for_tab(
+                tab,
+                request_id,
+                limit,
+                path_prefix_filter,
+                query_options,
+                match_full_path,
+                max_results_per_directory,
+            );
             (request_id, req)
         };
         if self.app.shell.search.tx.send(req).is_err() {
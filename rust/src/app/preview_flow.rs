@@ -1,13 +1,47 @@
-use super::{match_positions_for_path_with_compiled, EntryKind, FlistWalkerApp, HighlightCacheKey};
-use crate::app::PreviewRequest;
+use super::{
+    display_path_with_mode, match_positions_for_path_with_compiled, EntryKind, FlistWalkerApp,
+    HighlightCacheKey, HighlightScope, HighlightedDisplay, QuickLookState,
+};
+use crate::app::{PreviewRequest, PreviewResponse};
+use crate::query::QueryOptions;
+use crate::ui_model::MarkdownPreviewLine;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 impl FlistWalkerApp {
     pub(super) fn clear_preview_cache(&mut self) {
         self.shell.cache.preview.clear();
     }
 
+    /// 表示中の preview が markdown file の内容かを返す。判定は current row ではなく preview 元の path で行う。
+    pub(super) fn current_preview_is_markdown(&self) -> bool {
+        self.shell
+            .runtime
+            .preview_path
+            .as_deref()
+            .is_some_and(crate::ui_model::is_markdown_path)
+    }
+
+    /// 表示中の markdown preview の解析済み行を返す。同じ preview の間は前回の解析結果を使い回す。
+    pub(super) fn markdown_preview_lines(&mut self) -> Option<Arc<Vec<MarkdownPreviewLine>>> {
+        let path = self.shell.runtime.preview_path.as_deref()?;
+        Some(
+            self.shell
+                .cache
+                .markdown_preview
+                .lines(path, &self.shell.runtime.preview),
+        )
+    }
+
+    /// 表示中の preview が表として整形される `.csv` / `.tsv` の内容かを返す。
+    pub(super) fn current_preview_is_table(&self) -> bool {
+        self.shell
+            .runtime
+            .preview_path
+            .as_deref()
+            .is_some_and(|path| crate::ui_model::delimited_table_separator(path).is_some())
+    }
+
     pub(super) fn cache_preview(&mut self, path: PathBuf, preview: String) {
         self.shell
             .cache
@@ -22,31 +56,35 @@ impl FlistWalkerApp {
     pub(super) fn ensure_highlight_cache_scope(&mut self, prefer_relative: bool) {
         let query = self.shell.runtime.query_state.query.clone();
         let root = self.shell.runtime.root.clone();
-        let use_regex = self.shell.runtime.use_regex;
-        let ignore_case = self.shell.runtime.ignore_case;
-        if self.shell.cache.highlight.matches_scope(
-            &query,
-            &root,
-            use_regex,
-            ignore_case,
+        // Highlight spans do not depend on ranking or any-term mode, so keep those out of the scope.
+        let scope = HighlightScope {
+            query: QueryOptions {
+                rank_by_filename: false,
+                match_any_term: false,
+                ..self.query_options()
+            },
             prefer_relative,
-        ) {
+        };
+        if self
+            .shell
+            .cache
+            .highlight
+            .matches_scope(&query, &root, scope)
+        {
             return;
         }
-        self.shell.cache.highlight.reset_scope(
-            query,
-            root,
-            use_regex,
-            ignore_case,
-            prefer_relative,
-        );
+        self.shell.cache.highlight.reset_scope(query, root, scope);
     }
 
-    fn cache_highlight_positions_for_key(&mut self, key: HighlightCacheKey, positions: Vec<u16>) {
+    fn cache_highlighted_display_for_key(
+        &mut self,
+        key: HighlightCacheKey,
+        highlighted: Arc<HighlightedDisplay>,
+    ) {
         self.shell
             .cache
             .highlight
-            .insert_bounded(key, positions, Self::HIGHLIGHT_CACHE_MAX);
+            .insert_bounded(key, highlighted, Self::HIGHLIGHT_CACHE_MAX);
     }
 
     fn compact_highlight_positions(positions: HashSet<usize>) -> Vec<u16> {
@@ -59,18 +97,13 @@ impl FlistWalkerApp {
         compact
     }
 
-    pub(super) fn highlight_positions_for_path_cached(
+    /// 結果行の表示文字列と highlight 位置を返す。query/mode/root が変わるまで行ごとに再計算しない。
+    pub(super) fn highlighted_display_for_path_cached(
         &mut self,
         path: &Path,
         prefer_relative: bool,
-    ) -> Arc<Vec<u16>> {
-        static EMPTY: OnceLock<Arc<Vec<u16>>> = OnceLock::new();
-
+    ) -> Arc<HighlightedDisplay> {
         self.ensure_highlight_cache_scope(prefer_relative);
-        if self.shell.runtime.query_state.query.trim().is_empty() {
-            return Arc::clone(EMPTY.get_or_init(|| Arc::new(Vec::new())));
-        }
-
         let key = HighlightCacheKey {
             path: path.to_path_buf(),
             prefer_relative,
@@ -78,30 +111,46 @@ impl FlistWalkerApp {
             ignore_case: self.shell.runtime.ignore_case,
         };
 
-        if let Some(positions) = self.shell.cache.highlight.get(&key) {
-            return positions;
+        if let Some(highlighted) = self.shell.cache.highlight.get(&key) {
+            return highlighted;
         }
 
-        let positions = self
+        let display_root = self.display_root_for_path(path);
+        let mut display = display_path_with_mode(path, display_root, prefer_relative);
+        let positions = if self.shell.runtime.query_state.query.trim().is_empty() {
+            Vec::new()
+        } else {
+            self.shell
+                .cache
+                .highlight
+                .compiled()
+                .map(|compiled| {
+                    Self::compact_highlight_positions(match_positions_for_path_with_compiled(
+                        path,
+                        display_root,
+                        &compiled,
+                        prefer_relative,
+                    ))
+                })
+                .unwrap_or_default()
+        };
+        // The separator is appended after matching so it never becomes a highlight position.
+        let kind = self
             .shell
-            .cache
-            .highlight
-            .compiled()
-            .map(|compiled| {
-                Self::compact_highlight_positions(match_positions_for_path_with_compiled(
-                    path,
-                    &self.shell.runtime.root,
-                    &compiled,
-                    prefer_relative,
-                ))
-            })
-            .unwrap_or_default();
-        self.cache_highlight_positions_for_key(key.clone(), positions);
-        self.shell
-            .cache
-            .highlight
-            .get(&key)
-            .unwrap_or_else(|| Arc::clone(EMPTY.get_or_init(|| Arc::new(Vec::new()))))
+            .runtime
+            .dir_trailing_slash
+            .then(|| self.find_entry_kind(path));
+        if kind.flatten().and_then(|kind| kind.is_dir) == Some(true)
+            && !display.ends_with(['/', '\\'])
+        {
+            display.push(std::path::MAIN_SEPARATOR);
+        }
+        let highlighted = Arc::new(HighlightedDisplay { display, positions });
+        // Unresolved kinds may turn out to be folders; re-render them once the kind is known.
+        if kind.is_none_or(|kind| kind.is_some_and(|kind| kind.is_dir.is_some())) {
+            self.cache_highlighted_display_for_key(key, Arc::clone(&highlighted));
+        }
+        highlighted
     }
 
     pub(super) fn is_highlighted_position(positions: &[u16], idx: usize) -> bool {
@@ -118,6 +167,7 @@ impl FlistWalkerApp {
     }
 
     pub(super) fn request_preview_for_current(&mut self) {
+        self.shell.runtime.preview_path = None;
         if !self.shell.ui.show_preview {
             self.shell.runtime.preview.clear();
             self.shell.worker_bus.preview.clear_request();
@@ -128,6 +178,7 @@ impl FlistWalkerApp {
             if let Some((path, _)) = self.shell.runtime.results.get(row) {
                 if let Some(cached) = self.shell.cache.preview.get(path) {
                     self.shell.runtime.preview = cached.to_string();
+                    self.shell.runtime.preview_path = Some(path.clone());
                     self.shell.worker_bus.preview.clear_request();
                     return;
                 }
@@ -158,6 +209,11 @@ impl FlistWalkerApp {
                     request_id,
                     path,
                     is_dir,
+                    quick_look: false,
+                    max_file_bytes: self.shell.runtime.preview_max_file_bytes,
+                    dir_depth: self.shell.runtime.preview_dir_depth,
+                    skip_binary: self.shell.runtime.preview_skip_binary,
+                    metadata_details: self.shell.runtime.preview_metadata_details,
                 };
                 if self.shell.worker_bus.preview.tx.send(req).is_err() {
                     self.shell.worker_bus.preview.clear_request();
@@ -169,4 +225,72 @@ impl FlistWalkerApp {
         self.shell.runtime.preview.clear();
         self.shell.worker_bus.preview.clear_request();
     }
+
+    /// preview panel を 1 ページ分スクロールするよう次 frame の描画へ依頼する。
+    pub(super) fn scroll_preview_page(&mut self, direction: isize) {
+        if !self.shell.ui.show_preview() || self.shell.runtime.preview.is_empty() {
+            return;
+        }
+        self.shell.ui.pending_preview_scroll_pages = self
+            .shell
+            .ui
+            .pending_preview_scroll_pages
+            .saturating_add(direction);
+    }
+
+    pub(super) fn toggle_quick_look(&mut self) {
+        if self.shell.ui.quick_look.is_some() {
+            self.close_quick_look();
+            return;
+        }
+        let Some((path, _)) = self
+            .shell
+            .runtime
+            .current_row
+            .and_then(|row| self.shell.runtime.results.get(row))
+        else {
+            return;
+        };
+        let path = path.clone();
+        let Some(is_dir) = self.current_result_kind().and_then(|kind| kind.is_dir) else {
+            self.set_notice("Quick Look is unavailable until the entry type is resolved");
+            return;
+        };
+        let request_id = self.shell.worker_bus.preview.allocate_request_id();
+        let req = PreviewRequest {
+            request_id,
+            path: path.clone(),
+            is_dir,
+            quick_look: true,
+            max_file_bytes: self.shell.runtime.preview_max_file_bytes,
+            dir_depth: self.shell.runtime.preview_dir_depth,
+            skip_binary: self.shell.runtime.preview_skip_binary,
+            metadata_details: self.shell.runtime.preview_metadata_details,
+        };
+        if self.shell.worker_bus.preview.tx.send(req).is_err() {
+            self.set_notice("Preview worker is unavailable");
+            return;
+        }
+        self.shell.ui.quick_look = Some(QuickLookState {
+            request_id,
+            path,
+            text: None,
+        });
+    }
+
+    pub(super) fn close_quick_look(&mut self) {
+        self.shell.ui.quick_look = None;
+    }
+
+    pub(super) fn apply_quick_look_response(&mut self, response: PreviewResponse) {
+        if let Some(quick_look) = self
+            .shell
+            .ui
+            .quick_look
+            .as_mut()
+            .filter(|quick_look| quick_look.request_id == response.request_id)
+        {
+            quick_look.text = Some(response.preview);
+        }
+    }
 }
@@ -25,6 +25,46 @@ pub(super) enum RenderFileListDialogCommand {
     CancelUseWalker,
 }
 
+#[derive(Clone, Copy)]
+pub(super) enum RenderActionDialogCommand {
+    Confirm,
+    Cancel,
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum RenderResetSettingsDialogCommand {
+    Confirm,
+    Cancel,
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum RenderSmartFilterCommand {
+    Apply(usize),
+    Delete(usize),
+    OpenSave,
+    ConfirmSave,
+    CancelSave,
+}
+
+#[derive(Clone, Copy)]
+pub(super) enum RenderResultMenuCommand {
+    ExecuteSelected,
+    OpenFolder,
+    OpenInEditor,
+    CopyPath,
+    CopyPathForShell,
+    CopySiteUrl,
+    CopyName,
+    CopyResultsAsTree,
+    CopyAllResults,
+    TogglePin,
+    PinSameExtensionBelow,
+    SetAsRoot,
+    SetAsDisplayBase,
+    MoveTo,
+    Trash,
+}
+
 #[derive(Clone, Copy)]
 pub(super) enum RenderUpdateDialogCommand {
     StartInstall,
@@ -69,7 +109,17 @@ pub(super) enum RenderTabBarCommand {
 pub(super) enum RenderCommand {
     TopAction(RenderTopActionCommand),
     OpenRuntimeConfig,
+    OpenActiveFileList,
+    ReloadFileList,
+    OpenResetSettings,
+    ResetSettingsDialog(RenderResetSettingsDialogCommand),
+    SmartFilter(RenderSmartFilterCommand),
     FileListDialog(RenderFileListDialogCommand),
+    ActionDialog(RenderActionDialogCommand),
+    ResultMenu {
+        row: usize,
+        command: RenderResultMenuCommand,
+    },
     UpdateDialog(RenderUpdateDialogCommand),
     RootListDialog(RenderRootListDialogCommand),
     TabBar(RenderTabBarCommand),
@@ -275,6 +325,38 @@ impl FlistWalkerApp {
         }
     }
 
+    fn dispatch_result_menu_command(
+        &mut self,
+        ctx: &egui::Context,
+        row: usize,
+        command: RenderResultMenuCommand,
+    ) {
+        if row >= self.shell.runtime.results.len() {
+            return;
+        }
+        self.set_current_row(Some(row));
+        self.request_preview_for_current();
+        let paths = self.menu_target_paths(row);
+        match command {
+            RenderResultMenuCommand::ExecuteSelected => self.execute_paths(paths),
+            RenderResultMenuCommand::OpenFolder => self.open_folder_for_paths(paths),
+            RenderResultMenuCommand::OpenInEditor => self.open_current_in_editor(),
+            RenderResultMenuCommand::CopyPath => self.copy_paths(ctx, paths),
+            RenderResultMenuCommand::CopyPathForShell => self.copy_paths_for_shell(ctx, paths),
+            RenderResultMenuCommand::CopySiteUrl => self.copy_site_urls(ctx, paths),
+            RenderResultMenuCommand::CopyName => self.copy_current_name(ctx),
+            RenderResultMenuCommand::CopyResultsAsTree => self.copy_results_as_tree(ctx),
+            RenderResultMenuCommand::CopyAllResults => self.copy_all_results(ctx),
+            RenderResultMenuCommand::TogglePin => self.toggle_pin_current(),
+            RenderResultMenuCommand::PinSameExtensionBelow => self.pin_same_extension_below(),
+            RenderResultMenuCommand::SetAsRoot => self.set_current_as_root(),
+            RenderResultMenuCommand::SetAsDisplayBase => self.set_current_as_display_base(),
+            RenderResultMenuCommand::MoveTo => self.move_paths_via_dialog(paths),
+            RenderResultMenuCommand::Trash => self.trash_paths(paths),
+        }
+        self.refresh_status_line();
+    }
+
     pub(super) fn schedule_frame_repaint(&mut self, ctx: &egui::Context) {
         let memory_elapsed = self.shell.ui.last_memory_sample.elapsed();
         if memory_elapsed >= Self::MEMORY_SAMPLE_INTERVAL {
@@ -305,7 +387,14 @@ impl FlistWalkerApp {
 
         render_panels::render_top_panel(self, ctx);
         render_panels::render_status_panel(self, ctx);
+        render_panels::render_log_panel(self, ctx);
         render_dialogs::render_filelist_dialogs(self, ctx);
+        render_dialogs::render_action_confirmation_dialog(self, ctx);
+        render_dialogs::render_quick_look(self, ctx);
+        render_dialogs::render_basket_window(self, ctx);
+        render_dialogs::render_goto_row_dialog(self, ctx);
+        render_dialogs::render_save_smart_filter_dialog(self, ctx);
+        render_dialogs::render_reset_settings_dialog(self, ctx);
         render_dialogs::render_update_dialog(self, ctx);
         self.render_central_panel(ctx);
         render_dialogs::render_manage_root_list_dialog(self, ctx);
@@ -317,6 +406,77 @@ impl FlistWalkerApp {
         !preview_resize_in_progress
     }
 
+    /// 索引作成または検索が進行中で、結果一覧がまだ埋まりきっていないかを返す。
+    pub(super) fn results_activity_in_progress(&self) -> bool {
+        self.shell.indexing.in_progress || self.shell.search.in_progress()
+    }
+
+    pub(super) fn results_empty_state_message(&self) -> Option<String> {
+        if !self.shell.runtime.results.is_empty() || self.shell.search.in_progress() {
+            return None;
+        }
+        if self.query_below_min_length() {
+            return Some(format!(
+                "Type at least {} characters",
+                self.shell.runtime.min_query_length
+            ));
+        }
+        if self.shell.indexing.in_progress || self.shell.runtime.all_entries.is_empty() {
+            return Some("No entries indexed yet".to_string());
+        }
+        let query = self.shell.runtime.query_state.query.trim();
+        if query.is_empty() {
+            Some("No matches".to_string())
+        } else {
+            Some(format!("No matches for '{query}'"))
+        }
+    }
+
+    /// 表示 path をディレクトリ部分とファイル名の開始文字位置に分ける。
+    pub(super) fn result_directory_group(display: &str) -> (&str, usize) {
+        // A folder shown with a trailing separator still belongs to its parent group.
+        let trimmed = display.trim_end_matches(['/', '\\']);
+        match trimmed.rfind(['/', '\\']) {
+            Some(separator) => (&display[..separator], display[..=separator].chars().count()),
+            None => ("", 0),
+        }
+    }
+
+    /// ディレクトリ見出しの下に置く行として、ファイル名だけを字下げした表示と highlight 位置を返す。
+    pub(super) fn collapse_result_directory(
+        display: &str,
+        positions: &[u16],
+    ) -> (String, Vec<u16>) {
+        const INDENT: &str = "    ";
+        let (_, name_start) = Self::result_directory_group(display);
+        let indent = INDENT.chars().count();
+        let positions = positions
+            .iter()
+            .map(|position| usize::from(*position))
+            .filter(|position| *position >= name_start)
+            .map(|position| (position - name_start + indent) as u16)
+            .collect();
+        let name = display.chars().skip(name_start).collect::<String>();
+        (format!("{INDENT}{name}"), positions)
+    }
+
+    /// 結果行先頭の score 表示。CLI の `[  42.0]` 形式に揃える。
+    pub(super) fn result_score_label(score: f64) -> String {
+        format!("[{score:6.1}]")
+    }
+
+    /// score 列の右端に揃えて score ラベルを置く位置を返す。
+    pub(super) fn result_score_pos(
+        inner_rect: egui::Rect,
+        column_width: f32,
+        galley_size: egui::Vec2,
+    ) -> egui::Pos2 {
+        egui::pos2(
+            inner_rect.left() + column_width - galley_size.x,
+            inner_rect.center().y - (galley_size.y * 0.5),
+        )
+    }
+
     pub(super) fn result_row_text_pos(
         inner_rect: egui::Rect,
         galley_size: egui::Vec2,
@@ -347,6 +507,36 @@ impl FlistWalkerApp {
                 RenderCommand::OpenRuntimeConfig => {
                     self.open_runtime_config_file();
                 }
+                RenderCommand::OpenActiveFileList => {
+                    self.open_active_filelist();
+                }
+                RenderCommand::ReloadFileList => {
+                    self.reload_filelist();
+                }
+                RenderCommand::OpenResetSettings => {
+                    self.open_reset_settings_dialog();
+                }
+                RenderCommand::ResetSettingsDialog(RenderResetSettingsDialogCommand::Confirm) => {
+                    self.confirm_reset_settings(ctx);
+                }
+                RenderCommand::ResetSettingsDialog(RenderResetSettingsDialogCommand::Cancel) => {
+                    self.shell.ui.reset_settings_dialog = None;
+                }
+                RenderCommand::SmartFilter(RenderSmartFilterCommand::Apply(index)) => {
+                    self.apply_smart_filter(index);
+                }
+                RenderCommand::SmartFilter(RenderSmartFilterCommand::Delete(index)) => {
+                    self.delete_smart_filter(index);
+                }
+                RenderCommand::SmartFilter(RenderSmartFilterCommand::OpenSave) => {
+                    self.open_save_smart_filter();
+                }
+                RenderCommand::SmartFilter(RenderSmartFilterCommand::ConfirmSave) => {
+                    self.confirm_save_smart_filter();
+                }
+                RenderCommand::SmartFilter(RenderSmartFilterCommand::CancelSave) => {
+                    self.cancel_save_smart_filter();
+                }
                 RenderCommand::TopAction(RenderTopActionCommand::ApplyHistory) => {
                     self.accept_history_search();
                 }
@@ -395,6 +585,15 @@ impl FlistWalkerApp {
                 RenderCommand::FileListDialog(RenderFileListDialogCommand::CancelUseWalker) => {
                     self.cancel_pending_filelist_use_walker();
                 }
+                RenderCommand::ActionDialog(RenderActionDialogCommand::Confirm) => {
+                    self.confirm_pending_action();
+                }
+                RenderCommand::ActionDialog(RenderActionDialogCommand::Cancel) => {
+                    self.cancel_pending_action();
+                }
+                RenderCommand::ResultMenu { row, command } => {
+                    self.dispatch_result_menu_command(ctx, row, command);
+                }
                 RenderCommand::UpdateDialog(RenderUpdateDialogCommand::StartInstall) => {
                     self.start_update_install();
                 }
@@ -1,4 +1,6 @@
-use super::{FileListDialogKind, FlistWalkerApp, UpdateSupport};
+use super::{
+    normalize_path_for_display, ActionOperation, FileListDialogKind, FlistWalkerApp, UpdateSupport,
+};
 use eframe::egui;
 
 pub(super) fn render_filelist_dialogs(app: &mut FlistWalkerApp, ctx: &egui::Context) {
@@ -187,6 +189,264 @@ pub(super) fn render_filelist_dialogs(app: &mut FlistWalkerApp, ctx: &egui::Cont
     }
 }
 
+pub(super) fn render_action_confirmation_dialog(app: &mut FlistWalkerApp, ctx: &egui::Context) {
+    let current_tab_id = app.current_tab_id().unwrap_or_default();
+    let Some((title, message, confirm_label)) = app
+        .shell
+        .ui
+        .pending_action_confirmation
+        .as_ref()
+        .filter(|pending| pending.tab_id == current_tab_id)
+        .map(|pending| {
+            let count = pending.paths.len();
+            match &pending.operation {
+                ActionOperation::Launch | ActionOperation::OpenInEditor { .. } => {
+                    ("Open Pinned Items?", format!("Open {count} items?"), "Open")
+                }
+                ActionOperation::Move { .. } | ActionOperation::UndoMove { .. } => {
+                    ("Move Items?", format!("Move {count} items?"), "Move")
+                }
+                ActionOperation::Trash => (
+                    "Move Items to Trash?",
+                    format!("Move {count} items to trash?"),
+                    "Trash",
+                ),
+            }
+        })
+    else {
+        return;
+    };
+    let mut confirm = false;
+    let mut cancel = false;
+    egui::Window::new(title)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label(message);
+            ui.horizontal(|ui| {
+                if app
+                    .dialog_button(ui, confirm_label, app.shell.ui.action_dialog_button == 0)
+                    .clicked()
+                {
+                    confirm = true;
+                }
+                if app
+                    .dialog_button(ui, "Cancel", app.shell.ui.action_dialog_button == 1)
+                    .clicked()
+                {
+                    cancel = true;
+                }
+            });
+        });
+    if confirm {
+        app.queue_render_command(super::render::RenderCommand::ActionDialog(
+            super::render::RenderActionDialogCommand::Confirm,
+        ));
+    } else if cancel {
+        app.queue_render_command(super::render::RenderCommand::ActionDialog(
+            super::render::RenderActionDialogCommand::Cancel,
+        ));
+    }
+}
+
+pub(super) fn render_goto_row_dialog(app: &mut FlistWalkerApp, ctx: &egui::Context) {
+    let total = app.shell.runtime.results.len();
+    let Some(input) = app.shell.ui.goto_row_input.as_mut() else {
+        return;
+    };
+    let input_id = egui::Id::new("goto-row-input");
+    egui::Window::new("Go to Row")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 80.0))
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("Row (1-{total}):"));
+                ui.add(
+                    egui::TextEdit::singleline(input)
+                        .id(input_id)
+                        .desired_width(80.0),
+                );
+            });
+        });
+    ctx.memory_mut(|m| m.request_focus(input_id));
+}
+
+pub(super) fn render_save_smart_filter_dialog(app: &mut FlistWalkerApp, ctx: &egui::Context) {
+    let Some(input) = app.shell.ui.smart_filter_name_input.as_mut() else {
+        return;
+    };
+    let input_id = egui::Id::new("smart-filter-name-input");
+    let mut confirm = false;
+    let mut cancel = false;
+    egui::Window::new("Save Filter")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 80.0))
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.add(
+                    egui::TextEdit::singleline(input)
+                        .id(input_id)
+                        .desired_width(200.0)
+                        .hint_text("e.g. Images"),
+                );
+            });
+            ui.horizontal(|ui| {
+                confirm = ui.button("Save").clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+    ctx.memory_mut(|m| m.request_focus(input_id));
+    if confirm {
+        app.queue_render_command(super::render::RenderCommand::SmartFilter(
+            super::render::RenderSmartFilterCommand::ConfirmSave,
+        ));
+    } else if cancel {
+        app.queue_render_command(super::render::RenderCommand::SmartFilter(
+            super::render::RenderSmartFilterCommand::CancelSave,
+        ));
+    }
+}
+
+pub(super) fn render_reset_settings_dialog(app: &mut FlistWalkerApp, ctx: &egui::Context) {
+    let Some(dialog) = app.shell.ui.reset_settings_dialog.as_mut() else {
+        return;
+    };
+    let mut confirm = false;
+    let mut cancel = false;
+    egui::Window::new("Reset Settings?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label("Reset default root, preview panel, and toggles to their defaults?");
+            ui.checkbox(
+                &mut dialog.clear_saved_roots_and_history,
+                "Also clear saved roots and query history",
+            );
+            ui.horizontal(|ui| {
+                confirm = ui.button("Reset").clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+    if confirm {
+        app.queue_render_command(super::render::RenderCommand::ResetSettingsDialog(
+            super::render::RenderResetSettingsDialogCommand::Confirm,
+        ));
+    } else if cancel {
+        app.queue_render_command(super::render::RenderCommand::ResetSettingsDialog(
+            super::render::RenderResetSettingsDialogCommand::Cancel,
+        ));
+    }
+}
+
+pub(super) fn render_quick_look(app: &mut FlistWalkerApp, ctx: &egui::Context) {
+    let Some(quick_look) = app.shell.ui.quick_look.as_ref() else {
+        return;
+    };
+    let title = quick_look.path.display().to_string();
+    let mut text = quick_look
+        .text
+        .clone()
+        .unwrap_or_else(|| "Loading preview...".to_string());
+    let size = ctx.content_rect().size() * 0.8;
+    let mut open = true;
+    egui::Window::new("Quick Look")
+        .id(egui::Id::new("quick-look"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .fixed_size(size)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            ui.label(egui::RichText::new(title).strong());
+            ui.separator();
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut text)
+                            .interactive(false)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+        });
+    if !open {
+        app.close_quick_look();
+    }
+}
+
+pub(super) fn render_basket_window(app: &mut FlistWalkerApp, ctx: &egui::Context) {
+    if !app.shell.ui.show_basket {
+        return;
+    }
+    let mut open = true;
+    let mut remove_index = None;
+    let mut open_all = false;
+    let mut copy_paths = false;
+    let mut clear = false;
+    egui::Window::new(format!("Basket ({})", app.shell.runtime.basket.len()))
+        .id(egui::Id::new("basket-window"))
+        .open(&mut open)
+        .collapsible(false)
+        .default_size(egui::vec2(480.0, 320.0))
+        .show(ctx, |ui| {
+            ui.checkbox(
+                &mut app.shell.runtime.basket_enter_mode,
+                "Enter adds to basket",
+            )
+            .on_hover_text("Enter / Ctrl+J / Ctrl+M add the selection here instead of opening it");
+            ui.horizontal(|ui| {
+                let has_items = !app.shell.runtime.basket.is_empty();
+                open_all = ui
+                    .add_enabled(has_items, egui::Button::new("Open All"))
+                    .clicked();
+                copy_paths = ui
+                    .add_enabled(has_items, egui::Button::new("Copy Paths"))
+                    .clicked();
+                clear = ui
+                    .add_enabled(has_items, egui::Button::new("Clear"))
+                    .clicked();
+            });
+            ui.separator();
+            if app.shell.runtime.basket.is_empty() {
+                ui.weak("Press Ctrl+Shift+B to add the current selection.");
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for (index, path) in app.shell.runtime.basket.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("×").on_hover_text("Remove").clicked() {
+                                remove_index = Some(index);
+                            }
+                            ui.label(normalize_path_for_display(path));
+                        });
+                    }
+                });
+        });
+    if let Some(index) = remove_index {
+        app.remove_basket_item(index);
+    }
+    if open_all {
+        app.open_basket_items();
+    }
+    if copy_paths {
+        app.copy_basket_paths(ctx);
+    }
+    if clear {
+        app.clear_basket();
+    }
+    if !open {
+        app.shell.ui.show_basket = false;
+    }
+}
+
 pub(super) fn render_update_dialog(app: &mut FlistWalkerApp, ctx: &egui::Context) {
     if let Some(prompt) = app.shell.features.update.state.prompt.as_ref().cloned() {
         let mut confirm = false;
@@ -1,12 +1,17 @@
 #![allow(deprecated)]
 
+use super::render::RenderResultMenuCommand;
+use super::shell_support::parse_size_filter_bytes;
 use super::{
-    render_tabs, render_theme, EntryDisplayKind, EntryKind, FlistWalkerApp, ResultSortMode,
-    ResultSortScope,
+    normalize_path_for_display, render_tabs, render_theme, EntryDisplayKind, EntryKind,
+    FlistWalkerApp, ResultSortMode, ResultSortScope,
 };
 use crate::path_utils::normalize_windows_path_buf;
+use crate::ui_model::{table_preview_header_row, MarkdownPreviewLine};
 use eframe::egui;
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 const COMPACT_ROW_TEXT_Y_OFFSET: f32 = 2.0;
 const COMPACT_ROW_CHECKBOX_Y_OFFSET: f32 = -1.0;
@@ -240,14 +245,27 @@ pub(super) fn render_top_panel(app: &mut FlistWalkerApp, ctx: &egui::Context) {
                         egui::PopupCloseBehavior::CloseOnClickOutside,
                         |ui: &mut egui::Ui| {
                             ui.set_min_width(field_width);
-                            for (index, path) in app
-                                .shell
-                                .features
-                                .root_browser
-                                .saved_roots()
-                                .iter()
-                                .enumerate()
-                            {
+                            let mut filter = app.shell.ui.root_dropdown_filter.clone();
+                            let filter_response = ui.add(
+                                egui::TextEdit::singleline(&mut filter)
+                                    .id(FlistWalkerApp::root_dropdown_filter_id())
+                                    .desired_width(f32::INFINITY)
+                                    .hint_text("Filter saved roots..."),
+                            );
+                            if !filter_response.has_focus() {
+                                filter_response.request_focus();
+                            }
+                            app.set_root_dropdown_filter(filter);
+                            let visible = app.filtered_root_dropdown_indices();
+                            if visible.is_empty() {
+                                ui.weak("No matching roots");
+                            }
+                            for index in visible {
+                                let Some(path) =
+                                    app.shell.features.root_browser.saved_roots().get(index)
+                                else {
+                                    continue;
+                                };
                                 let text = normalize_windows_path_buf(path.clone())
                                     .to_string_lossy()
                                     .to_string();
@@ -295,10 +313,53 @@ pub(super) fn render_top_panel(app: &mut FlistWalkerApp, ctx: &egui::Context) {
             }
         });
 
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 2.0;
+            let crumbs = FlistWalkerApp::root_breadcrumbs(&app.shell.runtime.root);
+            let last = crumbs.len().saturating_sub(1);
+            let mut next_root: Option<PathBuf> = None;
+            for (index, (label, path)) in crumbs.into_iter().enumerate() {
+                if index > 0 {
+                    ui.weak("›");
+                }
+                if ui
+                    .selectable_label(index == last, label)
+                    .on_hover_text(path.to_string_lossy())
+                    .clicked()
+                {
+                    next_root = Some(path);
+                }
+            }
+            if let Some(root) = next_root {
+                app.apply_root_change(root);
+            }
+        });
+
         ui.horizontal(|ui| {
             let use_filelist_changed =
                 centered_checkbox(ui, &mut app.shell.runtime.use_filelist, "Use FileList")
                     .changed();
+            let mut search_all_saved_roots = app.shell.runtime.search_all_saved_roots;
+            if centered_checkbox(ui, &mut search_all_saved_roots, "All Saved Roots")
+                .on_hover_text("Index every saved root and search their union")
+                .changed()
+            {
+                app.set_search_all_saved_roots(search_all_saved_roots);
+            }
+            let mut git_tracked_only = app.shell.runtime.git_tracked_only;
+            if centered_checkbox(ui, &mut git_tracked_only, "Git Tracked Only")
+                .on_hover_text("Index `git ls-files` output instead of walking (falls back outside a git repo)")
+                .changed()
+            {
+                app.set_git_tracked_only(git_tracked_only);
+            }
+            let mut index_frozen = app.shell.indexing.frozen;
+            if centered_checkbox(ui, &mut index_frozen, "Freeze Index")
+                .on_hover_text("Hold new index updates and keep the current entries until unfrozen or refreshed")
+                .changed()
+            {
+                app.set_index_frozen(index_frozen);
+            }
             if centered_checkbox(ui, &mut app.shell.runtime.use_regex, "Regex").changed() {
                 app.invalidate_result_sort(true);
                 app.update_results();
@@ -308,6 +369,13 @@ pub(super) fn render_top_panel(app: &mut FlistWalkerApp, ctx: &egui::Context) {
                 app.invalidate_result_sort(true);
                 app.update_results();
             }
+            if centered_checkbox(ui, &mut app.shell.runtime.match_any_term, "Any Term")
+                .on_hover_text("Match entries that contain any space-separated term instead of all of them (exclusions still apply)")
+                .changed()
+            {
+                app.invalidate_result_sort(true);
+                app.update_results();
+            }
             let ignore_list_response = centered_checkbox(
                 ui,
                 &mut app.shell.ui.ignore_list_enabled,
@@ -340,6 +408,41 @@ pub(super) fn render_top_panel(app: &mut FlistWalkerApp, ctx: &egui::Context) {
                     centered_checkbox(ui, &mut app.shell.runtime.include_dirs, "Folders").changed(),
                 )
             };
+            let mut size_inputs_changed = false;
+            for (hint, hover, is_min) in [
+                ("Min size", "Hide files smaller than this size, e.g. 1 KB", true),
+                ("Max size", "Hide files larger than this size, e.g. 100 MB", false),
+            ] {
+                let text = if is_min {
+                    &mut app.shell.ui.size_filter_min_text
+                } else {
+                    &mut app.shell.ui.size_filter_max_text
+                };
+                let invalid = parse_size_filter_bytes(text).is_err();
+                let mut edit = egui::TextEdit::singleline(text)
+                    .hint_text(hint)
+                    .desired_width(64.0);
+                if invalid {
+                    edit = edit.text_color(ui.visuals().error_fg_color);
+                }
+                size_inputs_changed |= ui.add(edit).on_hover_text(hover).changed();
+            }
+            if size_inputs_changed {
+                app.apply_size_filter_inputs();
+            }
+            let mut hide_root_dotfiles = app.shell.runtime.hide_root_dotfiles;
+            let mut hide_nested_dotfiles = app.shell.runtime.hide_nested_dotfiles;
+            let root_dotfiles_changed =
+                centered_checkbox(ui, &mut hide_root_dotfiles, "Hide Root Dotfiles")
+                    .on_hover_text("Hide `.` entries directly under the root and everything inside them")
+                    .changed();
+            let nested_dotfiles_changed =
+                centered_checkbox(ui, &mut hide_nested_dotfiles, "Hide Nested Dotfiles")
+                    .on_hover_text("Hide `.` files and folders inside subfolders")
+                    .changed();
+            if root_dotfiles_changed || nested_dotfiles_changed {
+                app.set_dotfile_filters(hide_root_dotfiles, hide_nested_dotfiles);
+            }
             let mut show_preview = app.shell.ui.show_preview();
             if centered_checkbox(ui, &mut show_preview, "Preview").changed() {
                 app.shell.ui.set_show_preview(show_preview);
@@ -349,8 +452,29 @@ pub(super) fn render_top_panel(app: &mut FlistWalkerApp, ctx: &egui::Context) {
                 app.mark_ui_state_dirty();
                 app.persist_ui_state_now();
             }
+            render_smart_filter_menu(app, ui);
             ui.separator();
             centered_top_panel_label(ui, app.source_text());
+            if let Some(path) = app.active_filelist_path() {
+                let hover = normalize_windows_path_buf(path.to_path_buf())
+                    .to_string_lossy()
+                    .to_string();
+                if ui
+                    .small_button("Edit")
+                    .on_hover_text(format!("Open {hover}"))
+                    .clicked()
+                {
+                    app.queue_render_command(super::render::RenderCommand::OpenActiveFileList);
+                }
+            }
+            if app.shell.runtime.use_filelist
+                && ui
+                    .small_button("Reload")
+                    .on_hover_text("Re-read FileList.txt from the current root without walking")
+                    .clicked()
+            {
+                app.queue_render_command(super::render::RenderCommand::ReloadFileList);
+            }
             app.maybe_reindex_from_filter_toggles(
                 use_filelist_changed,
                 files_changed,
@@ -385,7 +509,21 @@ pub(super) fn render_top_panel(app: &mut FlistWalkerApp, ctx: &egui::Context) {
                 "Type to fuzzy-search files/folders..."
             })
             .show(ui);
+        let query_error = (!editing_history_search)
+            .then(|| app.shell.runtime.query_error.clone())
+            .flatten();
+        if query_error.is_some() {
+            ui.painter().rect_stroke(
+                output.response.rect,
+                ui.visuals().widgets.inactive.corner_radius,
+                egui::Stroke::new(1.5, ui.visuals().error_fg_color),
+                egui::StrokeKind::Inside,
+            );
+        }
         let _ = egui::Response::clone(&output.response).on_hover_ui_at_pointer(|ui| {
+            if let Some(error) = &query_error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
             if editing_history_search {
                 ui.label("Ctrl+R で履歴検索を開始。Enter / Ctrl+J / Ctrl+M で確定、Esc / Ctrl+G でキャンセル。");
             } else {
@@ -503,6 +641,29 @@ pub(super) fn render_status_panel(app: &mut FlistWalkerApp, ctx: &egui::Context)
                     )
                     .size()
                     .x;
+                if ui
+                    .selectable_label(app.shell.ui.show_log_panel, "Log")
+                    .on_hover_text("Show recent notices and errors with timestamps")
+                    .clicked()
+                {
+                    app.shell.ui.show_log_panel = !app.shell.ui.show_log_panel;
+                }
+                ui.separator();
+                match app.index_progress_fraction() {
+                    Some(Some(fraction)) => {
+                        ui.add(
+                            egui::ProgressBar::new(fraction)
+                                .desired_width(120.0)
+                                .show_percentage(),
+                        );
+                        ui.separator();
+                    }
+                    Some(None) => {
+                        ui.add(egui::Spinner::new().size(14.0));
+                        ui.separator();
+                    }
+                    None => {}
+                }
                 if let Some(label) = app.action_progress_label() {
                     ui.add(egui::Spinner::new().size(14.0));
                     ui.label(label);
@@ -545,6 +706,44 @@ pub(super) fn render_status_panel(app: &mut FlistWalkerApp, ctx: &egui::Context)
         });
 }
 
+pub(super) fn render_log_panel(app: &mut FlistWalkerApp, ctx: &egui::Context) {
+    if !app.shell.ui.show_log_panel {
+        return;
+    }
+    egui::TopBottomPanel::bottom("log-panel")
+        .resizable(true)
+        .default_height(140.0)
+        .show(ctx, |ui| {
+            let lines = app.log_panel_lines();
+            ui.horizontal(|ui| {
+                ui.strong(format!("Log ({})", lines.len()));
+                if ui
+                    .add_enabled(!lines.is_empty(), egui::Button::new("Copy"))
+                    .clicked()
+                {
+                    ui.ctx().copy_text(lines.join("\n"));
+                }
+                if ui
+                    .add_enabled(!lines.is_empty(), egui::Button::new("Clear"))
+                    .clicked()
+                {
+                    app.shell.runtime.log_messages.clear();
+                }
+            });
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    if lines.is_empty() {
+                        ui.weak("No messages yet.");
+                    }
+                    for line in &lines {
+                        ui.monospace(line);
+                    }
+                });
+        });
+}
+
 fn result_row_height(ui: &egui::Ui) -> f32 {
     ui.text_style_height(&egui::TextStyle::Body) + (FlistWalkerApp::RESULT_ROW_V_MARGIN * 2.0)
 }
@@ -565,9 +764,41 @@ pub(super) fn render_results_and_preview(app: &mut FlistWalkerApp, ui: &mut egui
             .min_width(FlistWalkerApp::MIN_PREVIEW_PANEL_WIDTH)
             .max_width(max_preview_width);
         let response = panel.show_inside(ui, |ui| {
-            ui.heading("Preview");
+            ui.horizontal(|ui| {
+                ui.heading("Preview");
+                if ui
+                    .checkbox(&mut app.shell.ui.render_markdown_preview, "Render markdown")
+                    .changed()
+                {
+                    app.mark_ui_state_dirty();
+                    app.persist_ui_state_now();
+                }
+                ui.checkbox(&mut app.shell.ui.copy_preview_line_numbers, "Line numbers");
+                if ui
+                    .add_enabled(
+                        !app.shell.runtime.preview.is_empty(),
+                        egui::Button::new("Copy").small(),
+                    )
+                    .on_hover_text("Copy the preview text")
+                    .clicked()
+                {
+                    app.copy_preview(ui.ctx());
+                }
+            });
+            let markdown_lines = (app.shell.ui.render_markdown_preview
+                && app.current_preview_is_markdown())
+            .then(|| app.markdown_preview_lines())
+            .flatten();
+            let render_table = app.current_preview_is_table();
             let preview_width = ui.available_width();
             let preview_height = ui.available_height();
+            let mut preview_scroll = egui::ScrollArea::both().auto_shrink([false, false]);
+            let scroll_pages = std::mem::take(&mut app.shell.ui.pending_preview_scroll_pages);
+            if scroll_pages != 0 {
+                let offset =
+                    app.shell.ui.preview_scroll_offset + scroll_pages as f32 * preview_height;
+                preview_scroll = preview_scroll.vertical_scroll_offset(offset.max(0.0));
+            }
             ui.allocate_ui_with_layout(
                 egui::vec2(preview_width, preview_height),
                 egui::Layout::top_down(egui::Align::Min),
@@ -575,18 +806,27 @@ pub(super) fn render_results_and_preview(app: &mut FlistWalkerApp, ui: &mut egui
                     let frame_fill = ui.visuals().extreme_bg_color;
                     egui::Frame::NONE.fill(frame_fill).show(ui, |ui| {
                         ui.set_min_size(egui::vec2(preview_width, preview_height));
-                        egui::ScrollArea::both()
-                            .auto_shrink([false, false])
-                            .show(ui, |ui| {
-                                ui.add_sized(
-                                    egui::vec2(preview_width, preview_height),
-                                    egui::TextEdit::multiline(&mut app.shell.runtime.preview)
-                                        .interactive(false)
-                                        .font(egui::TextStyle::Monospace)
-                                        .desired_width(f32::INFINITY)
-                                        .desired_rows(1),
-                                );
-                            });
+                        let output = preview_scroll.show(ui, |ui| {
+                            if let Some(lines) = &markdown_lines {
+                                let job = build_markdown_preview_job(ui, lines);
+                                ui.label(job);
+                                return;
+                            }
+                            if render_table {
+                                let job = build_table_preview_job(ui, &app.shell.runtime.preview);
+                                ui.label(job);
+                                return;
+                            }
+                            ui.add_sized(
+                                egui::vec2(preview_width, preview_height),
+                                egui::TextEdit::multiline(&mut app.shell.runtime.preview)
+                                    .interactive(false)
+                                    .font(egui::TextStyle::Monospace)
+                                    .desired_width(f32::INFINITY)
+                                    .desired_rows(1),
+                            );
+                        });
+                        app.shell.ui.preview_scroll_offset = output.state.offset.y;
                     });
                 },
             );
@@ -621,6 +861,16 @@ pub(super) fn render_results_and_preview(app: &mut FlistWalkerApp, ui: &mut egui
 pub(super) fn render_results_list(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
     ui.horizontal(|ui| {
         ui.heading("Results");
+        if app.results_activity_in_progress() {
+            ui.add(egui::Spinner::new().size(14.0))
+                .on_hover_text("Indexing or searching; results are still filling in");
+        }
+        if let Some(typed) = app.active_type_ahead(Instant::now()) {
+            ui.label(egui::RichText::new(format!("Find: {typed}")).strong())
+                .on_hover_text("Type-ahead jump; resets after a short pause");
+            ui.ctx()
+                .request_repaint_after(FlistWalkerApp::TYPE_AHEAD_IDLE_RESET);
+        }
         let total = app.shell.runtime.total_match_count;
         let shown = app.shell.runtime.results.len();
         if total > shown {
@@ -628,6 +878,66 @@ pub(super) fn render_results_list(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
         } else {
             ui.label(format!("{shown} shown"));
         }
+        centered_checkbox(
+            ui,
+            &mut app.shell.ui.group_results_by_directory,
+            "Group by folder",
+        )
+        .on_hover_text("Insert a folder header whenever consecutive results change folder");
+        centered_checkbox(ui, &mut app.shell.ui.show_result_scores, "Show scores")
+            .on_hover_text("Show each result's match score in a leading column");
+        centered_checkbox(
+            ui,
+            &mut app.shell.ui.show_extension_breakdown,
+            "Show breakdown",
+        )
+        .on_hover_text("Tally the file extensions of the current results");
+        centered_checkbox(ui, &mut app.shell.ui.wrap_result_rows, "Wrap rows")
+            .on_hover_text("Wrap long paths onto multiple lines instead of clipping them");
+        let mut hide_pinned = app.shell.runtime.hide_pinned_results;
+        if centered_checkbox(ui, &mut hide_pinned, "Hide pinned")
+            .on_hover_text("Hide results that are already pinned")
+            .changed()
+        {
+            app.set_hide_pinned_results(hide_pinned);
+        }
+        if ui
+            .selectable_label(app.shell.runtime.recent_view, "Recent")
+            .on_hover_text(
+                "Show the most recently modified files in the whole index without changing the query (click again or type to return)",
+            )
+            .clicked()
+        {
+            app.set_recent_view(!app.shell.runtime.recent_view);
+        }
+        if ui
+            .selectable_label(
+                app.shell.ui.show_basket,
+                format!("Basket ({})", app.shell.runtime.basket.len()),
+            )
+            .on_hover_text(
+                "Review paths collected across searches (Ctrl+Shift+B adds the selection)",
+            )
+            .clicked()
+        {
+            app.toggle_basket_window();
+        }
+        if let Some(base) = app.shell.runtime.display_base.clone() {
+            let name = base
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| normalize_path_for_display(&base));
+            if ui
+                .small_button(format!("Base: {name} ×"))
+                .on_hover_text(format!(
+                    "Paths are shown relative to {}. Click to reset to the root.",
+                    normalize_path_for_display(&base)
+                ))
+                .clicked()
+            {
+                app.clear_display_base();
+            }
+        }
         let row_height = ui.spacing().interact_size.y;
         let row_width = ui.available_width();
         ui.allocate_ui_with_layout(
@@ -679,6 +989,18 @@ pub(super) fn render_results_list(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
             },
         );
     });
+    if app.shell.ui.show_extension_breakdown {
+        if let Some(breakdown) = FlistWalkerApp::extension_breakdown_text(
+            app.shell
+                .runtime
+                .results
+                .iter()
+                .map(|(path, _)| path.as_path()),
+            FlistWalkerApp::EXTENSION_BREAKDOWN_TOP,
+        ) {
+            ui.label(egui::RichText::new(breakdown).small().weak());
+        }
+    }
     let scroll_enabled =
         FlistWalkerApp::results_scroll_enabled(app.shell.ui.preview_resize_in_progress());
     egui::ScrollArea::both()
@@ -693,20 +1015,79 @@ pub(super) fn render_results_list(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
             let clip_rect = ui.clip_rect();
             let row_width = ui.available_width().max(0.0);
             let row_height = result_row_height(ui);
+            let group_by_directory = app.shell.ui.group_results_by_directory;
+            let show_scores = app.shell.ui.show_result_scores;
+            let wrap_rows = app.shell.ui.wrap_result_rows;
+            let mut previous_group: Option<String> = None;
 
+            if let Some(message) = app.results_empty_state_message() {
+                ui.add_space(row_height);
+                ui.vertical_centered(|ui| {
+                    ui.label(egui::RichText::new(message).weak());
+                });
+            }
             for i in 0..app.shell.runtime.results.len() {
-                let Some((path, _score)) = app.shell.runtime.results.get(i) else {
+                let Some((path, score)) = app.shell.runtime.results.get(i) else {
                     continue;
                 };
                 let path = path.clone();
+                let score = show_scores.then_some(*score);
+                if group_by_directory {
+                    let display = super::display_path_with_mode(
+                        &path,
+                        app.display_root_for_path(&path),
+                        prefer_relative,
+                    );
+                    let (group, _) = FlistWalkerApp::result_directory_group(&display);
+                    if previous_group.as_deref() != Some(group) {
+                        let (rect, _) = ui.allocate_exact_size(
+                            egui::vec2(row_width, row_height),
+                            egui::Sense::hover(),
+                        );
+                        if clip_rect.intersects(rect) {
+                            render_result_group_header(ui, rect, group);
+                        }
+                        previous_group = Some(group.to_string());
+                    }
+                }
                 let is_current = app.shell.runtime.current_row == Some(i);
+                // Wrapped rows need their galley up front to know how tall the row is.
+                let mut layout = wrap_rows.then(|| {
+                    layout_result_row(
+                        app,
+                        ui,
+                        row_width,
+                        &path,
+                        score,
+                        is_current,
+                        prefer_relative,
+                        group_by_directory,
+                        true,
+                    )
+                });
+                let height = layout
+                    .as_ref()
+                    .map_or(row_height, |layout| layout.row_height(row_height));
                 let (rect, response) =
-                    ui.allocate_exact_size(egui::vec2(row_width, row_height), egui::Sense::click());
+                    ui.allocate_exact_size(egui::vec2(row_width, height), egui::Sense::click());
                 if is_current && app.shell.ui.scroll_to_current() {
                     ui.scroll_to_rect(rect, None);
                 }
                 if clip_rect.intersects(rect) {
-                    render_result_row(app, ui, rect, &path, is_current, prefer_relative);
+                    let layout = layout.take().unwrap_or_else(|| {
+                        layout_result_row(
+                            app,
+                            ui,
+                            row_width,
+                            &path,
+                            score,
+                            is_current,
+                            prefer_relative,
+                            group_by_directory,
+                            false,
+                        )
+                    });
+                    render_result_row(ui, rect, layout, is_current);
                 }
                 if response.clicked() {
                     clicked_row = Some(i);
@@ -714,6 +1095,19 @@ pub(super) fn render_results_list(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
                 if response.double_clicked() {
                     execute_row = Some(i);
                 }
+                let pinned = app.shell.runtime.pinned_paths.contains(&path);
+                let is_dir = app.find_entry_kind(&path).and_then(|kind| kind.is_dir) == Some(true);
+                let editor_configured = !app.shell.runtime.editor_command_template.is_empty();
+                let mut menu_command = None;
+                response.context_menu(|ui| {
+                    menu_command = result_context_menu(ui, pinned, is_dir, editor_configured);
+                });
+                if let Some(command) = menu_command {
+                    app.queue_render_command(super::render::RenderCommand::ResultMenu {
+                        row: i,
+                        command,
+                    });
+                }
             }
             if let Some(i) = clicked_row {
                 app.set_current_row(Some(i));
@@ -722,12 +1116,144 @@ pub(super) fn render_results_list(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
             }
             if let Some(i) = execute_row {
                 app.set_current_row(Some(i));
-                let open_parent_for_files = ui.input(|i| i.modifiers.shift);
-                app.execute_selected_for_activation(open_parent_for_files);
+                if ui.input(|i| i.modifiers.shift) {
+                    app.execute_selected_open_folder();
+                } else {
+                    app.execute_selected();
+                }
             }
         });
 }
 
+fn render_smart_filter_menu(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
+    use super::render::{RenderCommand, RenderSmartFilterCommand};
+    let mut command = None;
+    ui.menu_button("Filters", |ui| {
+        ui.set_min_width(200.0);
+        if app.shell.ui.smart_filters.is_empty() {
+            ui.weak("No saved filters");
+        }
+        for (index, filter) in app.shell.ui.smart_filters.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui
+                    .button(&filter.name)
+                    .on_hover_text(&filter.query)
+                    .clicked()
+                {
+                    command = Some(RenderSmartFilterCommand::Apply(index));
+                    ui.close();
+                }
+                if ui
+                    .small_button("x")
+                    .on_hover_text("Delete this filter")
+                    .clicked()
+                {
+                    command = Some(RenderSmartFilterCommand::Delete(index));
+                }
+            });
+        }
+        ui.separator();
+        if ui.button("Save Current Query...").clicked() {
+            command = Some(RenderSmartFilterCommand::OpenSave);
+            ui.close();
+        }
+    });
+    if let Some(command) = command {
+        app.queue_render_command(RenderCommand::SmartFilter(command));
+    }
+}
+
+fn result_context_menu(
+    ui: &mut egui::Ui,
+    pinned: bool,
+    is_dir: bool,
+    editor_configured: bool,
+) -> Option<RenderResultMenuCommand> {
+    let mut command = None;
+    let mut item = |ui: &mut egui::Ui, enabled: bool, label: &str, value| {
+        if ui.add_enabled(enabled, egui::Button::new(label)).clicked() {
+            command = Some(value);
+            ui.close();
+        }
+    };
+    item(
+        ui,
+        true,
+        "Open / Execute",
+        RenderResultMenuCommand::ExecuteSelected,
+    );
+    item(
+        ui,
+        true,
+        "Open Containing Folder",
+        RenderResultMenuCommand::OpenFolder,
+    );
+    if editor_configured {
+        item(
+            ui,
+            !is_dir,
+            "Open in Editor",
+            RenderResultMenuCommand::OpenInEditor,
+        );
+    }
+    ui.separator();
+    item(ui, true, "Copy Path", RenderResultMenuCommand::CopyPath);
+    item(
+        ui,
+        true,
+        "Copy Path for Shell",
+        RenderResultMenuCommand::CopyPathForShell,
+    );
+    item(
+        ui,
+        true,
+        "Copy as Site URL",
+        RenderResultMenuCommand::CopySiteUrl,
+    );
+    item(ui, true, "Copy Name", RenderResultMenuCommand::CopyName);
+    item(
+        ui,
+        true,
+        "Copy Results as Tree",
+        RenderResultMenuCommand::CopyResultsAsTree,
+    );
+    item(
+        ui,
+        true,
+        "Copy All Results",
+        RenderResultMenuCommand::CopyAllResults,
+    );
+    ui.separator();
+    item(
+        ui,
+        true,
+        if pinned { "Unpin" } else { "Pin" },
+        RenderResultMenuCommand::TogglePin,
+    );
+    item(
+        ui,
+        true,
+        "Pin Same Extension Below",
+        RenderResultMenuCommand::PinSameExtensionBelow,
+    );
+    item(
+        ui,
+        is_dir,
+        "Set as Root",
+        RenderResultMenuCommand::SetAsRoot,
+    );
+    item(
+        ui,
+        is_dir,
+        "Set as Display Base",
+        RenderResultMenuCommand::SetAsDisplayBase,
+    );
+    ui.separator();
+    item(ui, true, "Move to...", RenderResultMenuCommand::MoveTo);
+    item(ui, true, "Move to Trash", RenderResultMenuCommand::Trash);
+    command
+}
+
 pub(super) fn render_history_search_results(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
     ui.heading("History Results");
     ui.label(format!(
@@ -790,26 +1316,80 @@ pub(super) fn render_history_search_results(app: &mut FlistWalkerApp, ui: &mut e
         });
 }
 
-fn render_result_row(
+/// 結果行の描画に必要な galley 群。wrap 時は行高の算出にも使う。
+struct ResultRowLayout {
+    galley: std::sync::Arc<egui::Galley>,
+    score: Option<(std::sync::Arc<egui::Galley>, f32)>,
+}
+
+impl ResultRowLayout {
+    fn row_height(&self, min_height: f32) -> f32 {
+        (self.galley.size().y + (FlistWalkerApp::RESULT_ROW_V_MARGIN * 2.0)).max(min_height)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn layout_result_row(
     app: &mut FlistWalkerApp,
     ui: &egui::Ui,
-    rect: egui::Rect,
+    row_width: f32,
     path: &Path,
+    score: Option<f64>,
     is_current: bool,
     prefer_relative: bool,
-) {
+    collapse_directory: bool,
+    wrap: bool,
+) -> ResultRowLayout {
     let is_pinned = app.shell.runtime.pinned_paths.contains(path);
     let kind = app.find_entry_kind(path);
-    let display = super::display_path_with_mode(path, &app.shell.runtime.root, prefer_relative);
-    let positions = app.highlight_positions_for_path_cached(path, prefer_relative);
-    let job = build_result_row_job(
-        ui,
-        &display,
-        positions.as_slice(),
-        is_current,
-        is_pinned,
-        kind,
+    let highlighted = app.highlighted_display_for_path_cached(path, prefer_relative);
+    // The folder header carries the shared prefix; keep only the indented name here.
+    let (display, positions) = if collapse_directory {
+        let (display, positions) =
+            FlistWalkerApp::collapse_result_directory(&highlighted.display, &highlighted.positions);
+        (Cow::Owned(display), Cow::Owned(positions))
+    } else {
+        (
+            Cow::Borrowed(highlighted.display.as_str()),
+            Cow::Borrowed(highlighted.positions.as_slice()),
+        )
+    };
+    let kind_label = result_row_kind_label(path, kind, app.shell.runtime.result_type_icons);
+    let mut job = build_result_row_job(
+        ui, &display, &positions, is_current, is_pinned, kind, kind_label,
     );
+
+    let mut text_width = row_width - (FlistWalkerApp::RESULT_ROW_H_MARGIN * 2.0);
+    let score = score.map(|score| {
+        let font = egui::TextStyle::Monospace.resolve(ui.style());
+        let color = ui.visuals().weak_text_color();
+        // Size the column from the widest common label so scores stay right-aligned.
+        let column_width = ui
+            .painter()
+            .layout_no_wrap(
+                FlistWalkerApp::result_score_label(9999.9),
+                font.clone(),
+                color,
+            )
+            .size()
+            .x;
+        text_width -= column_width + ui.spacing().item_spacing.x;
+        let galley =
+            ui.painter()
+                .layout_no_wrap(FlistWalkerApp::result_score_label(score), font, color);
+        (galley, column_width)
+    });
+    if wrap {
+        job.wrap.max_width = text_width.max(1.0);
+        job.wrap.break_anywhere = true;
+    }
+    ResultRowLayout {
+        galley: ui.painter().layout_job(job),
+        score,
+    }
+}
+
+fn render_result_row(ui: &egui::Ui, rect: egui::Rect, layout: ResultRowLayout, is_current: bool) {
     let selected_bg = render_theme::selected_fill(ui.visuals().dark_mode);
     if is_current {
         ui.painter().rect_filled(
@@ -819,14 +1399,131 @@ fn render_result_row(
         );
     }
 
+    let mut inner_rect = rect.shrink2(egui::vec2(
+        FlistWalkerApp::RESULT_ROW_H_MARGIN,
+        FlistWalkerApp::RESULT_ROW_V_MARGIN,
+    ));
+    if let Some((score_galley, column_width)) = layout.score {
+        let color = ui.visuals().weak_text_color();
+        let score_pos =
+            FlistWalkerApp::result_score_pos(inner_rect, column_width, score_galley.size());
+        ui.painter().galley(score_pos, score_galley, color);
+        inner_rect.min.x += column_width + ui.spacing().item_spacing.x;
+    }
+    let text_pos = FlistWalkerApp::result_row_text_pos(inner_rect, layout.galley.size());
+    ui.painter()
+        .galley(text_pos, layout.galley, ui.visuals().text_color());
+}
+
+fn render_result_group_header(ui: &egui::Ui, rect: egui::Rect, group: &str) {
+    let label = if group.is_empty() { "." } else { group };
     let inner_rect = rect.shrink2(egui::vec2(
         FlistWalkerApp::RESULT_ROW_H_MARGIN,
         FlistWalkerApp::RESULT_ROW_V_MARGIN,
     ));
-    let galley = ui.painter().layout_job(job);
+    let galley = ui.painter().layout_no_wrap(
+        format!("▾ {label}"),
+        egui::TextStyle::Body.resolve(ui.style()),
+        ui.visuals().weak_text_color(),
+    );
     let text_pos = FlistWalkerApp::result_row_text_pos(inner_rect, galley.size());
     ui.painter()
-        .galley(text_pos, galley, ui.visuals().text_color());
+        .galley(text_pos, galley, ui.visuals().weak_text_color());
+}
+
+fn build_markdown_preview_job(
+    ui: &egui::Ui,
+    lines: &[MarkdownPreviewLine],
+) -> egui::text::LayoutJob {
+    let body_size = egui::TextStyle::Body.resolve(ui.style()).size;
+    let text_color = ui.visuals().text_color();
+    let strong_color = ui.visuals().strong_text_color();
+    let mut job = egui::text::LayoutJob::default();
+    for (index, line) in lines.iter().enumerate() {
+        if index > 0 {
+            job.append("\n", 0.0, egui::TextFormat::default());
+        }
+        let (text, format) = match line {
+            MarkdownPreviewLine::Heading { level, text } => {
+                let scale = match *level {
+                    1 => 1.6,
+                    2 => 1.35,
+                    3 => 1.15,
+                    _ => 1.0,
+                };
+                (
+                    Cow::Borrowed(text.as_str()),
+                    egui::TextFormat {
+                        font_id: egui::FontId::proportional(body_size * scale),
+                        color: strong_color,
+                        ..Default::default()
+                    },
+                )
+            }
+            MarkdownPreviewLine::Bullet { indent, text } => (
+                Cow::Owned(format!("{}• {}", "    ".repeat(*indent), text)),
+                egui::TextFormat {
+                    font_id: egui::FontId::proportional(body_size),
+                    color: text_color,
+                    ..Default::default()
+                },
+            ),
+            MarkdownPreviewLine::Code(text) => (
+                Cow::Borrowed(text.as_str()),
+                egui::TextFormat {
+                    font_id: egui::FontId::monospace(body_size),
+                    color: text_color,
+                    background: ui.visuals().code_bg_color,
+                    ..Default::default()
+                },
+            ),
+            MarkdownPreviewLine::Plain(text) => (
+                Cow::Borrowed(text.as_str()),
+                egui::TextFormat {
+                    font_id: egui::FontId::proportional(body_size),
+                    color: text_color,
+                    ..Default::default()
+                },
+            ),
+        };
+        job.append(&text, 0.0, format);
+    }
+    job
+}
+
+/// 表 preview を等幅で描画し、列見出しの行だけを強調する。
+fn build_table_preview_job(ui: &egui::Ui, preview: &str) -> egui::text::LayoutJob {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let header_row = table_preview_header_row(preview);
+    let mut job = egui::text::LayoutJob::default();
+    for (index, line) in preview.lines().enumerate() {
+        if index > 0 {
+            job.append("\n", 0.0, egui::TextFormat::default());
+        }
+        let format = if index == header_row {
+            egui::TextFormat {
+                font_id: font_id.clone(),
+                color: ui.visuals().strong_text_color(),
+                underline: egui::Stroke::new(1.0, ui.visuals().weak_text_color()),
+                ..Default::default()
+            }
+        } else {
+            egui::TextFormat::simple(font_id.clone(), ui.visuals().text_color())
+        };
+        job.append(line, 0.0, format);
+    }
+    job
+}
+
+fn result_row_kind_label(path: &Path, kind: Option<EntryKind>, use_icons: bool) -> &'static str {
+    match (kind.map(|k| k.display), use_icons) {
+        (Some(display_kind), true) => render_theme::entry_type_glyph(path, display_kind),
+        (Some(EntryDisplayKind::Dir), false) => "DIR ",
+        (Some(EntryDisplayKind::File), false) => "FILE",
+        (Some(EntryDisplayKind::Link), false) => "LINK",
+        (Some(EntryDisplayKind::Other), false) => "OTHR",
+        (None, _) => "....",
+    }
 }
 
 fn build_result_row_job(
@@ -836,6 +1533,7 @@ fn build_result_row_job(
     is_current: bool,
     is_pinned: bool,
     kind: Option<EntryKind>,
+    kind_label: &str,
 ) -> egui::text::LayoutJob {
     let marker_current = if is_current { "▶" } else { "·" };
     let marker_pin = if is_pinned { "◆" } else { "·" };
@@ -852,20 +1550,9 @@ fn build_result_row_job(
             ..Default::default()
         },
     );
-    let (kind_label, kind_color) = match kind.map(|k| k.display) {
-        Some(display_kind @ EntryDisplayKind::Dir) => {
-            ("DIR ", render_theme::entry_kind_color(display_kind))
-        }
-        Some(display_kind @ EntryDisplayKind::File) => {
-            ("FILE", render_theme::entry_kind_color(display_kind))
-        }
-        Some(display_kind @ EntryDisplayKind::Link) => {
-            ("LINK", render_theme::entry_kind_color(display_kind))
-        }
-        Some(display_kind @ EntryDisplayKind::Other) => {
-            ("OTHR", render_theme::entry_kind_color(display_kind))
-        }
-        None => ("....", ui.visuals().weak_text_color()),
+    let kind_color = match kind.map(|k| k.display) {
+        Some(display_kind) => render_theme::entry_kind_color(display_kind),
+        None => ui.visuals().weak_text_color(),
     };
     job.append(
         kind_label,
@@ -194,6 +194,12 @@ pub(super) fn render_tab_bar(app: &mut FlistWalkerApp, ui: &mut egui::Ui) {
             if settings_response.clicked() {
                 app.queue_render_command(RenderCommand::OpenRuntimeConfig);
             }
+            let reset_response = ui
+                .add_sized([row_height, row_height], egui::Button::new("↺"))
+                .on_hover_text("Reset settings to defaults...");
+            if reset_response.clicked() {
+                app.queue_render_command(RenderCommand::OpenResetSettings);
+            }
         });
         if let Some(index) = close_tab {
             app.queue_render_command(RenderCommand::TabBar(RenderTabBarCommand::CloseTab(index)));
@@ -1,5 +1,16 @@
 use super::EntryDisplayKind;
 use eframe::egui;
+use std::path::Path;
+
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "svg", "webp", "ico", "tif", "tiff",
+];
+const ARCHIVE_EXTENSIONS: &[&str] = &[
+    "zip", "tar", "gz", "tgz", "bz2", "xz", "7z", "rar", "zst", "lzh",
+];
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "exe", "com", "bat", "cmd", "msi", "ps1", "sh", "app", "appimage",
+];
 
 pub(super) fn selected_fill(dark_mode: bool) -> egui::Color32 {
     if dark_mode {
@@ -18,6 +29,31 @@ pub(super) fn entry_kind_color(kind: EntryDisplayKind) -> egui::Color32 {
     }
 }
 
+/// 結果行の先頭に表示する種別 glyph を kind と拡張子から選ぶ。
+pub(super) fn entry_type_glyph(path: &Path, kind: EntryDisplayKind) -> &'static str {
+    match kind {
+        EntryDisplayKind::Dir => "📁",
+        EntryDisplayKind::Link => "🔗",
+        EntryDisplayKind::Other => "❔",
+        EntryDisplayKind::File => {
+            let ext = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            if IMAGE_EXTENSIONS.contains(&ext.as_str()) {
+                "🖼"
+            } else if ARCHIVE_EXTENSIONS.contains(&ext.as_str()) {
+                "📦"
+            } else if EXECUTABLE_EXTENSIONS.contains(&ext.as_str()) {
+                "⚙"
+            } else {
+                "📄"
+            }
+        }
+    }
+}
+
 pub(super) fn highlight_text_color() -> egui::Color32 {
     egui::Color32::from_rgb(245, 158, 11)
 }
@@ -75,16 +75,21 @@ impl FlistWalkerApp {
         self.shell.tabs.sort_request_tab(request_id)
     }
 
-    /// action/preview/sort の応答を一括で処理する。
+    /// action/preview/sort/size の応答を一括で処理する。
     pub(super) fn poll_routed_worker_responses(&mut self) {
         self.poll_action_response();
         self.poll_sort_response();
+        self.poll_size_response();
         self.poll_preview_response();
     }
 
-    /// action worker の応答を現在 tab または背景 tab に反映する。
+    /// action worker の応答を現在 tab または背景 tab に反映する。完了した file 操作は全 tab に反映する。
     pub(super) fn poll_action_response(&mut self) {
-        while let Ok(response) = self.shell.worker_bus.action.rx.try_recv() {
+        while let Ok(mut response) = self.shell.worker_bus.action.rx.try_recv() {
+            self.settle_pending_undo(response.request_id, response.file_operation.as_ref());
+            if let Some(operation) = response.file_operation.take() {
+                self.apply_file_operation(operation);
+            }
             if self.apply_active_action_response(&response) {
                 continue;
             }
@@ -148,6 +153,10 @@ impl FlistWalkerApp {
 
     pub(super) fn poll_preview_response(&mut self) {
         while let Ok(response) = self.shell.worker_bus.preview.rx.try_recv() {
+            if response.quick_look {
+                self.apply_quick_look_response(response);
+                continue;
+            }
             if self.apply_active_preview_response(&response) {
                 continue;
             }
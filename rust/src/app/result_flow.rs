@@ -13,9 +13,11 @@ struct SortableResult {
 }
 
 impl FlistWalkerApp {
-    /// root 単位で破棄すべき sort metadata cache をまとめて消す。
+    /// root 単位で破棄すべき sort metadata と entry size の cache をまとめて消す。
     pub(super) fn clear_sort_metadata_cache(&mut self) {
         self.shell.cache.sort_metadata.clear();
+        self.shell.cache.entry_size.clear();
+        self.shell.worker_bus.size.clear_request();
     }
 
     /// 結果ソートに使う時刻属性を上限付き cache へ保存する。
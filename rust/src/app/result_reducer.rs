@@ -1,6 +1,6 @@
 use super::{
-    AppTabState, FlistWalkerApp, PreviewResponse, ResultSortMode, SearchResponse,
-    SortMetadataRequest, SortMetadataResponse,
+    AppTabState, FlistWalkerApp, PreviewResponse, ResultKindGrouping, ResultSortMode,
+    SearchResponse, SortMetadataRequest, SortMetadataResponse,
 };
 use crate::indexer::IndexSource;
 use std::path::PathBuf;
@@ -25,9 +25,32 @@ fn clamp_tab_result_selection(tab: &mut AppTabState) {
         .map(|row: usize| row.min(max_index));
 }
 
+/// 並び替え後の順序を保ったまま folder 群と file 群へ安定分割する。kind 未解決の entry は file 側に置く。
+fn group_results_by_kind(
+    app: &FlistWalkerApp,
+    results: Vec<(PathBuf, f64)>,
+) -> Vec<(PathBuf, f64)> {
+    let dirs_first = match app.shell.runtime.result_kind_grouping {
+        ResultKindGrouping::None => return results,
+        ResultKindGrouping::DirsFirst => true,
+        ResultKindGrouping::FilesFirst => false,
+    };
+    let (dirs, files): (Vec<_>, Vec<_>) = results.into_iter().partition(|(path, _)| {
+        app.find_entry_kind(path)
+            .is_some_and(|kind| kind.is_dir == Some(true))
+    });
+    let (mut head, tail) = if dirs_first {
+        (dirs, files)
+    } else {
+        (files, dirs)
+    };
+    head.extend(tail);
+    head
+}
+
 pub(super) fn apply_results_with_selection_policy(
     app: &mut FlistWalkerApp,
-    results: Vec<(PathBuf, f64)>,
+    mut results: Vec<(PathBuf, f64)>,
     keep_scroll_position: bool,
     preserve_selected_path: bool,
 ) {
@@ -47,7 +70,10 @@ pub(super) fn apply_results_with_selection_policy(
         })
         .flatten();
     let previous_row = app.shell.runtime.current_row;
-    app.shell.runtime.results = results;
+    if app.shell.runtime.hide_pinned_results {
+        results.retain(|(path, _)| !app.shell.runtime.pinned_paths.contains(path));
+    }
+    app.shell.runtime.results = group_results_by_kind(app, results);
     if app.shell.runtime.results.is_empty() {
         app.set_current_row(None);
         app.shell.runtime.preview.clear();
@@ -117,9 +143,16 @@ pub(super) fn apply_active_search_response(
         app.clear_notice();
     }
     app.shell.runtime.total_match_count = response.total_match_count;
-    app.shell.runtime.result_sort_mode = response.sort_mode;
-    app.shell.runtime.result_sort_scope = response.sort_scope;
-    app.replace_results_snapshot(response.results, false);
+    // The empty-query recency override is not a user-selected sort; keep the selector as-is.
+    if !app.empty_query_recent_first_active() {
+        app.shell.runtime.result_sort_mode = response.sort_mode;
+        app.shell.runtime.result_sort_scope = response.sort_scope;
+    }
+    if app.shell.runtime.sticky_results_scroll {
+        replace_results_snapshot_keeping_selected_path(app, response.results);
+    } else {
+        app.replace_results_snapshot(response.results, false);
+    }
     if matches!(app.shell.runtime.index.source, IndexSource::Walker) {
         // Search results can arrive after index completion. Queue kind resolution
         // from the newly installed result snapshot so deferred LINK entries are
@@ -154,6 +187,22 @@ pub(super) fn replace_results_snapshot(
     apply_results_with_selection_policy(app, results, keep_scroll_position, false);
 }
 
+/// sticky scroll 用: 選択中 path が新しい結果に残っていればその行を選び、scroll 位置も動かさない。
+fn replace_results_snapshot_keeping_selected_path(
+    app: &mut FlistWalkerApp,
+    results: Vec<(PathBuf, f64)>,
+) {
+    app.shell.worker_bus.sort.clear_request();
+    app.shell.runtime.base_results = results.clone();
+    let selected_still_present = app
+        .shell
+        .runtime
+        .current_row
+        .and_then(|row| app.shell.runtime.results.get(row))
+        .is_some_and(|(selected, _)| results.iter().any(|(path, _)| path == selected));
+    apply_results_with_selection_policy(app, results, selected_still_present, true);
+}
+
 pub(super) fn invalidate_result_sort(app: &mut FlistWalkerApp, keep_scroll_position: bool) {
     let had_non_score_sort = app.shell.runtime.result_sort_mode != ResultSortMode::Score;
     app.shell.worker_bus.sort.clear_request();
@@ -277,6 +326,7 @@ pub(super) fn apply_background_preview_response(
         };
         if current_path.is_some_and(|current_path| *current_path == response.path) {
             tab.result_state.preview = response.preview;
+            tab.result_state.preview_path = Some(response.path.clone());
         }
     }
 }
@@ -295,6 +345,7 @@ pub(super) fn apply_active_preview_response(
         if let Some((current_path, _)) = app.shell.runtime.results.get(row) {
             if *current_path == response.path {
                 app.shell.runtime.preview = response.preview.clone();
+                app.shell.runtime.preview_path = Some(response.path.clone());
             }
         }
     }
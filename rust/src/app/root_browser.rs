@@ -4,8 +4,26 @@ use super::FlistWalkerApp;
 use crate::path_utils::normalize_windows_path_buf;
 use crate::path_utils::path_key;
 use eframe::egui;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
 use std::path::{Path, PathBuf};
 
+/// root dropdown の絞り込み文字列が保存済み root の path に fuzzy 一致するかを判定する。
+fn root_matches_dropdown_filter(filter: &str, path: &Path) -> bool {
+    if filter.is_empty() {
+        return true;
+    }
+    let candidate = normalize_windows_path_buf(path.to_path_buf())
+        .to_string_lossy()
+        .to_string();
+    SkimMatcherV2::default()
+        .fuzzy_match(&candidate, filter)
+        .is_some()
+        || candidate
+            .to_ascii_lowercase()
+            .contains(&filter.to_ascii_lowercase())
+}
+
 impl FlistWalkerApp {
     /// ダイアログで選んだ root を現在 tab に適用する。
     pub(super) fn browse_for_root(&mut self) {
@@ -206,9 +224,6 @@ impl FlistWalkerApp {
             manage.draft_default_root = Some(replacement.clone());
         }
         manage.draft_roots[index] = replacement.clone();
-        manage
-            .draft_roots
-            .sort_by_key(|p| p.to_string_lossy().to_string().to_ascii_lowercase());
         manage.selected_index = manage
             .draft_roots
             .iter()
@@ -402,9 +417,6 @@ impl FlistWalkerApp {
             return;
         }
         manage.draft_roots.push(root.clone());
-        manage
-            .draft_roots
-            .sort_by_key(|p| p.to_string_lossy().to_string().to_ascii_lowercase());
         manage.selected_index = manage
             .draft_roots
             .iter()
@@ -415,7 +427,7 @@ impl FlistWalkerApp {
         manage.notice = format!("Added root to draft list: {}", root.display());
     }
 
-    fn browse_dialog_start_location(root: &Path) -> PathBuf {
+    pub(super) fn browse_dialog_start_location(root: &Path) -> PathBuf {
         let normalized = normalize_windows_path_buf(root.to_path_buf());
         if normalized.is_dir() {
             return normalized;
@@ -427,7 +439,10 @@ impl FlistWalkerApp {
     }
 
     #[cfg(test)]
-    fn select_root_via_dialog(&mut self, dialog_root: &Path) -> Result<Option<PathBuf>, String> {
+    pub(super) fn select_root_via_dialog(
+        &mut self,
+        dialog_root: &Path,
+    ) -> Result<Option<PathBuf>, String> {
         self.shell.features.root_browser.last_browse_dialog_root = Some(dialog_root.to_path_buf());
         self.shell
             .features
@@ -438,7 +453,10 @@ impl FlistWalkerApp {
     }
 
     #[cfg(not(test))]
-    fn select_root_via_dialog(&mut self, dialog_root: &Path) -> Result<Option<PathBuf>, String> {
+    pub(super) fn select_root_via_dialog(
+        &mut self,
+        dialog_root: &Path,
+    ) -> Result<Option<PathBuf>, String> {
         native_dialog::DialogBuilder::file()
             .set_location(dialog_root)
             .open_single_dir()
@@ -451,6 +469,11 @@ impl FlistWalkerApp {
         egui::Id::new(Self::ROOT_SELECTOR_POPUP_ID)
     }
 
+    /// root dropdown 内の絞り込み入力欄の stable id を返す。
+    pub(super) fn root_dropdown_filter_id() -> egui::Id {
+        Self::root_selector_popup_id().with("filter")
+    }
+
     pub(super) fn is_root_dropdown_open(&self, ctx: &egui::Context) -> bool {
         ctx.memory(|mem| mem.is_popup_open(Self::root_selector_popup_id()))
     }
@@ -465,25 +488,38 @@ impl FlistWalkerApp {
             .position(|path| path_key(path) == current_key)
     }
 
-    /// dropdown のハイライト位置を保存済み root 一覧に同期する。
-    pub(super) fn sync_root_dropdown_highlight(&mut self) {
-        let max_index = self
-            .shell
+    /// dropdown の絞り込み文字列に一致する保存済み root の index を一覧順で返す。
+    pub(super) fn filtered_root_dropdown_indices(&self) -> Vec<usize> {
+        let filter = self.shell.ui.root_dropdown_filter.trim();
+        self.shell
             .features
             .root_browser
             .saved_roots()
-            .len()
-            .checked_sub(1);
-        let next = match (self.shell.ui.root_dropdown_highlight(), max_index) {
-            (_, None) => None,
-            (Some(index), Some(max)) => Some(index.min(max)),
-            (None, Some(_)) => self.current_root_dropdown_index().or(Some(0usize)),
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| root_matches_dropdown_filter(filter, path))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// dropdown のハイライト位置を絞り込み後の保存済み root 一覧に同期する。
+    pub(super) fn sync_root_dropdown_highlight(&mut self) {
+        let visible = self.filtered_root_dropdown_indices();
+        let next = match self.shell.ui.root_dropdown_highlight() {
+            _ if visible.is_empty() => None,
+            Some(index) if visible.contains(&index) => Some(index),
+            Some(_) => visible.first().copied(),
+            None => self
+                .current_root_dropdown_index()
+                .filter(|index| visible.contains(index))
+                .or_else(|| visible.first().copied()),
         };
         self.shell.ui.set_root_dropdown_highlight(next);
     }
 
     /// root dropdown を開き、入力 focus を切り替える。
     pub(super) fn open_root_dropdown(&mut self, ctx: &egui::Context) {
+        self.shell.ui.root_dropdown_filter.clear();
         self.sync_root_dropdown_highlight();
         ctx.memory_mut(|mem| mem.open_popup(Self::root_selector_popup_id()));
         self.clear_focus_query_request();
@@ -493,18 +529,23 @@ impl FlistWalkerApp {
     /// root dropdown を閉じる。
     pub(super) fn close_root_dropdown(&mut self, ctx: &egui::Context) {
         ctx.memory_mut(|mem| mem.close_popup(Self::root_selector_popup_id()));
+        self.shell.ui.root_dropdown_filter.clear();
+    }
+
+    /// dropdown の絞り込み文字列を更新し、ハイライトを表示中の候補へ寄せる。
+    pub(super) fn set_root_dropdown_filter(&mut self, filter: String) {
+        if self.shell.ui.root_dropdown_filter == filter {
+            return;
+        }
+        self.shell.ui.root_dropdown_filter = filter;
+        self.shell.ui.set_root_dropdown_highlight(None);
+        self.sync_root_dropdown_highlight();
     }
 
-    /// root dropdown 内の候補選択を上下へ移動する。
+    /// root dropdown 内の候補選択を絞り込み後の一覧で上下へ移動する。
     pub(super) fn move_root_dropdown_selection(&mut self, delta: isize) {
-        let Some(max_index) = self
-            .shell
-            .features
-            .root_browser
-            .saved_roots()
-            .len()
-            .checked_sub(1)
-        else {
+        let visible = self.filtered_root_dropdown_indices();
+        let Some(max_position) = visible.len().checked_sub(1) else {
             self.shell.ui.set_root_dropdown_highlight(None);
             return;
         };
@@ -513,9 +554,12 @@ impl FlistWalkerApp {
             .ui
             .root_dropdown_highlight()
             .or_else(|| self.current_root_dropdown_index())
+            .and_then(|index| visible.iter().position(|candidate| *candidate == index))
             .unwrap_or(0) as isize;
-        let next = (current + delta).clamp(0, max_index as isize) as usize;
-        self.shell.ui.set_root_dropdown_highlight(Some(next));
+        let next = (current + delta).clamp(0, max_position as isize) as usize;
+        self.shell
+            .ui
+            .set_root_dropdown_highlight(Some(visible[next]));
     }
 
     /// dropdown で確定した root を現在 tab に反映する。
@@ -1,4 +1,4 @@
-use super::FlistWalkerApp;
+use super::{FlistWalkerApp, ResetSettingsDialogState};
 use crate::fs_atomic::write_text_atomic;
 use crate::path_utils::{normalize_windows_path_buf, path_key};
 use crate::runtime_config::{legacy_settings_base_dirs, migrate_file_if_needed, settings_base_dir};
@@ -13,6 +13,8 @@ use std::time::Instant;
 
 #[cfg(test)]
 static SAVED_ROOTS_FILE_PATH_OVERRIDE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+#[cfg(test)]
+static SMART_FILTERS_FILE_PATH_OVERRIDE: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(super) enum TabAccentColor {
@@ -26,6 +28,61 @@ pub(super) enum TabAccentColor {
     Magenta,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct SavedFilterToggles {
+    pub(super) include_files: bool,
+    pub(super) include_dirs: bool,
+    pub(super) use_regex: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum SmartFilterMode {
+    #[default]
+    Fuzzy,
+    Regex,
+}
+
+/// root を持たない名前付き query。どの root でも query・match mode・種別フィルタを一括で適用する。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct SmartFilter {
+    pub(super) name: String,
+    #[serde(default)]
+    pub(super) query: String,
+    #[serde(default)]
+    pub(super) mode: SmartFilterMode,
+    #[serde(default = "default_smart_filter_include")]
+    pub(super) include_files: bool,
+    #[serde(default = "default_smart_filter_include")]
+    pub(super) include_dirs: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct SourceFilterMemory {
+    #[serde(default)]
+    pub(super) filelist: Option<SavedFilterToggles>,
+    #[serde(default)]
+    pub(super) walker: Option<SavedFilterToggles>,
+}
+
+impl SourceFilterMemory {
+    pub(super) fn get(&self, use_filelist: bool) -> Option<SavedFilterToggles> {
+        if use_filelist {
+            self.filelist
+        } else {
+            self.walker
+        }
+    }
+
+    pub(super) fn set(&mut self, use_filelist: bool, toggles: SavedFilterToggles) {
+        if use_filelist {
+            self.filelist = Some(toggles);
+        } else {
+            self.walker = Some(toggles);
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub(super) struct SavedWindowGeometry {
     pub(super) x: f32,
@@ -43,6 +100,8 @@ pub(super) struct UiState {
     pub(super) show_preview: Option<bool>,
     #[serde(default = "default_ignore_list_enabled")]
     pub(super) ignore_list_enabled: bool,
+    #[serde(default = "default_render_markdown_preview")]
+    pub(super) render_markdown_preview: bool,
     pub(super) preview_panel_width: Option<f32>,
     #[serde(default)]
     pub(super) query_history: Vec<String>,
@@ -56,6 +115,10 @@ pub(super) struct UiState {
     pub(super) skipped_update_target_version: Option<String>,
     #[serde(default)]
     pub(super) suppress_update_check_failure_dialog: bool,
+    #[serde(default)]
+    pub(super) source_filters: SourceFilterMemory,
+    #[serde(default)]
+    pub(super) last_query: Option<String>,
 }
 
 impl Default for UiState {
@@ -65,6 +128,7 @@ impl Default for UiState {
             default_root: None,
             show_preview: None,
             ignore_list_enabled: true,
+            render_markdown_preview: true,
             preview_panel_width: None,
             query_history: Vec::new(),
             results_panel_width: None,
@@ -73,6 +137,8 @@ impl Default for UiState {
             window: None,
             skipped_update_target_version: None,
             suppress_update_check_failure_dialog: false,
+            source_filters: SourceFilterMemory::default(),
+            last_query: None,
         }
     }
 }
@@ -83,12 +149,15 @@ pub(super) struct LaunchSettings {
     pub(super) default_root: Option<PathBuf>,
     pub(super) show_preview: bool,
     pub(super) ignore_list_enabled: bool,
+    pub(super) render_markdown_preview: bool,
     pub(super) preview_panel_width: f32,
     pub(super) query_history: Vec<String>,
     pub(super) restore_tabs: Vec<SavedTabState>,
     pub(super) restore_active_tab: Option<usize>,
     pub(super) skipped_update_target_version: Option<String>,
     pub(super) suppress_update_check_failure_dialog: bool,
+    pub(super) source_filters: SourceFilterMemory,
+    pub(super) last_query: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -115,6 +184,14 @@ fn default_ignore_list_enabled() -> bool {
     true
 }
 
+fn default_render_markdown_preview() -> bool {
+    true
+}
+
+fn default_smart_filter_include() -> bool {
+    true
+}
+
 impl FlistWalkerApp {
     pub(super) const SET_DEFAULT_DISABLED_BY_RESTORE_TABS_NOTICE: &'static str =
         "Set as default is disabled because Restore tabs is enabled in runtime config";
@@ -202,6 +279,7 @@ impl FlistWalkerApp {
             default_root,
             show_preview,
             ignore_list_enabled: ui_state.ignore_list_enabled,
+            render_markdown_preview: ui_state.render_markdown_preview,
             preview_panel_width,
             query_history: if history_persist_disabled {
                 Vec::new()
@@ -220,6 +298,10 @@ impl FlistWalkerApp {
             restore_active_tab: ui_state.active_tab,
             skipped_update_target_version: ui_state.skipped_update_target_version,
             suppress_update_check_failure_dialog: ui_state.suppress_update_check_failure_dialog,
+            source_filters: ui_state.source_filters,
+            last_query: ui_state
+                .last_query
+                .filter(|query| !history_persist_disabled && !query.trim().is_empty()),
         }
     }
 
@@ -342,12 +424,7 @@ impl FlistWalkerApp {
         };
         let mut out = Vec::new();
         let mut seen = HashSet::new();
-        for raw in text.lines() {
-            let line = raw.trim();
-            if line.is_empty() {
-                continue;
-            }
-            let path = normalize_windows_path_buf(PathBuf::from(line));
+        for path in text.lines().filter_map(Self::saved_root_from_line) {
             let key = path_key(&path);
             if seen.insert(key) {
                 out.push(path);
@@ -356,6 +433,71 @@ impl FlistWalkerApp {
         out
     }
 
+    /// 保存 root ファイルの 1 行を root として解釈する。空行と `#` コメント行は `None`。
+    fn saved_root_from_line(raw: &str) -> Option<PathBuf> {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        Some(normalize_windows_path_buf(PathBuf::from(line)))
+    }
+
+    /// 既存ファイルのコメント・空行・並び順を保ったまま保存 root 一覧を書き戻す本文を作る。
+    /// 新しい root は削除された行の位置か次の既存 root の直前、該当がなければ末尾へ置く。
+    pub(super) fn saved_roots_text(existing: &str, roots: &[PathBuf]) -> String {
+        let keys = roots.iter().map(|root| path_key(root)).collect::<Vec<_>>();
+        let existing_keys = existing
+            .lines()
+            .filter_map(Self::saved_root_from_line)
+            .map(|path| path_key(&path))
+            .collect::<HashSet<_>>();
+        let mut lines = Vec::new();
+        let mut written = HashSet::new();
+        let mut next = 0usize;
+        for raw in existing.lines() {
+            let Some(path) = Self::saved_root_from_line(raw) else {
+                lines.push(raw.trim_end().to_string());
+                continue;
+            };
+            let key = path_key(&path);
+            let Some(position) = keys.iter().position(|candidate| *candidate == key) else {
+                // 削除された行は、同じ並び位置に来た新しい root（編集による置き換え）で埋める。
+                if next < keys.len()
+                    && !existing_keys.contains(&keys[next])
+                    && written.insert(keys[next].clone())
+                {
+                    lines.push(roots[next].to_string_lossy().to_string());
+                    next += 1;
+                }
+                continue;
+            };
+            if !written.insert(key) {
+                continue;
+            }
+            while next < position {
+                if !existing_keys.contains(&keys[next]) && written.insert(keys[next].clone()) {
+                    lines.push(roots[next].to_string_lossy().to_string());
+                }
+                next += 1;
+            }
+            next = next.max(position + 1);
+            lines.push(roots[position].to_string_lossy().to_string());
+        }
+        for (root, key) in roots.iter().zip(keys) {
+            if written.insert(key) {
+                lines.push(root.to_string_lossy().to_string());
+            }
+        }
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        if lines.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", lines.join("\n"))
+        }
+    }
+
     pub(super) fn save_saved_roots(&self) {
         let Some(file) = Self::saved_roots_file_path() else {
             return;
@@ -363,23 +505,121 @@ impl FlistWalkerApp {
         if let Some(parent) = file.parent() {
             let _ = fs::create_dir_all(parent);
         }
-        let text = self
-            .shell
-            .features
-            .root_browser
-            .saved_roots
-            .iter()
-            .map(|p| p.to_string_lossy().to_string())
-            .collect::<Vec<_>>()
-            .join("\n");
-        let text_to_write = if text.is_empty() {
-            String::new()
-        } else {
-            format!("{text}\n")
-        };
+        let existing = fs::read_to_string(&file).unwrap_or_default();
+        let text_to_write =
+            Self::saved_roots_text(&existing, &self.shell.features.root_browser.saved_roots);
         let _ = write_text_atomic(&file, &text_to_write);
     }
 
+    fn smart_filters_file_path() -> Option<PathBuf> {
+        #[cfg(test)]
+        if let Some(path) = SMART_FILTERS_FILE_PATH_OVERRIDE
+            .get_or_init(|| Mutex::new(None))
+            .lock()
+            .expect("smart filters path override lock")
+            .clone()
+        {
+            return Some(path);
+        }
+
+        settings_base_dir().map(|base| Self::smart_filters_file_path_in(&base))
+    }
+
+    pub(super) fn smart_filters_file_path_in(base: &Path) -> PathBuf {
+        base.join("filters.json")
+    }
+
+    #[cfg(test)]
+    pub(super) fn set_smart_filters_file_path_override_for_test(path: Option<PathBuf>) {
+        *SMART_FILTERS_FILE_PATH_OVERRIDE
+            .get_or_init(|| Mutex::new(None))
+            .lock()
+            .expect("smart filters path override lock") = path;
+    }
+
+    /// `filters.json` の smart filter 一覧を読む。壊れたファイルや名前が空の要素は無視する。
+    pub(super) fn load_smart_filters() -> Vec<SmartFilter> {
+        let Some(file) = Self::smart_filters_file_path() else {
+            return Vec::new();
+        };
+        let Ok(text) = fs::read_to_string(file) else {
+            return Vec::new();
+        };
+        serde_json::from_str::<Vec<SmartFilter>>(&text)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|filter| !filter.name.trim().is_empty())
+            .collect()
+    }
+
+    pub(super) fn save_smart_filters(&self) {
+        let Some(file) = Self::smart_filters_file_path() else {
+            return;
+        };
+        if let Some(parent) = file.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let Ok(text) = serde_json::to_string_pretty(&self.shell.ui.smart_filters) else {
+            return;
+        };
+        let _ = write_text_atomic(&file, &format!("{text}\n"));
+    }
+
+    /// 現在の query を smart filter として保存するための名前入力を開く。
+    pub(super) fn open_save_smart_filter(&mut self) {
+        if self.shell.runtime.query_state.query.trim().is_empty() {
+            self.set_notice("Type a query before saving it as a filter");
+            return;
+        }
+        self.shell.ui.smart_filter_name_input = Some(String::new());
+    }
+
+    pub(super) fn cancel_save_smart_filter(&mut self) {
+        self.shell.ui.smart_filter_name_input = None;
+        self.request_focus_query();
+    }
+
+    /// 入力名で現在の query・match mode・種別フィルタを保存する。同名の filter は上書きする。
+    pub(super) fn confirm_save_smart_filter(&mut self) {
+        let Some(input) = self.shell.ui.smart_filter_name_input.take() else {
+            return;
+        };
+        self.request_focus_query();
+        let name = input.trim().to_string();
+        if name.is_empty() {
+            self.set_notice("Filter name is empty");
+            return;
+        }
+        let runtime = &self.shell.runtime;
+        let filter = SmartFilter {
+            name: name.clone(),
+            query: runtime.query_state.query.clone(),
+            mode: if runtime.use_regex {
+                SmartFilterMode::Regex
+            } else {
+                SmartFilterMode::Fuzzy
+            },
+            include_files: runtime.include_files,
+            include_dirs: runtime.include_dirs,
+        };
+        let filters = &mut self.shell.ui.smart_filters;
+        match filters.iter_mut().find(|existing| existing.name == name) {
+            Some(existing) => *existing = filter,
+            None => filters.push(filter),
+        }
+        self.save_smart_filters();
+        self.set_notice(format!("Saved filter: {name}"));
+    }
+
+    pub(super) fn delete_smart_filter(&mut self, index: usize) {
+        if index >= self.shell.ui.smart_filters.len() {
+            return;
+        }
+        let removed = self.shell.ui.smart_filters.remove(index);
+        self.save_smart_filters();
+        self.set_notice(format!("Deleted filter: {}", removed.name));
+    }
+
     fn migrate_or_legacy_ui_state_path(current_path: &Path) -> PathBuf {
         let legacy_paths = legacy_settings_base_dirs()
             .into_iter()
@@ -435,6 +675,71 @@ impl FlistWalkerApp {
         self.set_notice(format!("Set default root: {}", root.display()));
     }
 
+    pub(super) fn open_reset_settings_dialog(&mut self) {
+        self.shell.ui.reset_settings_dialog = Some(ResetSettingsDialogState {
+            clear_saved_roots_and_history: false,
+        });
+    }
+
+    pub(super) fn confirm_reset_settings(&mut self, ctx: &egui::Context) {
+        let Some(dialog) = self.shell.ui.reset_settings_dialog.take() else {
+            return;
+        };
+        // Drop egui's remembered panel size so the preview width default applies immediately.
+        ctx.data_mut(|data| data.remove::<egui::PanelState>(egui::Id::new("preview-panel")));
+        self.reset_settings(dialog.clear_saved_roots_and_history);
+    }
+
+    /// 永続化された UI 設定を既定値へ戻し、即座に保存する。
+    pub(super) fn reset_settings(&mut self, clear_saved_roots_and_history: bool) {
+        self.shell.features.root_browser.default_root = None;
+        self.shell.ui.set_show_preview(true);
+        self.shell
+            .ui
+            .set_preview_panel_width(Self::DEFAULT_PREVIEW_PANEL_WIDTH);
+        let mut filters_changed = !self.shell.ui.ignore_list_enabled;
+        self.shell.ui.ignore_list_enabled = true;
+        self.shell.ui.render_markdown_preview = true;
+        self.shell.ui.source_filter_memory = SourceFilterMemory::default();
+        self.shell.features.update.state.skipped_target_version = None;
+        self.shell
+            .features
+            .update
+            .state
+            .suppress_check_failure_dialog = false;
+        if clear_saved_roots_and_history {
+            self.shell.features.root_browser.saved_roots.clear();
+            self.save_saved_roots();
+            self.shell.runtime.query_state.query_history.clear();
+            self.shell
+                .runtime
+                .query_state
+                .reset_query_history_navigation();
+            for tab in self.shell.tabs.iter_mut() {
+                tab.query_state.query_history.clear();
+                tab.query_state.query_history_cursor = None;
+                tab.query_state.query_history_draft = None;
+            }
+            // 保存される `last_query` は現在の query なので、履歴と一緒に消す。
+            if !self.shell.runtime.query_state.query.is_empty() {
+                self.shell.runtime.query_state.query.clear();
+                self.reset_history_search_state();
+                self.set_query_history_dirty_since(None);
+                filters_changed = true;
+            }
+        }
+        if filters_changed {
+            self.apply_entry_filters(false);
+        }
+        self.mark_ui_state_dirty();
+        self.persist_ui_state_now();
+        self.set_notice(if clear_saved_roots_and_history {
+            "Settings, saved roots, and history reset to defaults"
+        } else {
+            "Settings reset to defaults"
+        });
+    }
+
     pub(super) fn can_set_current_root_as_default(&self) -> bool {
         Self::can_set_current_root_as_default_with(Self::restore_tabs_enabled())
     }
@@ -495,6 +800,7 @@ impl FlistWalkerApp {
                 .map(|p| p.to_string_lossy().to_string()),
             show_preview: Some(self.shell.ui.show_preview),
             ignore_list_enabled: self.shell.ui.ignore_list_enabled,
+            render_markdown_preview: self.shell.ui.render_markdown_preview,
             preview_panel_width: Some(self.shell.ui.preview_panel_width),
             query_history: if history_persist_disabled {
                 Vec::new()
@@ -524,6 +830,9 @@ impl FlistWalkerApp {
                 .update
                 .state
                 .suppress_check_failure_dialog,
+            source_filters: self.shell.ui.source_filter_memory,
+            last_query: Some(self.shell.runtime.query_state.query.clone())
+                .filter(|query| !history_persist_disabled && !query.trim().is_empty()),
         };
         if let Ok(text) = serde_json::to_string_pretty(&state) {
             let _ = write_text_atomic(path, &text);
@@ -21,6 +21,48 @@ fn saved_roots_file_path_in_joins_base_directory() {
     );
 }
 
+#[test]
+fn smart_filter_defaults_missing_fields_to_fuzzy_files_and_folders() {
+    assert_eq!(
+        FlistWalkerApp::smart_filters_file_path_in(Path::new("/tmp/flistwalker-settings")),
+        PathBuf::from("/tmp/flistwalker-settings/filters.json")
+    );
+    let filters = serde_json::from_str::<Vec<SmartFilter>>(
+        r#"[{"name":"Logs","query":".log"},{"name":"Code","query":"rs$","mode":"regex","include_dirs":false}]"#,
+    )
+    .expect("parse filters");
+
+    assert_eq!(filters[0].mode, SmartFilterMode::Fuzzy);
+    assert!(filters[0].include_files && filters[0].include_dirs);
+    assert_eq!(filters[1].mode, SmartFilterMode::Regex);
+    assert!(filters[1].include_files && !filters[1].include_dirs);
+}
+
+#[test]
+fn saved_roots_text_keeps_comments_and_order_while_applying_changes() {
+    let existing = "# work\n/srv/b\n/srv/a\n\n# personal\n/home/z\n/home/removed\n";
+    let roots = [
+        PathBuf::from("/srv/b"),
+        PathBuf::from("/srv/edited"),
+        PathBuf::from("/home/z"),
+        PathBuf::from("/opt/new"),
+    ];
+
+    let text = FlistWalkerApp::saved_roots_text(existing, &roots);
+
+    assert_eq!(
+        text,
+        "# work\n/srv/b\n/srv/edited\n\n# personal\n/home/z\n/opt/new\n"
+    );
+    assert_eq!(
+        text.lines()
+            .filter_map(FlistWalkerApp::saved_root_from_line)
+            .collect::<Vec<_>>(),
+        roots
+    );
+    assert_eq!(FlistWalkerApp::saved_roots_text("", &[]), "");
+}
+
 fn temp_dir(name: &str) -> PathBuf {
     let nonce = SystemTime::now()
         .duration_since(UNIX_EPOCH)
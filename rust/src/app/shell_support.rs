@@ -1,18 +1,20 @@
 use super::{
-    egui, lexical_action_path_precheck, ActionPathPrecheck, Entry, EntryKind, FlistWalkerApp,
-    IndexSource, PathBuf, ResultSortMode,
+    egui, lexical_action_path_precheck, ActionPathPrecheck, Entry, EntryKind, EntrySize,
+    FlistWalkerApp, IndexSource, PathBuf, ResultSortMode, SortMetadataRequest,
 };
 use crate::actions::open_text_file_with_default_or_editor;
 use crate::path_utils::normalize_windows_path_buf;
+use crate::query::QueryOptions;
 use crate::runtime_config::{
     legacy_settings_base_dirs, migrate_file_if_needed, runtime_config_file_path, settings_base_dir,
     RuntimeConfig,
 };
+use crate::search::per_directory_cap;
 use anyhow::{Context, Result};
 use std::fs;
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Component, Path};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::OnceLock;
 use std::sync::{Arc, Mutex};
@@ -301,6 +303,31 @@ impl FlistWalkerApp {
         }
     }
 
+    pub(super) fn active_filelist_path(&self) -> Option<&Path> {
+        match &self.shell.runtime.index.source {
+            IndexSource::FileList(path) => Some(path.as_path()),
+            IndexSource::Walker | IndexSource::Git | IndexSource::None => None,
+        }
+    }
+
+    pub(super) fn open_active_filelist(&mut self) {
+        self.open_active_filelist_with(open_text_file_with_default_or_editor);
+    }
+
+    pub(super) fn open_active_filelist_with(&mut self, opener: impl FnOnce(&Path) -> Result<()>) {
+        let Some(path) = self.active_filelist_path().map(Path::to_path_buf) else {
+            self.set_notice("No FileList is in use");
+            return;
+        };
+        match opener(&path) {
+            Ok(()) => self.set_notice(format!(
+                "FileList opened: {}",
+                normalize_windows_path_buf(path).to_string_lossy()
+            )),
+            Err(err) => self.set_notice(format!("FileList open failed: {err}")),
+        }
+    }
+
     fn open_runtime_config_file_with(opener: impl FnOnce(&Path) -> Result<()>) -> Result<PathBuf> {
         let path = Self::ensure_runtime_config_file()?;
         opener(&path)?;
@@ -316,16 +343,49 @@ impl FlistWalkerApp {
         Ok(path)
     }
 
+    /// 各 path をその path 自身の owning root と照らし、root 外と分かる最初の path を返す。
     pub(super) fn first_action_path_outside_root(&self, paths: &[PathBuf]) -> Option<PathBuf> {
         paths
             .iter()
             .find(|path| {
-                lexical_action_path_precheck(&self.shell.runtime.root, path)
+                lexical_action_path_precheck(self.owning_root_for_path(path), path)
                     == ActionPathPrecheck::Reject
             })
             .cloned()
     }
 
+    /// 全保存 root 検索中は path を含む保存 root を表示・action の基準にする。
+    pub(super) fn owning_root_for_path(&self, path: &Path) -> &Path {
+        let root = &self.shell.runtime.root;
+        if !self.shell.runtime.search_all_saved_roots || path.starts_with(root) {
+            return root;
+        }
+        self.shell
+            .features
+            .root_browser
+            .saved_roots()
+            .iter()
+            .filter(|saved| path.starts_with(saved))
+            .max_by_key(|saved| saved.components().count())
+            .map_or(root.as_path(), PathBuf::as_path)
+    }
+
+    /// 相対表示の基準 path を返す。display base が path の祖先ならそれを、そうでなければ owning root を使う。
+    pub(super) fn display_root_for_path(&self, path: &Path) -> &Path {
+        match self.shell.runtime.display_base.as_deref() {
+            Some(base) if path.starts_with(base) => base,
+            _ => self.owning_root_for_path(path),
+        }
+    }
+
+    /// action worker が照合する、path ごとの owning root を同じ順で返す。
+    pub(super) fn action_roots_for_paths(&self, paths: &[PathBuf]) -> Vec<PathBuf> {
+        paths
+            .iter()
+            .map(|path| self.owning_root_for_path(path).to_path_buf())
+            .collect()
+    }
+
     pub(super) fn root_display_text(&self) -> String {
         normalize_windows_path_buf(self.shell.runtime.root.clone())
             .to_string_lossy()
@@ -358,17 +418,60 @@ impl FlistWalkerApp {
     pub(super) fn prefer_relative_display(&self) -> bool {
         matches!(
             self.shell.runtime.index.source,
-            IndexSource::Walker | IndexSource::FileList(_)
+            IndexSource::Walker | IndexSource::Git | IndexSource::FileList(_)
         )
     }
 
+    /// 検索で root 相対の表示 path と照合するかを返す。`match_full_path` なら常に絶対 path と照合する。
+    pub(super) fn prefer_relative_search(&self) -> bool {
+        !self.shell.runtime.match_full_path && self.prefer_relative_display()
+    }
+
+    /// runtime の検索 option から query の照合条件を組み立てる。
+    pub(super) fn query_options(&self) -> QueryOptions {
+        let runtime = &self.shell.runtime;
+        QueryOptions {
+            use_regex: runtime.use_regex,
+            ignore_case: runtime.ignore_case,
+            rank_by_filename: runtime.rank_by_filename,
+            fold_diacritics: runtime.fold_diacritics,
+            prefix_match: runtime.prefix_match,
+            match_tail_components: runtime.match_tail_components,
+            match_any_term: runtime.match_any_term,
+            extension_filter_keeps_dirs: runtime.extension_filter_keeps_dirs,
+        }
+    }
+
+    /// 空 query の一覧として entries の先頭から `limit` 件を返す。folder ごとの上限は `limit` で切る前に適用する。
+    pub(super) fn empty_query_results(&self, entries: &[Entry]) -> Vec<(PathBuf, f64)> {
+        let mut keep = per_directory_cap(self.shell.runtime.max_results_per_directory);
+        entries
+            .iter()
+            .filter(|entry| keep(entry.path()))
+            .take(self.shell.runtime.limit)
+            .map(|entry| (entry.path.clone(), 0.0))
+            .collect()
+    }
+
+    /// 空でない query の文字数が `min_query_length` に届かないかを返す。空 query は一覧表示なので対象外。
+    pub(super) fn query_below_min_length(&self) -> bool {
+        let len = self.shell.runtime.query_state.query.trim().chars().count();
+        !self.shell.runtime.recent_view && len > 0 && len < self.shell.runtime.min_query_length
+    }
+
     pub(super) fn prefer_relative_display_for(source: &IndexSource) -> bool {
-        matches!(source, IndexSource::Walker | IndexSource::FileList(_))
+        matches!(
+            source,
+            IndexSource::Walker | IndexSource::Git | IndexSource::FileList(_)
+        )
     }
 
     pub(super) fn use_filelist_requires_locked_filters(&self) -> bool {
         self.shell.runtime.use_filelist
-            && !matches!(self.shell.runtime.index.source, IndexSource::Walker)
+            && !matches!(
+                self.shell.runtime.index.source,
+                IndexSource::Walker | IndexSource::Git
+            )
     }
 
     pub(super) fn is_entry_visible_for_flags(
@@ -403,19 +506,175 @@ impl FlistWalkerApp {
                     root: Some(&self.shell.runtime.root),
                     prefer_relative: self.prefer_relative_display(),
                     ignore_case: self.shell.runtime.ignore_case,
+                    fold_diacritics: false,
+                    tail_components: None,
                 },
             )
         }) {
             return false;
         }
+        if self.dotfile_filter_active()
+            && is_hidden_dotfile_path(
+                entry.path(),
+                &self.shell.runtime.root,
+                self.shell.runtime.hide_root_dotfiles,
+                self.shell.runtime.hide_nested_dotfiles,
+            )
+        {
+            return false;
+        }
         let kind = self.find_entry_kind(entry.path()).or(entry.kind);
-        match kind {
+        let visible = match kind {
             Some(kind) => Entry::new(entry.path.clone(), Some(kind)).is_visible_for_flags(
                 self.shell.runtime.include_files,
                 self.shell.runtime.include_dirs,
             ),
             None => self.shell.runtime.include_files && self.shell.runtime.include_dirs,
+        };
+        visible && self.is_entry_within_size_filter(entry.path(), kind)
+    }
+
+    /// Size の min/max どちらかが設定されているかを返す。
+    pub(super) fn size_filter_active(&self) -> bool {
+        self.shell.runtime.size_filter_min_bytes.is_some()
+            || self.shell.runtime.size_filter_max_bytes.is_some()
+    }
+
+    fn is_entry_within_size_filter(&self, path: &Path, kind: Option<EntryKind>) -> bool {
+        if !self.size_filter_active() {
+            return true;
+        }
+        // Folders have no size of their own, so the range only narrows files.
+        if kind.is_some_and(|kind| kind.is_dir == Some(true)) {
+            return true;
+        }
+        // Sizes come from the size worker; an unknown size stays hidden until it arrives.
+        let size = match self.cached_entry_size(path) {
+            Some(EntrySize::File(size)) => size,
+            Some(EntrySize::NotFile) => return true,
+            Some(EntrySize::Unreadable) | None => return false,
+        };
+        self.shell
+            .runtime
+            .size_filter_min_bytes
+            .is_none_or(|min| size >= min)
+            && self
+                .shell
+                .runtime
+                .size_filter_max_bytes
+                .is_none_or(|max| size <= max)
+    }
+
+    /// size worker の結果、なければ sort 用に取得済みの metadata から entry size を引く。
+    fn cached_entry_size(&self, path: &Path) -> Option<EntrySize> {
+        self.shell.cache.entry_size.get(path).or_else(|| {
+            self.shell
+                .cache
+                .sort_metadata
+                .get_map()
+                .get(path)
+                .and_then(|metadata| metadata.size_bytes)
+                .map(EntrySize::File)
+        })
+    }
+
+    /// 表示対象の entry のうち size 未取得のものを worker へまとめて問い合わせる。
+    /// 古い要求は worker 側で最新の要求に置き換わるため、毎回未解決の全 path を送る。
+    pub(super) fn request_unknown_entry_sizes_for_active_entries(&mut self) {
+        if !self.size_filter_active() {
+            return;
+        }
+        let use_live_index =
+            self.shell.indexing.in_progress && !self.shell.runtime.index.entries.is_empty();
+        let source = if use_live_index {
+            self.shell.runtime.index.entries.as_slice()
+        } else {
+            self.shell.runtime.all_entries.as_ref()
+        };
+        let paths = source
+            .iter()
+            .filter(|entry| {
+                self.find_entry_kind(entry.path())
+                    .or(entry.kind)
+                    .and_then(|kind| kind.is_dir)
+                    != Some(true)
+                    && self.cached_entry_size(entry.path()).is_none()
+            })
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            return;
+        }
+        let request_id = self.shell.worker_bus.size.begin_request();
+        if self
+            .shell
+            .worker_bus
+            .size
+            .tx
+            .send(SortMetadataRequest {
+                request_id,
+                paths,
+                mode: ResultSortMode::SizeAsc,
+            })
+            .is_err()
+        {
+            self.shell.worker_bus.size.clear_request();
+            self.set_notice("Size worker is unavailable");
+        }
+        self.refresh_status_line();
+    }
+
+    /// size worker の応答を cache し、最新の要求が終わったら判定待ちの entry を絞り直す。
+    pub(super) fn poll_size_response(&mut self) {
+        let mut finished = false;
+        while let Ok(response) = self.shell.worker_bus.size.rx.try_recv() {
+            for (path, metadata) in response.entries {
+                self.shell.cache.entry_size.set(path, metadata.into());
+            }
+            if Some(response.request_id) == self.shell.worker_bus.size.pending_request_id {
+                self.shell.worker_bus.size.clear_request();
+                finished = true;
+            }
+        }
+        if finished && self.size_filter_active() {
+            self.apply_entry_filters(true);
+        } else if finished {
+            self.refresh_status_line();
+        }
+    }
+
+    /// Hide root dotfiles / Hide nested dotfiles のどちらかが有効かを返す。
+    pub(super) fn dotfile_filter_active(&self) -> bool {
+        self.shell.runtime.hide_root_dotfiles || self.shell.runtime.hide_nested_dotfiles
+    }
+
+    /// dotfile の表示条件を切り替え、再 index せずに現在の entry を絞り直す。
+    pub(super) fn set_dotfile_filters(&mut self, hide_root: bool, hide_nested: bool) {
+        if hide_root == self.shell.runtime.hide_root_dotfiles
+            && hide_nested == self.shell.runtime.hide_nested_dotfiles
+        {
+            return;
+        }
+        self.shell.runtime.hide_root_dotfiles = hide_root;
+        self.shell.runtime.hide_nested_dotfiles = hide_nested;
+        self.apply_entry_filters(false);
+    }
+
+    /// Size 入力欄の文字列を解釈し、変化があれば再 index せずに再フィルタする。
+    pub(super) fn apply_size_filter_inputs(&mut self) {
+        let min = parse_size_filter_bytes(&self.shell.ui.size_filter_min_text);
+        let max = parse_size_filter_bytes(&self.shell.ui.size_filter_max_text);
+        let (Ok(min), Ok(max)) = (min, max) else {
+            return;
+        };
+        if min == self.shell.runtime.size_filter_min_bytes
+            && max == self.shell.runtime.size_filter_max_bytes
+        {
+            return;
         }
+        self.shell.runtime.size_filter_min_bytes = min;
+        self.shell.runtime.size_filter_max_bytes = max;
+        self.apply_entry_filters(false);
     }
 
     // Regression Guard (v0.16.0):
@@ -447,6 +706,47 @@ impl FlistWalkerApp {
     }
 }
 
+/// root 直下の `.` 始まり entry (とその配下) と、より深い階層の `.` 始まり component を判定する。
+/// root 外の path は root 直下を持たないため、nested 側だけで判定する。
+fn is_hidden_dotfile_path(path: &Path, root: &Path, hide_root: bool, hide_nested: bool) -> bool {
+    let (relative, under_root) = match path.strip_prefix(root) {
+        Ok(relative) => (relative, true),
+        Err(_) => (path, false),
+    };
+    let mut dot_flags = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().starts_with('.')),
+            _ => None,
+        });
+    let root_level = under_root && dot_flags.next().unwrap_or(false);
+    (hide_root && root_level) || (hide_nested && dot_flags.any(|is_dot| is_dot))
+}
+
+/// `100 MB` / `1.5g` / `512` のような size 表記を byte 数へ変換する。空文字は `None`。
+pub(super) fn parse_size_filter_bytes(text: &str) -> Result<Option<u64>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let split = text
+        .find(|ch: char| !ch.is_ascii_digit() && ch != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value = number
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid size: {text}"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => return Err(format!("Invalid size unit: {text}")),
+    };
+    Ok(Some((value * multiplier as f64).round() as u64))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -542,4 +842,15 @@ mod tests {
 
         let _ = fs::remove_dir_all(&settings_root);
     }
+
+    #[test]
+    fn parse_size_filter_bytes_accepts_units_and_rejects_garbage() {
+        assert_eq!(parse_size_filter_bytes(""), Ok(None));
+        assert_eq!(parse_size_filter_bytes("512"), Ok(Some(512)));
+        assert_eq!(parse_size_filter_bytes("1 KB"), Ok(Some(1024)));
+        assert_eq!(parse_size_filter_bytes("1.5m"), Ok(Some(1_572_864)));
+        assert_eq!(parse_size_filter_bytes(" 2 GiB "), Ok(Some(2 << 30)));
+        assert!(parse_size_filter_bytes("MB").is_err());
+        assert!(parse_size_filter_bytes("10 parsecs").is_err());
+    }
 }
@@ -1,6 +1,6 @@
 use crate::app::cache::{
-    EntryKindCacheState, HighlightCacheState, IgnoreMatcherCacheState, PreviewCacheState,
-    SortMetadataCacheState,
+    EntryKindCacheState, EntrySizeCacheState, HighlightCacheState, IgnoreMatcherCacheState,
+    MarkdownPreviewCacheState, PreviewCacheState, SortMetadataCacheState,
 };
 use crate::app::index_coordinator::IndexCoordinator;
 use crate::app::query_state::QueryState;
@@ -8,16 +8,17 @@ use crate::app::search_coordinator::SearchCoordinator;
 use crate::app::tab_state::AppTabState;
 use crate::app::ui_state::RuntimeUiState;
 use crate::app::worker_bus::WorkerBus;
+use crate::app::worker_protocol::{ActionOperation, FileOperation};
 use crate::app::worker_runtime::WorkerRuntime;
 use crate::entry::Entry;
 use crate::indexer::{IndexBuildResult, IndexSource};
 use crate::updater::UpdateCandidate;
 use eframe::egui;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 #[derive(Default)]
 pub(super) struct BackgroundIndexState {
@@ -91,6 +92,46 @@ impl ResultSortMode {
     }
 }
 
+/// Shift+Enter で folder を起動したときの動作。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum FolderActivation {
+    #[default]
+    Open,
+    SetRoot,
+    NewWindow,
+}
+
+impl FolderActivation {
+    /// runtime config の `shift_enter_folder_action` を解釈する。未知の値は `Open` とする。
+    pub(super) fn from_config_value(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "set_root" => Self::SetRoot,
+            "new_window" => Self::NewWindow,
+            _ => Self::Open,
+        }
+    }
+}
+
+/// score / sort 適用後の結果を folder と file で分けて並べる方向。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum ResultKindGrouping {
+    #[default]
+    None,
+    DirsFirst,
+    FilesFirst,
+}
+
+impl ResultKindGrouping {
+    /// runtime config の `result_kind_grouping` を解釈する。未知の値は `None` とする。
+    pub(super) fn from_config_value(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "dirs_first" => Self::DirsFirst,
+            "files_first" => Self::FilesFirst,
+            _ => Self::None,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) struct TabAccentPalette {
     pub(super) background: egui::Color32,
@@ -141,6 +182,34 @@ pub(super) struct PendingFileListAncestorConfirmation {
     pub(super) entries: Vec<PathBuf>,
 }
 
+/// `.` で繰り返せる結果アクションの種類。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ActionKind {
+    Execute,
+    OpenFolder,
+    CopyPath,
+    CopyPathForShell,
+    CopySiteUrl,
+    CopyName,
+}
+
+pub(super) struct PendingActionConfirmation {
+    pub(super) tab_id: u64,
+    pub(super) paths: Vec<PathBuf>,
+    pub(super) open_parent_for_files: bool,
+    pub(super) operation: ActionOperation,
+}
+
+pub(super) struct QuickLookState {
+    pub(super) request_id: u64,
+    pub(super) path: PathBuf,
+    pub(super) text: Option<String>,
+}
+
+pub(super) struct ResetSettingsDialogState {
+    pub(super) clear_saved_roots_and_history: bool,
+}
+
 pub(super) struct PendingFileListAfterIndex {
     pub(super) tab_id: u64,
     pub(super) root: PathBuf,
@@ -276,10 +345,12 @@ pub(super) struct UpdateManager {
 
 pub(super) struct CacheStateBundle {
     pub(super) preview: PreviewCacheState,
+    pub(super) markdown_preview: MarkdownPreviewCacheState,
     pub(super) highlight: HighlightCacheState,
     pub(super) ignore_matcher: IgnoreMatcherCacheState,
     pub(super) entry_kind: EntryKindCacheState,
     pub(super) sort_metadata: SortMetadataCacheState,
+    pub(super) entry_size: EntrySizeCacheState,
 }
 
 pub struct AppRuntimeState {
@@ -292,6 +363,10 @@ pub struct AppRuntimeState {
     pub(super) ignore_list_terms: Arc<Vec<String>>,
     pub(super) include_files: bool,
     pub(super) include_dirs: bool,
+    pub(super) size_filter_min_bytes: Option<u64>,
+    pub(super) size_filter_max_bytes: Option<u64>,
+    pub(super) hide_root_dotfiles: bool,
+    pub(super) hide_nested_dotfiles: bool,
     pub(super) index: IndexBuildResult,
     pub(super) all_entries: Arc<Vec<Entry>>,
     pub(super) entries: Arc<Vec<Entry>>,
@@ -304,8 +379,53 @@ pub struct AppRuntimeState {
     pub(super) current_row: Option<usize>,
     pub(super) emacs_keybindings_enabled: bool,
     pub(super) tab_pin_moves_to_next_row: bool,
+    pub(super) action_confirm_threshold: usize,
+    pub(super) folder_activation: FolderActivation,
+    pub(super) result_kind_grouping: ResultKindGrouping,
+    pub(super) max_results_per_directory: usize,
+    pub(super) dir_trailing_slash: bool,
+    pub(super) remember_filters_per_source: bool,
+    pub(super) walker_throttle: Option<Duration>,
+    pub(super) empty_query_recent_first: bool,
+    pub(super) sticky_results_scroll: bool,
+    pub(super) preview_max_file_bytes: Option<u64>,
+    pub(super) preview_dir_depth: usize,
+    pub(super) preview_skip_binary: bool,
+    pub(super) preview_metadata_details: bool,
+    pub(super) filelist_header_comment: bool,
+    pub(super) query_path_prefix_filter: bool,
+    pub(super) result_type_icons: bool,
+    pub(super) rank_by_filename: bool,
+    pub(super) fold_diacritics: bool,
+    pub(super) prefix_match: bool,
+    pub(super) match_tail_components: Option<usize>,
+    pub(super) match_any_term: bool,
+    pub(super) eager_partial_search: bool,
+    pub(super) match_full_path: bool,
+    pub(super) editor_command_template: String,
+    pub(super) min_query_length: usize,
+    pub(super) wrap_navigation: bool,
+    pub(super) extension_filter_keeps_dirs: bool,
+    pub(super) walker_canonicalize_entries: bool,
+    pub(super) search_all_saved_roots: bool,
+    pub(super) display_base: Option<PathBuf>,
+    pub(super) hide_pinned_results: bool,
+    pub(super) recent_view: bool,
+    pub(super) last_action: Option<ActionKind>,
+    /// Ctrl+Z で取り消せる直前の file 操作。
+    pub(super) last_operation: Option<FileOperation>,
+    /// worker が処理中の取り消し要求の request id と、戻そうとした (移動元, 移動先) の組。
+    pub(super) pending_undo: Option<(u64, Vec<(PathBuf, PathBuf)>)>,
+    pub(super) basket: Vec<PathBuf>,
+    pub(super) basket_enter_mode: bool,
+    pub(super) query_error: Option<String>,
+    pub(super) git_tracked_only: bool,
     pub(super) preview: String,
+    /// `preview` が内容を表す path。読み込み中や取得不能の表示では `None`。
+    pub(super) preview_path: Option<PathBuf>,
     pub(super) notice: String,
+    /// Log panel 用に notice と error を時刻付きで保持する。上限を超えた古いものから捨てる。
+    pub(super) log_messages: VecDeque<(SystemTime, String)>,
     pub(super) status_line: String,
 }
 
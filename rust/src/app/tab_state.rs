@@ -59,6 +59,7 @@ pub(super) struct TabResultState {
     pub(super) pinned_paths: HashSet<PathBuf>,
     pub(super) current_row: Option<usize>,
     pub(super) preview: String,
+    pub(super) preview_path: Option<PathBuf>,
     pub(super) results_compacted: bool,
 }
 
@@ -334,6 +335,7 @@ impl TabResultState {
             pinned_paths: shell.shell.runtime.pinned_paths.clone(),
             current_row: shell.shell.runtime.current_row,
             preview: shell.shell.runtime.preview.clone(),
+            preview_path: shell.shell.runtime.preview_path.clone(),
             results_compacted: false,
         }
     }
@@ -350,6 +352,7 @@ impl TabResultState {
         shell.shell.runtime.pinned_paths = self.pinned_paths.clone();
         shell.shell.runtime.current_row = self.current_row;
         shell.shell.runtime.preview = self.preview.clone();
+        shell.shell.runtime.preview_path = self.preview_path.clone();
     }
 
     pub(super) fn swap_shell(&mut self, shell: &mut FlistWalkerApp) {
@@ -384,6 +387,10 @@ impl TabResultState {
         );
         mem::swap(&mut self.current_row, &mut shell.shell.runtime.current_row);
         mem::swap(&mut self.preview, &mut shell.shell.runtime.preview);
+        mem::swap(
+            &mut self.preview_path,
+            &mut shell.shell.runtime.preview_path,
+        );
     }
 }
 
@@ -493,6 +500,7 @@ impl AppTabState {
                 pinned_paths: HashSet::new(),
                 current_row: Some(0),
                 preview: String::new(),
+                preview_path: None,
                 results_compacted: false,
             },
             entry_kind_cache: EntryKindCacheState::default(),
@@ -576,6 +584,7 @@ impl AppTabState {
                 pinned_paths: HashSet::new(),
                 current_row: None,
                 preview: String::new(),
+                preview_path: None,
                 results_compacted: false,
             },
             entry_kind_cache: EntryKindCacheState::default(),
@@ -1,6 +1,7 @@
 use super::{
-    result_reducer, walker_truncated_notice, AppTabState, ClosedTabState, Entry, FlistWalkerApp,
-    IndexResponse, IndexSource, ResultSortMode, SavedTabState, SearchResponse, TabAccentColor,
+    result_reducer, walker_directory_cap_notice, walker_truncated_notice, AppTabState,
+    ClosedTabState, Entry, FlistWalkerApp, IndexResponse, IndexSource, ResultSortMode,
+    SavedTabState, SearchResponse, TabAccentColor,
 };
 use crate::path_utils::normalize_windows_path_buf;
 use crate::path_utils::path_key;
@@ -20,6 +21,31 @@ impl FlistWalkerApp {
     pub(super) fn apply_root_change(&mut self, new_root: PathBuf) {
         self.apply_root_change_direct(new_root);
     }
+
+    /// 現在 root の親 directory へ root を切り替える。親が無い (drive/filesystem root) なら何もしない。
+    pub(super) fn apply_root_change_to_parent(&mut self) {
+        let Some(parent) = self.shell.runtime.root.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        self.apply_root_change(parent);
+    }
+
+    /// breadcrumb 表示用に root を先頭 (filesystem root) から順に (表示名, 再構成 path) へ分解する。
+    pub(super) fn root_breadcrumbs(root: &Path) -> Vec<(String, PathBuf)> {
+        let mut crumbs = root
+            .ancestors()
+            .map(|ancestor| {
+                let label = ancestor.file_name().map_or_else(
+                    || ancestor.to_string_lossy().to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                (label, ancestor.to_path_buf())
+            })
+            .filter(|(label, _)| !label.is_empty())
+            .collect::<Vec<_>>();
+        crumbs.reverse();
+        crumbs
+    }
     fn settle_background_tab_index_failure(tab: &mut AppTabState, notice: Option<String>) {
         tab.index_state.clear_index_request_state();
         if let Some(notice) = notice {
@@ -70,6 +96,7 @@ impl FlistWalkerApp {
     fn deactivate_active_tab_for_transition(&mut self) -> usize {
         self.clear_tab_drag_state();
         self.shrink_checkpoint_buffers();
+        self.shell.runtime.recent_view = false;
         let previous_active = self.shell.tabs.active_tab_index();
         self.store_active_tab_payload();
         if let Some(previous_tab) = self.shell.tabs.get_mut(previous_active) {
@@ -125,7 +152,9 @@ impl FlistWalkerApp {
         };
 
         match msg {
-            IndexResponse::Started { request_id, source } => {
+            IndexResponse::Started {
+                request_id, source, ..
+            } => {
                 if tab.index_state.pending_index_request_id != Some(request_id) {
                     return effect;
                 }
@@ -296,6 +325,15 @@ impl FlistWalkerApp {
                     tab.notice = walker_truncated_notice(limit);
                 }
             }
+            IndexResponse::DirectoriesCapped {
+                request_id,
+                limit,
+                directories,
+            } => {
+                if tab.index_state.pending_index_request_id == Some(request_id) {
+                    tab.notice = walker_directory_cap_notice(limit, directories);
+                }
+            }
         }
 
         effect
@@ -452,15 +490,7 @@ impl FlistWalkerApp {
 
         if self.shell.runtime.base_results.is_empty() {
             if self.shell.runtime.query_state.query.trim().is_empty() {
-                let results = self
-                    .shell
-                    .runtime
-                    .entries
-                    .iter()
-                    .take(self.shell.runtime.limit)
-                    .cloned()
-                    .map(|entry| (entry.path, 0.0))
-                    .collect();
+                let results = self.empty_query_results(&self.shell.runtime.entries);
                 self.replace_results_snapshot(results, true);
             } else {
                 self.refresh_status_line();
@@ -12,6 +12,324 @@ use crate::app::worker_tasks::{
 };
 use std::sync::atomic::AtomicUsize;
 
+#[test]
+fn move_paths_to_directory_moves_in_worker_and_remaps_tabs_pins_and_basket() {
+    let root = test_root("move-to-directory");
+    let dest = root.join("dest");
+    let folder = root.join("folder");
+    fs::create_dir_all(&dest).expect("create dest");
+    fs::create_dir_all(&folder).expect("create folder");
+    let file = root.join("a.txt");
+    let nested = folder.join("b.txt");
+    let taken = root.join("taken.txt");
+    fs::write(&file, "a").expect("write a");
+    fs::write(&nested, "b").expect("write b");
+    fs::write(&taken, "c").expect("write taken");
+    fs::write(dest.join("taken.txt"), "existing").expect("write collision");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(nested.clone(), 0.0)];
+    app.shell.runtime.pinned_paths = [file.clone()].into_iter().collect();
+    app.create_new_tab();
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    let (action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.worker_bus.action.rx = action_rx_res;
+    let entries = vec![
+        file_entry(file.clone()),
+        dir_entry(folder.clone()),
+        file_entry(nested.clone()),
+        file_entry(taken.clone()),
+    ];
+    app.shell.runtime.all_entries = Arc::new(entries.clone());
+    app.shell.runtime.entries = Arc::new(entries);
+    app.shell.runtime.results = vec![(file.clone(), 0.0), (nested.clone(), 0.0)];
+    app.shell.runtime.pinned_paths = [file.clone(), folder.clone(), taken.clone()]
+        .into_iter()
+        .collect();
+    app.shell.runtime.basket = vec![nested.clone()];
+    app.set_entry_kind(&nested, EntryKind::file());
+
+    let paths = app.selected_paths();
+    app.move_paths_to_directory(paths, &dest);
+
+    assert!(file.exists(), "move must run in the action worker");
+    assert_eq!(app.shell.runtime.notice, "Moving 3 item(s)...");
+    let req = action_rx_req.try_recv().expect("move request");
+    assert_eq!(
+        req.operation,
+        ActionOperation::Move {
+            targets: vec![
+                dest.join("a.txt"),
+                dest.join("folder"),
+                dest.join("taken.txt")
+            ],
+        }
+    );
+    action_tx_res
+        .send(process_action_request_with(req, |_| {
+            unreachable!("move must not launch paths")
+        }))
+        .expect("send move response");
+    app.poll_action_response();
+
+    assert!(dest.join("a.txt").exists());
+    assert!(dest.join("folder").join("b.txt").exists());
+    assert!(taken.exists());
+    assert!(app.shell.runtime.notice.starts_with("Moved 2 of 3 item(s)"));
+    assert!(app.shell.runtime.notice.contains("already exists"));
+    let all_paths = app
+        .shell
+        .runtime
+        .all_entries
+        .iter()
+        .map(|entry| entry.path.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        all_paths,
+        vec![
+            dest.join("a.txt"),
+            dest.join("folder"),
+            dest.join("folder").join("b.txt"),
+            taken.clone(),
+        ]
+    );
+    assert_eq!(
+        app.shell.runtime.results[1].0,
+        dest.join("folder").join("b.txt")
+    );
+    assert!(app.shell.runtime.pinned_paths.contains(&dest.join("a.txt")));
+    assert!(app.shell.runtime.pinned_paths.contains(&taken));
+    assert_eq!(
+        app.shell.runtime.basket,
+        vec![dest.join("folder").join("b.txt")]
+    );
+    assert!(app
+        .find_entry_kind(&dest.join("folder").join("b.txt"))
+        .is_some());
+    let background = app.shell.tabs.get(0).expect("background tab");
+    assert!(background
+        .result_state
+        .pinned_paths
+        .contains(&dest.join("a.txt")));
+    assert_eq!(
+        background.result_state.results[0].0,
+        dest.join("folder").join("b.txt")
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn ctrl_z_undoes_last_move_in_worker_and_restores_paths() {
+    let root = test_root("undo-move");
+    let dest = root.join("dest");
+    fs::create_dir_all(&dest).expect("create dest");
+    let file = root.join("a.txt");
+    fs::write(&file, "a").expect("write a");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    let (action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.worker_bus.action.rx = action_rx_res;
+    app.shell.runtime.results = vec![(file.clone(), 0.0)];
+    app.shell.runtime.pinned_paths.insert(file.clone());
+    let run_worker = |app: &mut FlistWalkerApp| {
+        let req = action_rx_req.try_recv().expect("file operation request");
+        action_tx_res
+            .send(process_action_request_with(req, |_| {
+                unreachable!("file operations must not launch paths")
+            }))
+            .expect("send file operation response");
+        app.poll_action_response();
+    };
+
+    app.undo_last_operation();
+    assert_eq!(app.shell.runtime.notice, "Nothing to undo");
+
+    app.move_paths_to_directory(vec![file.clone()], &dest);
+    run_worker(&mut app);
+    assert!(dest.join("a.txt").exists());
+    assert_eq!(
+        app.shell.runtime.last_operation,
+        Some(FileOperation::Move(vec![(
+            file.clone(),
+            dest.join("a.txt")
+        )]))
+    );
+
+    run_shortcuts_frame_with_modifiers(
+        &mut app,
+        true,
+        gui_shortcut_modifiers(false),
+        vec![egui::Event::Key {
+            key: egui::Key::Z,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: gui_shortcut_modifiers(false),
+        }],
+    );
+    assert_eq!(app.shell.runtime.notice, "Undoing move of 1 item(s)...");
+    run_worker(&mut app);
+
+    assert!(file.exists());
+    assert!(!dest.join("a.txt").exists());
+    assert_eq!(app.shell.runtime.notice, "Undid move of 1 item(s)");
+    assert_eq!(app.shell.runtime.results[0].0, file);
+    assert!(app.shell.runtime.pinned_paths.contains(&file));
+    assert!(app.shell.runtime.last_operation.is_none());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn undo_keeps_moves_that_were_not_undone() {
+    let root = test_root("undo-move-kept");
+    let dest = root.join("dest");
+    fs::create_dir_all(&dest).expect("create dest");
+    let file = root.join("a.txt");
+    fs::write(&file, "a").expect("write a");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(1);
+    let (action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req.clone();
+    app.shell.worker_bus.action.rx = action_rx_res;
+    let run_worker = |app: &mut FlistWalkerApp| {
+        let req = action_rx_req.try_recv().expect("file operation request");
+        action_tx_res
+            .send(process_action_request_with(req, |_| {
+                unreachable!("file operations must not launch paths")
+            }))
+            .expect("send file operation response");
+        app.poll_action_response();
+    };
+    app.move_paths_to_directory(vec![file.clone()], &dest);
+    run_worker(&mut app);
+    let moved = Some(FileOperation::Move(vec![(
+        file.clone(),
+        dest.join("a.txt"),
+    )]));
+    assert_eq!(app.shell.runtime.last_operation, moved);
+
+    // A busy worker rejects the request, so the move stays undoable.
+    action_tx_req
+        .try_send(ActionRequest {
+            request_id: 999,
+            roots: Vec::new(),
+            paths: Vec::new(),
+            open_parent_for_files: false,
+            operation: ActionOperation::Launch,
+        })
+        .expect("fill action queue");
+    app.undo_last_operation();
+    assert_eq!(app.shell.runtime.notice, "Action worker is busy");
+    assert_eq!(app.shell.runtime.last_operation, moved);
+    let _ = action_rx_req.try_recv().expect("drain filler request");
+
+    // The undo fails because the destination is occupied; the move comes back for a retry.
+    fs::write(&file, "blocker").expect("write blocker");
+    app.undo_last_operation();
+    assert!(app.shell.runtime.last_operation.is_none());
+    run_worker(&mut app);
+    assert!(dest.join("a.txt").exists());
+    assert_eq!(app.shell.runtime.last_operation, moved);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn move_paths_to_directory_asks_for_confirmation_above_threshold() {
+    let root = test_root("move-confirm");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    let (_action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.worker_bus.action.rx = action_rx_res;
+    app.shell.runtime.action_confirm_threshold = 1;
+    app.shell.runtime.pinned_paths.insert(root.join("a.txt"));
+    app.shell.runtime.pinned_paths.insert(root.join("b.txt"));
+
+    let paths = app.selected_paths();
+    app.move_paths_to_directory(paths, &root.join("dest"));
+
+    assert!(action_rx_req.try_recv().is_err());
+    assert_eq!(
+        app.shell.runtime.notice,
+        "Confirm moving items or cancel first"
+    );
+    app.confirm_pending_action();
+    let req = action_rx_req
+        .try_recv()
+        .expect("confirmed move request should be enqueued");
+    assert!(matches!(req.operation, ActionOperation::Move { .. }));
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn open_current_in_editor_requires_template_and_opens_through_action_worker() {
+    let root = test_root("open-in-editor");
+    fs::create_dir_all(&root).expect("create dir");
+    let file = root.join("main.rs");
+    fs::write(&file, "fn main() {}").expect("write file");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(file.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    let (action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.worker_bus.action.rx = action_rx_res;
+
+    app.open_current_in_editor();
+    assert!(app
+        .shell
+        .runtime
+        .notice
+        .contains("Set editor_command_template"));
+    assert!(action_rx_req.try_recv().is_err());
+
+    app.shell.runtime.editor_command_template = "code -g {path}:{line}".to_string();
+    app.open_current_in_editor();
+    assert!(app.shell.runtime.notice.starts_with("Opening in editor:"));
+    let req = action_rx_req
+        .try_recv()
+        .expect("editor request should be enqueued");
+    assert_eq!(req.paths, vec![file]);
+    assert_eq!(
+        req.operation,
+        ActionOperation::OpenInEditor {
+            template: "code -g {path}:{line}".to_string(),
+            line: None,
+        }
+    );
+    action_tx_res
+        .send(process_action_request_with(req, |_| {
+            unreachable!("editor request must not use the default launcher")
+        }))
+        .expect("send editor response");
+    app.poll_action_response();
+
+    assert!(app.shell.runtime.notice.starts_with("Opened in editor:"));
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn open_current_in_editor_blocks_paths_outside_root() {
+    let root = test_root("open-in-editor-outside-root");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(root.join("..").join("outside.rs"), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.shell.runtime.editor_command_template =
+        "flistwalker-missing-editor-for-test {path}".to_string();
+
+    app.open_current_in_editor();
+
+    assert!(app
+        .shell
+        .runtime
+        .notice
+        .starts_with("Action blocked: path is outside current root:"));
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn execute_selected_enqueues_action_request_without_sync_io() {
     let root = test_root("async-action-enqueue");
@@ -31,7 +349,7 @@ fn execute_selected_enqueues_action_request_without_sync_io() {
         .try_recv()
         .expect("action request should be enqueued");
     assert_eq!(req.paths, vec![missing]);
-    assert_eq!(req.root, root);
+    assert_eq!(req.roots, vec![root.clone()]);
     assert!(!req.open_parent_for_files);
     assert!(app.shell.worker_bus.action.pending_request_id.is_some());
     assert!(app.shell.worker_bus.action.in_progress);
@@ -40,7 +358,7 @@ fn execute_selected_enqueues_action_request_without_sync_io() {
 }
 
 #[test]
-fn execute_selected_for_activation_uses_open_folder_mode_when_requested() {
+fn execute_selected_open_folder_requests_containing_folder_for_files() {
     let root = test_root("activation-open-folder");
     let folder = root.join("src");
     fs::create_dir_all(&folder).expect("create dir");
@@ -54,14 +372,140 @@ fn execute_selected_for_activation_uses_open_folder_mode_when_requested() {
     app.shell.runtime.results = vec![(selected.clone(), 0.0)];
     app.shell.runtime.current_row = Some(0);
 
-    app.execute_selected_for_activation(true);
+    app.execute_selected_open_folder();
+
+    let req = action_rx_req
+        .try_recv()
+        .expect("action request should be enqueued");
+    assert_eq!(req.paths, vec![selected]);
+    assert_eq!(req.roots, vec![root.clone()]);
+    assert!(req.open_parent_for_files);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn open_folder_activation_sets_root_for_folder_when_configured() {
+    let root = test_root("activation-folder-set-root");
+    let folder = root.join("src");
+    fs::create_dir_all(&folder).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.runtime.folder_activation = FolderActivation::SetRoot;
+    app.shell.runtime.results = vec![(folder.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.set_entry_kind(&folder, EntryKind::dir());
+
+    app.execute_selected_open_folder();
+
+    assert_eq!(app.shell.runtime.root, folder);
+    assert!(action_rx_req.try_recv().is_err());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn open_folder_activation_keeps_containing_folder_action_for_files() {
+    let root = test_root("activation-folder-set-root-file");
+    fs::create_dir_all(&root).expect("create dir");
+    let selected = root.join("picked.txt");
+    fs::write(&selected, "x").expect("write file");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.runtime.folder_activation = FolderActivation::SetRoot;
+    app.shell.runtime.results = vec![(selected.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.set_entry_kind(&selected, EntryKind::file());
+
+    app.execute_selected_open_folder();
 
     let req = action_rx_req
         .try_recv()
         .expect("action request should be enqueued");
     assert_eq!(req.paths, vec![selected]);
-    assert_eq!(req.root, root);
     assert!(req.open_parent_for_files);
+    assert_eq!(app.shell.runtime.root, root);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn folder_activation_parses_config_values() {
+    assert_eq!(
+        FolderActivation::from_config_value("open"),
+        FolderActivation::Open
+    );
+    assert_eq!(
+        FolderActivation::from_config_value(" Set_Root "),
+        FolderActivation::SetRoot
+    );
+    assert_eq!(
+        FolderActivation::from_config_value("new_window"),
+        FolderActivation::NewWindow
+    );
+    assert_eq!(
+        FolderActivation::from_config_value("unknown"),
+        FolderActivation::Open
+    );
+}
+
+#[test]
+fn execute_selected_asks_confirmation_when_pinned_exceeds_threshold() {
+    let root = test_root("action-confirm-threshold");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    let (_action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.worker_bus.action.rx = action_rx_res;
+    app.shell.runtime.action_confirm_threshold = 2;
+    for name in ["c.txt", "a.txt", "b.txt"] {
+        app.shell.runtime.pinned_paths.insert(root.join(name));
+    }
+
+    app.execute_selected();
+
+    assert!(action_rx_req.try_recv().is_err());
+    assert_eq!(
+        app.shell
+            .ui
+            .pending_action_confirmation
+            .as_ref()
+            .map(|pending| pending.paths.len()),
+        Some(3)
+    );
+
+    app.confirm_pending_action();
+
+    let req = action_rx_req
+        .try_recv()
+        .expect("confirmed action request should be enqueued");
+    assert_eq!(
+        req.paths,
+        vec![root.join("a.txt"), root.join("b.txt"), root.join("c.txt")]
+    );
+    assert!(app.shell.ui.pending_action_confirmation.is_none());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn cancel_pending_action_discards_confirmation_without_dispatch() {
+    let root = test_root("action-confirm-cancel");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    let (_action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.worker_bus.action.rx = action_rx_res;
+    app.shell.runtime.action_confirm_threshold = 1;
+    app.shell.runtime.pinned_paths.insert(root.join("a.txt"));
+    app.shell.runtime.pinned_paths.insert(root.join("b.txt"));
+
+    app.execute_selected();
+    app.cancel_pending_action();
+
+    assert!(action_rx_req.try_recv().is_err());
+    assert!(app.shell.ui.pending_action_confirmation.is_none());
+    assert_eq!(app.shell.runtime.notice, "Canceled opening pinned items");
     let _ = fs::remove_dir_all(&root);
 }
 
@@ -184,9 +628,10 @@ fn tc_150_action_worker_uses_two_workers_and_bounds_total_to_ten() {
 
     let request = |request_id| ActionRequest {
         request_id,
-        root: root.clone(),
+        roots: vec![root.clone()],
         paths: vec![selected.clone()],
         open_parent_for_files: false,
+        operation: ActionOperation::Launch,
     };
     tx.send(request(1)).expect("send first action");
     tx.send(request(2)).expect("send second action");
@@ -255,9 +700,10 @@ fn tc_153_action_shutdown_drains_accepted_queue_with_terminal_cancellation() {
     for request_id in 1..=4 {
         tx.send(ActionRequest {
             request_id,
-            root: PathBuf::from("shutdown-root"),
+            roots: vec![PathBuf::from("shutdown-root")],
             paths: vec![PathBuf::from("shutdown-root/selected.txt")],
             open_parent_for_files: false,
+            operation: ActionOperation::Launch,
         })
         .expect("accept action before channel close");
     }
@@ -287,9 +733,10 @@ fn tc_153_action_terminal_outcome_distinguishes_success_and_executor_failure() {
     fs::write(&selected, "selected").expect("write selected");
     let request = || ActionRequest {
         request_id: 41,
-        root: root.clone(),
+        roots: vec![root.clone()],
         paths: vec![selected.clone()],
         open_parent_for_files: false,
+        operation: ActionOperation::Launch,
     };
 
     let (_response, completed) = process_action_request_with_outcome(request(), |_| Ok(()));
@@ -313,9 +760,10 @@ fn tc_150_action_full_preserves_prior_accepted_request_state() {
     let (tx, rx) = bounded_request_channel::<ActionRequest>(1);
     tx.send(ActionRequest {
         request_id: 1,
-        root: root.clone(),
+        roots: vec![root.clone()],
         paths: vec![selected.clone()],
         open_parent_for_files: false,
+        operation: ActionOperation::Launch,
     })
     .expect("fill action queue");
     app.shell.worker_bus.action.tx = tx;
@@ -400,12 +848,16 @@ fn authorized_action_targets_deduplicate_same_parent_directory() {
     fs::write(&file_a2, "pub fn f() {}").expect("write file a2");
     fs::write(&file_b, "pub fn g() {}").expect("write file b");
 
-    let targets = authorize_action_targets(&root, &[file_a1, file_a2, file_b, dir_a.clone()], true)
-        .expect("authorize targets")
-        .targets
-        .into_iter()
-        .map(|target| target.display_path)
-        .collect::<Vec<_>>();
+    let targets = authorize_action_targets(
+        &vec![root.clone(); 4],
+        &[file_a1, file_a2, file_b, dir_a.clone()],
+        true,
+    )
+    .expect("authorize targets")
+    .targets
+    .into_iter()
+    .map(|target| target.display_path)
+    .collect::<Vec<_>>();
 
     assert_eq!(targets, vec![dir_a, dir_b]);
     let _ = fs::remove_dir_all(&root);
@@ -446,9 +898,10 @@ fn tc_050_worker_rejects_mixed_selection_before_executor_call() {
     let response = process_action_request_with(
         ActionRequest {
             request_id: 100,
-            root: root.clone(),
+            roots: vec![root.clone(); 2],
             paths: vec![inside, outside],
             open_parent_for_files: false,
+            operation: ActionOperation::Launch,
         },
         |path| {
             calls.push(path.to_path_buf());
@@ -476,9 +929,10 @@ fn tc_050_worker_dispatches_only_resolved_path_and_preserves_display_notice() {
     let response = process_action_request_with(
         ActionRequest {
             request_id: 101,
-            root: root.clone(),
+            roots: vec![root.clone()],
             paths: vec![selected.clone()],
             open_parent_for_files: false,
+            operation: ActionOperation::Launch,
         },
         |path| {
             calls.push(path.to_path_buf());
@@ -504,9 +958,10 @@ fn tc_050_worker_fails_closed_when_target_cannot_be_resolved() {
     let response = process_action_request_with(
         ActionRequest {
             request_id: 102,
-            root: root.clone(),
+            roots: vec![root.clone()],
             paths: vec![missing],
             open_parent_for_files: false,
+            operation: ActionOperation::Launch,
         },
         |_| {
             call_count += 1;
@@ -528,9 +983,10 @@ fn tc_050_worker_fails_closed_when_root_cannot_be_resolved() {
     let response = process_action_request_with(
         ActionRequest {
             request_id: 106,
-            root,
+            roots: vec![root],
             paths: vec![selected.clone()],
             open_parent_for_files: false,
+            operation: ActionOperation::Launch,
         },
         |_| {
             call_count += 1;
@@ -557,9 +1013,10 @@ fn tc_050_executor_failure_notice_uses_display_path_without_execution_error_deta
     let response = process_action_request_with(
         ActionRequest {
             request_id: 107,
-            root: root.clone(),
+            roots: vec![root.clone()],
             paths: vec![selected.clone()],
             open_parent_for_files: false,
+            operation: ActionOperation::Launch,
         },
         |_| anyhow::bail!("OS failure at {canonical_text}"),
     );
@@ -589,9 +1046,10 @@ fn tc_050_worker_reports_partial_completion_when_recheck_fails() {
     let response = process_action_request_with(
         ActionRequest {
             request_id: 103,
-            root: root.clone(),
+            roots: vec![root.clone(); 2],
             paths: vec![first, second],
             open_parent_for_files: true,
+            operation: ActionOperation::Launch,
         },
         |path| {
             calls.push(path.to_path_buf());
@@ -608,6 +1066,62 @@ fn tc_050_worker_reports_partial_completion_when_recheck_fails() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn worker_authorizes_each_path_against_its_own_root() {
+    let root = test_root("action-worker-own-root");
+    let other = test_root("action-worker-own-root-other");
+    fs::create_dir_all(&root).expect("create root");
+    fs::create_dir_all(&other).expect("create other root");
+    let first = root.join("first.txt");
+    let second = other.join("second.txt");
+    fs::write(&first, "first").expect("write first");
+    fs::write(&second, "second").expect("write second");
+    let mut calls = Vec::new();
+
+    let response = process_action_request_with(
+        ActionRequest {
+            request_id: 107,
+            roots: vec![root.clone(), other.clone()],
+            paths: vec![first.clone(), second.clone()],
+            open_parent_for_files: false,
+            operation: ActionOperation::Launch,
+        },
+        |path| {
+            calls.push(path.to_path_buf());
+            Ok(())
+        },
+    );
+    assert_eq!(calls.len(), 2);
+    assert!(!response.notice.starts_with("Action blocked"));
+
+    let response = process_action_request_with(
+        ActionRequest {
+            request_id: 108,
+            roots: vec![root.clone(), root.clone()],
+            paths: vec![first, second],
+            open_parent_for_files: false,
+            operation: ActionOperation::Launch,
+        },
+        |_| panic!("paths outside their root must not launch"),
+    );
+    assert!(response.notice.starts_with("Action blocked"));
+
+    let response = process_action_request_with(
+        ActionRequest {
+            request_id: 109,
+            roots: vec![root.clone()],
+            paths: vec![other.join("second.txt")],
+            open_parent_for_files: false,
+            operation: ActionOperation::Trash,
+        },
+        |_| Ok(()),
+    );
+    assert!(response.file_operation.is_none());
+    assert!(other.join("second.txt").exists());
+    let _ = fs::remove_dir_all(&root);
+    let _ = fs::remove_dir_all(&other);
+}
+
 #[cfg(unix)]
 #[test]
 fn tc_051_symlink_escape_is_rejected_but_open_parent_of_file_link_is_allowed() {
@@ -626,9 +1140,10 @@ fn tc_051_symlink_escape_is_rejected_but_open_parent_of_file_link_is_allowed() {
     let direct = process_action_request_with(
         ActionRequest {
             request_id: 104,
-            root: root.clone(),
+            roots: vec![root.clone()],
             paths: vec![link.clone()],
             open_parent_for_files: false,
+            operation: ActionOperation::Launch,
         },
         |path| {
             direct_calls.push(path.to_path_buf());
@@ -646,9 +1161,10 @@ fn tc_051_symlink_escape_is_rejected_but_open_parent_of_file_link_is_allowed() {
     let parent = process_action_request_with(
         ActionRequest {
             request_id: 105,
-            root: root.clone(),
+            roots: vec![root.clone()],
             paths: vec![link],
             open_parent_for_files: true,
+            operation: ActionOperation::Launch,
         },
         |path| {
             parent_calls.push(path.to_path_buf());
@@ -669,9 +1185,10 @@ fn tc_051_symlink_escape_is_rejected_but_open_parent_of_file_link_is_allowed() {
     let directory_response = process_action_request_with(
         ActionRequest {
             request_id: 108,
-            root: root.clone(),
+            roots: vec![root.clone()],
             paths: vec![dir_link],
             open_parent_for_files: true,
+            operation: ActionOperation::Launch,
         },
         |path| {
             directory_calls.push(path.to_path_buf());
@@ -687,9 +1204,10 @@ fn tc_051_symlink_escape_is_rejected_but_open_parent_of_file_link_is_allowed() {
     let broken_response = process_action_request_with(
         ActionRequest {
             request_id: 109,
-            root: root.clone(),
+            roots: vec![root.clone()],
             paths: vec![broken_link.clone()],
             open_parent_for_files: true,
+            operation: ActionOperation::Launch,
         },
         |path| {
             broken_calls.push(path.to_path_buf());
@@ -773,9 +1291,10 @@ fn tc_051_windows_worker_accepts_case_and_extended_prefix_for_same_target() {
         let response = process_action_request_with(
             ActionRequest {
                 request_id: 110,
-                root: root.clone(),
+                roots: vec![root.clone()],
                 paths: vec![candidate],
                 open_parent_for_files: false,
+                operation: ActionOperation::Launch,
             },
             |path| {
                 calls.push(path.to_path_buf());
@@ -807,9 +1326,10 @@ fn tc_051_windows_junction_escape_manual_evidence() {
         let response = process_action_request_with(
             ActionRequest {
                 request_id: 151,
-                root: root.clone(),
+                roots: vec![root.clone(); 2],
                 paths: vec![inside.clone(), junction.clone()],
                 open_parent_for_files,
+                operation: ActionOperation::Launch,
             },
             |path| {
                 calls.push(path.to_path_buf());
@@ -846,6 +1366,7 @@ fn stale_action_completion_is_ignored_by_request_id() {
     tx.send(ActionResponse {
         request_id: 1,
         notice: "Action failed: stale".to_string(),
+        file_operation: None,
     })
     .expect("send stale action response");
     app.poll_action_response();
@@ -857,6 +1378,7 @@ fn stale_action_completion_is_ignored_by_request_id() {
     tx.send(ActionResponse {
         request_id: 2,
         notice: "Action: latest".to_string(),
+        file_operation: None,
     })
     .expect("send latest action response");
     app.poll_action_response();
@@ -889,6 +1411,32 @@ fn action_progress_label_is_shown_only_while_action_runs() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn results_tree_text_nests_paths_under_root_with_branch_characters() {
+    let root = PathBuf::from("/work");
+    let paths = vec![
+        root.join("src").join("main.rs"),
+        root.join("README.md"),
+        root.join("src").join("app").join("mod.rs"),
+        root.join("docs").join("spec.md"),
+    ];
+
+    let text = FlistWalkerApp::results_tree_text(&root, &paths);
+
+    let expected = [
+        "/work",
+        "├── README.md",
+        "├── docs/",
+        "│   └── spec.md",
+        "└── src/",
+        "    ├── app/",
+        "    │   └── mod.rs",
+        "    └── main.rs",
+    ]
+    .join("\n");
+    assert_eq!(text, expected);
+}
+
 #[test]
 #[cfg(target_os = "windows")]
 fn clipboard_text_normalizes_extended_and_unc_paths() {
@@ -923,3 +1471,91 @@ fn regression_copy_selected_paths_notice_normalizes_extended_prefix() {
     assert!(!app.shell.runtime.notice.contains(r"\\?\"));
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn all_results_text_adds_filelist_header_with_query_when_enabled() {
+    let root = PathBuf::from("/work/repo");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "main".to_string());
+    app.shell.runtime.results = vec![
+        (root.join("src/main.rs"), 1.0),
+        (root.join("docs/main.md"), 0.5),
+    ];
+    let created_at = UNIX_EPOCH + Duration::from_secs(86_400);
+    let paths = FlistWalkerApp::clipboard_paths_text(&[
+        root.join("src/main.rs"),
+        root.join("docs/main.md"),
+    ]);
+
+    assert_eq!(app.all_results_text(created_at), paths);
+
+    app.shell.runtime.filelist_header_comment = true;
+    assert_eq!(
+        app.all_results_text(created_at),
+        format!(
+            "# FlistWalker FileList\n# root: {}\n# query: main\n# created: 1970-01-02 00:00 UTC\n# entries: 2\n{paths}",
+            root.display()
+        )
+    );
+}
+
+#[test]
+fn shell_quote_escapes_embedded_quotes_per_platform() {
+    assert_eq!(
+        FlistWalkerApp::shell_quote("/tmp/it's a file.txt", false),
+        r"'/tmp/it'\''s a file.txt'"
+    );
+    assert_eq!(
+        FlistWalkerApp::shell_quote(r#"C:\Program Files\say "hi".txt"#, true),
+        r#"'C:\Program Files\say "hi".txt'"#
+    );
+    // PowerShell expands `$` and backticks inside double quotes, so Windows uses single quotes.
+    assert_eq!(
+        FlistWalkerApp::shell_quote(r"C:\work\it's $HOME `n 100%.txt", true),
+        r"'C:\work\it''s $HOME `n 100%.txt'"
+    );
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn shell_quoted_paths_text_joins_quoted_paths_on_one_line() {
+    let paths = vec![
+        PathBuf::from("/work/my docs/a.txt"),
+        PathBuf::from("/work/b.txt"),
+    ];
+    let text = FlistWalkerApp::shell_quoted_paths_text(&paths);
+    assert_eq!(text, "'/work/my docs/a.txt' '/work/b.txt'");
+}
+
+#[test]
+fn copy_selected_site_urls_uses_root_relative_forward_slash_paths() {
+    let root = test_root("copy-site-url");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let logo = root.join("assets").join("img").join("logo.png");
+    let outside = PathBuf::from("/elsewhere/other.css");
+    app.shell.runtime.results = vec![(logo.clone(), 0.0), (outside.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    let ctx = egui::Context::default();
+
+    assert_eq!(
+        FlistWalkerApp::site_relative_url(&logo, &root).as_deref(),
+        Some("/assets/img/logo.png")
+    );
+    assert_eq!(FlistWalkerApp::site_relative_url(&outside, &root), None);
+
+    app.copy_selected_site_urls(&ctx);
+    assert_eq!(
+        app.shell.runtime.notice,
+        "Copied site URL: /assets/img/logo.png"
+    );
+    assert_eq!(app.shell.runtime.last_action, Some(ActionKind::CopySiteUrl));
+
+    app.shell.runtime.pinned_paths.insert(logo);
+    app.shell.runtime.pinned_paths.insert(outside);
+    app.copy_selected_site_urls(&ctx);
+    assert_eq!(
+        app.shell.runtime.notice,
+        "Copied site URL: /assets/img/logo.png (skipped 1 outside root)"
+    );
+    let _ = fs::remove_dir_all(&root);
+}
@@ -190,6 +190,34 @@ fn move_row_sets_scroll_tracking() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn move_row_wraps_from_edge_rows_only_when_wrap_navigation_is_enabled() {
+    let root = test_root("wrap-navigation");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "".to_string());
+    app.shell.ui.show_preview = false;
+    app.shell.runtime.results = (0..15)
+        .map(|index| (root.join(format!("{index:02}.txt")), 0.0))
+        .collect();
+    app.shell.runtime.current_row = Some(14);
+
+    app.move_row(1);
+    assert_eq!(app.shell.runtime.current_row, Some(14));
+
+    app.shell.runtime.wrap_navigation = true;
+    app.move_row(1);
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+    app.move_row(-1);
+    assert_eq!(app.shell.runtime.current_row, Some(14));
+
+    app.shell.runtime.current_row = Some(8);
+    app.move_page(1);
+    assert_eq!(app.shell.runtime.current_row, Some(14));
+    app.move_page(1);
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn prefer_relative_display_is_enabled_for_filelist_source() {
     let root = test_root("prefer-relative-filelist");
@@ -229,3 +257,31 @@ fn app_defaults_use_filelist_on() {
     assert!(app.shell.runtime.use_filelist);
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn set_notice_appends_to_bounded_log_without_touching_status_line() {
+    let root = test_root("notice-log");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.log_messages.clear();
+
+    app.set_notice("Copy failed: denied");
+    app.clear_notice();
+
+    assert!(app.shell.runtime.notice.is_empty());
+    let lines = app.log_panel_lines();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].ends_with("UTC  Copy failed: denied"));
+
+    for index in 0..FlistWalkerApp::LOG_PANEL_MAX_MESSAGES + 5 {
+        app.set_notice(format!("notice {index}"));
+    }
+
+    let log = &app.shell.runtime.log_messages;
+    assert_eq!(log.len(), FlistWalkerApp::LOG_PANEL_MAX_MESSAGES);
+    assert_eq!(
+        log.front().map(|(_, message)| message.as_str()),
+        Some("notice 5")
+    );
+    let _ = fs::remove_dir_all(&root);
+}
@@ -92,6 +92,156 @@ fn all_matches_sort_scope_reissues_search_request_for_non_score_sort() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn match_full_path_searches_absolute_paths_while_display_stays_relative() {
+    let root = test_root("match-full-path");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "main".to_string());
+    let (search_tx, search_rx) = mpsc::channel::<SearchRequest>();
+    app.shell.search.tx = search_tx;
+    app.shell.runtime.index.source = IndexSource::Walker;
+    app.shell.runtime.entries = Arc::new(vec![file_entry(root.join("main.rs"))]);
+
+    app.update_results();
+    assert!(
+        search_rx
+            .try_recv()
+            .expect("relative request")
+            .prefer_relative
+    );
+
+    app.shell.runtime.match_full_path = true;
+    app.update_results();
+    assert!(
+        !search_rx
+            .try_recv()
+            .expect("full path request")
+            .prefer_relative
+    );
+    assert!(app.prefer_relative_display());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn min_query_length_skips_search_for_short_queries_and_shows_hint() {
+    let root = test_root("min-query-length");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "m".to_string());
+    let (search_tx, search_rx) = mpsc::channel::<SearchRequest>();
+    app.shell.search.tx = search_tx;
+    app.shell.runtime.min_query_length = 2;
+    app.shell.runtime.entries = Arc::new(vec![file_entry(root.join("main.rs"))]);
+    app.shell.runtime.results = vec![(root.join("main.rs"), 0.0)];
+
+    app.update_results();
+    assert!(search_rx.try_recv().is_err());
+    assert!(app.shell.runtime.results.is_empty());
+    assert!(!app.shell.search.in_progress());
+    assert_eq!(
+        app.results_empty_state_message().as_deref(),
+        Some("Type at least 2 characters")
+    );
+
+    app.shell.runtime.query_state.query = "ma".to_string();
+    app.update_results();
+    assert_eq!(search_rx.try_recv().expect("search request").query, "ma");
+
+    app.shell.runtime.query_state.query.clear();
+    app.update_results();
+    assert_eq!(app.shell.runtime.results, vec![(root.join("main.rs"), 0.0)]);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn empty_query_recent_first_requests_modified_sort_without_changing_selector() {
+    let root = test_root("empty-query-recent-first");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 2, String::new());
+    let (search_tx, search_rx) = mpsc::channel::<SearchRequest>();
+    let (response_tx, response_rx) = mpsc::channel::<SearchResponse>();
+    app.shell.search.tx = search_tx;
+    app.shell.search.rx = response_rx;
+    app.shell.runtime.empty_query_recent_first = true;
+    app.shell.runtime.entries = Arc::new(vec![
+        file_entry(root.join("old.txt")),
+        file_entry(root.join("new.txt")),
+    ]);
+
+    app.update_results();
+
+    let request = search_rx.try_recv().expect("recent-first search request");
+    assert!(request.query.is_empty());
+    assert_eq!(request.sort_mode, ResultSortMode::ModifiedDesc);
+    assert_eq!(request.sort_scope, ResultSortScope::AllMatches);
+    response_tx
+        .send(SearchResponse {
+            request_id: request.request_id,
+            results: vec![(root.join("new.txt"), 0.0), (root.join("old.txt"), 0.0)],
+            total_match_count: 2,
+            sort_mode: request.sort_mode,
+            sort_scope: request.sort_scope,
+            error: None,
+        })
+        .expect("send search response");
+    app.poll_search_response();
+    assert_eq!(app.shell.runtime.results[0].0, root.join("new.txt"));
+    assert_eq!(app.shell.runtime.result_sort_mode, ResultSortMode::Score);
+    assert_eq!(
+        app.shell.runtime.result_sort_scope,
+        ResultSortScope::ShownResults
+    );
+
+    app.shell.runtime.query_state.query = "new".to_string();
+    app.update_results();
+    let request = search_rx.try_recv().expect("query search request");
+    assert_eq!(request.sort_mode, ResultSortMode::Score);
+    assert_eq!(request.sort_scope, ResultSortScope::ShownResults);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn recent_view_requests_modified_files_without_touching_query() {
+    let root = test_root("recent-view");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "zz[".to_string());
+    let (search_tx, search_rx) = mpsc::channel::<SearchRequest>();
+    app.shell.search.tx = search_tx;
+    app.shell.runtime.use_regex = true;
+    app.shell.runtime.entries = Arc::new(vec![
+        file_entry(root.join("a.txt")),
+        dir_entry(root.join("src")),
+        file_entry(root.join("src").join("b.rs")),
+    ]);
+
+    app.set_recent_view(true);
+
+    let request = search_rx.try_recv().expect("recent view search request");
+    assert!(request.query.is_empty());
+    assert_eq!(request.sort_mode, ResultSortMode::ModifiedDesc);
+    assert_eq!(request.sort_scope, ResultSortScope::AllMatches);
+    assert_eq!(
+        request
+            .entries
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>(),
+        vec![root.join("a.txt"), root.join("src").join("b.rs")]
+    );
+    assert_eq!(app.shell.runtime.query_state.query, "zz[");
+    assert!(app.shell.runtime.query_error.is_none());
+    assert_eq!(app.shell.runtime.result_sort_mode, ResultSortMode::Score);
+
+    app.shell.runtime.use_regex = false;
+    app.shell.runtime.query_state.query = "b".to_string();
+    app.mark_query_edited();
+    app.update_results();
+    assert!(!app.shell.runtime.recent_view);
+    let request = search_rx.try_recv().expect("query search request");
+    assert_eq!(request.query, "b");
+    assert_eq!(request.sort_mode, ResultSortMode::Score);
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn search_result_refresh_clamps_cursor_row_instead_of_following_path_regression() {
     let root = test_root("search-refresh-clamp-row");
@@ -114,6 +264,60 @@ fn search_result_refresh_clamps_cursor_row_instead_of_following_path_regression(
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn sticky_results_scroll_follows_selected_path_without_scrolling() {
+    let root = test_root("sticky-results-scroll");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "t".to_string());
+    let (search_tx, search_rx) = mpsc::channel::<SearchRequest>();
+    let (response_tx, response_rx) = mpsc::channel::<SearchResponse>();
+    app.shell.search.tx = search_tx;
+    app.shell.search.rx = response_rx;
+    app.shell.ui.show_preview = false;
+    app.shell.runtime.sticky_results_scroll = true;
+    app.shell.runtime.entries = Arc::new(vec![
+        unknown_entry(root.join("first.txt")),
+        unknown_entry(root.join("second.txt")),
+        unknown_entry(root.join("third.txt")),
+    ]);
+    app.shell.runtime.results = vec![
+        (root.join("first.txt"), 1.0),
+        (root.join("second.txt"), 1.0),
+        (root.join("third.txt"), 1.0),
+    ];
+    app.shell.runtime.current_row = Some(2);
+    app.clear_scroll_to_current();
+
+    for (results, expected_row, expect_scroll) in [
+        (
+            vec![(root.join("third.txt"), 2.0), (root.join("first.txt"), 1.0)],
+            Some(0),
+            false,
+        ),
+        (vec![(root.join("second.txt"), 1.0)], Some(0), true),
+    ] {
+        app.shell.runtime.query_state.query.push('x');
+        app.update_results();
+        let request = search_rx.try_recv().expect("search request");
+        response_tx
+            .send(SearchResponse {
+                request_id: request.request_id,
+                results,
+                total_match_count: 2,
+                sort_mode: request.sort_mode,
+                sort_scope: request.sort_scope,
+                error: None,
+            })
+            .expect("send search response");
+        app.poll_search_response();
+
+        assert_eq!(app.shell.runtime.current_row, expected_row);
+        assert_eq!(app.shell.ui.scroll_to_current(), expect_scroll);
+        app.clear_scroll_to_current();
+    }
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn search_result_refresh_does_not_auto_select_first_row_without_user_action_regression() {
     let root = test_root("search-refresh-keep-none");
@@ -430,14 +634,110 @@ fn tc_155_regression_highlight_scope_compiles_once_for_multiple_rows() {
     app.shell.runtime.query_state.query = "main".to_string();
     crate::query::reset_compile_counts();
 
-    let first = app.highlight_positions_for_path_cached(&root.join("src/main.rs"), true);
-    let second = app.highlight_positions_for_path_cached(&root.join("tests/main_test.rs"), true);
+    let first = app.highlighted_display_for_path_cached(&root.join("src/main.rs"), true);
+    let second = app.highlighted_display_for_path_cached(&root.join("tests/main_test.rs"), true);
 
-    assert!(!first.is_empty());
-    assert!(!second.is_empty());
+    assert!(!first.positions.is_empty());
+    assert!(!second.positions.is_empty());
     assert_eq!(crate::query::query_compile_count(), 1);
 }
 
+#[test]
+fn highlighted_display_is_reused_until_query_or_mode_changes() {
+    let root = PathBuf::from("/tmp/highlight-display-reuse");
+    let path = root.join("src/main.rs");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.query_state.query = "main".to_string();
+
+    let first = app.highlighted_display_for_path_cached(&path, true);
+    let again = app.highlighted_display_for_path_cached(&path, true);
+    assert!(Arc::ptr_eq(&first, &again));
+    assert!(first.display.ends_with("main.rs"));
+
+    app.shell.runtime.query_state.query = "src".to_string();
+    let after_query = app.highlighted_display_for_path_cached(&path, true);
+    assert!(!Arc::ptr_eq(&first, &after_query));
+    assert_ne!(first.positions, after_query.positions);
+
+    app.shell.runtime.use_regex = true;
+    let after_mode = app.highlighted_display_for_path_cached(&path, true);
+    assert!(!Arc::ptr_eq(&after_query, &after_mode));
+}
+
+#[test]
+fn display_base_rebases_relative_display_and_highlight_positions() {
+    let root = PathBuf::from("/tmp/highlight-display-base");
+    let base = root.join("deep");
+    let inside = base.join("src").join("main.rs");
+    let outside = root.join("other").join("main.rs");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.query_state.query = "main".to_string();
+    app.shell.runtime.results = vec![(base.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.set_entry_kind(&base, EntryKind::dir());
+    let highlighted_text = |highlighted: &HighlightedDisplay| {
+        let chars = highlighted.display.chars().collect::<Vec<_>>();
+        highlighted
+            .positions
+            .iter()
+            .map(|idx| chars[usize::from(*idx)])
+            .collect::<String>()
+    };
+
+    let before = app.highlighted_display_for_path_cached(&inside, true);
+    assert!(before.display.starts_with("deep"));
+
+    app.set_current_as_display_base();
+    assert_eq!(
+        app.shell.runtime.display_base.as_deref(),
+        Some(base.as_path())
+    );
+    let rebased = app.highlighted_display_for_path_cached(&inside, true);
+    assert!(rebased.display.starts_with("src"));
+    assert_eq!(highlighted_text(rebased.as_ref()), "main");
+    let fallback = app.highlighted_display_for_path_cached(&outside, true);
+    assert!(fallback.display.starts_with("other"));
+    assert_eq!(highlighted_text(fallback.as_ref()), "main");
+
+    app.clear_display_base();
+    assert!(app.shell.runtime.display_base.is_none());
+    let restored = app.highlighted_display_for_path_cached(&inside, true);
+    assert!(restored.display.starts_with("deep"));
+}
+
+#[test]
+fn dir_trailing_slash_appends_separator_to_folders_without_highlighting_it() {
+    let root = PathBuf::from("/tmp/highlight-dir-trailing-slash");
+    let dir = root.join("src");
+    let file = root.join("src.rs");
+    let unresolved = root.join("srcgen");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.query_state.query = "src".to_string();
+    app.shell.runtime.dir_trailing_slash = true;
+    app.set_entry_kind(&dir, EntryKind::dir());
+    app.set_entry_kind(&file, EntryKind::file());
+
+    let dir_display = app.highlighted_display_for_path_cached(&dir, true);
+    assert!(dir_display.display.ends_with(std::path::MAIN_SEPARATOR));
+    let separator_index = dir_display.display.chars().count() - 1;
+    assert!(!dir_display
+        .positions
+        .contains(&u16::try_from(separator_index).expect("short display")));
+    assert_eq!(
+        FlistWalkerApp::result_directory_group(&dir_display.display).0,
+        ""
+    );
+
+    let file_display = app.highlighted_display_for_path_cached(&file, true);
+    assert!(file_display.display.ends_with("src.rs"));
+
+    let before = app.highlighted_display_for_path_cached(&unresolved, true);
+    assert!(!before.display.ends_with(std::path::MAIN_SEPARATOR));
+    app.set_entry_kind(&unresolved, EntryKind::dir());
+    let after = app.highlighted_display_for_path_cached(&unresolved, true);
+    assert!(after.display.ends_with(std::path::MAIN_SEPARATOR));
+}
+
 #[test]
 fn request_preview_is_skipped_when_preview_is_hidden() {
     let root = test_root("preview-hidden");
@@ -462,6 +762,42 @@ fn request_preview_is_skipped_when_preview_is_hidden() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn markdown_preview_follows_preview_path_and_reuses_parsed_lines() {
+    let root = test_root("markdown-preview-path");
+    fs::create_dir_all(&root).expect("create dir");
+    let readme = root.join("README.md");
+    let notes = root.join("notes.txt");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(readme.clone(), 0.0), (notes.clone(), 0.0)];
+    app.set_entry_kind(&readme, EntryKind::file());
+    app.set_entry_kind(&notes, EntryKind::file());
+    app.cache_preview(readme.clone(), "# Title".to_string());
+
+    app.shell.runtime.current_row = Some(0);
+    app.request_preview_for_current();
+    assert_eq!(
+        app.shell.runtime.preview_path.as_deref(),
+        Some(readme.as_path())
+    );
+    assert!(app.current_preview_is_markdown());
+    let first = app.markdown_preview_lines().expect("markdown lines");
+    let second = app.markdown_preview_lines().expect("markdown lines");
+    assert!(Arc::ptr_eq(&first, &second));
+
+    app.shell.runtime.current_row = Some(1);
+    app.request_preview_for_current();
+    assert_eq!(app.shell.runtime.preview, "Loading preview...");
+    assert!(app.shell.runtime.preview_path.is_none());
+    assert!(!app.current_preview_is_markdown());
+
+    app.shell.runtime.current_row = Some(0);
+    app.request_preview_for_current();
+    app.shell.runtime.current_row = Some(1);
+    assert!(app.current_preview_is_markdown());
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn request_preview_when_hidden_keeps_post_index_kind_resolution_queue() {
     let root = test_root("preview-hidden-keeps-kind-queue");
@@ -516,3 +852,88 @@ fn entry_kind_cache_survives_tab_state_roundtrip() {
     assert_eq!(app.find_entry_kind(&path), Some(EntryKind::link(false)));
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn result_kind_grouping_stably_partitions_dirs_and_files() {
+    let root = test_root("result-kind-grouping");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.ui.show_preview = false;
+    app.set_entry_kind(&root.join("src"), EntryKind::dir());
+    app.set_entry_kind(&root.join("docs"), EntryKind::dir());
+    app.set_entry_kind(&root.join("a.txt"), EntryKind::file());
+    let results = vec![
+        (root.join("a.txt"), 3.0),
+        (root.join("src"), 2.5),
+        (root.join("unknown"), 2.0),
+        (root.join("docs"), 1.0),
+    ];
+    let order = |app: &FlistWalkerApp| {
+        app.shell
+            .runtime
+            .results
+            .iter()
+            .map(|(path, _)| path.strip_prefix(&root).expect("under root").to_path_buf())
+            .collect::<Vec<_>>()
+    };
+
+    app.shell.runtime.result_kind_grouping = ResultKindGrouping::DirsFirst;
+    app.replace_results_snapshot(results.clone(), false);
+    assert_eq!(
+        order(&app),
+        ["src", "docs", "a.txt", "unknown"].map(PathBuf::from)
+    );
+
+    app.shell.runtime.result_kind_grouping = ResultKindGrouping::FilesFirst;
+    app.replace_results_snapshot(results, false);
+    assert_eq!(
+        order(&app),
+        ["a.txt", "unknown", "src", "docs"].map(PathBuf::from)
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn max_results_per_directory_caps_folders_before_the_result_limit() {
+    let root = test_root("max-results-per-directory");
+    let mut app = FlistWalkerApp::new(root.clone(), 3, String::new());
+    app.shell.ui.show_preview = false;
+    let (search_tx, search_rx) = mpsc::channel::<SearchRequest>();
+    app.shell.search.tx = search_tx;
+    app.shell.runtime.entries = Arc::new(vec![
+        file_entry(root.join("crowded/a.txt")),
+        file_entry(root.join("crowded/b.txt")),
+        file_entry(root.join("crowded/d.txt")),
+        file_entry(root.join("other/c.txt")),
+        file_entry(root.join("other/e.txt")),
+        file_entry(root.join("top.txt")),
+    ]);
+    let order = |app: &FlistWalkerApp| {
+        app.shell
+            .runtime
+            .results
+            .iter()
+            .map(|(path, _)| path.strip_prefix(&root).expect("under root").to_path_buf())
+            .collect::<Vec<_>>()
+    };
+
+    app.update_results();
+    assert_eq!(
+        order(&app),
+        ["crowded/a.txt", "crowded/b.txt", "crowded/d.txt"].map(PathBuf::from)
+    );
+
+    // The crowded folder must not use up the limit before other folders are reached.
+    app.shell.runtime.max_results_per_directory = 1;
+    app.update_results();
+    assert_eq!(
+        order(&app),
+        ["crowded/a.txt", "other/c.txt", "top.txt"].map(PathBuf::from)
+    );
+
+    app.shell.runtime.query_state.query = "txt".to_string();
+    app.update_results();
+    let req = search_rx.try_recv().expect("search request");
+    assert_eq!(req.max_results_per_directory, 1);
+    assert_eq!(req.limit, 3);
+}
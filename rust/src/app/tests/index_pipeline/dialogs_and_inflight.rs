@@ -1,4 +1,5 @@
 use super::*;
+use crate::app::session::SavedFilterToggles;
 
 #[test]
 fn request_index_refresh_reenables_files_when_both_filters_are_off() {
@@ -40,6 +41,33 @@ fn request_index_refresh_uses_latest_toggle_state() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn reload_filelist_requests_filelist_only_index_and_refuses_without_filelist() {
+    let root = test_root("reload-filelist");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = bounded_request_channel::<IndexRequest>(2);
+    app.shell.indexing.tx = tx;
+    app.shell.runtime.git_tracked_only = true;
+
+    app.reload_filelist();
+
+    assert!(rx.try_recv().is_err());
+    assert_eq!(
+        app.shell.runtime.notice,
+        "No FileList.txt or filelist.txt in the current root to reload"
+    );
+
+    fs::write(root.join("FileList.txt"), "a.txt\n").expect("write filelist");
+    app.reload_filelist();
+
+    let req = rx.try_recv().expect("index request should be sent");
+    assert!(req.filelist_only);
+    assert!(req.use_filelist);
+    assert!(!req.git_tracked_only);
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn request_create_filelist_walker_refresh_resets_index_state_and_registers_request() {
     let root = test_root("create-filelist-walker-refresh-reset");
@@ -155,6 +183,68 @@ fn use_filelist_with_walker_source_keeps_type_filters_editable() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn use_filelist_toggle_restores_remembered_filters_per_source() {
+    let root = test_root("use-filelist-remembers-filters");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = bounded_request_channel::<IndexRequest>(4);
+    app.shell.indexing.tx = tx;
+    app.shell.runtime.remember_filters_per_source = true;
+    app.shell.runtime.index.source = IndexSource::Walker;
+    app.shell.ui.source_filter_memory.walker = Some(SavedFilterToggles {
+        include_files: false,
+        include_dirs: true,
+        use_regex: true,
+    });
+    app.shell.runtime.use_filelist = false;
+    app.shell.runtime.include_files = true;
+    app.shell.runtime.include_dirs = false;
+    app.shell.runtime.use_regex = false;
+
+    app.maybe_reindex_from_filter_toggles(true, false, false);
+
+    let req = rx.try_recv().expect("index request should be sent");
+    assert!(!req.include_files);
+    assert!(req.include_dirs);
+    assert!(app.shell.runtime.use_regex);
+    assert_eq!(
+        app.shell.ui.source_filter_memory.filelist,
+        Some(SavedFilterToggles {
+            include_files: true,
+            include_dirs: false,
+            use_regex: false,
+        })
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn use_filelist_toggle_keeps_filters_when_memory_disabled() {
+    let root = test_root("use-filelist-sticky-filters");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, _rx) = bounded_request_channel::<IndexRequest>(4);
+    app.shell.indexing.tx = tx;
+    app.shell.runtime.index.source = IndexSource::Walker;
+    app.shell.ui.source_filter_memory.walker = Some(SavedFilterToggles {
+        include_files: false,
+        include_dirs: true,
+        use_regex: true,
+    });
+    app.shell.runtime.use_filelist = false;
+    app.shell.runtime.include_files = true;
+    app.shell.runtime.include_dirs = false;
+
+    app.maybe_reindex_from_filter_toggles(true, false, false);
+
+    assert!(app.shell.runtime.include_files);
+    assert!(!app.shell.runtime.include_dirs);
+    assert!(!app.shell.runtime.use_regex);
+    assert_eq!(app.shell.ui.source_filter_memory.filelist, None);
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn create_filelist_with_use_filelist_enabled_and_walker_source_skips_confirmation() {
     let root = test_root("filelist-use-filelist-walker-source-no-confirm");
@@ -351,6 +441,11 @@ fn preempt_background_when_active_index_is_queued() {
         use_filelist: false,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     });
     {
         let mut latest = app
@@ -529,6 +624,11 @@ fn same_tab_request_waits_until_previous_inflight_finishes() {
         use_filelist: false,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     });
 
     assert!(app.pop_next_index_request().is_none());
@@ -556,6 +656,11 @@ fn tc_152_full_index_worker_queue_requeues_without_marking_inflight() {
             use_filelist: false,
             include_files: true,
             include_dirs: true,
+            throttle: None,
+            extra_roots: Vec::new(),
+            canonicalize_entries: false,
+            git_tracked_only: false,
+            filelist_only: false,
         })
         .expect("fill worker queue");
     }
@@ -567,6 +672,11 @@ fn tc_152_full_index_worker_queue_requeues_without_marking_inflight() {
         use_filelist: false,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     app.shell
         .indexing
@@ -608,6 +718,11 @@ fn tc_152_dispatch_keeps_coordinator_inflight_at_two() {
             use_filelist: false,
             include_files: true,
             include_dirs: true,
+            throttle: None,
+            extra_roots: Vec::new(),
+            canonicalize_entries: false,
+            git_tracked_only: false,
+            filelist_only: false,
         });
     }
 
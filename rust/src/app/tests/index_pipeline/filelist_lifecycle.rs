@@ -229,6 +229,59 @@ fn non_empty_query_incremental_refresh_skips_small_delta_during_indexing() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn frozen_index_holds_active_batches_until_unfrozen() {
+    let root = test_root("frozen-index-holds-batches");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = mpsc::channel::<IndexResponse>();
+    app.shell.indexing.rx = rx;
+    app.shell.runtime.index.entries.clear();
+    app.shell.indexing.pending_entries.clear();
+    app.shell.indexing.pending_entries_request_id = None;
+    app.shell.indexing.pending_request_id = Some(41);
+    app.shell.indexing.in_progress = true;
+    app.set_index_frozen(true);
+
+    let path = root.join("main.rs");
+    tx.send(IndexResponse::Batch {
+        request_id: 41,
+        entries: vec![IndexEntry {
+            path: path.clone(),
+            kind: EntryKind::file(),
+            kind_known: true,
+        }],
+    })
+    .expect("send index batch");
+
+    app.poll_index_response();
+    assert!(app.shell.runtime.index.entries.is_empty());
+    assert_eq!(app.shell.indexing.frozen_responses.len(), 1);
+
+    app.set_index_frozen(false);
+    assert_eq!(
+        app.shell.runtime.notice,
+        "Index unfrozen; applying 1 held update(s)"
+    );
+    app.poll_index_response();
+    assert!(app.shell.indexing.frozen_responses.is_empty());
+    assert_eq!(app.shell.runtime.index.entries, vec![file_entry(path)]);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn request_index_refresh_unfreezes_index() {
+    let root = test_root("frozen-index-refresh");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.set_index_frozen(true);
+
+    app.request_index_refresh();
+
+    assert!(!app.shell.indexing.frozen);
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn walker_truncated_notice_points_to_config_file_setting() {
     let root = test_root("walker-truncated-config-notice");
@@ -259,6 +312,32 @@ fn walker_truncated_notice_points_to_config_file_setting() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn walker_directory_cap_notice_names_the_config_setting() {
+    let root = test_root("walker-directory-cap-notice");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = mpsc::channel::<IndexResponse>();
+    app.shell.indexing.rx = rx;
+    app.shell.indexing.pending_request_id = Some(92);
+    app.shell.indexing.in_progress = true;
+
+    tx.send(IndexResponse::DirectoriesCapped {
+        request_id: 92,
+        limit: 10_000,
+        directories: 2,
+    })
+    .expect("send directories capped response");
+
+    app.poll_index_response();
+
+    assert_eq!(
+        app.shell.runtime.notice,
+        "Walker took only the first 10000 entries from 2 large folder(s) (set walker_max_entries_per_dir in the config file to adjust)"
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn non_empty_query_incremental_refresh_updates_entries_with_large_delta() {
     let root = test_root("incremental-large-delta");
@@ -28,6 +28,34 @@ fn search_error_updates_notice() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn invalid_regex_query_is_reported_without_enqueueing_search() {
+    let root = test_root("search-invalid-regex");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = mpsc::channel::<SearchRequest>();
+    app.shell.search.tx = tx;
+    let kept = vec![(root.join("main.rs"), 1.0)];
+    app.shell.runtime.results = kept.clone();
+    app.shell.runtime.use_regex = true;
+
+    app.shell.runtime.query_state.query = "main(".to_string();
+    app.update_results();
+
+    assert!(app.shell.runtime.query_error.is_some());
+    assert!(app.shell.runtime.notice.contains("Invalid query:"));
+    assert!(!app.shell.search.in_progress());
+    assert!(rx.try_recv().is_err());
+    assert_eq!(app.shell.runtime.results, kept);
+
+    app.shell.runtime.query_state.query = "main".to_string();
+    app.update_results();
+
+    assert!(app.shell.runtime.query_error.is_none());
+    assert!(rx.try_recv().is_ok());
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn search_response_requeues_unknown_walker_result_kind() {
     let root = test_root("search-response-requeues-unknown-kind");
@@ -4,26 +4,27 @@ pub(super) use crate::app::index_coordinator::IndexResponseRoute;
 pub(super) use crate::app::search_coordinator::SearchResponseRoute;
 pub(super) use crate::app::session::UiState;
 pub(super) use crate::app::state::{
-    BackgroundIndexState, PendingActiveIndexFinish, PendingFileListAfterIndex,
+    BackgroundIndexState, FolderActivation, PendingActiveIndexFinish, PendingFileListAfterIndex,
     PendingFileListAncestorConfirmation, PendingFileListConfirmation,
-    PendingFileListUseWalkerConfirmation, SortMetadata, UpdateCheckFailureState, UpdateManager,
-    UpdatePromptState, UpdateState,
+    PendingFileListUseWalkerConfirmation, ResultKindGrouping, SortMetadata,
+    UpdateCheckFailureState, UpdateManager, UpdatePromptState, UpdateState,
 };
 pub(super) use crate::app::worker_channel::bounded_request_channel;
 pub(super) use crate::app::worker_protocol::{
-    KindResolveRequest, KindResolveResponse, UpdateRequestKind,
+    ActionOperation, FileOperation, KindResolveRequest, KindResolveResponse, UpdateRequestKind,
 };
 pub(super) use crate::app::{clear_process_shutdown_request, process_shutdown_requested};
 pub(super) use crate::app::{
-    egui, ActionRequest, ActionResponse, AppRuntimeState, AppShellState, CacheStateBundle,
-    EntryKind, FileListDialogKind, FileListManager, FileListRequest, FileListResponse,
-    FlistWalkerApp, HighlightCacheKey, HighlightCacheState, IgnoreMatcherCacheState,
-    IndexBuildResult, IndexEntry, IndexRequest, IndexResponse, IndexSource, LaunchSettings,
-    PreviewRequest, PreviewResponse, QueryState, ResultSortMode, ResultSortScope, RootBrowserState,
-    RuntimeUiState, SavedTabState, SavedWindowGeometry, SearchCoordinator, SearchRequest,
-    SearchResponse, SortMetadataCacheState, SortMetadataRequest, SortMetadataResponse,
-    TabAccentColor, TabAccentPalette, TabDragState, TabSessionState, UpdateRequest, UpdateResponse,
-    WorkerBus, WorkerRuntime,
+    egui, ActionKind, ActionRequest, ActionResponse, AppRuntimeState, AppShellState,
+    CacheStateBundle, EntryKind, FileListDialogKind, FileListManager, FileListRequest,
+    FileListResponse, FlistWalkerApp, HighlightCacheKey, HighlightCacheState, HighlightedDisplay,
+    IgnoreMatcherCacheState, IndexBuildResult, IndexEntry, IndexRequest, IndexResponse,
+    IndexSource, LaunchSettings, PreviewRequest, PreviewResponse, QueryState, ResultSortMode,
+    ResultSortScope, RootBrowserState, RuntimeUiState, SavedTabState, SavedWindowGeometry,
+    SearchCoordinator, SearchRequest, SearchResponse, SmartFilter, SmartFilterMode,
+    SortMetadataCacheState, SortMetadataRequest, SortMetadataResponse, TabAccentColor,
+    TabAccentPalette, TabDragState, TabSessionState, UpdateRequest, UpdateResponse, WorkerBus,
+    WorkerRuntime,
 };
 pub(super) use crate::app::{render_tabs, request_process_shutdown, spawn_kind_resolver_worker};
 pub(super) use crate::entry::Entry;
@@ -74,6 +75,7 @@ mod shortcut_navigation;
 mod shortcut_pin_focus;
 mod shortcut_root_history;
 mod shutdown;
+mod smart_filters;
 mod tab_background_responses;
 mod tab_contract;
 mod tab_drag;
@@ -23,6 +23,11 @@ fn queued_request_for_tab_exists_is_true_for_matching_tab() {
         use_filelist: true,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     });
 
     assert!(app.queued_request_for_tab_exists(tab_id));
@@ -90,6 +95,24 @@ fn should_refresh_incremental_search_is_true_for_large_delta_after_interval() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn should_refresh_incremental_search_skips_throttle_when_eager_partial_search_is_enabled() {
+    let root = test_root("pipeline-refresh-eager-partial");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "main".to_string());
+    app.shell.indexing.in_progress = true;
+    app.shell.indexing.incremental_filtered_entries = vec![unknown_entry(root.join("a.txt"))];
+    app.shell.indexing.last_search_snapshot_len = 0;
+    app.shell.indexing.last_incremental_results_refresh = Instant::now();
+
+    assert!(!app.should_refresh_incremental_search());
+    app.shell.runtime.eager_partial_search = true;
+    assert!(app.should_refresh_incremental_search());
+    app.shell.indexing.last_search_snapshot_len = 1;
+    assert!(!app.should_refresh_incremental_search());
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn regression_ignore_list_is_applied_when_files_and_folders_are_both_enabled() {
     let root = test_root("ignore-list-fast-path-regression");
@@ -154,3 +177,141 @@ fn regression_ignore_list_toggle_off_keeps_all_entries_visible() {
     );
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn size_filter_inputs_refilter_files_without_reindexing_and_keep_folders() {
+    let root = test_root("size-filter");
+    fs::create_dir_all(root.join("dir")).expect("create dir");
+    let small = root.join("small.txt");
+    let large = root.join("large.bin");
+    fs::write(&small, "tiny").expect("write small");
+    fs::write(&large, vec![0u8; 4096]).expect("write large");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = bounded_request_channel::<IndexRequest>(2);
+    app.shell.indexing.tx = tx;
+    app.shell.runtime.all_entries = Arc::new(vec![
+        file_entry(small.clone()),
+        file_entry(large.clone()),
+        dir_entry(root.join("dir")),
+    ]);
+    app.shell.runtime.index.entries.clear();
+    app.shell.runtime.index.source = IndexSource::Walker;
+    app.shell.indexing.in_progress = false;
+
+    app.shell.ui.size_filter_min_text = "1 KB".to_string();
+    app.apply_size_filter_inputs();
+
+    assert_eq!(app.shell.runtime.size_filter_min_bytes, Some(1024));
+    // Sizes are read by the worker; until they arrive the files stay pending.
+    assert_eq!(
+        app.shell
+            .runtime
+            .entries
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>(),
+        vec![root.join("dir")]
+    );
+    assert!(app.shell.worker_bus.size.in_progress);
+    for _ in 0..200 {
+        app.poll_size_response();
+        if !app.shell.worker_bus.size.in_progress {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(
+        app.shell
+            .runtime
+            .entries
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>(),
+        vec![large.clone(), root.join("dir")]
+    );
+
+    app.shell.ui.size_filter_min_text.clear();
+    app.shell.ui.size_filter_max_text = "1 K".to_string();
+    app.apply_size_filter_inputs();
+
+    assert_eq!(
+        app.shell
+            .runtime
+            .entries
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>(),
+        vec![small, root.join("dir")]
+    );
+    assert!(!app.shell.worker_bus.size.in_progress);
+    assert!(rx.try_recv().is_err());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn dotfile_toggles_hide_root_and_nested_dotfiles_independently_without_reindexing() {
+    let root = test_root("dotfile-filter");
+    fs::create_dir_all(&root).expect("create root");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = bounded_request_channel::<IndexRequest>(2);
+    app.shell.indexing.tx = tx;
+    let root_dotfile = root.join(".env");
+    let root_dotdir_child = root.join(".config").join("app.toml");
+    let nested_dotfile = root.join("src").join(".cache");
+    let plain = root.join("src").join("main.rs");
+    app.shell.runtime.all_entries = Arc::new(vec![
+        file_entry(root_dotfile.clone()),
+        file_entry(root_dotdir_child.clone()),
+        file_entry(nested_dotfile.clone()),
+        file_entry(plain.clone()),
+    ]);
+    app.shell.runtime.index.entries.clear();
+    app.shell.runtime.index.source = IndexSource::Walker;
+    app.shell.indexing.in_progress = false;
+    let visible = |app: &FlistWalkerApp| {
+        app.shell
+            .runtime
+            .entries
+            .iter()
+            .map(|entry| entry.path.clone())
+            .collect::<Vec<_>>()
+    };
+
+    app.set_dotfile_filters(true, false);
+    assert_eq!(visible(&app), vec![nested_dotfile.clone(), plain.clone()]);
+
+    app.set_dotfile_filters(false, true);
+    assert_eq!(
+        visible(&app),
+        vec![root_dotfile, root_dotdir_child, plain.clone()]
+    );
+
+    app.set_dotfile_filters(true, true);
+    assert_eq!(visible(&app), vec![plain]);
+    assert!(rx.try_recv().is_err());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn index_progress_fraction_uses_filelist_total_and_falls_back_to_indeterminate() {
+    let root = test_root("pipeline-index-progress");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    reset_index_request_state_for_test(&mut app);
+    assert_eq!(app.index_progress_fraction(), None);
+
+    app.shell.indexing.begin_active_refresh(5, false);
+    assert_eq!(app.index_progress_fraction(), Some(None));
+
+    app.shell.indexing.expected_total = Some(4);
+    app.shell.indexing.pending_entries.push_back(IndexEntry {
+        path: root.join("a.txt"),
+        kind: EntryKind::file(),
+        kind_known: true,
+    });
+    assert_eq!(app.index_progress_fraction(), Some(Some(0.25)));
+
+    app.shell.indexing.begin_active_refresh(6, false);
+    assert_eq!(app.shell.indexing.expected_total, None);
+    let _ = fs::remove_dir_all(&root);
+}
@@ -425,12 +425,51 @@ fn query_history_persist_can_be_disabled_via_env() {
 
     let saved = FlistWalkerApp::load_ui_state_from_path(&ui_state_path);
     assert!(saved.query_history.is_empty());
+    assert!(saved.last_query.is_none());
 
     let launch = FlistWalkerApp::load_launch_settings_from_path_with_history_persist_disabled(
         &ui_state_path,
         true,
     );
     assert!(launch.query_history.is_empty());
+    assert!(launch.last_query.is_none());
+
+    let _ = fs::remove_file(&ui_state_path);
+    let _ = fs::remove_dir_all(&root);
+    let _ = fs::remove_dir_all(&ui_state_dir);
+}
+
+#[test]
+fn last_query_round_trips_through_ui_state_and_seeds_fresh_launch() {
+    let root = test_root("last-query-restore");
+    let ui_state_dir = test_root("last-query-restore-dir");
+    let ui_state_path = ui_state_dir.join(".flistwalker_ui_state.json");
+    fs::create_dir_all(&root).expect("create root");
+    fs::create_dir_all(&ui_state_dir).expect("create ui state dir");
+
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.ui.ui_state_dirty = false;
+    app.shell.runtime.query_state.query = "report 2024".to_string();
+    app.mark_query_edited();
+    assert!(app.shell.ui.ui_state_dirty);
+    app.save_ui_state_to_path_with_history_persist_disabled(&ui_state_path, false);
+
+    let launch = FlistWalkerApp::load_launch_settings_from_path_with_history_persist_disabled(
+        &ui_state_path,
+        false,
+    );
+    let last_query = launch.last_query.as_deref();
+    assert_eq!(last_query, Some("report 2024"));
+    assert_eq!(
+        FlistWalkerApp::startup_query(String::new(), last_query, false, true),
+        "report 2024"
+    );
+    assert_eq!(
+        FlistWalkerApp::startup_query("cli".to_string(), last_query, false, true),
+        "cli"
+    );
+    assert!(FlistWalkerApp::startup_query(String::new(), last_query, true, true).is_empty());
+    assert!(FlistWalkerApp::startup_query(String::new(), last_query, false, false).is_empty());
 
     let _ = fs::remove_file(&ui_state_path);
     let _ = fs::remove_dir_all(&root);
@@ -1,9 +1,10 @@
 use super::*;
 use crate::app::render::{
-    RenderCommand, RenderFileListDialogCommand, RenderTabBarCommand, RenderTopActionCommand,
-    RenderUpdateDialogCommand,
+    RenderCommand, RenderFileListDialogCommand, RenderResultMenuCommand, RenderTabBarCommand,
+    RenderTopActionCommand, RenderUpdateDialogCommand,
 };
 use crate::app::render_theme;
+use crate::app::worker_tasks::process_action_request_with;
 use crate::app::{render_dialogs, render_panels};
 use crate::entry::EntryDisplayKind;
 use crate::updater::UpdateCandidate;
@@ -223,6 +224,28 @@ fn results_scroll_enabled_is_false_when_preview_resize_is_active() {
     assert!(!FlistWalkerApp::results_scroll_enabled(true));
 }
 
+#[test]
+fn result_directory_grouping_splits_folder_and_indents_name_with_shifted_highlights() {
+    assert_eq!(
+        FlistWalkerApp::result_directory_group("web/app/index.html"),
+        ("web/app", 8)
+    );
+    assert_eq!(
+        FlistWalkerApp::result_directory_group("docs\\日本語\\index.html"),
+        ("docs\\日本語", 9)
+    );
+    assert_eq!(
+        FlistWalkerApp::result_directory_group("index.html"),
+        ("", 0)
+    );
+
+    let (display, positions) =
+        FlistWalkerApp::collapse_result_directory("web/app/index.html", &[0, 1, 8, 9, 10]);
+
+    assert_eq!(display, "    index.html");
+    assert_eq!(positions, vec![4, 5, 6]);
+}
+
 #[test]
 fn result_row_text_pos_is_left_aligned_and_vertically_centered() {
     let inner = egui::Rect::from_min_max(egui::pos2(8.0, 10.0), egui::pos2(208.0, 34.0));
@@ -234,6 +257,19 @@ fn result_row_text_pos_is_left_aligned_and_vertically_centered() {
     assert_eq!(pos.y, inner.center().y - (galley_size.y * 0.5));
 }
 
+#[test]
+fn result_score_label_matches_cli_format_and_right_aligns() {
+    assert_eq!(FlistWalkerApp::result_score_label(42.0), "[  42.0]");
+    assert_eq!(FlistWalkerApp::result_score_label(1234.56), "[1234.6]");
+
+    let inner = egui::Rect::from_min_max(egui::pos2(8.0, 10.0), egui::pos2(208.0, 34.0));
+    let galley_size = egui::vec2(40.0, 14.0);
+    let pos = FlistWalkerApp::result_score_pos(inner, 64.0, galley_size);
+
+    assert_eq!(pos.x + galley_size.x, inner.left() + 64.0);
+    assert_eq!(pos.y, inner.center().y - (galley_size.y * 0.5));
+}
+
 #[test]
 fn render_theme_selected_fill_preserves_light_and_dark_rgb_contract() {
     assert_eq!(
@@ -531,6 +567,35 @@ fn render_panels_and_dialogs_execute_in_headless_frame() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn wrapped_result_rows_render_long_paths_in_narrow_window() {
+    let root = test_root("render-wrap-rows");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let long = root
+        .join("very-long-directory-name-segment-".repeat(6))
+        .join("deeply-nested-file-name.txt");
+    app.shell.runtime.results = vec![(long, 12.0), (root.join("short.txt"), 3.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.shell.ui.wrap_result_rows = true;
+    app.shell.ui.show_result_scores = true;
+
+    let ctx = egui::Context::default();
+    ctx.begin_pass(egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(320.0, 480.0),
+        )),
+        ..Default::default()
+    });
+    render_panels::render_central_panel(&mut app, &ctx);
+    let _ = ctx.end_pass();
+
+    assert!(app.shell.ui.pending_render_commands.is_empty());
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn disabled_emacs_keybindings_prevent_textedit_ctrl_k_from_editing_query() {
     let root = test_root("render-disabled-ctrl-k");
@@ -567,6 +632,53 @@ fn disabled_emacs_keybindings_prevent_textedit_ctrl_k_from_editing_query() {
     let _ = fs::remove_dir_all(&root);
 }
 
+fn run_ctrl_y_query_frame(app: &mut FlistWalkerApp, ctx: &egui::Context) -> bool {
+    let mods = egui::Modifiers {
+        ctrl: true,
+        command: true,
+        ..Default::default()
+    };
+    ctx.begin_pass(egui::RawInput {
+        modifiers: mods,
+        events: vec![egui::Event::Key {
+            key: egui::Key::Y,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: mods,
+        }],
+        ..Default::default()
+    });
+    ctx.memory_mut(|m| m.request_focus(app.shell.ui.query_input_id));
+    render_panels::render_top_panel(app, ctx);
+    let output = ctx.end_pass();
+    output
+        .viewport_output
+        .get(&egui::ViewportId::ROOT)
+        .is_some_and(|viewport| {
+            viewport
+                .commands
+                .contains(&egui::ViewportCommand::RequestPaste)
+        })
+}
+
+#[test]
+fn ctrl_y_requests_clipboard_paste_only_when_kill_buffer_is_empty() {
+    let root = test_root("render-ctrl-y-clipboard");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.query_state.query = "alpha".to_string();
+    let ctx = egui::Context::default();
+
+    assert!(run_ctrl_y_query_frame(&mut app, &ctx));
+    assert_eq!(app.shell.runtime.query_state.query, "alpha");
+
+    app.shell.runtime.query_state.kill_buffer = " beta".to_string();
+    assert!(!run_ctrl_y_query_frame(&mut app, &ctx));
+    assert_eq!(app.shell.runtime.query_state.query, "alpha beta");
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn run_ui_frame_executes_render_facade_in_headless_frame() {
     let root = test_root("render-run-ui-frame");
@@ -681,3 +793,255 @@ fn tab_close_button_hover_visuals_make_close_hit_area_visible() {
     assert_eq!(disabled.stroke, egui::Stroke::NONE);
     assert!(disabled.text.r() < palette.foreground.r());
 }
+
+#[test]
+fn results_empty_state_distinguishes_indexing_from_zero_matches() {
+    let root = test_root("results-empty-state");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results.clear();
+    app.shell.search.set_in_progress(false);
+
+    app.shell.indexing.in_progress = true;
+    assert_eq!(
+        app.results_empty_state_message().as_deref(),
+        Some("No entries indexed yet")
+    );
+
+    app.shell.indexing.in_progress = false;
+    app.shell.runtime.all_entries = Arc::new(vec![file_entry(root.join("main.rs"))]);
+    app.shell.runtime.query_state.query = "zzz".to_string();
+    assert_eq!(
+        app.results_empty_state_message().as_deref(),
+        Some("No matches for 'zzz'")
+    );
+
+    app.shell.runtime.results = vec![(root.join("main.rs"), 0.0)];
+    assert!(app.results_empty_state_message().is_none());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn results_activity_tracks_indexing_and_search_progress() {
+    let root = test_root("results-activity");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.indexing.in_progress = false;
+    app.shell.search.set_in_progress(false);
+    assert!(!app.results_activity_in_progress());
+
+    app.shell.indexing.in_progress = true;
+    assert!(app.results_activity_in_progress());
+
+    app.shell.indexing.in_progress = false;
+    app.shell.search.set_in_progress(true);
+    assert!(app.results_activity_in_progress());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn open_active_filelist_uses_filelist_source_path_only() {
+    let root = test_root("open-active-filelist");
+    fs::create_dir_all(&root).expect("create dir");
+    let filelist = root.join("FileList.txt");
+    fs::write(&filelist, "a.txt\n").expect("write filelist");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+
+    app.shell.runtime.index.source = IndexSource::Walker;
+    let mut opened = Vec::new();
+    app.open_active_filelist_with(|path| {
+        opened.push(path.to_path_buf());
+        Ok(())
+    });
+    assert!(opened.is_empty());
+    assert_eq!(app.shell.runtime.notice, "No FileList is in use");
+
+    app.shell.runtime.index.source = IndexSource::FileList(filelist.clone());
+    app.open_active_filelist_with(|path| {
+        opened.push(path.to_path_buf());
+        Ok(())
+    });
+    assert_eq!(opened, vec![filelist]);
+    assert!(app.shell.runtime.notice.starts_with("FileList opened:"));
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn dispatch_result_menu_command_targets_right_clicked_row() {
+    let root = test_root("render-command-result-menu");
+    fs::create_dir_all(root.join("sub")).expect("create dir");
+    let file = root.join("a.txt");
+    let dir = root.join("sub");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(file.clone(), 0.0), (dir.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(1);
+    app.set_entry_kind(&file, EntryKind::file());
+    app.set_entry_kind(&dir, EntryKind::dir());
+    let ctx = egui::Context::default();
+
+    app.queue_render_command(RenderCommand::ResultMenu {
+        row: 0,
+        command: RenderResultMenuCommand::TogglePin,
+    });
+    app.dispatch_render_commands(&ctx);
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+    assert!(app.shell.runtime.pinned_paths.contains(&file));
+
+    app.queue_render_command(RenderCommand::ResultMenu {
+        row: 0,
+        command: RenderResultMenuCommand::SetAsRoot,
+    });
+    app.dispatch_render_commands(&ctx);
+    assert_eq!(app.shell.runtime.notice, "Set as Root requires a folder");
+    assert_eq!(app.shell.runtime.root, root);
+
+    app.queue_render_command(RenderCommand::ResultMenu {
+        row: 0,
+        command: RenderResultMenuCommand::CopyName,
+    });
+    app.dispatch_render_commands(&ctx);
+    assert_eq!(app.shell.runtime.notice, "Copied name: a.txt");
+
+    app.queue_render_command(RenderCommand::ResultMenu {
+        row: 1,
+        command: RenderResultMenuCommand::SetAsRoot,
+    });
+    app.dispatch_render_commands(&ctx);
+    assert_eq!(app.shell.runtime.root, dir);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn dispatch_result_menu_command_uses_clicked_row_unless_it_is_pinned() {
+    let root = test_root("render-command-result-menu-pins");
+    fs::create_dir_all(&root).expect("create dir");
+    let pinned = [root.join("a.txt"), root.join("b.txt")];
+    let unpinned = root.join("c.txt");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![
+        (pinned[0].clone(), 0.0),
+        (pinned[1].clone(), 0.0),
+        (unpinned.clone(), 0.0),
+    ];
+    app.shell.runtime.pinned_paths = pinned.iter().cloned().collect();
+    let ctx = egui::Context::default();
+
+    app.queue_render_command(RenderCommand::ResultMenu {
+        row: 2,
+        command: RenderResultMenuCommand::CopyPath,
+    });
+    app.dispatch_render_commands(&ctx);
+    assert_eq!(
+        app.shell.runtime.notice,
+        format!("Copied path: {}", normalize_path_for_display(&unpinned))
+    );
+
+    app.queue_render_command(RenderCommand::ResultMenu {
+        row: 0,
+        command: RenderResultMenuCommand::CopyPath,
+    });
+    app.dispatch_render_commands(&ctx);
+    assert_eq!(app.shell.runtime.notice, "Copied 2 paths to clipboard");
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn dispatch_result_menu_trash_removes_clicked_row_everywhere() {
+    let root = test_root("render-command-result-menu-trash");
+    fs::create_dir_all(&root).expect("create dir");
+    let kept = root.join("keep.txt");
+    let trashed = root.join("trash.txt");
+    fs::write(&kept, "k").expect("write kept");
+    fs::write(&trashed, "t").expect("write trashed");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (action_tx_req, action_rx_req) = bounded_request_channel::<ActionRequest>(8);
+    let (action_tx_res, action_rx_res) = mpsc::channel::<ActionResponse>();
+    app.shell.worker_bus.action.tx = action_tx_req;
+    app.shell.worker_bus.action.rx = action_rx_res;
+    let entries = vec![file_entry(kept.clone()), file_entry(trashed.clone())];
+    app.shell.runtime.all_entries = Arc::new(entries.clone());
+    app.shell.runtime.entries = Arc::new(entries);
+    app.shell.runtime.results = vec![(kept.clone(), 0.0), (trashed.clone(), 0.0)];
+    app.shell.runtime.pinned_paths.insert(kept.clone());
+    app.shell.runtime.basket = vec![trashed.clone()];
+    let ctx = egui::Context::default();
+
+    app.queue_render_command(RenderCommand::ResultMenu {
+        row: 1,
+        command: RenderResultMenuCommand::Trash,
+    });
+    app.dispatch_render_commands(&ctx);
+
+    assert!(trashed.exists(), "trash must run in the action worker");
+    let req = action_rx_req.try_recv().expect("trash request");
+    assert_eq!(req.paths, vec![trashed.clone()]);
+    assert_eq!(req.operation, ActionOperation::Trash);
+    action_tx_res
+        .send(process_action_request_with(req, |_| {
+            unreachable!("trash must not launch paths")
+        }))
+        .expect("send trash response");
+    app.poll_action_response();
+
+    assert!(!trashed.exists());
+    assert_eq!(app.shell.runtime.notice, "Moved 1 item(s) to trash");
+    assert_eq!(app.shell.runtime.results, vec![(kept.clone(), 0.0)]);
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+    assert_eq!(app.shell.runtime.all_entries.len(), 1);
+    assert!(app.shell.runtime.basket.is_empty());
+    assert!(app.shell.runtime.pinned_paths.contains(&kept));
+
+    app.undo_last_operation();
+    assert!(app
+        .shell
+        .runtime
+        .notice
+        .starts_with("Trash cannot be undone"));
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn entry_type_glyph_maps_kinds_and_extension_categories() {
+    assert_eq!(
+        render_theme::entry_type_glyph(Path::new("src"), EntryDisplayKind::Dir),
+        "📁"
+    );
+    assert_eq!(
+        render_theme::entry_type_glyph(Path::new("photo.JPG"), EntryDisplayKind::File),
+        "🖼"
+    );
+    assert_eq!(
+        render_theme::entry_type_glyph(Path::new("backup.tar.gz"), EntryDisplayKind::File),
+        "📦"
+    );
+    assert_eq!(
+        render_theme::entry_type_glyph(Path::new("setup.exe"), EntryDisplayKind::File),
+        "⚙"
+    );
+    assert_eq!(
+        render_theme::entry_type_glyph(Path::new("notes.txt"), EntryDisplayKind::File),
+        "📄"
+    );
+}
+
+#[test]
+fn extension_breakdown_text_ranks_top_extensions_and_counts_the_rest() {
+    let paths = [
+        "src/main.rs",
+        "src/lib.RS",
+        "src/app.rs",
+        "README.md",
+        "docs/spec.md",
+        "Cargo.toml",
+        "Makefile",
+        "logo.png",
+    ]
+    .map(PathBuf::from);
+
+    let text = FlistWalkerApp::extension_breakdown_text(paths.iter().map(PathBuf::as_path), 3);
+    assert_eq!(text.as_deref(), Some("rs: 3, md: 2, (none): 1, +2 more"));
+    assert_eq!(
+        FlistWalkerApp::extension_breakdown_text(std::iter::empty(), 3),
+        None
+    );
+}
@@ -463,3 +463,165 @@ fn manage_root_list_editing_default_root_follows_replacement_on_apply() {
     );
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn reset_settings_restores_defaults_and_optionally_clears_lists() {
+    let _scope = saved_roots_test_scope("reset-settings");
+    let root = test_root("reset-settings-root");
+    fs::create_dir_all(&root).expect("create root");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.features.root_browser.default_root = Some(root.clone());
+    app.shell.features.root_browser.saved_roots = vec![root.clone()];
+    app.shell.ui.set_show_preview(false);
+    app.shell.ui.set_preview_panel_width(900.0);
+    app.shell.ui.render_markdown_preview = false;
+    app.shell
+        .runtime
+        .query_state
+        .query_history
+        .push_back("main".to_string());
+
+    app.reset_settings(false);
+
+    assert!(app.shell.features.root_browser.default_root.is_none());
+    assert!(app.shell.ui.show_preview());
+    assert_eq!(
+        app.shell.ui.preview_panel_width(),
+        FlistWalkerApp::DEFAULT_PREVIEW_PANEL_WIDTH
+    );
+    assert!(app.shell.ui.render_markdown_preview);
+    assert_eq!(
+        app.shell.features.root_browser.saved_roots,
+        vec![root.clone()]
+    );
+    assert_eq!(app.shell.runtime.query_state.query_history.len(), 1);
+    assert_eq!(app.shell.runtime.notice, "Settings reset to defaults");
+
+    app.reset_settings(true);
+
+    assert!(app.shell.features.root_browser.saved_roots.is_empty());
+    assert!(FlistWalkerApp::load_saved_roots().is_empty());
+    assert!(app.shell.runtime.query_state.query_history.is_empty());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn reset_settings_reapplies_ignore_list_and_drops_last_query_with_history() {
+    let _scope = saved_roots_test_scope("reset-settings-filters");
+    let root = test_root("reset-settings-filters-root");
+    fs::create_dir_all(&root).expect("create root");
+    let ignored = root.join("old.txt");
+    let kept = root.join("keep.txt");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.all_entries =
+        Arc::new(vec![file_entry(ignored.clone()), file_entry(kept.clone())]);
+    app.shell.runtime.index.entries.clear();
+    app.shell.runtime.index.source = IndexSource::Walker;
+    app.shell.indexing.in_progress = false;
+    app.shell.runtime.ignore_list_terms = Arc::new(vec!["old".to_string()]);
+    app.shell.ui.ignore_list_enabled = false;
+    app.apply_entry_filters(false);
+    assert!(app.shell.runtime.entries.iter().any(|e| e.path == ignored));
+
+    app.reset_settings(false);
+    assert!(app.shell.ui.ignore_list_enabled);
+    assert_eq!(app.shell.runtime.entries.as_ref(), &[file_entry(kept)]);
+
+    app.shell.runtime.query_state.query = "keep".to_string();
+    app.reset_settings(true);
+    assert!(app.shell.runtime.query_state.query.is_empty());
+    let ui_state_path = root.join("ui_state.json");
+    app.save_ui_state_to_path(&ui_state_path);
+    let saved = FlistWalkerApp::load_ui_state_from_path(&ui_state_path);
+    assert_eq!(saved.last_query, None);
+    assert_eq!(saved.results_panel_width, None);
+    assert_eq!(
+        saved.preview_panel_width,
+        Some(FlistWalkerApp::DEFAULT_PREVIEW_PANEL_WIDTH)
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn search_all_saved_roots_indexes_other_roots_and_displays_them_relative_to_owner() {
+    let root = test_root("search-all-saved-roots-active");
+    let other = test_root("search-all-saved-roots-other");
+    fs::create_dir_all(&root).expect("create root");
+    fs::create_dir_all(&other).expect("create other root");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.features.root_browser.saved_roots = vec![root.clone(), other.clone()];
+    let (tx, rx) = bounded_request_channel::<IndexRequest>(2);
+    app.shell.indexing.tx = tx;
+    reset_index_request_state_for_test(&mut app);
+
+    app.set_search_all_saved_roots(true);
+
+    let req = rx.try_recv().expect("all saved roots refresh");
+    assert_eq!(req.root, root);
+    assert_eq!(req.extra_roots, vec![other.clone()]);
+    let outside = other.join("notes").join("todo.txt");
+    let inside = root.join("main.rs");
+    let paths = vec![outside.clone()];
+    assert_eq!(app.owning_root_for_path(&outside), other.as_path());
+    assert_eq!(app.action_roots_for_paths(&paths), vec![other.clone()]);
+    assert_eq!(app.first_action_path_outside_root(&paths), None);
+    let mixed = vec![inside.clone(), outside.clone()];
+    assert_eq!(
+        app.action_roots_for_paths(&mixed),
+        vec![root.clone(), other.clone()]
+    );
+    assert_eq!(app.first_action_path_outside_root(&mixed), None);
+    let escaped = vec![outside.clone(), other.join("..").join("escape.txt")];
+    assert_eq!(
+        app.first_action_path_outside_root(&escaped),
+        Some(other.join("..").join("escape.txt"))
+    );
+
+    app.set_search_all_saved_roots(false);
+
+    let req = rx.try_recv().expect("single root refresh");
+    assert!(req.extra_roots.is_empty());
+    assert_eq!(app.owning_root_for_path(&outside), root.as_path());
+    assert_eq!(app.first_action_path_outside_root(&paths), Some(outside));
+    let _ = fs::remove_dir_all(&root);
+    let _ = fs::remove_dir_all(&other);
+}
+
+#[test]
+fn manage_root_list_appends_added_roots_and_keeps_comments_in_roots_file() {
+    let scope = saved_roots_test_scope("manage-root-list-order-settings");
+    let root = test_root("manage-root-list-order");
+    let zeta = root.join("zeta");
+    let alpha = root.join("alpha");
+    let middle = root.join("middle");
+    for dir in [&zeta, &alpha, &middle] {
+        fs::create_dir_all(dir).expect("create root dir");
+    }
+    let middle_canonical =
+        normalize_windows_path_buf(middle.canonicalize().unwrap_or_else(|_| middle.clone()));
+    let roots_file = FlistWalkerApp::saved_roots_file_path_in(&scope.settings_base);
+    fs::write(
+        &roots_file,
+        format!("# favourites\n{}\n\n{}\n", zeta.display(), alpha.display()),
+    )
+    .expect("write roots file");
+    let mut app = FlistWalkerApp::new(zeta.clone(), 50, String::new());
+    app.shell.features.root_browser.saved_roots = FlistWalkerApp::load_saved_roots();
+    assert_eq!(
+        app.shell.features.root_browser.saved_roots,
+        vec![zeta.clone(), alpha.clone()]
+    );
+
+    app.open_manage_root_list();
+    app.shell.features.root_browser.manage_list.input_path = middle.to_string_lossy().to_string();
+    app.add_manage_root_list_input();
+    app.apply_manage_root_list_changes();
+
+    let expected = vec![zeta.clone(), alpha.clone(), middle_canonical.clone()];
+    assert_eq!(app.shell.features.root_browser.saved_roots, expected);
+    assert_eq!(FlistWalkerApp::load_saved_roots(), expected);
+    let text = fs::read_to_string(&roots_file).expect("read roots file");
+    assert!(text.starts_with("# favourites\n"));
+    assert!(text.ends_with(&format!("{}\n", middle_canonical.display())));
+    let _ = fs::remove_dir_all(&root);
+}
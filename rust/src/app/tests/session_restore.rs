@@ -1,4 +1,5 @@
 use super::*;
+use std::ffi::OsString;
 
 fn canonical_or_self(path: &Path) -> PathBuf {
     path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
@@ -62,6 +63,34 @@ fn choose_startup_root_prefers_last_root_over_default_root() {
     assert_eq!(chosen, last_root);
 }
 
+#[test]
+fn startup_root_env_value_accepts_directories_and_rejects_invalid_values() {
+    let root = test_root("startup-root-env");
+    fs::create_dir_all(&root).expect("create root");
+    let file = root.join("not-a-dir.txt");
+    fs::write(&file, "x").expect("write file");
+
+    assert_eq!(FlistWalkerApp::startup_root_from_env_value(None), Ok(None));
+    assert_eq!(
+        FlistWalkerApp::startup_root_from_env_value(Some(OsString::new())),
+        Ok(None)
+    );
+    let resolved = FlistWalkerApp::startup_root_from_env_value(Some(root.clone().into()))
+        .expect("valid root")
+        .expect("root");
+    assert_eq!(canonical_or_self(&resolved), canonical_or_self(&root));
+    let err = FlistWalkerApp::startup_root_from_env_value(Some(file.clone().into()))
+        .expect_err("file is rejected");
+    assert!(
+        err.starts_with("Ignored FLISTWALKER_ROOT: not a directory:"),
+        "{err}"
+    );
+    assert!(
+        FlistWalkerApp::startup_root_from_env_value(Some(root.join("missing").into())).is_err()
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn choose_startup_root_prefers_default_root_when_restore_tabs_is_disabled() {
     let fallback_root = PathBuf::from("/fallback");
@@ -445,6 +474,7 @@ fn background_tab_activation_consumes_pending_restore_refresh_once() {
             request_id: preview_request_id,
             path: indexed_file.clone(),
             preview: "preview-body".to_string(),
+            quick_look: false,
         })
         .expect("send background preview response");
     index_res_tx
@@ -137,6 +137,33 @@ fn ctrl_o_browse_uses_existing_parent_when_current_root_is_missing() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn ctrl_shift_a_copies_all_results_without_pinning_them() {
+    let root = test_root("shortcut-copy-all-results");
+    fs::create_dir_all(&root).expect("create dir");
+    let first = root.join("a.txt");
+    let second = root.join("b.txt");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "query".to_string());
+    app.shell.runtime.results = vec![(first, 0.0), (second, 0.0)];
+    app.shell.runtime.current_row = Some(0);
+
+    run_shortcuts_frame(
+        &mut app,
+        true,
+        vec![egui::Event::Key {
+            key: egui::Key::A,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: gui_shortcut_modifiers(true),
+        }],
+    );
+
+    assert!(app.shell.runtime.pinned_paths.is_empty());
+    assert_eq!(app.shell.runtime.notice, "Copied 2 results to clipboard");
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn ctrl_shift_o_browses_in_new_tab() {
     let root = test_root("shortcut-ctrl-shift-o");
@@ -433,3 +460,83 @@ fn deferred_copy_shortcut_copies_selected_path_even_with_query_text() {
     )));
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn period_repeats_last_copy_action_on_new_current_row() {
+    let root = test_root("shortcut-repeat-last-action");
+    fs::create_dir_all(&root).expect("create dir");
+    let first = root.join("first.txt");
+    let second = root.join("second.txt");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(first, 0.0), (second, 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    let period = || egui::Event::Key {
+        key: egui::Key::Period,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    };
+
+    run_shortcuts_frame(&mut app, false, vec![period()]);
+    assert_eq!(app.shell.runtime.notice, "No action to repeat yet");
+
+    let ctx = egui::Context::default();
+    app.copy_current_name(&ctx);
+    assert_eq!(app.shell.runtime.last_action, Some(ActionKind::CopyName));
+    app.shell.runtime.current_row = Some(1);
+
+    run_shortcuts_frame(&mut app, false, vec![period()]);
+    assert_eq!(app.shell.runtime.notice, "Copied name: second.txt");
+
+    app.shell.runtime.current_row = Some(0);
+    run_shortcuts_frame(&mut app, true, vec![period()]);
+    assert_eq!(app.shell.runtime.notice, "Copied name: second.txt");
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn typing_with_results_focused_jumps_to_matching_file_name() {
+    let root = test_root("shortcut-type-ahead");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![
+        (root.join("src").join("alpha.rs"), 0.0),
+        (root.join("src").join("main.rs"), 0.0),
+        (root.join("docs").join("manual.md"), 0.0),
+    ];
+    app.shell.runtime.current_row = Some(0);
+
+    run_shortcuts_frame(&mut app, false, vec![egui::Event::Text("ma".to_string())]);
+    assert_eq!(app.shell.runtime.current_row, Some(1));
+    assert_eq!(app.active_type_ahead(Instant::now()), Some("ma"));
+
+    run_shortcuts_frame(&mut app, false, vec![egui::Event::Text("nl".to_string())]);
+    assert_eq!(app.shell.runtime.current_row, Some(2));
+
+    run_shortcuts_frame(&mut app, true, vec![egui::Event::Text("a".to_string())]);
+    assert_eq!(app.shell.runtime.current_row, Some(2));
+    assert_eq!(app.shell.ui.type_ahead_buffer, "manl");
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn type_ahead_resets_after_idle_and_leaves_leading_period_to_repeat() {
+    let root = test_root("type-ahead-reset");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(root.join("main.rs"), 0.0), (root.join("zeta.rs"), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    let start = Instant::now();
+
+    assert!(!app.type_ahead_select(".", start));
+    assert!(app.type_ahead_select("z", start));
+    assert_eq!(app.shell.runtime.current_row, Some(1));
+
+    let later = start + FlistWalkerApp::TYPE_AHEAD_IDLE_RESET;
+    assert!(app.active_type_ahead(later).is_none());
+    assert!(app.type_ahead_select("m", later));
+    assert_eq!(app.shell.ui.type_ahead_buffer, "m");
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+    let _ = fs::remove_dir_all(&root);
+}
@@ -246,6 +246,44 @@ fn home_and_end_move_selection_when_query_not_focused() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn ctrl_home_and_ctrl_end_move_selection_even_when_query_focused() {
+    let root = test_root("shortcut-ctrl-home-end-focus");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = (0..5)
+        .map(|i| (root.join(format!("f{i}.txt")), 0.0))
+        .collect();
+    app.shell.runtime.current_row = Some(2);
+
+    run_shortcuts_frame(
+        &mut app,
+        true,
+        vec![egui::Event::Key {
+            key: egui::Key::End,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: gui_shortcut_modifiers(false),
+        }],
+    );
+    assert_eq!(app.shell.runtime.current_row, Some(4));
+
+    run_shortcuts_frame(
+        &mut app,
+        true,
+        vec![egui::Event::Key {
+            key: egui::Key::Home,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: gui_shortcut_modifiers(false),
+        }],
+    );
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn page_up_down_move_selection_when_query_not_focused() {
     let root = test_root("shortcut-page-no-focus");
@@ -284,6 +322,42 @@ fn page_up_down_move_selection_when_query_not_focused() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn shift_page_keys_scroll_preview_without_moving_selection() {
+    let root = test_root("shortcut-preview-scroll");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = (0..30)
+        .map(|i| (root.join(format!("f{i}.txt")), 0.0))
+        .collect();
+    app.shell.runtime.current_row = Some(15);
+    app.shell.ui.set_show_preview(true);
+    app.shell.runtime.preview = "line\n".repeat(500);
+    let shift_page = |key| egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::SHIFT,
+    };
+
+    for query_focused in [true, false] {
+        app.shell.ui.pending_preview_scroll_pages = 0;
+        for _ in 0..2 {
+            run_shortcuts_frame(
+                &mut app,
+                query_focused,
+                vec![shift_page(egui::Key::PageDown)],
+            );
+        }
+        assert_eq!(app.shell.ui.pending_preview_scroll_pages, 2);
+        run_shortcuts_frame(&mut app, query_focused, vec![shift_page(egui::Key::PageUp)]);
+        assert_eq!(app.shell.ui.pending_preview_scroll_pages, 1);
+        assert_eq!(app.shell.runtime.current_row, Some(15));
+    }
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn ctrl_v_and_alt_v_page_move_when_query_not_focused() {
     let root = test_root("shortcut-emacs-page-no-focus");
@@ -469,3 +543,199 @@ fn regression_arrow_keys_move_selection_even_when_query_focused() {
     assert_eq!(app.shell.runtime.current_row, Some(0));
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn space_toggles_quick_look_for_current_row_when_results_are_focused() {
+    let root = test_root("space-quick-look");
+    fs::create_dir_all(&root).expect("create dir");
+    let file = root.join("notes.txt");
+    fs::write(&file, "body").expect("write file");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (preview_tx_req, preview_rx_req) = mpsc::channel::<PreviewRequest>();
+    app.shell.worker_bus.preview.tx = preview_tx_req;
+    app.shell.runtime.results = vec![(file.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.set_entry_kind(&file, EntryKind::file());
+    let space = || egui::Event::Key {
+        key: egui::Key::Space,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    };
+
+    run_shortcuts_frame(&mut app, false, vec![space()]);
+
+    let req = preview_rx_req.try_recv().expect("quick look request");
+    assert!(req.quick_look);
+    assert_eq!(req.path, file);
+    assert!(!req.is_dir);
+    let request_id = app
+        .shell
+        .ui
+        .quick_look
+        .as_ref()
+        .expect("quick look open")
+        .request_id;
+    assert_eq!(request_id, req.request_id);
+
+    app.apply_quick_look_response(PreviewResponse {
+        request_id,
+        path: file.clone(),
+        preview: "full body".to_string(),
+        quick_look: true,
+    });
+    assert_eq!(
+        app.shell
+            .ui
+            .quick_look
+            .as_ref()
+            .and_then(|quick_look| quick_look.text.as_deref()),
+        Some("full body")
+    );
+
+    run_shortcuts_frame(&mut app, false, vec![space()]);
+    assert!(app.shell.ui.quick_look.is_none());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn space_in_focused_query_does_not_open_quick_look() {
+    let root = test_root("space-quick-look-query");
+    fs::create_dir_all(&root).expect("create dir");
+    let file = root.join("notes.txt");
+    fs::write(&file, "body").expect("write file");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(file.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.set_entry_kind(&file, EntryKind::file());
+
+    run_shortcuts_frame(
+        &mut app,
+        true,
+        vec![egui::Event::Key {
+            key: egui::Key::Space,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::NONE,
+        }],
+    );
+
+    assert!(app.shell.ui.quick_look.is_none());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn alt_g_opens_goto_row_and_enter_jumps_to_clamped_one_based_row() {
+    let root = test_root("goto-row");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = (0..5)
+        .map(|i| (root.join(format!("file-{i}.txt")), 0.0))
+        .collect();
+    app.shell.runtime.current_row = Some(0);
+    let key = |key, modifiers| egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers,
+    };
+
+    run_shortcuts_frame(
+        &mut app,
+        true,
+        vec![key(egui::Key::G, egui::Modifiers::ALT)],
+    );
+    assert_eq!(app.shell.ui.goto_row_input.as_deref(), Some(""));
+
+    app.shell.ui.goto_row_input = Some("3".to_string());
+    run_shortcuts_frame(
+        &mut app,
+        false,
+        vec![key(egui::Key::Enter, egui::Modifiers::NONE)],
+    );
+    assert!(app.shell.ui.goto_row_input.is_none());
+    assert_eq!(app.shell.runtime.current_row, Some(2));
+
+    app.open_goto_row();
+    app.shell.ui.goto_row_input = Some("99".to_string());
+    app.apply_goto_row();
+    assert_eq!(app.shell.runtime.current_row, Some(4));
+
+    app.open_goto_row();
+    app.shell.ui.goto_row_input = Some("abc".to_string());
+    app.apply_goto_row();
+    assert_eq!(app.shell.runtime.current_row, Some(4));
+    assert_eq!(app.shell.runtime.notice, "Invalid row number: abc");
+
+    app.open_goto_row();
+    run_shortcuts_frame(
+        &mut app,
+        false,
+        vec![key(egui::Key::Escape, egui::Modifiers::NONE)],
+    );
+    assert!(app.shell.ui.goto_row_input.is_none());
+    assert_eq!(app.shell.runtime.current_row, Some(4));
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn alt_e_cycles_files_and_folders_filter_and_requests_reindex() {
+    let root = test_root("cycle-kind-filter");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    let (tx, rx) = bounded_request_channel::<IndexRequest>(2);
+    app.shell.indexing.tx = tx;
+    app.shell.runtime.use_filelist = false;
+    let alt_e = || egui::Event::Key {
+        key: egui::Key::E,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::ALT,
+    };
+
+    for (files, dirs) in [(true, false), (false, true), (true, true)] {
+        reset_index_request_state_for_test(&mut app);
+        run_shortcuts_frame(&mut app, true, vec![alt_e()]);
+        assert_eq!(
+            (
+                app.shell.runtime.include_files,
+                app.shell.runtime.include_dirs
+            ),
+            (files, dirs)
+        );
+        let req = rx.try_recv().expect("index request should be sent");
+        assert_eq!((req.include_files, req.include_dirs), (files, dirs));
+    }
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn alt_m_toggles_match_mode_and_reissues_search() {
+    let root = test_root("cycle-match-mode");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "ma.n".to_string());
+    let (search_tx, search_rx) = mpsc::channel::<SearchRequest>();
+    app.shell.search.tx = search_tx;
+    app.shell.runtime.entries = Arc::new(vec![Entry::file(root.join("main.rs"))]);
+    let alt_m = || egui::Event::Key {
+        key: egui::Key::M,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::ALT,
+    };
+
+    for use_regex in [true, false] {
+        run_shortcuts_frame(&mut app, true, vec![alt_m()]);
+        assert_eq!(app.shell.runtime.use_regex, use_regex);
+        let expected = if use_regex { "regex" } else { "fuzzy" };
+        assert_eq!(app.shell.runtime.notice, format!("Match mode: {expected}"));
+        let request = search_rx.try_recv().expect("search request should be sent");
+        assert_eq!(request.query_options.use_regex, use_regex);
+    }
+    let _ = fs::remove_dir_all(&root);
+}
@@ -110,6 +110,76 @@ fn tab_can_toggle_pin_and_move_to_next_row_when_configured() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn ctrl_space_toggles_pin_in_place_even_when_tab_moves_to_next_row() {
+    let root = test_root("shortcut-ctrl-space-pin");
+    fs::create_dir_all(&root).expect("create dir");
+    let selected = root.join("picked.txt");
+    let next = root.join("next.txt");
+    fs::write(&selected, "x").expect("write file");
+    fs::write(&next, "y").expect("write next file");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(selected.clone(), 0.0), (next.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.shell.runtime.tab_pin_moves_to_next_row = true;
+    let ctrl_space = || egui::Event::Key {
+        key: egui::Key::Space,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::CTRL,
+    };
+
+    for query_focused in [false, true] {
+        run_shortcuts_frame(&mut app, query_focused, vec![ctrl_space()]);
+        assert!(app.shell.runtime.pinned_paths.contains(&selected));
+        assert_eq!(app.shell.runtime.current_row, Some(0));
+        assert!(app.shell.runtime.status_line.contains("Pinned: 1"));
+
+        run_shortcuts_frame(&mut app, query_focused, vec![ctrl_space()]);
+        assert!(!app.shell.runtime.pinned_paths.contains(&selected));
+        assert_eq!(app.shell.runtime.current_row, Some(0));
+    }
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn hide_pinned_removes_pinned_rows_and_restores_them_when_disabled() {
+    let root = test_root("hide-pinned-results");
+    let paths = ["a.txt", "b.txt", "c.txt"].map(|name| root.join(name));
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.ui.show_preview = false;
+    app.shell.runtime.entries = Arc::new(paths.iter().cloned().map(file_entry).collect());
+    app.shell.runtime.tab_pin_moves_to_next_row = true;
+    app.update_results();
+    app.set_hide_pinned_results(true);
+    app.shell.runtime.current_row = Some(0);
+    let tab = || egui::Event::Key {
+        key: egui::Key::Tab,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    };
+
+    run_shortcuts_frame(&mut app, false, vec![tab()]);
+    assert!(app.shell.runtime.pinned_paths.contains(&paths[0]));
+    assert_eq!(
+        app.shell.runtime.results,
+        vec![(paths[1].clone(), 0.0), (paths[2].clone(), 0.0)]
+    );
+    assert_eq!(app.shell.runtime.current_row, Some(0));
+
+    app.move_row(1);
+    assert_eq!(app.shell.runtime.current_row, Some(1));
+    app.move_row(1);
+    assert_eq!(app.shell.runtime.current_row, Some(1));
+
+    app.set_hide_pinned_results(false);
+    assert_eq!(app.shell.runtime.results.len(), 3);
+    assert!(app.shell.runtime.pinned_paths.contains(&paths[0]));
+}
+
 #[test]
 fn regression_tab_shortcut_clears_focus_traversal_target() {
     let root = test_root("regression-tab-focus-traversal");
@@ -215,3 +285,94 @@ fn regression_ctrl_i_toggles_pin_regardless_of_query_focus() {
     assert_eq!(app.shell.runtime.current_row, Some(0));
     let _ = fs::remove_dir_all(&root);
 }
+
+#[test]
+fn pin_same_extension_below_pins_matching_rows_from_current_row() {
+    let root = test_root("pin-same-extension-below");
+    let above = root.join("above.rs");
+    let current = root.join("current.rs");
+    let other = root.join("notes.txt");
+    let upper = root.join("UPPER.RS");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![
+        (above.clone(), 0.0),
+        (current.clone(), 0.0),
+        (other.clone(), 0.0),
+        (upper.clone(), 0.0),
+    ];
+    app.shell.runtime.current_row = Some(1);
+
+    app.pin_same_extension_below();
+
+    assert!(app.shell.runtime.pinned_paths.contains(&current));
+    assert!(app.shell.runtime.pinned_paths.contains(&upper));
+    assert!(!app.shell.runtime.pinned_paths.contains(&above));
+    assert!(!app.shell.runtime.pinned_paths.contains(&other));
+    assert_eq!(
+        app.shell.runtime.notice,
+        "Pinned 2 .rs result(s) from current row"
+    );
+
+    app.shell.runtime.current_row = Some(2);
+    app.shell.runtime.results[2].0 = root.join("Makefile");
+    app.pin_same_extension_below();
+    assert_eq!(app.shell.runtime.notice, "Current row has no extension");
+    assert_eq!(app.shell.runtime.pinned_paths.len(), 2);
+}
+
+#[test]
+fn basket_collects_selection_across_query_and_root_changes() {
+    let root = test_root("basket-collect");
+    let other_root = root.join("other");
+    fs::create_dir_all(&other_root).expect("create dirs");
+    let first = root.join("first.txt");
+    let second = other_root.join("second.txt");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.runtime.results = vec![(first.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+
+    run_shortcuts_frame(
+        &mut app,
+        true,
+        vec![egui::Event::Key {
+            key: egui::Key::B,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: gui_shortcut_modifiers(true),
+        }],
+    );
+    assert_eq!(app.shell.runtime.basket, vec![first.clone()]);
+    assert!(app.shell.runtime.pinned_paths.is_empty());
+
+    app.shell.runtime.query_state.query = "second".to_string();
+    app.apply_root_change(other_root.clone());
+    app.shell.runtime.results = vec![(second.clone(), 0.0)];
+    app.shell.runtime.current_row = Some(0);
+    app.shell.runtime.basket_enter_mode = true;
+
+    run_shortcuts_frame(
+        &mut app,
+        false,
+        vec![egui::Event::Key {
+            key: egui::Key::Enter,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::NONE,
+        }],
+    );
+    assert_eq!(app.shell.runtime.basket, vec![first, second]);
+    assert_eq!(
+        app.shell.runtime.notice,
+        "Added 1 item(s) to basket (2 total)"
+    );
+    assert!(app.shell.worker_bus.action.pending_request_id.is_none());
+    assert!(app.status_line_text().contains("Basket: 2"));
+
+    app.remove_basket_item(0);
+    assert_eq!(app.shell.runtime.basket.len(), 1);
+    app.clear_basket();
+    assert!(app.shell.runtime.basket.is_empty());
+    let _ = fs::remove_dir_all(&root);
+}
@@ -248,3 +248,95 @@ fn history_search_escape_and_ctrl_g_cancel_and_restore_original_query() {
         let _ = fs::remove_dir_all(&root);
     }
 }
+
+#[test]
+fn backspace_with_empty_query_re_roots_to_parent() {
+    let parent = test_root("backspace-parent-root");
+    let root = parent.join("child");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, "draft".to_string());
+    // egui derives `repeat` from keys held across frames, so all frames share one context.
+    let ctx = egui::Context::default();
+    let run_frame = |app: &mut FlistWalkerApp, pressed: bool| {
+        ctx.begin_pass(egui::RawInput {
+            events: vec![egui::Event::Key {
+                key: egui::Key::Backspace,
+                physical_key: None,
+                pressed,
+                repeat: false,
+                modifiers: egui::Modifiers::NONE,
+            }],
+            ..Default::default()
+        });
+        ctx.memory_mut(|m| m.request_focus(app.shell.ui.query_input_id));
+        app.handle_shortcuts(&ctx);
+        let _ = ctx.end_pass();
+    };
+
+    run_frame(&mut app, true);
+    assert_eq!(app.shell.runtime.root, root);
+
+    // Holding Backspace past the last query character must not re-root.
+    app.shell.runtime.query_state.query.clear();
+    run_frame(&mut app, true);
+    assert_eq!(app.shell.runtime.root, root);
+
+    run_frame(&mut app, false);
+    run_frame(&mut app, true);
+    assert_eq!(app.shell.runtime.root, parent);
+    let _ = fs::remove_dir_all(&parent);
+}
+
+#[test]
+fn root_breadcrumbs_rebuild_each_ancestor_prefix() {
+    let root = PathBuf::from("/work/repo/src");
+
+    let crumbs = FlistWalkerApp::root_breadcrumbs(&root);
+
+    assert_eq!(
+        crumbs,
+        vec![
+            ("/".to_string(), PathBuf::from("/")),
+            ("work".to_string(), PathBuf::from("/work")),
+            ("repo".to_string(), PathBuf::from("/work/repo")),
+            ("src".to_string(), PathBuf::from("/work/repo/src")),
+        ]
+    );
+}
+
+#[test]
+fn root_dropdown_filter_limits_candidates_and_selection() {
+    let root = test_root("root-dropdown-filter");
+    let alpha = root.join("alpha-project");
+    let beta = root.join("beta-docs");
+    let gamma = root.join("gamma-project");
+    for dir in [&root, &alpha, &beta, &gamma] {
+        fs::create_dir_all(dir).expect("create dir");
+    }
+    let mut app = FlistWalkerApp::new(root.clone(), 50, String::new());
+    app.shell.features.root_browser.saved_roots =
+        vec![root.clone(), alpha.clone(), beta, gamma.clone()];
+    let ctx = egui::Context::default();
+    app.open_root_dropdown(&ctx);
+
+    app.set_root_dropdown_filter("prjct".to_string());
+    assert_eq!(app.filtered_root_dropdown_indices(), vec![1, 3]);
+    assert_eq!(app.shell.ui.root_dropdown_highlight, Some(1));
+
+    app.move_root_dropdown_selection(1);
+    assert_eq!(app.shell.ui.root_dropdown_highlight, Some(3));
+    app.move_root_dropdown_selection(1);
+    assert_eq!(app.shell.ui.root_dropdown_highlight, Some(3));
+
+    app.apply_root_dropdown_selection(&ctx);
+    assert!(!app.is_root_dropdown_open(&ctx));
+    assert_eq!(app.shell.runtime.root, gamma);
+    assert!(app.shell.ui.root_dropdown_filter.is_empty());
+
+    app.open_root_dropdown(&ctx);
+    app.set_root_dropdown_filter("no-such-root".to_string());
+    assert!(app.filtered_root_dropdown_indices().is_empty());
+    assert_eq!(app.shell.ui.root_dropdown_highlight, None);
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,76 @@
+use super::*;
+use std::sync::{Mutex, OnceLock};
+
+static SMART_FILTERS_TEST_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+struct SmartFiltersTestScope {
+    _guard: std::sync::MutexGuard<'static, ()>,
+    settings_base: PathBuf,
+}
+
+impl Drop for SmartFiltersTestScope {
+    fn drop(&mut self) {
+        FlistWalkerApp::set_smart_filters_file_path_override_for_test(None);
+        let _ = fs::remove_dir_all(&self.settings_base);
+    }
+}
+
+fn smart_filters_test_scope(name: &str) -> SmartFiltersTestScope {
+    let guard = SMART_FILTERS_TEST_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let base = test_root(name);
+    fs::create_dir_all(&base).expect("create smart filters test dir");
+    FlistWalkerApp::set_smart_filters_file_path_override_for_test(Some(
+        FlistWalkerApp::smart_filters_file_path_in(&base),
+    ));
+    SmartFiltersTestScope {
+        _guard: guard,
+        settings_base: base,
+    }
+}
+
+#[test]
+fn saved_smart_filter_persists_and_reapplies_query_mode_and_kind_filters() {
+    let _scope = smart_filters_test_scope("smart-filter-roundtrip");
+    let root = test_root("smart-filter-roundtrip-root");
+    fs::create_dir_all(&root).expect("create dir");
+    let mut app = FlistWalkerApp::new(root.clone(), 50, r"\.png$".to_string());
+    app.shell.runtime.use_filelist = false;
+    app.shell.runtime.use_regex = true;
+    app.shell.runtime.include_dirs = false;
+
+    app.open_save_smart_filter();
+    app.shell.ui.smart_filter_name_input = Some(" Images ".to_string());
+    app.confirm_save_smart_filter();
+
+    assert!(app.shell.ui.smart_filter_name_input.is_none());
+    assert_eq!(app.shell.runtime.notice, "Saved filter: Images");
+    let loaded = FlistWalkerApp::load_smart_filters();
+    assert_eq!(
+        loaded,
+        vec![SmartFilter {
+            name: "Images".to_string(),
+            query: r"\.png$".to_string(),
+            mode: SmartFilterMode::Regex,
+            include_files: true,
+            include_dirs: false,
+        }]
+    );
+
+    let mut other = FlistWalkerApp::new(root.clone(), 50, "other".to_string());
+    other.shell.runtime.use_filelist = false;
+    other.shell.ui.smart_filters = loaded;
+    other.apply_smart_filter(0);
+
+    assert_eq!(other.shell.runtime.query_state.query, r"\.png$");
+    assert!(other.shell.runtime.use_regex);
+    assert!(other.shell.runtime.include_files);
+    assert!(!other.shell.runtime.include_dirs);
+    assert_eq!(other.shell.runtime.notice, "Applied filter: Images");
+
+    other.delete_smart_filter(0);
+    assert!(FlistWalkerApp::load_smart_filters().is_empty());
+    let _ = fs::remove_dir_all(&root);
+}
@@ -56,6 +56,7 @@ fn background_tab_search_and_preview_responses_are_retained() {
             request_id: preview_request_id,
             path: selected.clone(),
             preview: "preview-body".to_string(),
+            quick_look: false,
         })
         .expect("send preview response");
     app.poll_search_response();
@@ -488,6 +489,11 @@ fn background_tab_search_and_index_responses_do_not_override_active_results() {
         use_filelist: true,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     };
     app.shell
         .indexing
@@ -111,6 +111,7 @@ fn tab_state_contract_round_trip_pins_field_layout() {
         pinned_paths: HashSet::from([root.join("pinned.txt")]),
         current_row: Some(0),
         preview: "preview".to_string(),
+        preview_path: Some(root.join("visible.txt")),
         results_compacted: false,
     };
     let snapshot = AppTabState {
@@ -273,6 +274,10 @@ fn tab_state_contract_round_trip_pins_field_layout() {
         snapshot.result_state.current_row
     );
     assert_eq!(restored.result_state.preview, snapshot.result_state.preview);
+    assert_eq!(
+        restored.result_state.preview_path,
+        snapshot.result_state.preview_path
+    );
     assert_eq!(restored.notice, snapshot.notice);
     assert_eq!(restored.pending_request_id, snapshot.pending_request_id);
     assert_eq!(
@@ -348,12 +348,14 @@ fn tc_154_stale_background_routes_never_mutate_active_scratch() {
         request_id: 2202,
         path: stale_path.clone(),
         preview: "stale preview".to_string(),
+        quick_look: false,
     });
 
     app.bind_action_request_to_tab(2203, active_id);
     app.apply_background_action_response(ActionResponse {
         request_id: 2203,
         notice: "stale notice".to_string(),
+        file_operation: None,
     });
 
     app.bind_sort_request_to_tab(2204, active_id);
@@ -73,6 +73,11 @@ fn close_tab_clears_filelist_and_request_routing_for_removed_tab() {
         use_filelist: true,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     });
     app.shell.indexing.pending_queue.push_back(IndexRequest {
         request_id: 12,
@@ -81,6 +86,11 @@ fn close_tab_clears_filelist_and_request_routing_for_removed_tab() {
         use_filelist: true,
         include_files: true,
         include_dirs: true,
+        throttle: None,
+        extra_roots: Vec::new(),
+        canonicalize_entries: false,
+        git_tracked_only: false,
+        filelist_only: false,
     });
     if let Ok(mut latest) = app.shell.indexing.latest_request_ids.lock() {
         latest.insert(removed_tab_id, 11);
@@ -1,17 +1,34 @@
-use super::{SavedWindowGeometry, TabDragState};
+use super::{
+    PendingActionConfirmation, QuickLookState, ResetSettingsDialogState, SavedWindowGeometry,
+    SmartFilter, SourceFilterMemory, TabDragState,
+};
 use eframe::egui;
 use std::time::Instant;
 
 pub(super) struct RuntimeUiState {
     pub(super) pending_copy_shortcut: bool,
     pub(super) root_dropdown_highlight: Option<usize>,
+    pub(super) root_dropdown_filter: String,
     pub(super) scroll_to_current: bool,
     pub(super) preview_resize_in_progress: bool,
+    pub(super) pending_preview_scroll_pages: isize,
+    pub(super) preview_scroll_offset: f32,
     pub(super) focus_query_requested: bool,
     pub(super) unfocus_query_requested: bool,
     pub(super) show_preview: bool,
     pub(super) ignore_list_enabled: bool,
+    pub(super) render_markdown_preview: bool,
+    pub(super) copy_preview_line_numbers: bool,
+    pub(super) group_results_by_directory: bool,
+    pub(super) show_result_scores: bool,
+    pub(super) show_extension_breakdown: bool,
+    pub(super) wrap_result_rows: bool,
+    pub(super) show_basket: bool,
+    pub(super) show_log_panel: bool,
+    pub(super) size_filter_min_text: String,
+    pub(super) size_filter_max_text: String,
     pub(super) preview_panel_width: f32,
+    pub(super) source_filter_memory: SourceFilterMemory,
     pub(super) window_geometry: Option<SavedWindowGeometry>,
     pub(super) pending_window_geometry: Option<SavedWindowGeometry>,
     pub(super) last_window_geometry_change: Instant,
@@ -24,6 +41,15 @@ pub(super) struct RuntimeUiState {
     pub(super) query_input_id: egui::Id,
     pub(super) tab_drag_state: Option<TabDragState>,
     pub(super) pending_render_commands: Vec<super::render::RenderCommand>,
+    pub(super) pending_action_confirmation: Option<PendingActionConfirmation>,
+    pub(super) action_dialog_button: usize,
+    pub(super) quick_look: Option<QuickLookState>,
+    pub(super) goto_row_input: Option<String>,
+    pub(super) type_ahead_buffer: String,
+    pub(super) last_type_ahead_input: Option<Instant>,
+    pub(super) smart_filters: Vec<SmartFilter>,
+    pub(super) smart_filter_name_input: Option<String>,
+    pub(super) reset_settings_dialog: Option<ResetSettingsDialogState>,
     pub(super) cjk_font_applied: bool,
 }
 
@@ -31,17 +57,23 @@ impl RuntimeUiState {
     pub(super) fn new(
         show_preview: bool,
         ignore_list_enabled: bool,
+        render_markdown_preview: bool,
         preview_panel_width: f32,
+        source_filter_memory: SourceFilterMemory,
     ) -> Self {
         Self {
             pending_copy_shortcut: false,
             root_dropdown_highlight: None,
+            root_dropdown_filter: String::new(),
             scroll_to_current: true,
             preview_resize_in_progress: false,
+            pending_preview_scroll_pages: 0,
+            preview_scroll_offset: 0.0,
             focus_query_requested: true,
             unfocus_query_requested: false,
             show_preview,
             preview_panel_width,
+            source_filter_memory,
             window_geometry: None,
             pending_window_geometry: None,
             last_window_geometry_change: Instant::now(),
@@ -54,7 +86,26 @@ impl RuntimeUiState {
             query_input_id: egui::Id::new("query-input"),
             tab_drag_state: None,
             pending_render_commands: Vec::new(),
+            pending_action_confirmation: None,
+            action_dialog_button: 0,
+            quick_look: None,
+            goto_row_input: None,
+            type_ahead_buffer: String::new(),
+            last_type_ahead_input: None,
+            smart_filters: Vec::new(),
+            smart_filter_name_input: None,
+            reset_settings_dialog: None,
             ignore_list_enabled,
+            render_markdown_preview,
+            copy_preview_line_numbers: false,
+            group_results_by_directory: false,
+            show_result_scores: false,
+            show_extension_breakdown: false,
+            wrap_result_rows: false,
+            show_basket: false,
+            show_log_panel: false,
+            size_filter_min_text: String::new(),
+            size_filter_max_text: String::new(),
             cjk_font_applied: false,
         }
     }
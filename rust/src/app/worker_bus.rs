@@ -5,7 +5,9 @@ use super::worker_protocol::{
     KindResolveResponse, PreviewRequest, PreviewResponse, SortMetadataRequest,
     SortMetadataResponse, UpdateRequest, UpdateResponse,
 };
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
 
 pub(super) struct PreviewWorkerBus {
     pub(super) tx: Sender<PreviewRequest>,
@@ -13,15 +15,22 @@ pub(super) struct PreviewWorkerBus {
     pub(super) next_request_id: u64,
     pub(super) pending_request_id: Option<u64>,
     pub(super) in_progress: bool,
+    pub(super) latest_request_id: Arc<AtomicU64>,
 }
 
 impl PreviewWorkerBus {
+    pub(super) fn allocate_request_id(&mut self) -> u64 {
+        worker_bus_lifecycle::allocate_request_id(&mut self.next_request_id)
+    }
+
     pub(super) fn begin_request(&mut self) -> u64 {
-        worker_bus_lifecycle::begin_request(
+        let request_id = worker_bus_lifecycle::begin_request(
             &mut self.next_request_id,
             &mut self.pending_request_id,
             &mut self.in_progress,
-        )
+        );
+        self.latest_request_id.store(request_id, Ordering::Relaxed);
+        request_id
     }
 
     pub(super) fn clear_request(&mut self) {
@@ -93,6 +102,8 @@ pub(super) struct WorkerBus {
     pub(super) preview: PreviewWorkerBus,
     pub(super) action: ActionWorkerBus,
     pub(super) sort: SortWorkerBus,
+    /// Size filter 用の file size を sort 要求と競合せずに調べる、sort metadata worker の別 instance。
+    pub(super) size: SortWorkerBus,
     pub(super) kind: KindWorkerBus,
     pub(super) filelist: FileListWorkerBus,
     pub(super) update: UpdateWorkerBus,
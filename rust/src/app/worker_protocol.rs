@@ -1,22 +1,25 @@
 use super::{ResultSortMode, ResultSortScope, SortMetadata};
 use crate::entry::{Entry, EntryKind};
 use crate::indexer::IndexSource;
+use crate::query::QueryOptions;
 use crate::updater::UpdateCandidate;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub(super) struct SearchRequest {
     pub(super) request_id: u64,
     pub(super) query: String,
     pub(super) entries: Arc<Vec<Entry>>,
     pub(super) limit: usize,
-    pub(super) use_regex: bool,
-    pub(super) ignore_case: bool,
+    pub(super) query_options: QueryOptions,
     pub(super) root: PathBuf,
     pub(super) prefer_relative: bool,
     pub(super) sort_mode: ResultSortMode,
     pub(super) sort_scope: ResultSortScope,
+    pub(super) path_prefix_filter: bool,
+    pub(super) max_results_per_directory: usize,
 }
 
 pub(super) struct SearchResponse {
@@ -53,12 +56,18 @@ pub(super) struct IndexRequest {
     pub(super) use_filelist: bool,
     pub(super) include_files: bool,
     pub(super) include_dirs: bool,
+    pub(super) throttle: Option<Duration>,
+    pub(super) extra_roots: Vec<PathBuf>,
+    pub(super) canonicalize_entries: bool,
+    pub(super) git_tracked_only: bool,
+    pub(super) filelist_only: bool,
 }
 
 pub(super) enum IndexResponse {
     Started {
         request_id: u64,
         source: IndexSource,
+        total: Option<usize>,
     },
     Batch {
         request_id: u64,
@@ -83,30 +92,73 @@ pub(super) enum IndexResponse {
         request_id: u64,
         limit: usize,
     },
+    DirectoriesCapped {
+        request_id: u64,
+        limit: usize,
+        directories: usize,
+    },
 }
 
 pub(super) struct PreviewRequest {
     pub(super) request_id: u64,
     pub(super) path: PathBuf,
     pub(super) is_dir: bool,
+    pub(super) quick_look: bool,
+    pub(super) max_file_bytes: Option<u64>,
+    pub(super) dir_depth: usize,
+    pub(super) skip_binary: bool,
+    pub(super) metadata_details: bool,
 }
 
 pub(super) struct PreviewResponse {
     pub(super) request_id: u64,
     pub(super) path: PathBuf,
     pub(super) preview: String,
+    pub(super) quick_look: bool,
+}
+
+/// action worker が `paths` に対して行う処理。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum ActionOperation {
+    /// 既定動作で実行またはオープンする。
+    Launch,
+    /// `paths[i]` を `targets[i]` へ移動する。
+    Move { targets: Vec<PathBuf> },
+    /// 直前の移動を取り消し、`paths[i]` を元の場所 `targets[i]` へ戻す。
+    UndoMove { targets: Vec<PathBuf> },
+    /// OS の trash へ移す。
+    Trash,
+    /// editor command template で開く。行が分からなければ `{line}` 部分を省く。
+    OpenInEditor {
+        template: String,
+        line: Option<usize>,
+    },
+}
+
+/// action worker で完了した file 操作。UI 側は index・結果・pin の path 付け替えや除去に使う。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum FileOperation {
+    /// 成功した (移動元, 移動先) の組。
+    Move(Vec<(PathBuf, PathBuf)>),
+    /// 取り消しで戻した (移動先, 元の場所) の組。
+    UndoMove(Vec<(PathBuf, PathBuf)>),
+    /// trash へ移した path。
+    Trash(Vec<PathBuf>),
 }
 
 pub(super) struct ActionRequest {
     pub(super) request_id: u64,
-    pub(super) root: PathBuf,
+    /// `paths` と同じ順に並べた、各 path を含む root。取り消しでは戻し先を含む root。
+    pub(super) roots: Vec<PathBuf>,
     pub(super) paths: Vec<PathBuf>,
     pub(super) open_parent_for_files: bool,
+    pub(super) operation: ActionOperation,
 }
 
 pub(super) struct ActionResponse {
     pub(super) request_id: u64,
     pub(super) notice: String,
+    pub(super) file_operation: Option<FileOperation>,
 }
 
 pub(super) enum UpdateRequestKind {
@@ -175,6 +227,7 @@ pub(super) struct FileListRequest {
     pub(super) root: PathBuf,
     pub(super) entries: Vec<PathBuf>,
     pub(super) propagate_to_ancestors: bool,
+    pub(super) header_comment: bool,
     pub(super) cancel: Arc<AtomicBool>,
 }
 
@@ -116,6 +116,7 @@ impl FlistWalkerApp {
         let (dummy_action_tx, _) =
             super::worker_channel::bounded_request_channel::<ActionRequest>(1);
         let (dummy_sort_tx, _) = mpsc::channel::<SortMetadataRequest>();
+        let (dummy_size_tx, _) = mpsc::channel::<SortMetadataRequest>();
         let (dummy_kind_tx, _) =
             super::worker_channel::bounded_request_channel::<KindResolveRequest>(1);
         let (dummy_filelist_tx, _) = mpsc::channel::<FileListRequest>();
@@ -127,6 +128,7 @@ impl FlistWalkerApp {
         let old_action_tx =
             std::mem::replace(&mut self.shell.worker_bus.action.tx, dummy_action_tx);
         let old_sort_tx = std::mem::replace(&mut self.shell.worker_bus.sort.tx, dummy_sort_tx);
+        let old_size_tx = std::mem::replace(&mut self.shell.worker_bus.size.tx, dummy_size_tx);
         let old_kind_tx = std::mem::replace(&mut self.shell.worker_bus.kind.tx, dummy_kind_tx);
         let old_filelist_tx =
             std::mem::replace(&mut self.shell.worker_bus.filelist.tx, dummy_filelist_tx);
@@ -137,6 +139,7 @@ impl FlistWalkerApp {
         drop(old_preview_tx);
         drop(old_action_tx);
         drop(old_sort_tx);
+        drop(old_size_tx);
         drop(old_kind_tx);
         drop(old_filelist_tx);
         drop(old_update_tx);
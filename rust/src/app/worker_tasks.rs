@@ -1,32 +1,41 @@
 use super::action_authorization::{
-    authorize_action_targets, reauthorize_action_target, ActionAuthorizationFailure,
+    authorize_action_targets, authorize_file_operation_parent, authorize_file_operation_source,
+    canonicalize_action_roots, reauthorize_action_target, ActionAuthorizationFailure,
 };
 use super::index_worker::resolve_entry_kind;
 use super::worker_channel::{
     bounded_request_channel, trace_worker_snapshot, BoundedSender, WorkerTraceContext,
 };
 use super::worker_protocol::{
-    ActionRequest, ActionResponse, FileListRequest, FileListResponse, KindResolveRequest,
-    KindResolveResponse, PreviewRequest, PreviewResponse, SearchRequest, SearchResponse,
-    SortMetadataRequest, SortMetadataResponse, UpdateRequest, UpdateRequestKind, UpdateResponse,
+    ActionOperation, ActionRequest, ActionResponse, FileListRequest, FileListResponse,
+    FileOperation, KindResolveRequest, KindResolveResponse, PreviewRequest, PreviewResponse,
+    SearchRequest, SearchResponse, SortMetadataRequest, SortMetadataResponse, UpdateRequest,
+    UpdateRequestKind, UpdateResponse,
 };
 use super::worker_support::action_notice_for_targets;
-use super::SortMetadata;
+use super::{FlistWalkerApp, SortMetadata};
+use crate::actions::move_path;
 #[cfg(not(test))]
-use crate::actions::execute_or_open;
+use crate::actions::{execute_or_open, open_at_line, trash_path};
 use crate::entry::EntryKind;
-use crate::indexer::write_filelist_cancellable;
+use crate::indexer::{build_filelist_header, write_filelist_cancellable};
 use crate::search::{
-    rank_search_results, SearchPrefixCache, SearchResultSortMode, SearchResultSortScope,
+    filter_entries_by_path_prefix, rank_search_results, SearchPrefixCache, SearchRankOptions,
+    SearchResultSortMode, SearchResultSortScope,
+};
+use crate::ui_model::{
+    append_metadata_details, build_binary_skipped_preview_text, build_preview_text_cancellable,
+    build_size_skipped_preview_text, is_probably_binary, normalize_path_for_display,
+    should_skip_preview, PREVIEW_MAX_BYTES, PREVIEW_MAX_LINES,
 };
-use crate::ui_model::{build_preview_text_with_kind, normalize_path_for_display};
 use crate::updater::{check_for_update, prepare_and_start_update};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::SystemTime;
 use tracing::{info, warn};
 
 pub(crate) type SharedKindResolver = Arc<dyn Fn(&Path) -> Option<EntryKind> + Send + Sync>;
@@ -50,6 +59,15 @@ fn trace_worker_receiver_closed(flow: &'static str, request_id: u64) {
     );
 }
 
+fn trace_worker_superseded(flow: &'static str, request_id: u64) {
+    info!(
+        flow,
+        event = "superseded",
+        request_id,
+        "worker request superseded"
+    );
+}
+
 fn search_sort_mode(mode: super::ResultSortMode) -> SearchResultSortMode {
     match mode {
         super::ResultSortMode::Score => SearchResultSortMode::Score,
@@ -91,17 +109,35 @@ pub(super) fn spawn_search_worker(
                 req = newer;
             }
             trace_worker_started("search", req.request_id);
+            // An absolute-path query lists everything under that prefix instead of fuzzy matching.
+            let prefix_entries = req
+                .path_prefix_filter
+                .then(|| {
+                    filter_entries_by_path_prefix(
+                        &req.entries,
+                        &req.query,
+                        req.query_options.ignore_case,
+                    )
+                })
+                .flatten()
+                .map(Arc::new);
+            let (entries, query) = match prefix_entries.as_ref() {
+                Some(entries) => (entries, ""),
+                None => (&req.entries, req.query.as_str()),
+            };
             let (result_set, error) = rank_search_results(
-                &req.entries,
-                &req.query,
+                entries,
+                query,
                 &req.root,
                 req.limit,
-                req.use_regex,
-                req.ignore_case,
-                req.prefer_relative,
+                SearchRankOptions {
+                    query: req.query_options,
+                    prefer_relative: req.prefer_relative,
+                    sort_mode: search_sort_mode(req.sort_mode),
+                    sort_scope: search_sort_scope(req.sort_scope),
+                    max_results_per_directory: req.max_results_per_directory,
+                },
                 &mut prefix_cache,
-                search_sort_mode(req.sort_mode),
-                search_sort_scope(req.sort_scope),
             );
             info!(
                 flow = "search",
@@ -135,6 +171,7 @@ pub(super) fn spawn_search_worker(
 
 pub(super) fn spawn_preview_worker(
     shutdown: Arc<AtomicBool>,
+    latest_request_id: Arc<AtomicU64>,
 ) -> (
     Sender<PreviewRequest>,
     Receiver<PreviewResponse>,
@@ -144,33 +181,85 @@ pub(super) fn spawn_preview_worker(
     let (tx_res, rx_res) = mpsc::channel::<PreviewResponse>();
 
     let handle = thread::spawn(move || {
-        while let Ok(mut req) = rx_req.recv() {
+        'worker: while let Ok(req) = rx_req.recv() {
             if shutdown.load(Ordering::Relaxed) {
                 break;
             }
+            // Quick Look and panel previews are drained independently so one never starves the other.
+            let mut latest = vec![req];
             while let Ok(newer) = rx_req.try_recv() {
-                req = newer;
+                latest.retain(|queued| queued.quick_look != newer.quick_look);
+                latest.push(newer);
             }
-            trace_worker_started("preview", req.request_id);
-            let preview = build_preview_text_with_kind(&req.path, req.is_dir);
-            info!(
-                flow = "preview",
-                event = "finished",
-                request_id = req.request_id,
-                path = %req.path.display(),
-                preview_chars = preview.chars().count(),
-                "worker request finished"
-            );
-            if tx_res
-                .send(PreviewResponse {
-                    request_id: req.request_id,
-                    path: req.path,
-                    preview,
-                })
-                .is_err()
-            {
-                trace_worker_receiver_closed("preview", req.request_id);
-                break;
+            for req in latest {
+                trace_worker_started("preview", req.request_id);
+                let skipped = req
+                    .max_file_bytes
+                    .and_then(|max_file_bytes| {
+                        build_size_skipped_preview_text(&req.path, req.is_dir, max_file_bytes)
+                    })
+                    .or_else(|| {
+                        // Sniff only a small prefix so binaries never pay for a full read.
+                        (req.skip_binary
+                            && !req.is_dir
+                            && !should_skip_preview(&req.path, false)
+                            && is_probably_binary(&req.path))
+                        .then(|| build_binary_skipped_preview_text(&req.path))
+                    });
+                let preview = if let Some(skipped) = skipped {
+                    skipped
+                } else if req.quick_look {
+                    build_preview_text_cancellable(
+                        &req.path,
+                        req.is_dir,
+                        FlistWalkerApp::QUICK_LOOK_MAX_LINES,
+                        FlistWalkerApp::QUICK_LOOK_MAX_BYTES,
+                        req.dir_depth,
+                        &|| false,
+                    )
+                    .unwrap_or_default()
+                } else {
+                    // Panel previews abort mid-read once a newer selection has been requested.
+                    let is_cancelled =
+                        || latest_request_id.load(Ordering::Relaxed) != req.request_id;
+                    let Some(preview) = build_preview_text_cancellable(
+                        &req.path,
+                        req.is_dir,
+                        PREVIEW_MAX_LINES,
+                        PREVIEW_MAX_BYTES,
+                        req.dir_depth,
+                        &is_cancelled,
+                    ) else {
+                        trace_worker_superseded("preview", req.request_id);
+                        continue;
+                    };
+                    preview
+                };
+                let preview = if req.metadata_details {
+                    append_metadata_details(&req.path, &preview)
+                } else {
+                    preview
+                };
+                info!(
+                    flow = "preview",
+                    event = "finished",
+                    request_id = req.request_id,
+                    path = %req.path.display(),
+                    preview_chars = preview.chars().count(),
+                    "worker request finished"
+                );
+                if tx_res
+                    .send(PreviewResponse {
+                        request_id: req.request_id,
+                        path: req.path,
+                        preview,
+                        quick_look: req.quick_look,
+                    })
+                    .is_err()
+                {
+                    trace_worker_receiver_closed("preview", req.request_id);
+                    break 'worker;
+                }
             }
         }
     });
@@ -319,10 +408,14 @@ pub(super) fn spawn_filelist_worker(
             }
             let _tab_id = req.tab_id;
             let count = req.entries.len();
+            let header = req
+                .header_comment
+                .then(|| build_filelist_header(&req.root, "", count, SystemTime::now()));
             let result = write_filelist_cancellable(
                 &req.root,
                 &req.entries,
                 "FileList.txt",
+                header.as_deref(),
                 req.propagate_to_ancestors,
                 &|| shutdown.load(Ordering::Relaxed) || req.cancel.load(Ordering::Relaxed),
             )
@@ -452,6 +545,7 @@ pub(crate) fn spawn_action_worker_with(
                         .send(ActionResponse {
                             request_id: req.request_id,
                             notice: "Action canceled: application is shutting down".to_string(),
+                            file_operation: None,
                         })
                         .is_err()
                     {
@@ -532,7 +626,14 @@ pub(crate) fn process_action_request_with_outcome(
     req: ActionRequest,
     mut execute: impl FnMut(&Path) -> anyhow::Result<()>,
 ) -> (ActionResponse, ActionTerminalOutcome) {
-    let batch = match authorize_action_targets(&req.root, &req.paths, req.open_parent_for_files) {
+    let editor = match &req.operation {
+        ActionOperation::Launch => None,
+        ActionOperation::OpenInEditor { template, line } => Some((template.as_str(), *line)),
+        ActionOperation::Move { targets } => return process_move_request(&req, targets, false),
+        ActionOperation::UndoMove { targets } => return process_move_request(&req, targets, true),
+        ActionOperation::Trash => return process_trash_request(&req),
+    };
+    let batch = match authorize_action_targets(&req.roots, &req.paths, req.open_parent_for_files) {
         Ok(batch) => batch,
         Err(err) => {
             warn!(
@@ -549,6 +650,7 @@ pub(crate) fn process_action_request_with_outcome(
                 ActionResponse {
                     request_id: req.request_id,
                     notice: action_blocked_notice(&err),
+                    file_operation: None,
                 },
                 ActionTerminalOutcome::Failed,
             );
@@ -557,7 +659,7 @@ pub(crate) fn process_action_request_with_outcome(
     let total = batch.targets.len();
 
     for (completed, target) in batch.targets.iter().enumerate() {
-        let execution_path = match reauthorize_action_target(&batch.canonical_root, target) {
+        let execution_path = match reauthorize_action_target(target) {
             Ok(path) => path,
             Err(err) => {
                 let result = if completed == 0 { "blocked" } else { "partial" };
@@ -583,12 +685,17 @@ pub(crate) fn process_action_request_with_outcome(
                     ActionResponse {
                         request_id: req.request_id,
                         notice,
+                        file_operation: None,
                     },
                     ActionTerminalOutcome::Failed,
                 );
             }
         };
-        if let Err(err) = execute(&execution_path) {
+        let executed = match editor {
+            Some((template, line)) => run_editor_target(&execution_path, line, template),
+            None => execute(&execution_path),
+        };
+        if let Err(err) = executed {
             let result = if completed == 0 { "failed" } else { "partial" };
             warn!(
                 flow = "action",
@@ -611,6 +718,7 @@ pub(crate) fn process_action_request_with_outcome(
                             "Action failed after launching {completed} of {total} items while opening {display_path}"
                         )
                     },
+                    file_operation: None,
                 },
                 ActionTerminalOutcome::Failed,
             );
@@ -634,12 +742,161 @@ pub(crate) fn process_action_request_with_outcome(
     (
         ActionResponse {
             request_id: req.request_id,
-            notice: action_notice_for_targets(&display_targets),
+            notice: match (editor, display_targets.as_slice()) {
+                (Some(_), [path]) => {
+                    format!("Opened in editor: {}", normalize_path_for_display(path))
+                }
+                _ => action_notice_for_targets(&display_targets),
+            },
+            file_operation: None,
         },
         ActionTerminalOutcome::Completed,
     )
 }
 
+/// path ごとにその path 自身の root 内か確かめてから移動する。衝突や失敗は file ごとに notice へまとめ、残りの移動は続ける。
+/// 取り消し (`undo`) では移動元ではなく、戻し先が root 内かを確かめる。
+fn process_move_request(
+    req: &ActionRequest,
+    targets: &[PathBuf],
+    undo: bool,
+) -> (ActionResponse, ActionTerminalOutcome) {
+    let total = req.paths.len();
+    let mut moved = Vec::new();
+    let mut failures = Vec::new();
+    let canonical_roots = canonicalize_action_roots(&req.roots);
+    for ((path, target), canonical_root) in req.paths.iter().zip(targets).zip(&canonical_roots) {
+        let Some(canonical_root) = canonical_root else {
+            failures.push(format!(
+                "Move blocked: {}: current root could not be resolved",
+                normalize_path_for_display(path)
+            ));
+            continue;
+        };
+        if path == target {
+            failures.push(format!(
+                "{} is already in that folder",
+                normalize_path_for_display(path)
+            ));
+            continue;
+        }
+        let authorized = if undo {
+            authorize_file_operation_parent(canonical_root, target)
+        } else {
+            authorize_file_operation_source(canonical_root, path)
+        };
+        if let Err(err) = authorized {
+            failures.push(action_blocked_notice(&err));
+            continue;
+        }
+        match move_path(path, target) {
+            Ok(()) => moved.push((path.clone(), target.clone())),
+            Err(err) => failures.push(format!("{err:#}")),
+        }
+    }
+    let summary = if undo { "Undid move of" } else { "Moved" };
+    let dest = if undo {
+        String::new()
+    } else {
+        targets
+            .first()
+            .and_then(|target| target.parent())
+            .map(|dest| format!(" to {}", normalize_path_for_display(dest)))
+            .unwrap_or_default()
+    };
+    let (notice, outcome) = if failures.is_empty() {
+        (
+            format!("{summary} {} item(s){dest}", moved.len()),
+            ActionTerminalOutcome::Completed,
+        )
+    } else {
+        warn!(
+            flow = "action",
+            event = "move_failed",
+            request_id = req.request_id,
+            completed = moved.len(),
+            total,
+            "move request finished with failures"
+        );
+        (
+            format!(
+                "{summary} {} of {total} item(s){dest}; {}",
+                moved.len(),
+                failures.join("; ")
+            ),
+            ActionTerminalOutcome::Failed,
+        )
+    };
+    (
+        ActionResponse {
+            request_id: req.request_id,
+            notice,
+            file_operation: (!moved.is_empty()).then_some(if undo {
+                FileOperation::UndoMove(moved)
+            } else {
+                FileOperation::Move(moved)
+            }),
+        },
+        outcome,
+    )
+}
+
+/// path ごとにその path 自身の root 内か確かめてから trash へ移す。失敗は file ごとに notice へまとめ、残りは続ける。
+fn process_trash_request(req: &ActionRequest) -> (ActionResponse, ActionTerminalOutcome) {
+    let total = req.paths.len();
+    let mut trashed = Vec::new();
+    let mut failures = Vec::new();
+    let canonical_roots = canonicalize_action_roots(&req.roots);
+    for (path, canonical_root) in req.paths.iter().zip(&canonical_roots) {
+        let Some(canonical_root) = canonical_root else {
+            failures.push(format!(
+                "Trash blocked: {}: current root could not be resolved",
+                normalize_path_for_display(path)
+            ));
+            continue;
+        };
+        if let Err(err) = authorize_file_operation_source(canonical_root, path) {
+            failures.push(action_blocked_notice(&err));
+            continue;
+        }
+        match run_trash_target(path) {
+            Ok(()) => trashed.push(path.clone()),
+            Err(err) => failures.push(format!("{err:#}")),
+        }
+    }
+    let (notice, outcome) = if failures.is_empty() {
+        (
+            format!("Moved {} item(s) to trash", trashed.len()),
+            ActionTerminalOutcome::Completed,
+        )
+    } else {
+        warn!(
+            flow = "action",
+            event = "trash_failed",
+            request_id = req.request_id,
+            completed = trashed.len(),
+            total,
+            "trash request finished with failures"
+        );
+        (
+            format!(
+                "Moved {} of {total} item(s) to trash; {}",
+                trashed.len(),
+                failures.join("; ")
+            ),
+            ActionTerminalOutcome::Failed,
+        )
+    };
+    (
+        ActionResponse {
+            request_id: req.request_id,
+            notice,
+            file_operation: (!trashed.is_empty()).then_some(FileOperation::Trash(trashed)),
+        },
+        outcome,
+    )
+}
+
 fn action_blocked_notice(failure: &ActionAuthorizationFailure) -> String {
     match &failure.display_path {
         Some(path) => format!(
@@ -662,6 +919,36 @@ fn run_action_target(_path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(not(test))]
+fn run_editor_target(path: &Path, line: Option<usize>, template: &str) -> anyhow::Result<()> {
+    open_at_line(path, line, template)
+}
+
+#[cfg(test)]
+fn run_editor_target(_path: &Path, _line: Option<usize>, template: &str) -> anyhow::Result<()> {
+    // editor を実際に起動せず、template の検証だけ本番と揃える。
+    if template.trim().is_empty() {
+        anyhow::bail!("editor command template is empty");
+    }
+    Ok(())
+}
+
+#[cfg(not(test))]
+fn run_trash_target(path: &Path) -> anyhow::Result<()> {
+    trash_path(path)
+}
+
+#[cfg(test)]
+fn run_trash_target(path: &Path) -> anyhow::Result<()> {
+    // Tests must not depend on a desktop trash; deleting keeps the bookkeeping observable.
+    if std::fs::symlink_metadata(path)?.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
 pub(super) fn spawn_sort_metadata_worker(
     shutdown: Arc<AtomicBool>,
 ) -> (
@@ -0,0 +1,95 @@
+use regex::Regex;
+use std::path::Path;
+
+/// FileList の `!` 行から作る除外条件。位置に関係なく include 行より優先する。
+///
+/// pattern は gitignore 風の glob で、FileList のあるフォルダ (または root) からの相対 path に
+/// `/` 区切りで照合する。`*` と `?` は `/` を跨がず、`**` は跨ぐ。`/` を含まない pattern は
+/// どの階層の名前にも一致し、一致したフォルダ配下の path もまとめて除外する。
+#[derive(Debug, Default)]
+pub(super) struct FileListExclusions {
+    patterns: Vec<Regex>,
+}
+
+impl FileListExclusions {
+    /// `!` を除いた pattern を追加する。空の pattern は無視する。
+    pub(super) fn add(&mut self, pattern: &str) {
+        if let Some(regex) = filelist_glob_regex(pattern) {
+            self.patterns.push(regex);
+        }
+    }
+
+    /// 有効な pattern が 1 つも無いかを返す。
+    pub(super) fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// path を各 base からの相対 path にして、いずれかの pattern に一致するかを返す。
+    pub(super) fn excludes(&self, path: &Path, bases: &[&Path]) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        bases
+            .iter()
+            .filter_map(|base| path.strip_prefix(base).ok())
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+            .any(|relative| {
+                self.patterns
+                    .iter()
+                    .any(|pattern| pattern.is_match(&relative))
+            })
+    }
+}
+
+/// FileList 行が除外 pattern (`!` 始まり) なら `!` を除いた pattern を返す。
+pub(super) fn filelist_exclusion_pattern(line: &str) -> Option<&str> {
+    line.strip_prefix('!').map(str::trim)
+}
+
+/// `!` で始まる path を除外行と区別できるよう、書き出す行の先頭に `\` を付ける。
+pub(super) fn escape_filelist_entry(line: String) -> String {
+    if line.starts_with('!') {
+        format!("\\{line}")
+    } else {
+        line
+    }
+}
+
+/// `escape_filelist_entry` が付けた `\!` の `\` を外して元の path 文字列へ戻す。
+pub(super) fn unescape_filelist_entry(line: &str) -> &str {
+    if line.starts_with("\\!") {
+        &line[1..]
+    } else {
+        line
+    }
+}
+
+fn filelist_glob_regex(pattern: &str) -> Option<Regex> {
+    let pattern = pattern.replace('\\', "/");
+    let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+    let pattern = pattern.trim_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+    let mut source = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    // `**/` also matches zero folders, so `docs/**/a.md` covers `docs/a.md`.
+                    chars.next();
+                    source.push_str("(?:.*/)?");
+                } else {
+                    source.push_str(".*");
+                }
+            }
+            '*' => source.push_str("[^/]*"),
+            '?' => source.push_str("[^/]"),
+            other => source.push_str(&regex::escape(other.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    source.push_str("(?:/.*)?$");
+    Regex::new(&source).ok()
+}
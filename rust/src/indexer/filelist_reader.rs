@@ -2,9 +2,12 @@ use anyhow::{Context, Result};
 use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::io::{BufRead, BufReader, Cursor, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
+use super::filelist_exclude::{
+    filelist_exclusion_pattern, unescape_filelist_entry, FileListExclusions,
+};
 use super::filelist_hierarchy::apply_nested_filelist_overrides;
 use super::filelist_writer::filelist_modified_time;
 
@@ -14,6 +17,22 @@ const FILELIST_MAX_LINE_PAYLOAD_BYTES: usize = 1024 * 1024;
 const FILELIST_MAX_RAW_LINE_BYTES: usize = FILELIST_MAX_LINE_PAYLOAD_BYTES + 5;
 const UTF8_BOM: &[u8; 3] = b"\xEF\xBB\xBF";
 
+/// FileList の entry 区切り。改行を含まず NUL を含む list は `find -print0` 形式とみなす。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileListDelimiter {
+    Newline,
+    Nul,
+}
+
+impl FileListDelimiter {
+    fn byte(self) -> u8 {
+        match self {
+            Self::Newline => b'\n',
+            Self::Nul => 0,
+        }
+    }
+}
+
 pub fn find_filelist(root: &Path) -> Option<PathBuf> {
     let upper = root.join("FileList.txt");
     if upper.is_file() {
@@ -50,6 +69,64 @@ pub fn parse_filelist(
     parse_filelist_collect(filelist_path, root, include_files, include_dirs, &|| false)
 }
 
+/// 標準入力など seek できない入力から FileList を読み、各行を `root` 基準で解決する。
+/// 行の検証と区切り判定は file の FileList と同じ経路で行い、`!` 行は読み終えてからまとめて適用する。
+/// `source` は error message に出す入力名。
+pub fn parse_filelist_reader<R>(reader: R, source: &Path, root: &Path) -> Result<Vec<PathBuf>>
+where
+    R: Read,
+{
+    let mut reader = BufReader::with_capacity(FILELIST_READ_BUFFER_BYTES, reader);
+    let mut head = Vec::new();
+    let (delimiter, _) =
+        detect_filelist_delimiter(&mut reader, source, &|| false, Some(&mut head))?;
+    // Bytes read ahead for delimiter detection are replayed because the input cannot seek.
+    let mut reader = Cursor::new(head).chain(reader);
+    let mut exclusions = FileListExclusions::default();
+    let mut entries = Vec::new();
+    for_each_filelist_line(&mut reader, source, delimiter, &|| false, |line| {
+        if let Some(pattern) = filelist_exclusion_pattern(line) {
+            exclusions.add(pattern);
+            return;
+        }
+        if let Some(path) =
+            resolve_filelist_entry_candidates(unescape_filelist_entry(line), root, root)
+                .into_iter()
+                .next()
+        {
+            entries.push(path);
+        }
+    })?;
+    let mut seen = HashSet::new();
+    entries.retain(|path| !exclusions.excludes(path, &[root]) && seen.insert(path.clone()));
+    Ok(entries)
+}
+
+/// 2 つの FileList から得た path 集合の差分。各 list の元の順序を保つ。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileListDiff {
+    pub added: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+/// `old` に無く `new` にある path を added、その逆を removed として返す。
+pub fn diff_filelist_entries(old: &[PathBuf], new: &[PathBuf]) -> FileListDiff {
+    let old_set = old.iter().collect::<HashSet<_>>();
+    let new_set = new.iter().collect::<HashSet<_>>();
+    FileListDiff {
+        added: new
+            .iter()
+            .filter(|path| !old_set.contains(path))
+            .cloned()
+            .collect(),
+        removed: old
+            .iter()
+            .filter(|path| !new_set.contains(path))
+            .cloned()
+            .collect(),
+    }
+}
+
 pub fn build_entries_from_filelist_hierarchy<C>(
     filelist_path: &Path,
     root: &Path,
@@ -228,6 +305,7 @@ fn read_bounded_raw_line<R, C>(
     raw: &mut Vec<u8>,
     filelist_path: &Path,
     line_number: usize,
+    delimiter: FileListDelimiter,
     should_cancel: &C,
 ) -> Result<usize>
 where
@@ -242,7 +320,7 @@ where
         line_number,
         consumed: 0,
     };
-    match cancellable.read_until(b'\n', raw) {
+    match cancellable.read_until(delimiter.byte(), raw) {
         Ok(bytes_read) => Ok(bytes_read),
         Err(err) if is_filelist_read_canceled(&err) => Err(anyhow::anyhow!("superseded")),
         Err(err) => Err(anyhow::anyhow!(
@@ -254,6 +332,7 @@ where
 
 fn validate_filelist_line<'a>(
     raw: &'a [u8],
+    delimiter: FileListDelimiter,
     first_line: bool,
     line_start_offset: usize,
     line_number: usize,
@@ -270,9 +349,11 @@ fn validate_filelist_line<'a>(
     let bom_len = usize::from(first_line && raw.starts_with(UTF8_BOM)) * UTF8_BOM.len();
     let serialized = &raw[bom_len..];
     let mut payload_end = serialized.len();
-    if serialized.get(payload_end.wrapping_sub(1)) == Some(&b'\n') {
+    if serialized.get(payload_end.wrapping_sub(1)) == Some(&delimiter.byte()) {
         payload_end -= 1;
-        if serialized.get(payload_end.wrapping_sub(1)) == Some(&b'\r') {
+        if delimiter == FileListDelimiter::Newline
+            && serialized.get(payload_end.wrapping_sub(1)) == Some(&b'\r')
+        {
             payload_end -= 1;
         }
     }
@@ -282,8 +363,9 @@ fn validate_filelist_line<'a>(
             filelist_path.display()
         );
     }
-    if serialized.contains(&0) {
-        let nul_offset = serialized
+    let payload = &serialized[..payload_end];
+    if payload.contains(&0) {
+        let nul_offset = payload
             .iter()
             .position(|byte| *byte == 0)
             .expect("contains confirmed a NUL byte");
@@ -306,51 +388,147 @@ fn validate_filelist_line<'a>(
     })
 }
 
+/// 改行を含まず NUL を含む FileList だけを NUL 区切りと判定する。
+/// 通常の text list は最初の改行で走査を打ち切る。読み進めたかどうかも返し、
+/// `read_ahead` があれば読み進めた byte をそこへ残す。
+fn detect_filelist_delimiter<R, C>(
+    reader: &mut R,
+    filelist_path: &Path,
+    should_cancel: &C,
+    mut read_ahead: Option<&mut Vec<u8>>,
+) -> Result<(FileListDelimiter, bool)>
+where
+    R: BufRead,
+    C: Fn() -> bool,
+{
+    let mut saw_nul = false;
+    let mut consumed_any = false;
+    loop {
+        if should_cancel() {
+            anyhow::bail!("superseded");
+        }
+        let available = reader
+            .fill_buf()
+            .with_context(|| format!("failed to read {}", filelist_path.display()))?;
+        if available.is_empty() {
+            break;
+        }
+        if available.contains(&b'\n') {
+            return Ok((FileListDelimiter::Newline, consumed_any));
+        }
+        saw_nul |= available.contains(&0);
+        if let Some(read_ahead) = read_ahead.as_deref_mut() {
+            read_ahead.extend_from_slice(available);
+        }
+        let consumed = available.len();
+        reader.consume(consumed);
+        consumed_any = true;
+    }
+    let delimiter = if saw_nul {
+        FileListDelimiter::Nul
+    } else {
+        FileListDelimiter::Newline
+    };
+    Ok((delimiter, consumed_any))
+}
+
+/// 検証の走査で同時に集める FileList 全体の情報。
+#[derive(Debug, Default)]
+struct FileListScan {
+    /// 位置に関係なく include 行より優先する `!` 行の除外条件。
+    exclusions: FileListExclusions,
+    /// 空行、`#` コメント、`!` 行を除いた entry 行の数。
+    entry_lines: usize,
+}
+
+/// FileList 全体を検証し、同じ走査で `!` 行の除外条件と entry 行数を集める。
 fn validate_filelist_reader<R, C>(
     reader: &mut R,
     filelist_path: &Path,
+    delimiter: FileListDelimiter,
     should_cancel: &C,
-) -> Result<()>
+) -> Result<FileListScan>
 where
     R: BufRead,
     C: Fn() -> bool,
 {
+    let mut scan = FileListScan::default();
     let mut raw = Vec::new();
     let mut line_number = 1usize;
     let mut line_start_offset = 0usize;
     loop {
-        let bytes_read =
-            read_bounded_raw_line(reader, &mut raw, filelist_path, line_number, should_cancel)?;
+        let bytes_read = read_bounded_raw_line(
+            reader,
+            &mut raw,
+            filelist_path,
+            line_number,
+            delimiter,
+            should_cancel,
+        )?;
         if bytes_read == 0 {
-            return Ok(());
+            return Ok(scan);
         }
-        validate_filelist_line(
+        let validated = validate_filelist_line(
             &raw,
+            delimiter,
             line_number == 1,
             line_start_offset,
             line_number,
             filelist_path,
         )?;
+        // Most lines have no `!`; the byte scan keeps the common path as cheap as plain validation.
+        if validated.logical.as_bytes().contains(&b'!') {
+            if let Some(pattern) = filelist_exclusion_pattern(validated.logical.trim()) {
+                scan.exclusions.add(pattern);
+            }
+        }
+        // Same skip rules as `for_each_filelist_line` and the `!` check in the stream.
+        if !matches!(
+            validated.logical.trim_start().as_bytes().first(),
+            None | Some(b'#' | b'!')
+        ) {
+            scan.entry_lines = scan.entry_lines.saturating_add(1);
+        }
         line_start_offset = line_start_offset.saturating_add(bytes_read);
         line_number = line_number.saturating_add(1);
     }
 }
 
+#[cfg(test)]
 pub(crate) fn open_validated_filelist<C>(
     filelist_path: &Path,
     should_cancel: &C,
 ) -> Result<BufReader<File>>
+where
+    C: Fn() -> bool,
+{
+    open_validated_filelist_with_delimiter(filelist_path, should_cancel)
+        .map(|(reader, _, _)| reader)
+}
+
+fn open_validated_filelist_with_delimiter<C>(
+    filelist_path: &Path,
+    should_cancel: &C,
+) -> Result<(BufReader<File>, FileListDelimiter, FileListScan)>
 where
     C: Fn() -> bool,
 {
     let file = File::open(filelist_path)
         .with_context(|| format!("failed to read {}", filelist_path.display()))?;
     let mut reader = BufReader::with_capacity(FILELIST_READ_BUFFER_BYTES, file);
-    validate_filelist_reader(&mut reader, filelist_path, should_cancel)?;
+    let (delimiter, consumed) =
+        detect_filelist_delimiter(&mut reader, filelist_path, should_cancel, None)?;
+    // A newline in the first buffer leaves it unconsumed, so only rewind after scanning further.
+    if consumed {
+        reader
+            .seek(SeekFrom::Start(0))
+            .with_context(|| format!("failed to reread {}", filelist_path.display()))?;
+    }
+    let scan = validate_filelist_reader(&mut reader, filelist_path, delimiter, should_cancel)?;
     reader
         .seek(SeekFrom::Start(0))
         .with_context(|| format!("failed to reread {}", filelist_path.display()))?;
-    Ok(reader)
+    Ok((reader, delimiter, scan))
 }
 
 #[cfg(test)]
@@ -369,16 +547,21 @@ pub(super) fn read_filelist_text_strict(filelist_path: &Path) -> std::io::Result
     let mut line_number = 1usize;
     let mut line_start_offset = 0usize;
     loop {
-        let bytes_read =
-            read_bounded_raw_line(&mut reader, &mut raw, filelist_path, line_number, &|| false)
-                .map_err(|err| {
-                    std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
-                })?;
+        let bytes_read = read_bounded_raw_line(
+            &mut reader,
+            &mut raw,
+            filelist_path,
+            line_number,
+            FileListDelimiter::Newline,
+            &|| false,
+        )
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?;
         if bytes_read == 0 {
             return Ok(text);
         }
         let line = validate_filelist_line(
             &raw,
+            FileListDelimiter::Newline,
             line_number == 1,
             line_start_offset,
             line_number,
@@ -397,32 +580,131 @@ pub fn parse_filelist_stream<F, C>(
     include_files: bool,
     include_dirs: bool,
     should_cancel: C,
+    on_entry: F,
+) -> Result<()>
+where
+    F: FnMut(PathBuf, Option<bool>),
+    C: Fn() -> bool,
+{
+    parse_filelist_stream_with_total(
+        filelist_path,
+        root,
+        include_files,
+        include_dirs,
+        should_cancel,
+        |_| Ok(()),
+        on_entry,
+    )
+}
+
+/// 検証後、entry を流す前に `on_total` へ見込み件数を渡す。
+/// 件数は file/dir 両方を取り込み `!` 行が無いときだけ確定でき、それ以外は `None` を渡す。
+pub fn parse_filelist_stream_with_total<F, C, T>(
+    filelist_path: &Path,
+    root: &Path,
+    include_files: bool,
+    include_dirs: bool,
+    should_cancel: C,
+    on_total: T,
     mut on_entry: F,
 ) -> Result<()>
 where
     F: FnMut(PathBuf, Option<bool>),
     C: Fn() -> bool,
+    T: FnOnce(Option<usize>) -> Result<()>,
 {
-    let mut reader = open_validated_filelist(filelist_path, &should_cancel)?;
+    // `!` lines win over includes wherever they appear; the validation pass already collected them.
+    let (mut reader, delimiter, scan) =
+        open_validated_filelist_with_delimiter(filelist_path, &should_cancel)?;
+    let exclusions = scan.exclusions;
+    let total =
+        (include_files && include_dirs && exclusions.is_empty()).then_some(scan.entry_lines);
+    on_total(total)?;
 
-    let mut raw = Vec::new();
     let mut seen = HashSet::new();
     let filelist_base = filelist_path.parent().unwrap_or(root);
+    let bases = [filelist_base, root];
+    let mut emit = |path: PathBuf, is_dir: Option<bool>| {
+        if !exclusions.excludes(&path, &bases) && seen.insert(path.clone()) {
+            on_entry(path, is_dir);
+        }
+    };
+    for_each_filelist_line(
+        &mut reader,
+        filelist_path,
+        delimiter,
+        &should_cancel,
+        |line| {
+            if filelist_exclusion_pattern(line).is_some() {
+                return;
+            }
+            let candidates = resolve_filelist_entry_candidates(
+                unescape_filelist_entry(line),
+                filelist_base,
+                root,
+            );
+            if include_files && include_dirs {
+                // Keep FileList indexing on the current control fast path: choose the
+                // platform-preferred lexical candidate and avoid per-line existence probes
+                // in the initial stream.
+                if let Some(path) = candidates.into_iter().next() {
+                    emit(path, None);
+                }
+                return;
+            }
+
+            for candidate in candidates {
+                let Ok(meta) = candidate.metadata() else {
+                    continue;
+                };
+                let is_dir = meta.is_dir();
+                let is_file = meta.is_file();
+                if is_file && !include_files {
+                    continue;
+                }
+                if is_dir && !include_dirs {
+                    continue;
+                }
+                if !is_file && !is_dir {
+                    continue;
+                }
+                emit(candidate, Some(is_dir));
+                break;
+            }
+        },
+    )
+}
+
+/// 検証済み FileList を読み、空行と `#` コメントを除いた trim 済みの各行を渡す。
+fn for_each_filelist_line<R, C>(
+    reader: &mut R,
+    filelist_path: &Path,
+    delimiter: FileListDelimiter,
+    should_cancel: &C,
+    mut on_line: impl FnMut(&str),
+) -> Result<()>
+where
+    R: BufRead,
+    C: Fn() -> bool,
+{
+    let mut raw = Vec::new();
     let mut line_number = 1usize;
     let mut line_start_offset = 0usize;
     loop {
         let bytes_read = read_bounded_raw_line(
-            &mut reader,
+            reader,
             &mut raw,
             filelist_path,
             line_number,
-            &should_cancel,
+            delimiter,
+            should_cancel,
         )?;
         if bytes_read == 0 {
             break;
         }
         let validated = validate_filelist_line(
             &raw,
+            delimiter,
             line_number == 1,
             line_start_offset,
             line_number,
@@ -434,39 +716,7 @@ where
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
-        let candidates = resolve_filelist_entry_candidates(line, filelist_base, root);
-        if include_files && include_dirs {
-            // Keep FileList indexing on the current control fast path: choose the
-            // platform-preferred lexical candidate and avoid per-line existence probes
-            // in the initial stream.
-            if let Some(path) = candidates.into_iter().next() {
-                if seen.insert(path.clone()) {
-                    on_entry(path, None);
-                }
-            }
-            continue;
-        }
-
-        for candidate in candidates {
-            let Ok(meta) = candidate.metadata() else {
-                continue;
-            };
-            let is_dir = meta.is_dir();
-            let is_file = meta.is_file();
-            if is_file && !include_files {
-                continue;
-            }
-            if is_dir && !include_dirs {
-                continue;
-            }
-            if !is_file && !is_dir {
-                continue;
-            }
-            if seen.insert(candidate.clone()) {
-                on_entry(candidate, Some(is_dir));
-            }
-            break;
-        }
+        on_line(line);
     }
     Ok(())
 }
@@ -6,6 +6,9 @@ use std::fs::File;
 use std::path::{Component, Path, PathBuf};
 use std::time::SystemTime;
 
+use crate::ui_model::format_system_time;
+
+use super::filelist_exclude::{escape_filelist_entry, unescape_filelist_entry};
 use super::filelist_reader::{
     looks_like_windows_absolute_path, read_filelist_text_strict, strip_wrapping_quotes,
 };
@@ -43,10 +46,47 @@ where
     })
 }
 
+/// FileList 先頭へ置く `#` 行の metadata header を組み立てる。読み込み側は `#` 行を読み飛ばす。
+/// 空の query は `(none)` と書き、改行は header 行を壊さないよう空白にする。
+pub fn build_filelist_header(
+    root: &Path,
+    query: &str,
+    count: usize,
+    created_at: SystemTime,
+) -> String {
+    let created = format_system_time(created_at).unwrap_or_else(|| "unknown".to_string());
+    let query = query.trim();
+    let query = if query.is_empty() {
+        "(none)".to_string()
+    } else {
+        query.replace(['\r', '\n'], " ")
+    };
+    format!(
+        "# FlistWalker FileList\n# root: {}\n# query: {}\n# created: {}\n# entries: {}\n",
+        root.display(),
+        query,
+        created,
+        count
+    )
+}
+
+/// entry を FileList の 1 行にする。`!` で始まる相対 path は除外行と誤読されないよう `\!` にする。
 pub(super) fn filelist_line_for_entry(
     entry: &Path,
     root_lexical: &Path,
     root_canonical: Option<&Path>,
+) -> String {
+    escape_filelist_entry(filelist_path_text_for_entry(
+        entry,
+        root_lexical,
+        root_canonical,
+    ))
+}
+
+fn filelist_path_text_for_entry(
+    entry: &Path,
+    root_lexical: &Path,
+    root_canonical: Option<&Path>,
 ) -> String {
     if let Ok(relative) = entry.strip_prefix(root_lexical) {
         return normalize_relative_lexically(relative)
@@ -149,9 +189,10 @@ fn find_all_filelists_in_directory(dir: &Path) -> std::io::Result<Vec<PathBuf>>
 
 pub(crate) fn normalize_filelist_entry_for_text_compare(line: &str) -> Option<String> {
     let trimmed = line.trim();
-    if trimmed.is_empty() || trimmed.starts_with('#') {
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
         return None;
     }
+    let trimmed = unescape_filelist_entry(trimmed);
 
     let raw = strip_wrapping_quotes(trimmed);
     if raw.is_empty() {
@@ -360,13 +401,21 @@ pub fn write_filelist(
     filename: &str,
     propagate_to_ancestors: bool,
 ) -> Result<PathBuf> {
-    write_filelist_cancellable(root, entries, filename, propagate_to_ancestors, &|| false)
+    write_filelist_cancellable(
+        root,
+        entries,
+        filename,
+        None,
+        propagate_to_ancestors,
+        &|| false,
+    )
 }
 
 pub fn write_filelist_cancellable<C>(
     root: &Path,
     entries: &[PathBuf],
     filename: &str,
+    header: Option<&str>,
     propagate_to_ancestors: bool,
     should_cancel: &C,
 ) -> Result<PathBuf>
@@ -374,7 +423,10 @@ where
     C: Fn() -> bool,
 {
     let out = root.join(filename);
-    let text = build_filelist_text_cancellable(entries, root, should_cancel)?;
+    let mut text = build_filelist_text_cancellable(entries, root, should_cancel)?;
+    if let Some(header) = header {
+        text.insert_str(0, header);
+    }
     if should_cancel() {
         anyhow::bail!("filelist creation canceled");
     }
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+/// root 配下で git 管理下にある file を `git ls-files -z` で列挙する。
+/// git が無い、または root が repository 外なら `None` を返し、呼び出し側は walker へ戻す。
+pub fn git_tracked_files(root: &Path) -> Option<Vec<PathBuf>> {
+    let mut command = Command::new("git");
+    command
+        .args(["ls-files", "-z"])
+        .current_dir(root)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(target_os = "windows")]
+    command.creation_flags(CREATE_NO_WINDOW);
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_git_ls_files_output(root, &output.stdout))
+}
+
+/// `git ls-files -z` の NUL 区切り出力 (root からの相対 path) を絶対 path に変換する。
+pub fn parse_git_ls_files_output(root: &Path, stdout: &[u8]) -> Vec<PathBuf> {
+    stdout
+        .split(|byte| *byte == 0)
+        .filter(|raw| !raw.is_empty())
+        .map(|raw| {
+            let mut path = root.to_path_buf();
+            for component in raw.split(|byte| *byte == b'/') {
+                if !component.is_empty() {
+                    path.push(component_from_bytes(component));
+                }
+            }
+            path
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn component_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+fn component_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// tracked file 群から root 配下の中間 directory を重複なく導出する。root 自身は含めない。
+pub fn git_tracked_dirs(root: &Path, files: &[PathBuf]) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    let mut dirs = Vec::new();
+    for file in files {
+        let mut current = file.parent();
+        while let Some(dir) = current {
+            if dir == root || !dir.starts_with(root) || !seen.insert(dir.to_path_buf()) {
+                break;
+            }
+            dirs.push(dir.to_path_buf());
+            current = dir.parent();
+        }
+    }
+    dirs
+}
@@ -1,6 +1,8 @@
+mod filelist_exclude;
 mod filelist_hierarchy;
 mod filelist_reader;
 mod filelist_writer;
+mod git;
 mod walker;
 
 use crate::entry::Entry;
@@ -10,19 +12,23 @@ use std::time::Instant;
 use tracing::info;
 
 pub use filelist_reader::{
-    apply_filelist_hierarchy_overrides, build_entries_from_filelist_hierarchy, find_filelist,
-    find_filelist_in_first_level, parse_filelist, parse_filelist_stream,
+    apply_filelist_hierarchy_overrides, build_entries_from_filelist_hierarchy,
+    diff_filelist_entries, find_filelist, find_filelist_in_first_level, parse_filelist,
+    parse_filelist_reader, parse_filelist_stream, parse_filelist_stream_with_total, FileListDiff,
 };
 pub use filelist_writer::{
-    ancestor_filelist_propagation_needed, build_filelist_text, build_filelist_text_cancellable,
-    has_ancestor_filelists, write_filelist, write_filelist_cancellable,
+    ancestor_filelist_propagation_needed, build_filelist_header, build_filelist_text,
+    build_filelist_text_cancellable, has_ancestor_filelists, write_filelist,
+    write_filelist_cancellable,
 };
+pub use git::{git_tracked_dirs, git_tracked_files, parse_git_ls_files_output};
 pub use walker::{walk_dirs, walk_entries, walk_files};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IndexSource {
     FileList(PathBuf),
     Walker,
+    Git,
     None,
 }
 
@@ -1,5 +1,6 @@
 mod perf;
 
+use super::filelist_exclude::FileListExclusions;
 #[cfg(not(windows))]
 use super::filelist_reader::windows_path_to_wsl;
 use super::filelist_reader::{
@@ -97,6 +98,44 @@ fn find_filelist_accepts_lowercase_name() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn parse_filelist_reader_resolves_lines_against_root_and_skips_comments() {
+    let root = PathBuf::from("/tmp/flist-root");
+    let parsed = parse_filelist_reader(
+        "\u{feff}# comment\nsrc/main.rs\n\n/abs/other.rs\nsrc/main.rs\n".as_bytes(),
+        Path::new("<stdin>"),
+        &root,
+    )
+    .expect("parse reader");
+    assert_eq!(
+        parsed,
+        vec![root.join("src/main.rs"), PathBuf::from("/abs/other.rs")]
+    );
+}
+
+#[test]
+fn diff_filelist_entries_keeps_list_order_for_added_and_removed() {
+    let old = vec![
+        PathBuf::from("/r/b"),
+        PathBuf::from("/r/a"),
+        PathBuf::from("/r/c"),
+    ];
+    let new = vec![
+        PathBuf::from("/r/d"),
+        PathBuf::from("/r/a"),
+        PathBuf::from("/r/e"),
+    ];
+    let diff = diff_filelist_entries(&old, &new);
+    assert_eq!(
+        diff.added,
+        vec![PathBuf::from("/r/d"), PathBuf::from("/r/e")]
+    );
+    assert_eq!(
+        diff.removed,
+        vec![PathBuf::from("/r/b"), PathBuf::from("/r/c")]
+    );
+}
+
 #[test]
 fn parse_filelist_resolves_relative_and_absolute_paths() {
     let root = test_root("parse");
@@ -124,6 +163,105 @@ fn parse_filelist_resolves_relative_and_absolute_paths() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn parse_filelist_excludes_nested_paths_marked_with_bang_lines() {
+    let root = test_root("parse-exclusions");
+    fs::create_dir_all(root.join("docs/private")).expect("create dir");
+    let filelist = root.join("FileList.txt");
+    fs::write(
+        &filelist,
+        "!docs/private/\ndocs\ndocs/readme.md\ndocs/private\ndocs/private/secret.md\nbuild/run.log\n!*.log\n",
+    )
+    .expect("write filelist");
+
+    let parsed = parse_filelist(&filelist, &root, true, true).expect("parse filelist");
+    assert_eq!(parsed, vec![root.join("docs"), root.join("docs/readme.md")]);
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn parse_filelist_reader_applies_bang_exclusions_after_includes() {
+    let root = PathBuf::from("/tmp/flist-root");
+    let parsed = parse_filelist_reader(
+        "docs/a.md\ndocs/private/b.md\n!docs/private\nsrc/main.rs\n".as_bytes(),
+        Path::new("<stdin>"),
+        &root,
+    )
+    .expect("parse reader");
+    assert_eq!(
+        parsed,
+        vec![root.join("docs/a.md"), root.join("src/main.rs")]
+    );
+}
+
+#[test]
+fn parse_filelist_reader_rejects_invalid_lines_like_filelist_files() {
+    let root = PathBuf::from("/tmp/flist-root");
+    let err = parse_filelist_reader(
+        b"src/main.rs\nbad\0name\n".as_slice(),
+        Path::new("<stdin>"),
+        &root,
+    )
+    .expect_err("NUL inside a newline list must fail");
+    assert!(err.to_string().contains("NUL bytes are not allowed"));
+
+    let err = parse_filelist_reader(b"ok\n\xFF\n".as_slice(), Path::new("<stdin>"), &root)
+        .expect_err("invalid UTF-8 must fail");
+    assert!(err
+        .to_string()
+        .contains("invalid FileList encoding at byte 3"));
+}
+
+#[test]
+fn filelist_with_bang_named_entries_round_trips_through_write_and_read() {
+    let root = test_root("write-filelist-bang-names");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("docs")).expect("create dir");
+    fs::write(root.join("!foo"), "x").expect("write file");
+    fs::write(root.join("docs/!notes.md"), "x").expect("write file");
+    let entries = vec![root.join("!foo"), root.join("docs/!notes.md")];
+
+    let out = write_filelist_cancellable(&root, &entries, "FileList.txt", None, false, &|| false)
+        .expect("write filelist");
+
+    let content = fs::read_to_string(&out).expect("read filelist");
+    assert_eq!(content, "\\!foo\ndocs/!notes.md\n");
+    assert_eq!(
+        parse_filelist_reader(content.as_bytes(), &out, &root).expect("parse reader"),
+        entries
+    );
+    assert_eq!(
+        parse_filelist(&out, &root, true, true).expect("parse filelist"),
+        entries
+    );
+    assert_eq!(
+        normalize_filelist_entry_for_text_compare("\\!foo"),
+        Some("!foo".to_string())
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn filelist_exclusion_globs_follow_gitignore_style_anchoring() {
+    let excluded = |pattern: &str, relative: &str| {
+        let mut exclusions = FileListExclusions::default();
+        exclusions.add(pattern);
+        let base = Path::new("/work");
+        exclusions.excludes(&base.join(relative), &[base])
+    };
+    assert!(excluded("docs/private/", "docs/private/notes/secret.md"));
+    assert!(!excluded("docs/private/", "docs/private-notes.md"));
+    assert!(!excluded("docs/private/", "src/docs/private/a.md"));
+    assert!(excluded("/build", "build/out.txt"));
+    assert!(excluded("*.log", "build/out/run.log"));
+    assert!(!excluded("*.log", "build/run.log.txt"));
+    assert!(excluded("docs/*/draft.md", "docs/a/draft.md"));
+    assert!(!excluded("docs/*/draft.md", "docs/a/b/draft.md"));
+    assert!(excluded("docs/**/draft.md", "docs/draft.md"));
+    assert!(excluded("docs/**/draft.md", "docs/a/b/draft.md"));
+    assert!(!excluded("", "docs/a.md"));
+}
+
 #[test]
 fn build_index_uses_filelist_when_present() {
     let root = test_root("build-filelist");
@@ -178,6 +316,44 @@ fn build_index_with_metadata_reports_walker_source() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn parse_git_ls_files_output_splits_nul_delimited_paths_under_root() {
+    let root = PathBuf::from("repo");
+    let stdout = b"README.md\0src/main.rs\0src/app/mod.rs\0name with space.txt\0";
+
+    let files = parse_git_ls_files_output(&root, stdout);
+    assert_eq!(
+        files,
+        vec![
+            root.join("README.md"),
+            root.join("src").join("main.rs"),
+            root.join("src").join("app").join("mod.rs"),
+            root.join("name with space.txt"),
+        ]
+    );
+
+    let dirs = git_tracked_dirs(&root, &files);
+    assert_eq!(dirs, vec![root.join("src"), root.join("src").join("app")]);
+}
+
+#[test]
+fn git_tracked_files_returns_none_outside_git_repository() {
+    let root = test_root("git-outside");
+    fs::create_dir_all(&root).expect("create dir");
+    fs::write(root.join("a.txt"), "x").expect("write file");
+
+    // Temp roots are never inside a repository unless TMPDIR itself is one.
+    if std::process::Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(&root)
+        .output()
+        .is_ok_and(|output| !output.status.success())
+    {
+        assert_eq!(git_tracked_files(&root), None);
+    }
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn walkers_are_separated_for_files_and_dirs() {
     let root = test_root("walk-separate");
@@ -408,6 +584,43 @@ fn parse_and_write_filelist() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn write_filelist_header_comment_round_trips_through_parse_filelist_reader() {
+    let root = test_root("write-filelist-header");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).expect("create dir");
+    let header = build_filelist_header(
+        &root,
+        "main\nrs",
+        1,
+        UNIX_EPOCH + Duration::from_secs(86_400),
+    );
+
+    let out = write_filelist_cancellable(
+        &root,
+        &[root.join("src/main.rs")],
+        "FileList.txt",
+        Some(&header),
+        false,
+        &|| false,
+    )
+    .expect("write filelist");
+
+    let content = fs::read_to_string(&out).expect("read filelist");
+    assert_eq!(
+        content,
+        format!(
+            "# FlistWalker FileList\n# root: {}\n# query: main rs\n# created: 1970-01-02 00:00 UTC\n# entries: 1\nsrc/main.rs\n",
+            root.display()
+        )
+    );
+    assert_eq!(
+        parse_filelist_reader(content.as_bytes(), &out, &root).expect("parse reader"),
+        vec![root.join("src/main.rs")]
+    );
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn write_filelist_cancellable_stops_before_replacing_output() {
     let root = test_root("write-filelist-cancel");
@@ -420,6 +633,7 @@ fn write_filelist_cancellable_stops_before_replacing_output() {
         &root,
         &[root.join("src/main.rs")],
         "FileList.txt",
+        None,
         false,
         &|| true,
     )
@@ -534,6 +748,34 @@ fn tc161_rejects_utf16_legacy_invalid_utf8_nul_and_truncated_sequences() {
     );
 }
 
+#[test]
+fn parse_filelist_splits_nul_delimited_entries() {
+    let root = test_root("filelist-nul-delimited");
+    fs::create_dir_all(root.join("src")).expect("create dir");
+    let main = root.join("src").join("main.rs");
+    let spaced = root.join("name with space.txt");
+    fs::write(&main, "x").expect("write main");
+    fs::write(&spaced, "x").expect("write spaced");
+    let filelist = root.join("FileList.txt");
+    fs::write(
+        &filelist,
+        b"src/main.rs\0# comment\0\"name with space.txt\"\0\0",
+    )
+    .expect("write NUL filelist");
+
+    let entries = parse_filelist(&filelist, &root, true, true).expect("parse NUL filelist");
+    assert_eq!(entries, vec![main.clone(), spaced.clone()]);
+
+    let text_entries = parse_filelist_reader(
+        b"src/main.rs\0\"name with space.txt\"\0".as_slice(),
+        Path::new("<stdin>"),
+        &root,
+    )
+    .expect("parse NUL reader");
+    assert_eq!(text_entries, vec![main, spaced]);
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn tc161_stable_invalid_root_emits_no_valid_prefix_and_reports_byte_offset() {
     let root = test_root("tc161-invalid-after-valid-prefix");
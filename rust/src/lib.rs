@@ -8,10 +8,68 @@ pub mod path_utils;
 pub mod query;
 pub mod runtime_config;
 pub mod search;
+pub mod serve;
 pub mod ui_model;
 pub mod update_security;
 pub mod updater;
 
+use std::path::{Path, PathBuf};
+
+/// `index_and_search` の CLI 相当オプション。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexSearchOptions {
+    pub limit: usize,
+    pub use_filelist: bool,
+    pub include_files: bool,
+    pub include_dirs: bool,
+    pub use_regex: bool,
+    pub ignore_case: bool,
+}
+
+impl Default for IndexSearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 1000,
+            use_filelist: true,
+            include_files: true,
+            include_dirs: true,
+            use_regex: false,
+            ignore_case: true,
+        }
+    }
+}
+
+/// root を index して query を検索する。空 query は index 順の先頭 `limit` 件を score 0 で返す。
+pub fn index_and_search(
+    root: &Path,
+    query: &str,
+    options: IndexSearchOptions,
+) -> anyhow::Result<Vec<(PathBuf, f64)>> {
+    let entries = indexer::build_index(
+        root,
+        options.use_filelist,
+        options.include_files,
+        options.include_dirs,
+    )?;
+    if query.trim().is_empty() {
+        return Ok(entries
+            .into_iter()
+            .take(options.limit)
+            .map(|path| (path, 0.0))
+            .collect());
+    }
+    search::try_search_entries_with_scope(
+        query,
+        &entries,
+        options.limit,
+        options.use_regex,
+        options.ignore_case,
+        Some(root),
+        true,
+    )
+    .map_err(anyhow::Error::msg)
+}
+
 #[cfg(test)]
 pub(crate) fn env_var_test_lock() -> &'static std::sync::Mutex<()> {
     use std::sync::{Mutex, OnceLock};
@@ -19,3 +77,52 @@ pub(crate) fn env_var_test_lock() -> &'static std::sync::Mutex<()> {
     static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
     LOCK.get_or_init(|| Mutex::new(()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn index_and_search_walks_root_and_ranks_matches() {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("fff-rs-index-and-search-{nonce}"));
+        fs::create_dir_all(root.join("src")).expect("create dir");
+        fs::write(root.join("src").join("main.rs"), "").expect("write file");
+        fs::write(root.join("README.md"), "").expect("write file");
+
+        let results =
+            index_and_search(&root, "main", IndexSearchOptions::default()).expect("search");
+        assert_eq!(
+            results
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>(),
+            vec![root.join("src").join("main.rs")]
+        );
+
+        let dirs_only = IndexSearchOptions {
+            include_files: false,
+            ..IndexSearchOptions::default()
+        };
+        let results = index_and_search(&root, "", dirs_only).expect("list");
+        assert_eq!(
+            results
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>(),
+            vec![root.join("src")]
+        );
+
+        let invalid = IndexSearchOptions {
+            use_regex: true,
+            ..IndexSearchOptions::default()
+        };
+        assert!(index_and_search(&root, "(", invalid).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
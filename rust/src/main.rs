@@ -12,10 +12,13 @@ use tracing_subscriber::EnvFilter;
 
 use flist_walker::app::{configure_egui_fonts, request_process_shutdown, FlistWalkerApp};
 use flist_walker::ignore_list::{ensure_ignore_list_sample, load_ignore_terms_from_current_exe};
-use flist_walker::indexer::build_index;
+use flist_walker::indexer::{
+    build_index, diff_filelist_entries, parse_filelist, parse_filelist_reader, FileListDiff,
+};
 use flist_walker::query::{CompiledIgnoreTerms, QueryScope};
 use flist_walker::runtime_config::initialize_runtime_config;
 use flist_walker::search::search_entries_with_scope;
+use flist_walker::serve::{serve_unix_socket, SearchServer};
 use flist_walker::updater::{
     recover_interrupted_update_on_startup, run_internal_update_helper_if_requested,
 };
@@ -37,8 +40,28 @@ struct Args {
     root: Option<PathBuf>,
     #[arg(long, default_value_t = 1000)]
     limit: usize,
+    #[arg(long, default_value_t = 0, requires = "cli")]
+    offset: usize,
     #[arg(long, default_value_t = false)]
     cli: bool,
+    #[arg(long, default_value_t = false, conflicts_with = "case_sensitive")]
+    ignore_case: bool,
+    #[arg(long, default_value_t = false)]
+    case_sensitive: bool,
+    #[arg(long, value_name = "PATH", requires = "cli")]
+    filelist: Option<PathBuf>,
+    #[arg(long, default_value_t = false, requires = "cli")]
+    stats: bool,
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        requires = "cli",
+        conflicts_with = "filelist"
+    )]
+    diff_filelist: Option<Vec<PathBuf>>,
+    #[arg(long, value_name = "SOCKET", conflicts_with = "cli")]
+    serve: Option<PathBuf>,
 }
 
 #[cfg(target_os = "windows")]
@@ -56,39 +79,127 @@ fn configure_windows_dpi_mode() {
 #[cfg(not(target_os = "windows"))]
 fn configure_windows_dpi_mode() {}
 
-fn run_cli(args: &Args) -> Result<()> {
-    let root = resolve_root(args.root.as_deref().unwrap_or(Path::new(".")))?;
+fn load_cli_entries(filelist: Option<&Path>, root: &Path) -> Result<Vec<PathBuf>> {
+    match filelist {
+        Some(path) if path == Path::new("-") => {
+            parse_filelist_reader(std::io::stdin().lock(), Path::new("<stdin>"), root)
+        }
+        Some(path) => {
+            let file = std::fs::File::open(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            parse_filelist_reader(file, path, root)
+        }
+        None => build_index(root, true, true, true),
+    }
+}
+
+fn load_filtered_cli_entries(args: &Args, root: &Path) -> Result<Vec<PathBuf>> {
     let ignore_terms = load_ignore_terms_from_current_exe();
     let compiled_ignore_terms = CompiledIgnoreTerms::compile(&ignore_terms, true);
-    let entries = build_index(&root, true, true, true)?
+    Ok(load_cli_entries(args.filelist.as_deref(), root)?
         .into_iter()
         .filter(|path| {
             !compiled_ignore_terms.matches_path(
                 path,
                 QueryScope {
-                    root: Some(&root),
+                    root: Some(root),
                     prefer_relative: true,
                     ignore_case: true,
+                    fold_diacritics: false,
+                    tail_components: None,
                 },
             )
         })
-        .collect::<Vec<_>>();
+        .collect())
+}
+
+/// 2 つの FileList を比べる。相対 path はそれぞれの FileList がある folder 基準で解決する。
+fn filelist_diff(old: &Path, new: &Path, root: &Path) -> Result<FileListDiff> {
+    Ok(diff_filelist_entries(
+        &parse_filelist(old, root, true, true)?,
+        &parse_filelist(new, root, true, true)?,
+    ))
+}
+
+/// 2 つの FileList の差分を `+ path` / `- path` で標準出力へ、件数を標準エラー出力へ出す。
+fn run_filelist_diff(old: &Path, new: &Path, root: &Path) -> Result<()> {
+    let diff = filelist_diff(old, new, root)?;
+    for path in &diff.added {
+        println!("+ {}", path.display());
+    }
+    for path in &diff.removed {
+        println!("- {}", path.display());
+    }
+    eprintln!(
+        "diff: added={}, removed={}",
+        diff.added.len(),
+        diff.removed.len()
+    );
+    Ok(())
+}
+
+fn run_cli(args: &Args) -> Result<()> {
+    let root = resolve_root(args.root.as_deref().unwrap_or(Path::new(".")))?;
+    if let Some([old, new]) = args.diff_filelist.as_deref() {
+        return run_filelist_diff(old, new, &root);
+    }
+    let index_start = Instant::now();
+    let entries = load_filtered_cli_entries(args, &root)?;
+    let index_elapsed = index_start.elapsed();
     let query = args.query.trim();
     if query.is_empty() {
-        for path in entries.iter().take(args.limit) {
+        let shown = entries.len().saturating_sub(args.offset).min(args.limit);
+        for path in entries.iter().skip(args.offset).take(args.limit) {
             println!("{}", path.display());
         }
+        if args.stats {
+            eprintln!(
+                "stats: indexed={} in {:.1}ms, shown={}",
+                entries.len(),
+                index_elapsed.as_secs_f64() * 1000.0,
+                shown
+            );
+        }
         return Ok(());
     }
 
-    let results =
-        search_entries_with_scope(query, &entries, args.limit, false, true, Some(&root), true);
+    let ignore_case = !args.case_sensitive;
+    let search_start = Instant::now();
+    // Score the leading window too so each page keeps the same ranking order.
+    let results = search_entries_with_scope(
+        query,
+        &entries,
+        args.offset.saturating_add(args.limit),
+        false,
+        ignore_case,
+        Some(&root),
+        true,
+    );
+    let search_elapsed = search_start.elapsed();
+    let results = results.into_iter().skip(args.offset).collect::<Vec<_>>();
+    let matched = results.len();
     for (path, score) in results {
         println!("[{score:6.1}] {}", path.display());
     }
+    if args.stats {
+        eprintln!(
+            "stats: indexed={} in {:.1}ms, matched={} in {:.1}ms",
+            entries.len(),
+            index_elapsed.as_secs_f64() * 1000.0,
+            matched,
+            search_elapsed.as_secs_f64() * 1000.0
+        );
+    }
     Ok(())
 }
 
+fn run_serve(args: &Args, socket_path: &Path) -> Result<()> {
+    let root = resolve_root(args.root.as_deref().unwrap_or(Path::new(".")))?;
+    let entries = load_filtered_cli_entries(args, &root)?;
+    let server = SearchServer::new(entries, root, args.limit, !args.case_sensitive);
+    serve_unix_socket(server, socket_path)
+}
+
 fn run_gui(args: &Args) -> Result<()> {
     let startup_start = Instant::now();
     trace_startup_phase(startup_start, "run_gui_enter");
@@ -300,7 +411,9 @@ fn main() -> Result<()> {
     if let Err(err) = ensure_ignore_list_sample() {
         warn!("failed to materialize ignore list sample: {}", err);
     }
-    if args.cli {
+    if let Some(socket_path) = args.serve.as_deref() {
+        run_serve(&args, socket_path)
+    } else if args.cli {
         run_cli(&args)
     } else {
         run_gui(&args)
@@ -311,6 +424,20 @@ fn main() -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn args_default_to_ignore_case_and_accept_case_sensitive_flag() {
+        let args = Args::try_parse_from(["flistwalker", "--cli", "Main"]).expect("parse args");
+        assert!(!args.case_sensitive);
+
+        let args = Args::try_parse_from(["flistwalker", "--cli", "--case-sensitive", "Main"])
+            .expect("parse args");
+        assert!(args.case_sensitive);
+
+        assert!(
+            Args::try_parse_from(["flistwalker", "--ignore-case", "--case-sensitive"]).is_err()
+        );
+    }
+
     #[test]
     fn build_root_viewport_applies_defaults() {
         let viewport = build_root_viewport(None, None);
@@ -339,4 +466,46 @@ mod tests {
         assert_eq!(viewport.min_inner_size, Some(MIN_WINDOW_SIZE));
         assert!(viewport.icon.is_some());
     }
+
+    #[test]
+    fn cli_filelist_flag_requires_cli_and_resolves_lines_against_root() {
+        assert!(Args::try_parse_from(["flistwalker", "--filelist", "-"]).is_err());
+        let args = Args::try_parse_from(["flistwalker", "--cli", "--filelist", "-", "main"])
+            .expect("parse args");
+        assert_eq!(args.filelist.as_deref(), Some(Path::new("-")));
+
+        let dir =
+            std::env::temp_dir().join(format!("flistwalker-cli-filelist-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let list = dir.join("paths.txt");
+        std::fs::write(&list, "src/main.rs\n# skipped\n").expect("write list");
+        let root = PathBuf::from("/tmp/cli-root");
+
+        let entries = load_cli_entries(Some(&list), &root).expect("load entries");
+
+        assert_eq!(entries, vec![root.join("src/main.rs")]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn filelist_diff_resolves_each_list_against_its_own_folder() {
+        let dir = std::env::temp_dir().join(format!("flistwalker-cli-diff-{}", std::process::id()));
+        let old_dir = dir.join("old");
+        let new_dir = dir.join("new");
+        std::fs::create_dir_all(&old_dir).expect("create old dir");
+        std::fs::create_dir_all(&new_dir).expect("create new dir");
+        let old = old_dir.join("FileList.txt");
+        let new = new_dir.join("FileList.txt");
+        std::fs::write(&old, "kept.txt\ngone.txt\n").expect("write old list");
+        std::fs::write(&new, "kept.txt\n").expect("write new list");
+
+        let diff = filelist_diff(&old, &new, Path::new("/tmp/cli-root")).expect("diff lists");
+
+        assert_eq!(diff.added, vec![new_dir.join("kept.txt")]);
+        assert_eq!(
+            diff.removed,
+            vec![old_dir.join("kept.txt"), old_dir.join("gone.txt")]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -2,6 +2,7 @@ use std::path::Path;
 
 mod compiled;
 
+pub(crate) use compiled::fold_case;
 #[cfg(test)]
 pub(crate) use compiled::{ignore_compile_count, query_compile_count, reset_compile_counts};
 pub use compiled::{
@@ -14,6 +15,8 @@ pub struct QuerySpec {
     pub include_terms: Vec<String>,
     pub exact_terms: Vec<String>,
     pub exclude_terms: Vec<String>,
+    /// `@rs,toml` で指定された拡張子 (先頭 `.` なし)。空なら拡張子で絞り込まない。
+    pub extensions: Vec<String>,
 }
 
 pub fn include_alternatives(term: &str) -> Vec<&str> {
@@ -72,14 +75,24 @@ pub fn parse_include_alternative(candidate: &str) -> Option<(bool, String)> {
 }
 
 pub fn parse_query(query: &str) -> QuerySpec {
+    parse_query_for_mode(query, false)
+}
+
+/// regex mode では `@` も pattern の一部なので、`@ext` 拡張子指定を解釈せず通常 token として残す。
+pub(crate) fn parse_query_for_mode(query: &str, use_regex: bool) -> QuerySpec {
     let mut include_terms = Vec::new();
     let mut exact_terms = Vec::new();
     let mut exclude_terms = Vec::new();
+    let mut extensions = Vec::new();
 
     for token in query.split_whitespace() {
         if token.is_empty() || token == "!" || token == "'" {
             continue;
         }
+        if let Some(parsed) = (!use_regex).then(|| parse_extension_token(token)).flatten() {
+            extensions.extend(parsed);
+            continue;
+        }
         if let Some(stripped) = token.strip_prefix('!') {
             if !stripped.is_empty() {
                 exclude_terms.push(normalize_quoted_term(stripped));
@@ -104,9 +117,21 @@ pub fn parse_query(query: &str) -> QuerySpec {
         include_terms,
         exact_terms,
         exclude_terms,
+        extensions,
     }
 }
 
+/// `@ext1,ext2` 形式の token を拡張子一覧へ変換する。
+/// 各拡張子は英数字のみで空要素を許さない。`@types/node` や `@rs,` のような token は通常 token 扱い。
+fn parse_extension_token(token: &str) -> Option<Vec<String>> {
+    let list = token.strip_prefix('@')?;
+    list.split(',')
+        .map(|ext| {
+            (!ext.is_empty() && ext.chars().all(char::is_alphanumeric)).then(|| ext.to_string())
+        })
+        .collect()
+}
+
 pub fn token_uses_regex_syntax(token: &str) -> bool {
     token.chars().any(|ch| {
         matches!(
@@ -129,8 +154,8 @@ pub fn has_visible_match(
     let Ok(compiled) = CompiledQuery::compile(
         query,
         QueryOptions {
-            use_regex: false,
             ignore_case,
+            ..QueryOptions::default()
         },
     ) else {
         return false;
@@ -156,6 +181,8 @@ pub fn path_matches_ignore_terms(
             root: Some(root),
             prefer_relative,
             ignore_case,
+            fold_diacritics: false,
+            tail_components: None,
         },
     )
 }
@@ -163,9 +190,9 @@ pub fn path_matches_ignore_terms(
 #[cfg(test)]
 mod tests {
     use super::{
-        has_visible_match, parse_include_alternative, parse_query, path_matches_ignore_terms,
-        query_compile_count, reset_compile_counts, split_anchor, token_uses_regex_syntax,
-        CompiledQuery, EvidenceLevel, QueryOptions, QuerySpec,
+        has_visible_match, parse_include_alternative, parse_query, parse_query_for_mode,
+        path_matches_ignore_terms, query_compile_count, reset_compile_counts, split_anchor,
+        token_uses_regex_syntax, CompiledQuery, EvidenceLevel, QueryOptions, QuerySpec,
     };
     use std::path::PathBuf;
 
@@ -183,10 +210,110 @@ mod tests {
                 ],
                 exact_terms: vec!["file".to_string()],
                 exclude_terms: vec!["readme".to_string()],
+                extensions: Vec::new(),
             }
         );
     }
 
+    #[test]
+    fn parse_query_collects_extension_shorthand() {
+        let spec = parse_query("@rs,toml main @ @, @rs, @.md @types/node @angular-cli");
+
+        assert_eq!(
+            spec,
+            QuerySpec {
+                include_terms: vec![
+                    "main".to_string(),
+                    "@".to_string(),
+                    "@,".to_string(),
+                    "@rs,".to_string(),
+                    "@.md".to_string(),
+                    "@types/node".to_string(),
+                    "@angular-cli".to_string(),
+                ],
+                exact_terms: Vec::new(),
+                exclude_terms: Vec::new(),
+                extensions: vec!["rs".to_string(), "toml".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn extension_shorthand_restricts_matches_to_listed_extensions() {
+        let compiled = CompiledQuery::compile(
+            "@rs,TOML main",
+            QueryOptions {
+                ignore_case: true,
+                ..QueryOptions::default()
+            },
+        )
+        .expect("compile query");
+        let root = PathBuf::from("/tmp/root");
+        let matches = |name: &str| {
+            let prepared = compiled.prepare_candidate(&root.join(name), Some(&root), true);
+            compiled
+                .evaluate(&prepared, EvidenceLevel::RankOnly)
+                .is_some()
+        };
+
+        assert!(matches("src/main.rs"));
+        assert!(matches("main/Cargo.toml"));
+        assert!(!matches("src/main.py"));
+        assert!(!matches("src/main"));
+    }
+
+    #[test]
+    fn extension_shorthand_is_a_plain_term_in_regex_mode() {
+        assert_eq!(
+            parse_query_for_mode("@rs main", true).include_terms,
+            vec!["@rs".to_string(), "main".to_string()]
+        );
+        let compiled = CompiledQuery::compile(
+            "@rs",
+            QueryOptions {
+                use_regex: true,
+                ..QueryOptions::default()
+            },
+        )
+        .expect("compile query");
+        let root = PathBuf::from("/tmp/root");
+        let prepared = compiled.prepare_candidate(&root.join("@rs/lib.py"), Some(&root), true);
+
+        assert!(compiled
+            .evaluate(&prepared, EvidenceLevel::RankOnly)
+            .is_some());
+    }
+
+    #[test]
+    fn extension_shorthand_decides_folders_by_entry_kind() {
+        let root = PathBuf::from("/tmp/root");
+        let matches = |extension_filter_keeps_dirs: bool, name: &str, is_dir: Option<bool>| {
+            let compiled = CompiledQuery::compile(
+                "@d conf",
+                QueryOptions {
+                    extension_filter_keeps_dirs,
+                    ..QueryOptions::default()
+                },
+            )
+            .expect("compile query");
+            let prepared = compiled
+                .prepare_candidate(&root.join(name), Some(&root), true)
+                .with_is_dir(is_dir);
+            compiled
+                .evaluate(&prepared, EvidenceLevel::RankOnly)
+                .is_some()
+        };
+
+        // A dotted folder name is not an extension, so `conf.d` is dropped by default.
+        assert!(!matches(false, "etc/conf.d", Some(true)));
+        assert!(!matches(false, "etc/conf", Some(true)));
+        assert!(matches(false, "etc/conf.d", Some(false)));
+        assert!(matches(false, "etc/conf.d", None));
+        assert!(matches(true, "etc/conf", Some(true)));
+        assert!(matches(true, "etc/conf.d", Some(true)));
+        assert!(!matches(true, "etc/conf.txt", Some(false)));
+    }
+
     #[test]
     fn parse_include_alternative_keeps_exact_marker_information() {
         assert_eq!(
@@ -316,8 +443,8 @@ mod tests {
         let compiled = CompiledQuery::compile(
             "テスト 'main !vendor",
             QueryOptions {
-                use_regex: false,
                 ignore_case: true,
+                ..QueryOptions::default()
             },
         )
         .expect("compile query");
@@ -342,4 +469,159 @@ mod tests {
         assert!(highlighted_text.contains("main"));
         assert_eq!(query_compile_count(), 1);
     }
+
+    #[test]
+    fn fold_diacritics_matches_accented_names_and_maps_spans_to_original_chars() {
+        let root = PathBuf::from("/tmp/root");
+        let options = |fold_diacritics| QueryOptions {
+            ignore_case: true,
+            fold_diacritics,
+            ..QueryOptions::default()
+        };
+        let strict = CompiledQuery::compile("'resume", options(false)).expect("compile query");
+        let folded = CompiledQuery::compile("'resume", options(true)).expect("compile query");
+        let accented_query =
+            CompiledQuery::compile("'Résumé", options(true)).expect("compile query");
+
+        for (name, expected_spans) in [
+            ("docs/Résumé.txt", vec![5, 6, 7, 8, 9, 10]),
+            ("docs/Re\u{301}sume\u{301}.txt", vec![5, 6, 8, 9, 10, 11]),
+        ] {
+            let path = root.join(name);
+            let prepared = strict.prepare_candidate(&path, Some(&root), true);
+            assert!(strict
+                .evaluate(&prepared, EvidenceLevel::RankOnly)
+                .is_none());
+
+            let prepared = folded.prepare_candidate(&path, Some(&root), true);
+            let evaluation = folded
+                .evaluate(&prepared, EvidenceLevel::WithSpans)
+                .expect("folded match");
+            assert_eq!(evaluation.spans, expected_spans);
+
+            let prepared = accented_query.prepare_candidate(&path, Some(&root), true);
+            assert!(accented_query
+                .evaluate(&prepared, EvidenceLevel::RankOnly)
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn prefix_match_requires_segment_prefix_and_prefers_shorter_suffix() {
+        let root = PathBuf::from("/tmp/root");
+        let compiled = CompiledQuery::compile(
+            "MAI",
+            QueryOptions {
+                ignore_case: true,
+                prefix_match: true,
+                ..QueryOptions::default()
+            },
+        )
+        .expect("compile query");
+        let evaluate = |name: &str| {
+            let path = root.join(name);
+            let prepared = compiled.prepare_candidate(&path, Some(&root), true);
+            compiled.evaluate(&prepared, EvidenceLevel::WithSpans)
+        };
+
+        assert!(evaluate("src/domain.rs").is_none());
+        assert!(evaluate("src/m_a_i.rs").is_none());
+        let short = evaluate("src/main.rs").expect("basename prefix match");
+        assert_eq!(short.spans, vec![4, 5, 6]);
+        let long = evaluate("src/maintenance_notes.rs").expect("basename prefix match");
+        assert!(short.score > long.score);
+        let segment = evaluate("maint/domain.rs").expect("folder prefix match");
+        assert_eq!(segment.spans, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn match_tail_components_ignores_ancestor_directories() {
+        let root = PathBuf::from("/tmp/root");
+        let path = root.join("alpha/beta/gamma/file.rs");
+        let compile = |query: &str, match_tail_components| {
+            CompiledQuery::compile(
+                query,
+                QueryOptions {
+                    ignore_case: true,
+                    match_tail_components,
+                    ..QueryOptions::default()
+                },
+            )
+            .expect("compile query")
+        };
+
+        let full = compile("alpha", None);
+        let prepared = full.prepare_candidate(&path, Some(&root), true);
+        assert!(full.evaluate(&prepared, EvidenceLevel::RankOnly).is_some());
+
+        let tail = compile("alpha", Some(2));
+        let prepared = tail.prepare_candidate(&path, Some(&root), true);
+        assert!(tail.evaluate(&prepared, EvidenceLevel::RankOnly).is_none());
+
+        let tail = compile("gamma", Some(2));
+        let prepared = tail.prepare_candidate(&path, Some(&root), true);
+        let evaluation = tail
+            .evaluate(&prepared, EvidenceLevel::WithSpans)
+            .expect("tail match");
+        assert_eq!(evaluation.spans, vec![11, 12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn match_any_term_accepts_any_include_term_and_keeps_exclusions() {
+        let root = PathBuf::from("/tmp/root");
+        let compile = |query: &str, match_any_term| {
+            CompiledQuery::compile(
+                query,
+                QueryOptions {
+                    ignore_case: true,
+                    match_any_term,
+                    ..QueryOptions::default()
+                },
+            )
+            .expect("compile query")
+        };
+        let evaluate = |compiled: &CompiledQuery, name: &str| {
+            let path = root.join(name);
+            let prepared = compiled.prepare_candidate(&path, Some(&root), true);
+            compiled.evaluate(&prepared, EvidenceLevel::RankOnly)
+        };
+
+        let all = compile("alpha zebra", false);
+        assert!(evaluate(&all, "alpha/notes.txt").is_none());
+        assert!(evaluate(&all, "alpha/zebra.txt").is_some());
+
+        let any = compile("alpha zebra !draft", true);
+        let one = evaluate(&any, "alpha/notes.txt").expect("first term match");
+        assert!(evaluate(&any, "docs/zebra.txt").is_some());
+        let both = evaluate(&any, "alpha/zebra.txt").expect("both terms match");
+        assert!(both.score > one.score);
+        assert!(evaluate(&any, "alpha/draft.txt").is_none());
+        assert!(evaluate(&any, "docs/readme.txt").is_none());
+
+        let exclusions_only = compile("!draft", true);
+        assert!(evaluate(&exclusions_only, "docs/readme.txt").is_some());
+    }
+
+    #[test]
+    fn ignore_case_folds_non_ascii_letters_and_keeps_span_alignment() {
+        let root = PathBuf::from("/tmp/root");
+        let path = root.join("docs/İstanbul Öl.txt");
+        let options = |ignore_case| QueryOptions {
+            ignore_case,
+            ..QueryOptions::default()
+        };
+
+        let strict = CompiledQuery::compile("öl", options(false)).expect("compile query");
+        let prepared = strict.prepare_candidate(&path, Some(&root), true);
+        assert!(strict
+            .evaluate(&prepared, EvidenceLevel::RankOnly)
+            .is_none());
+
+        let folded = CompiledQuery::compile("öl", options(true)).expect("compile query");
+        let prepared = folded.prepare_candidate(&path, Some(&root), true);
+        let evaluation = folded
+            .evaluate(&prepared, EvidenceLevel::WithSpans)
+            .expect("case-folded match");
+        assert_eq!(evaluation.spans, vec![14, 15]);
+    }
 }
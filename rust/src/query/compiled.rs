@@ -1,13 +1,16 @@
 use super::{
-    include_alternatives, parse_include_alternative, parse_query, split_anchor,
+    include_alternatives, parse_include_alternative, parse_query_for_mode, split_anchor,
     token_uses_regex_syntax,
 };
 use crate::path_utils::{display_path_with_mode, normalize_windows_path};
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use regex::{Regex, RegexBuilder};
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 #[cfg(test)]
 thread_local! {
@@ -31,10 +34,17 @@ pub(crate) fn ignore_compile_count() -> usize {
     IGNORE_COMPILE_COUNT.get()
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct QueryOptions {
     pub use_regex: bool,
     pub ignore_case: bool,
+    pub rank_by_filename: bool,
+    pub fold_diacritics: bool,
+    pub prefix_match: bool,
+    pub match_tail_components: Option<usize>,
+    pub match_any_term: bool,
+    /// `@ext` 指定がある間も folder を残すか。既定では folder を除外する。
+    pub extension_filter_keeps_dirs: bool,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -42,6 +52,8 @@ pub struct QueryScope<'a> {
     pub root: Option<&'a Path>,
     pub prefer_relative: bool,
     pub ignore_case: bool,
+    pub fold_diacritics: bool,
+    pub tail_components: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -59,13 +71,23 @@ pub struct QueryEvaluation {
 #[derive(Debug, Clone)]
 pub struct PreparedCandidate {
     name: String,
+    stem: String,
     full: String,
     visible: String,
     filename: String,
     filename_start: usize,
+    tail_byte_start: usize,
+    tail_start: usize,
+    is_dir: Option<bool>,
 }
 
 impl PreparedCandidate {
+    /// entry kind から分かる folder 判定を付ける。`None` (未解決) は file として扱う。
+    pub fn with_is_dir(mut self, is_dir: Option<bool>) -> Self {
+        self.is_dir = is_dir;
+        self
+    }
+
     fn from_path(path: &Path, scope: QueryScope<'_>) -> Self {
         let normalized_path = normalize_windows_path(path);
         let filename = normalized_path
@@ -83,20 +105,53 @@ impl PreparedCandidate {
             .chars()
             .count()
             .saturating_sub(filename.chars().count());
+        let stem = match filename.rfind('.') {
+            Some(dot) if dot > 0 => &filename[..dot],
+            _ => filename.as_str(),
+        };
+        let tail_byte_start = scope.tail_components.map_or(0, |components| {
+            tail_components_byte_start(&visible, components)
+        });
+        let tail_start = visible[..tail_byte_start].chars().count();
+        let fold = scope.fold_diacritics;
         Self {
-            name: normalize_text(&filename, scope.ignore_case),
-            full: normalize_text(&visible, scope.ignore_case),
+            name: normalize_text(&fold_text(&filename, fold), scope.ignore_case),
+            stem: normalize_text(&fold_text(stem, fold), scope.ignore_case),
+            full: normalize_text(
+                &fold_text(&visible[tail_byte_start..], fold),
+                scope.ignore_case,
+            ),
             visible,
             filename,
             filename_start,
+            tail_byte_start,
+            tail_start,
+            is_dir: None,
         }
     }
 
+    /// path 一致判定に使う表示文字列。`match_tail_components` 指定時は末尾 N component だけになる。
+    fn match_text(&self) -> &str {
+        &self.visible[self.tail_byte_start..]
+    }
+
     pub fn visible_text(&self) -> &str {
         &self.visible
     }
 }
 
+/// 表示 path の末尾 `components` 個の component が始まる byte 位置を返す。0 は path 全体を表す。
+fn tail_components_byte_start(visible: &str, components: usize) -> usize {
+    if components == 0 {
+        return 0;
+    }
+    visible
+        .trim_end_matches(['/', '\\'])
+        .match_indices(['/', '\\'])
+        .nth_back(components - 1)
+        .map_or(0, |(index, separator)| index + separator.len())
+}
+
 #[derive(Debug, Clone)]
 struct LiteralPattern {
     anchored_start: bool,
@@ -121,6 +176,7 @@ struct ExactTermMatcher {
 #[derive(Debug, Clone)]
 struct IncludeAlternative {
     exact: bool,
+    prefix: bool,
     literal: LiteralPattern,
 }
 
@@ -130,15 +186,29 @@ enum IncludeMatcher {
     Alternatives(Vec<IncludeAlternative>),
 }
 
+#[derive(Debug, Clone)]
+enum ExcludeMatcher {
+    Regex(Regex),
+    Alternatives(AlternativeSet),
+}
+
 #[derive(Debug, Clone)]
 pub struct CompiledQuery {
     exact_terms: Vec<ExactTermMatcher>,
-    exclude_terms: Vec<AlternativeSet>,
+    exclude_terms: Vec<ExcludeMatcher>,
     include_terms: Vec<IncludeMatcher>,
     include_literal_bonus_terms: Vec<AlternativeSet>,
     include_exact_bonus_terms: Vec<LiteralPattern>,
+    extensions: Vec<String>,
+    extension_filter_keeps_dirs: bool,
     score_query: String,
+    any_term_score_queries: Vec<String>,
     ignore_case: bool,
+    rank_by_filename: bool,
+    fold_diacritics: bool,
+    prefix_match: bool,
+    match_tail_components: Option<usize>,
+    match_any_term: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -173,13 +243,16 @@ impl CompiledQuery {
     pub fn compile(query: &str, options: QueryOptions) -> Result<Self, String> {
         #[cfg(test)]
         QUERY_COMPILE_COUNT.set(QUERY_COMPILE_COUNT.get().saturating_add(1));
-        let spec = parse_query(query);
+        let spec = parse_query_for_mode(
+            &fold_text(query, options.fold_diacritics),
+            options.use_regex,
+        );
         let exact_terms = compile_exact_term_matchers(&spec.exact_terms, options.ignore_case);
         let exclude_terms = spec
             .exclude_terms
             .iter()
-            .map(|term| compile_alternative_set(term, options.ignore_case))
-            .collect::<Vec<_>>();
+            .map(|term| compile_exclude_matcher(term, options.use_regex, options.ignore_case))
+            .collect::<Result<Vec<_>, _>>()?;
         let mut include_terms = Vec::with_capacity(spec.include_terms.len());
         let mut include_literal_bonus_terms = Vec::new();
         let mut include_exact_bonus_terms = Vec::new();
@@ -188,6 +261,7 @@ impl CompiledQuery {
                 term,
                 options.use_regex,
                 options.ignore_case,
+                options.prefix_match,
             )?);
             if !options.use_regex {
                 let literal_bonus_set =
@@ -210,18 +284,36 @@ impl CompiledQuery {
             }
         }
 
+        let any_term_score_queries = if options.match_any_term {
+            spec.include_terms
+                .iter()
+                .map(|term| build_score_query(std::slice::from_ref(term), &[], options.ignore_case))
+                .filter(|query| !query.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         Ok(Self {
             exact_terms,
             exclude_terms,
             include_terms,
             include_literal_bonus_terms,
             include_exact_bonus_terms,
+            extensions: spec.extensions,
+            extension_filter_keeps_dirs: options.extension_filter_keeps_dirs,
             score_query: build_score_query(
                 &spec.include_terms,
                 &spec.exact_terms,
                 options.ignore_case,
             ),
+            any_term_score_queries,
             ignore_case: options.ignore_case,
+            rank_by_filename: options.rank_by_filename,
+            fold_diacritics: options.fold_diacritics,
+            prefix_match: options.prefix_match,
+            match_tail_components: options.match_tail_components,
+            match_any_term: options.match_any_term,
         })
     }
 
@@ -245,6 +337,8 @@ impl CompiledQuery {
                 root,
                 prefer_relative,
                 ignore_case: self.ignore_case,
+                fold_diacritics: self.fold_diacritics,
+                tail_components: self.match_tail_components,
             },
         )
     }
@@ -286,12 +380,57 @@ impl CompiledQuery {
 
 fn normalize_text(text: &str, ignore_case: bool) -> String {
     if ignore_case {
-        text.to_ascii_lowercase()
+        fold_case(text)
     } else {
         text.to_string()
     }
 }
 
+/// 1 文字へ小文字化できる文字だけを Unicode の小文字へ寄せる。`İ` のように複数文字へ
+/// 展開される文字はそのまま残し、highlight 位置計算で文字数がずれないようにする。
+fn fold_char(ch: char) -> char {
+    if ch.is_ascii() {
+        return ch.to_ascii_lowercase();
+    }
+    let mut lower = ch.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(single), None) => single,
+        _ => ch,
+    }
+}
+
+/// 大文字小文字を無視する比較用に、文字数を保ったまま text を小文字化する。
+pub(crate) fn fold_case(text: &str) -> String {
+    if text.is_ascii() {
+        text.to_ascii_lowercase()
+    } else {
+        text.chars().map(fold_char).collect()
+    }
+}
+
+/// NFD 分解後に結合文字を取り除き、アクセント記号を無視した比較用テキストを返す。
+fn fold_text(text: &str, fold_diacritics: bool) -> Cow<'_, str> {
+    if !fold_diacritics || text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.nfd().filter(|ch| !is_combining_mark(*ch)).collect())
+}
+
+/// `fold_text` と同じ変換を行い、変換後の各文字が元の何文字目に由来するかを併せて返す。
+fn fold_text_with_origins(text: &str) -> (String, Vec<usize>) {
+    let mut folded = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len());
+    for (index, ch) in text.chars().enumerate() {
+        for decomposed in std::iter::once(ch).nfd() {
+            if !is_combining_mark(decomposed) {
+                folded.push(decomposed);
+                origins.push(index);
+            }
+        }
+    }
+    (folded, origins)
+}
+
 fn compile_literal_pattern(term: &str, ignore_case: bool) -> Option<LiteralPattern> {
     let normalized = normalize_text(term, ignore_case);
     let (anchored_start, anchored_end, core) = split_anchor(&normalized);
@@ -371,6 +510,7 @@ fn compile_include_matcher(
     term: &str,
     use_regex: bool,
     ignore_case: bool,
+    prefix_match: bool,
 ) -> Result<IncludeMatcher, String> {
     if use_regex && token_uses_regex_syntax(term) {
         return RegexBuilder::new(term)
@@ -384,13 +524,34 @@ fn compile_include_matcher(
             .into_iter()
             .filter_map(parse_include_alternative)
             .filter_map(|(exact, candidate)| {
-                compile_literal_pattern(&candidate, ignore_case)
-                    .map(|literal| IncludeAlternative { exact, literal })
+                compile_literal_pattern(&candidate, ignore_case).map(|literal| IncludeAlternative {
+                    exact,
+                    prefix: prefix_match && !exact,
+                    literal,
+                })
             })
             .collect(),
     ))
 }
 
+fn compile_exclude_matcher(
+    term: &str,
+    use_regex: bool,
+    ignore_case: bool,
+) -> Result<ExcludeMatcher, String> {
+    if use_regex && token_uses_regex_syntax(term) {
+        return RegexBuilder::new(term)
+            .case_insensitive(ignore_case)
+            .build()
+            .map(ExcludeMatcher::Regex)
+            .map_err(|error| format!("invalid regex '!{term}': {error}"));
+    }
+    Ok(ExcludeMatcher::Alternatives(compile_alternative_set(
+        term,
+        ignore_case,
+    )))
+}
+
 fn build_score_query(
     include_terms: &[String],
     exact_terms: &[String],
@@ -483,37 +644,88 @@ fn matches_include_literal(pattern: &LiteralPattern, name: &str, full: &str) ->
         || is_subsequence(&pattern.core_chars, full)
 }
 
+/// prefix match mode 用の比較対象。basename と visible path の各 segment を返す。
+fn prefix_segments<'a>(name: &'a str, full: &'a str) -> impl Iterator<Item = &'a str> {
+    std::iter::once(name).chain(full.split(['/', '\\']))
+}
+
+/// basename またはいずれかの path segment が term で始まるかを判定する。`$` 付きは segment 全体一致。
+fn matches_prefix_literal(pattern: &LiteralPattern, name: &str, full: &str) -> bool {
+    prefix_segments(name, full).any(|segment| {
+        if pattern.anchored_end {
+            segment == pattern.core
+        } else {
+            segment.starts_with(&pattern.core)
+        }
+    })
+}
+
+fn matches_include_alternative(alternative: &IncludeAlternative, name: &str, full: &str) -> bool {
+    if alternative.exact {
+        matches_anchored_literal(&alternative.literal, name)
+            || matches_anchored_literal(&alternative.literal, full)
+    } else if alternative.prefix {
+        matches_prefix_literal(&alternative.literal, name, full)
+    } else {
+        matches_include_literal(&alternative.literal, name, full)
+    }
+}
+
 fn matches_include_matcher(matcher: &IncludeMatcher, name: &str, full: &str) -> bool {
     match matcher {
         IncludeMatcher::Regex(regex) => regex.is_match(name) || regex.is_match(full),
-        IncludeMatcher::Alternatives(alternatives) => alternatives.iter().any(|alternative| {
-            if alternative.exact {
-                matches_anchored_literal(&alternative.literal, name)
-                    || matches_anchored_literal(&alternative.literal, full)
-            } else {
-                matches_include_literal(&alternative.literal, name, full)
-            }
-        }),
+        IncludeMatcher::Alternatives(alternatives) => alternatives
+            .iter()
+            .any(|alternative| matches_include_alternative(alternative, name, full)),
     }
 }
 
-fn matches_compiled_query(compiled: &CompiledQuery, candidate: &PreparedCandidate) -> bool {
-    !compiled
-        .exclude_terms
-        .iter()
-        .any(|term| matches_alternative_set(term, &candidate.name, &candidate.full))
-        && matches_positive_terms(compiled, candidate)
+fn matches_exclude_matcher(matcher: &ExcludeMatcher, name: &str, full: &str) -> bool {
+    match matcher {
+        ExcludeMatcher::Regex(regex) => regex.is_match(name) || regex.is_match(full),
+        ExcludeMatcher::Alternatives(set) => matches_alternative_set(set, name, full),
+    }
 }
 
-fn matches_positive_terms(compiled: &CompiledQuery, candidate: &PreparedCandidate) -> bool {
+fn matches_extension_filter(compiled: &CompiledQuery, candidate: &PreparedCandidate) -> bool {
+    if compiled.extensions.is_empty() {
+        return true;
+    }
+    if candidate.is_dir == Some(true) {
+        // Folder names such as `foo.d` are not file extensions; keep or drop them as a whole.
+        return compiled.extension_filter_keeps_dirs;
+    }
+    let extension = match candidate.filename.rfind('.') {
+        Some(dot) if dot > 0 => &candidate.filename[dot + 1..],
+        _ => return false,
+    };
     compiled
-        .exact_terms
+        .extensions
         .iter()
-        .all(|term| matches_exact_term(term, &candidate.name, &candidate.full))
-        && compiled
-            .include_terms
+        .any(|wanted| wanted.eq_ignore_ascii_case(extension))
+}
+
+fn matches_compiled_query(compiled: &CompiledQuery, candidate: &PreparedCandidate) -> bool {
+    matches_extension_filter(compiled, candidate)
+        && !compiled
+            .exclude_terms
             .iter()
-            .all(|matcher| matches_include_matcher(matcher, &candidate.name, &candidate.full))
+            .any(|matcher| matches_exclude_matcher(matcher, &candidate.name, &candidate.full))
+        && matches_positive_terms(compiled, candidate)
+}
+
+fn matches_positive_terms(compiled: &CompiledQuery, candidate: &PreparedCandidate) -> bool {
+    let exact =
+        |term: &ExactTermMatcher| matches_exact_term(term, &candidate.name, &candidate.full);
+    let include = |matcher: &IncludeMatcher| {
+        matches_include_matcher(matcher, &candidate.name, &candidate.full)
+    };
+    // Any-term mode needs at least one hit; a query with only exclusions still matches everything.
+    if compiled.match_any_term && compiled.has_positive_terms() {
+        return compiled.exact_terms.iter().any(exact)
+            || compiled.include_terms.iter().any(include);
+    }
+    compiled.exact_terms.iter().all(exact) && compiled.include_terms.iter().all(include)
 }
 
 fn fallback_score(query: &str, text: &str) -> f64 {
@@ -545,7 +757,26 @@ fn bonus_for_alternative_set(set: &AlternativeSet, candidate: &PreparedCandidate
     {
         bonus += 150.0;
     }
-    bonus
+    bonus + stem_bonus_for_alternative_set(set, candidate)
+}
+
+// Favor query hits inside the basename stem so `main` ranks `main.py` above `domain.main`;
+// extension-only hits get nothing extra.
+fn stem_bonus_for_alternative_set(set: &AlternativeSet, candidate: &PreparedCandidate) -> f64 {
+    set.alternatives
+        .iter()
+        .map(|pattern| {
+            if candidate.stem == pattern.core {
+                120.0
+            } else if candidate.stem.starts_with(&pattern.core) {
+                80.0
+            } else if candidate.stem.contains(&pattern.core) {
+                40.0
+            } else {
+                0.0
+            }
+        })
+        .fold(0.0, f64::max)
 }
 
 fn score_entry(
@@ -553,8 +784,19 @@ fn score_entry(
     compiled: &CompiledQuery,
     candidate: &PreparedCandidate,
 ) -> f64 {
-    let mut score = if compiled.score_query.is_empty() {
+    let filename_score = if compiled.rank_by_filename && !compiled.score_query.is_empty() {
+        matcher
+            .fuzzy_match(&candidate.name, &compiled.score_query)
+            .filter(|value| *value > 0)
+    } else {
+        None
+    };
+    let mut score = if compiled.match_any_term && !compiled.any_term_score_queries.is_empty() {
+        any_term_score(matcher, compiled, candidate)
+    } else if compiled.score_query.is_empty() {
         0.0
+    } else if let Some(value) = filename_score {
+        value as f64
     } else {
         matcher
             .fuzzy_match(&candidate.full, &compiled.score_query)
@@ -584,12 +826,64 @@ fn score_entry(
             }
         }
     }
+    if compiled.prefix_match {
+        score += prefix_suffix_bonus(compiled, candidate);
+    }
     score
 }
 
+// The joined score query rarely fuzzy-matches when only some terms hit, so any-term mode
+// sums the fuzzy score of each term that matches on its own.
+fn any_term_score(
+    matcher: &SkimMatcherV2,
+    compiled: &CompiledQuery,
+    candidate: &PreparedCandidate,
+) -> f64 {
+    compiled
+        .any_term_score_queries
+        .iter()
+        .filter_map(|query| {
+            let filename_score = if compiled.rank_by_filename {
+                matcher
+                    .fuzzy_match(&candidate.name, query)
+                    .filter(|value| *value > 0)
+            } else {
+                None
+            };
+            filename_score.or_else(|| matcher.fuzzy_match(&candidate.full, query))
+        })
+        .map(|value| value as f64)
+        .sum()
+}
+
+// Prefix mode rewards segments that leave less text after the typed prefix, so `mai`
+// ranks `main.rs` above `maintenance_notes.rs`.
+fn prefix_suffix_bonus(compiled: &CompiledQuery, candidate: &PreparedCandidate) -> f64 {
+    compiled
+        .include_terms
+        .iter()
+        .filter_map(|matcher| match matcher {
+            IncludeMatcher::Alternatives(alternatives) => Some(alternatives),
+            IncludeMatcher::Regex(_) => None,
+        })
+        .map(|alternatives| {
+            alternatives
+                .iter()
+                .filter(|alternative| alternative.prefix)
+                .flat_map(|alternative| {
+                    prefix_segments(&candidate.name, &candidate.full)
+                        .filter(|segment| segment.starts_with(&alternative.literal.core))
+                        .map(|segment| segment.len() - alternative.literal.core.len())
+                })
+                .min()
+                .map_or(0.0, |remaining| 100.0 - remaining.min(100) as f64)
+        })
+        .sum()
+}
+
 fn chars_equal(left: char, right: char, ignore_case: bool) -> bool {
-    if ignore_case && left.is_ascii() && right.is_ascii() {
-        left.eq_ignore_ascii_case(&right)
+    if ignore_case {
+        fold_char(left) == fold_char(right)
     } else {
         left == right
     }
@@ -633,16 +927,24 @@ fn fuzzy_positions(text: &str, pattern: &LiteralPattern, ignore_case: bool) -> V
     }
     let text_chars = text.chars().collect::<Vec<_>>();
     let query_chars = pattern.core.chars().collect::<Vec<_>>();
-    if query_chars.len() <= text_chars.len() {
-        for start in 0..=text_chars.len() - query_chars.len() {
+    if !query_chars.is_empty() && query_chars.len() <= text_chars.len() {
+        let mut occurrences = Vec::new();
+        let mut start = 0usize;
+        while start + query_chars.len() <= text_chars.len() {
             if query_chars
                 .iter()
                 .enumerate()
                 .all(|(offset, query)| chars_equal(text_chars[start + offset], *query, ignore_case))
             {
-                return (start..start + query_chars.len()).collect();
+                occurrences.extend(start..start + query_chars.len());
+                start += query_chars.len();
+            } else {
+                start += 1;
             }
         }
+        if !occurrences.is_empty() {
+            return occurrences;
+        }
     }
     let mut positions = Vec::with_capacity(query_chars.len());
     let mut query_index = 0usize;
@@ -661,6 +963,33 @@ fn fuzzy_positions(text: &str, pattern: &LiteralPattern, ignore_case: bool) -> V
     }
 }
 
+/// text 先頭または区切り文字直後から始まる最初の prefix 一致位置を返す。
+fn prefix_positions(text: &str, pattern: &LiteralPattern, ignore_case: bool) -> Vec<usize> {
+    let text_chars = text.chars().collect::<Vec<_>>();
+    let core_chars = pattern.core.chars().collect::<Vec<_>>();
+    if core_chars.is_empty() || core_chars.len() > text_chars.len() {
+        return Vec::new();
+    }
+    for start in 0..=text_chars.len() - core_chars.len() {
+        if start > 0 && !matches!(text_chars[start - 1], '/' | '\\') {
+            continue;
+        }
+        let end = start + core_chars.len();
+        if pattern.anchored_end && end < text_chars.len() && !matches!(text_chars[end], '/' | '\\')
+        {
+            continue;
+        }
+        if core_chars
+            .iter()
+            .enumerate()
+            .all(|(offset, query)| chars_equal(text_chars[start + offset], *query, ignore_case))
+        {
+            return (start..end).collect();
+        }
+    }
+    Vec::new()
+}
+
 fn regex_positions(text: &str, regex: &Regex) -> Vec<usize> {
     let mut positions = Vec::new();
     for matched in regex.find_iter(text) {
@@ -674,76 +1003,116 @@ fn regex_positions(text: &str, regex: &Regex) -> Vec<usize> {
     positions
 }
 
+/// highlight 位置の計算対象テキスト。アクセント無視時は折り畳み後の位置を元の文字位置へ戻す。
+struct SpanText<'a> {
+    text: Cow<'a, str>,
+    origins: Option<Vec<usize>>,
+}
+
+impl<'a> SpanText<'a> {
+    fn new(text: &'a str, fold_diacritics: bool) -> Self {
+        if !fold_diacritics || text.is_ascii() {
+            return Self {
+                text: Cow::Borrowed(text),
+                origins: None,
+            };
+        }
+        let (folded, origins) = fold_text_with_origins(text);
+        Self {
+            text: Cow::Owned(folded),
+            origins: Some(origins),
+        }
+    }
+
+    fn original_positions(&self, positions: Vec<usize>) -> impl Iterator<Item = usize> + '_ {
+        positions
+            .into_iter()
+            .map(|position| self.origins.as_ref().map_or(position, |map| map[position]))
+    }
+}
+
 fn add_pattern_positions(
     spans: &mut BTreeSet<usize>,
+    filename: &SpanText<'_>,
+    visible: &SpanText<'_>,
     candidate: &PreparedCandidate,
     pattern: &LiteralPattern,
-    exact: bool,
+    positions: fn(&str, &LiteralPattern, bool) -> Vec<usize>,
     ignore_case: bool,
 ) {
-    let filename_hits = if exact {
-        exact_positions(&candidate.filename, pattern, ignore_case)
-    } else {
-        fuzzy_positions(&candidate.filename, pattern, ignore_case)
-    };
+    let filename_hits = positions(&filename.text, pattern, ignore_case);
     if !filename_hits.is_empty() {
         spans.extend(
-            filename_hits
-                .into_iter()
+            filename
+                .original_positions(filename_hits)
                 .map(|position| candidate.filename_start + position),
         );
         return;
     }
-    let visible_hits = if exact {
-        exact_positions(&candidate.visible, pattern, ignore_case)
-    } else {
-        fuzzy_positions(&candidate.visible, pattern, ignore_case)
-    };
-    spans.extend(visible_hits);
+    let visible_hits = positions(&visible.text, pattern, ignore_case);
+    spans.extend(
+        visible
+            .original_positions(visible_hits)
+            .map(|position| candidate.tail_start + position),
+    );
 }
 
 fn collect_spans(compiled: &CompiledQuery, candidate: &PreparedCandidate) -> Vec<usize> {
     let mut spans = BTreeSet::new();
+    let filename = SpanText::new(&candidate.filename, compiled.fold_diacritics);
+    let visible = SpanText::new(candidate.match_text(), compiled.fold_diacritics);
     for term in &compiled.exact_terms {
         if let Some(pattern) = term.set.alternatives.iter().find(|pattern| {
             matches_anchored_literal(pattern, &candidate.name)
                 || matches_anchored_literal(pattern, &candidate.full)
         }) {
-            add_pattern_positions(&mut spans, candidate, pattern, true, compiled.ignore_case);
+            add_pattern_positions(
+                &mut spans,
+                &filename,
+                &visible,
+                candidate,
+                pattern,
+                exact_positions,
+                compiled.ignore_case,
+            );
         }
     }
     for matcher in &compiled.include_terms {
         match matcher {
             IncludeMatcher::Regex(regex) => {
-                let filename_hits = regex_positions(&candidate.filename, regex);
+                let filename_hits = regex_positions(&filename.text, regex);
                 if filename_hits.is_empty() {
-                    spans.extend(regex_positions(&candidate.visible, regex));
+                    spans.extend(
+                        visible
+                            .original_positions(regex_positions(&visible.text, regex))
+                            .map(|position| candidate.tail_start + position),
+                    );
                 } else {
                     spans.extend(
-                        filename_hits
-                            .into_iter()
+                        filename
+                            .original_positions(filename_hits)
                             .map(|position| candidate.filename_start + position),
                     );
                 }
             }
             IncludeMatcher::Alternatives(alternatives) => {
                 if let Some(alternative) = alternatives.iter().find(|alternative| {
-                    if alternative.exact {
-                        matches_anchored_literal(&alternative.literal, &candidate.name)
-                            || matches_anchored_literal(&alternative.literal, &candidate.full)
-                    } else {
-                        matches_include_literal(
-                            &alternative.literal,
-                            &candidate.name,
-                            &candidate.full,
-                        )
-                    }
+                    matches_include_alternative(alternative, &candidate.name, &candidate.full)
                 }) {
+                    let positions = if alternative.exact {
+                        exact_positions
+                    } else if alternative.prefix {
+                        prefix_positions
+                    } else {
+                        fuzzy_positions
+                    };
                     add_pattern_positions(
                         &mut spans,
+                        &filename,
+                        &visible,
                         candidate,
                         &alternative.literal,
-                        alternative.exact,
+                        positions,
                         compiled.ignore_case,
                     );
                 }
@@ -17,6 +17,11 @@ const WINDOWS_SETTINGS_DIR_NAME: &str = "flistwalker";
 const UNIX_SETTINGS_DIR_NAME: &str = ".flistwalker";
 const SEARCH_PARALLEL_THRESHOLD_DEFAULT: usize = 25_000;
 const WALKER_MAX_ENTRIES_DEFAULT: usize = 500_000;
+const ACTION_CONFIRM_THRESHOLD_DEFAULT: usize = 10;
+const PREVIEW_MAX_FILE_MB_DEFAULT: u64 = 512;
+const INDEX_FLUSH_INTERVAL_MS_DEFAULT: u64 = 100;
+const SHIFT_ENTER_FOLDER_ACTION_DEFAULT: &str = "open";
+const RESULT_KIND_GROUPING_DEFAULT: &str = "none";
 const WINDOW_TRACE_LOG_NAME: &str = ".flistwalker_window_trace.log";
 
 const SEARCH_PARALLEL_THRESHOLD_ENV: &str = "FLISTWALKER_SEARCH_PARALLEL_THRESHOLD";
@@ -32,6 +37,7 @@ const UPDATE_ALLOW_SAME_VERSION_ENV: &str = "FLISTWALKER_UPDATE_ALLOW_SAME_VERSI
 const UPDATE_ALLOW_DOWNGRADE_ENV: &str = "FLISTWALKER_UPDATE_ALLOW_DOWNGRADE";
 const DISABLE_SELF_UPDATE_ENV: &str = "FLISTWALKER_DISABLE_SELF_UPDATE";
 const FORCE_UPDATE_CHECK_FAILURE_ENV: &str = "FLISTWALKER_FORCE_UPDATE_CHECK_FAILURE";
+pub const CONFIG_DIR_ENV: &str = "FLISTWALKER_CONFIG_DIR";
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
@@ -46,6 +52,38 @@ pub struct RuntimeConfig {
     pub restore_tabs_enabled: bool,
     pub emacs_keybindings_enabled: bool,
     pub tab_pin_moves_to_next_row: bool,
+    pub action_confirm_threshold: usize,
+    pub remember_filters_per_source: bool,
+    pub walker_throttle_ms: u64,
+    pub empty_query_recent_first: bool,
+    pub preview_max_file_mb: u64,
+    pub query_path_prefix_filter: bool,
+    pub result_type_icons: bool,
+    pub rank_by_filename: bool,
+    pub index_batch_size: usize,
+    pub index_flush_interval_ms: u64,
+    pub preview_dir_depth: usize,
+    pub preview_skip_binary: bool,
+    pub fold_diacritics: bool,
+    pub walker_canonicalize_entries: bool,
+    pub shift_enter_folder_action: String,
+    pub sticky_results_scroll: bool,
+    pub preview_metadata_details: bool,
+    pub result_kind_grouping: String,
+    pub filelist_header_comment: bool,
+    pub max_results_per_directory: usize,
+    pub dir_trailing_slash: bool,
+    pub exclude_root_filelist: bool,
+    pub prefix_match: bool,
+    pub match_tail_components: usize,
+    pub eager_partial_search: bool,
+    pub match_full_path: bool,
+    pub editor_command_template: String,
+    pub min_query_length: usize,
+    pub restore_last_query: bool,
+    pub walker_max_entries_per_dir: usize,
+    pub wrap_navigation: bool,
+    pub extension_filter_keeps_dirs: bool,
     pub update_feed_url: String,
     pub update_allow_same_version: bool,
     pub update_allow_downgrade: bool,
@@ -93,6 +131,70 @@ struct RuntimeConfigSeed {
     #[serde(skip_serializing_if = "Option::is_none")]
     tab_pin_moves_to_next_row: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    action_confirm_threshold: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remember_filters_per_source: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    walker_throttle_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    empty_query_recent_first: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview_max_file_mb: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query_path_prefix_filter: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result_type_icons: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rank_by_filename: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index_batch_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index_flush_interval_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview_dir_depth: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview_skip_binary: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fold_diacritics: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    walker_canonicalize_entries: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shift_enter_folder_action: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sticky_results_scroll: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview_metadata_details: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result_kind_grouping: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filelist_header_comment: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_results_per_directory: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dir_trailing_slash: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exclude_root_filelist: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix_match: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    match_tail_components: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eager_partial_search: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    match_full_path: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    editor_command_template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_query_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restore_last_query: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    walker_max_entries_per_dir: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wrap_navigation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extension_filter_keeps_dirs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     update_feed_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     update_allow_same_version: Option<bool>,
@@ -135,6 +237,38 @@ impl Default for RuntimeConfig {
             restore_tabs_enabled: false,
             emacs_keybindings_enabled: true,
             tab_pin_moves_to_next_row: false,
+            action_confirm_threshold: ACTION_CONFIRM_THRESHOLD_DEFAULT,
+            remember_filters_per_source: false,
+            walker_throttle_ms: 0,
+            empty_query_recent_first: false,
+            preview_max_file_mb: PREVIEW_MAX_FILE_MB_DEFAULT,
+            query_path_prefix_filter: false,
+            result_type_icons: true,
+            rank_by_filename: false,
+            index_batch_size: 0,
+            index_flush_interval_ms: INDEX_FLUSH_INTERVAL_MS_DEFAULT,
+            preview_dir_depth: 1,
+            preview_skip_binary: true,
+            fold_diacritics: false,
+            walker_canonicalize_entries: false,
+            shift_enter_folder_action: SHIFT_ENTER_FOLDER_ACTION_DEFAULT.to_string(),
+            sticky_results_scroll: false,
+            preview_metadata_details: false,
+            result_kind_grouping: RESULT_KIND_GROUPING_DEFAULT.to_string(),
+            filelist_header_comment: false,
+            max_results_per_directory: 0,
+            dir_trailing_slash: false,
+            exclude_root_filelist: true,
+            prefix_match: false,
+            match_tail_components: 0,
+            eager_partial_search: false,
+            match_full_path: false,
+            editor_command_template: String::new(),
+            min_query_length: 1,
+            restore_last_query: false,
+            walker_max_entries_per_dir: 0,
+            wrap_navigation: false,
+            extension_filter_keeps_dirs: false,
             update_feed_url: DEFAULT_UPDATE_FEED_URL.to_string(),
             update_allow_same_version: false,
             update_allow_downgrade: false,
@@ -234,7 +368,13 @@ pub fn initialize_runtime_config() -> RuntimeConfig {
     RuntimeConfig::load_or_seed()
 }
 
+/// 設定 file の保存先 directory を返す。`FLISTWALKER_CONFIG_DIR` が空でなければ最優先で使う。
+/// home/config directory を決められない場合は `None` で、設定は保存されない。
 pub fn settings_base_dir() -> Option<PathBuf> {
+    if let Some(base) = env::var_os(CONFIG_DIR_ENV).filter(|value| !value.is_empty()) {
+        return Some(PathBuf::from(base));
+    }
+
     #[cfg(windows)]
     {
         local_app_data_dir().map(|base| base.join(WINDOWS_SETTINGS_DIR_NAME))
@@ -475,6 +615,38 @@ impl RuntimeConfig {
             restore_tabs_enabled,
             emacs_keybindings_enabled: true,
             tab_pin_moves_to_next_row: false,
+            action_confirm_threshold: ACTION_CONFIRM_THRESHOLD_DEFAULT,
+            remember_filters_per_source: false,
+            walker_throttle_ms: 0,
+            empty_query_recent_first: false,
+            preview_max_file_mb: PREVIEW_MAX_FILE_MB_DEFAULT,
+            query_path_prefix_filter: false,
+            result_type_icons: true,
+            rank_by_filename: false,
+            index_batch_size: 0,
+            index_flush_interval_ms: INDEX_FLUSH_INTERVAL_MS_DEFAULT,
+            preview_dir_depth: 1,
+            preview_skip_binary: true,
+            fold_diacritics: false,
+            walker_canonicalize_entries: false,
+            shift_enter_folder_action: SHIFT_ENTER_FOLDER_ACTION_DEFAULT.to_string(),
+            sticky_results_scroll: false,
+            preview_metadata_details: false,
+            result_kind_grouping: RESULT_KIND_GROUPING_DEFAULT.to_string(),
+            filelist_header_comment: false,
+            max_results_per_directory: 0,
+            dir_trailing_slash: false,
+            exclude_root_filelist: true,
+            prefix_match: false,
+            match_tail_components: 0,
+            eager_partial_search: false,
+            match_full_path: false,
+            editor_command_template: String::new(),
+            min_query_length: 1,
+            restore_last_query: false,
+            walker_max_entries_per_dir: 0,
+            wrap_navigation: false,
+            extension_filter_keeps_dirs: false,
             update_feed_url: update_feed_url
                 .as_ref()
                 .cloned()
@@ -501,6 +673,38 @@ impl RuntimeConfig {
             restore_tabs_enabled: Some(config.restore_tabs_enabled),
             emacs_keybindings_enabled: Some(config.emacs_keybindings_enabled),
             tab_pin_moves_to_next_row: Some(config.tab_pin_moves_to_next_row),
+            action_confirm_threshold: Some(config.action_confirm_threshold),
+            remember_filters_per_source: Some(config.remember_filters_per_source),
+            walker_throttle_ms: Some(config.walker_throttle_ms),
+            empty_query_recent_first: Some(config.empty_query_recent_first),
+            preview_max_file_mb: Some(config.preview_max_file_mb),
+            query_path_prefix_filter: Some(config.query_path_prefix_filter),
+            result_type_icons: Some(config.result_type_icons),
+            rank_by_filename: Some(config.rank_by_filename),
+            index_batch_size: Some(config.index_batch_size),
+            index_flush_interval_ms: Some(config.index_flush_interval_ms),
+            preview_dir_depth: Some(config.preview_dir_depth),
+            preview_skip_binary: Some(config.preview_skip_binary),
+            fold_diacritics: Some(config.fold_diacritics),
+            walker_canonicalize_entries: Some(config.walker_canonicalize_entries),
+            shift_enter_folder_action: Some(config.shift_enter_folder_action.clone()),
+            sticky_results_scroll: Some(config.sticky_results_scroll),
+            preview_metadata_details: Some(config.preview_metadata_details),
+            result_kind_grouping: Some(config.result_kind_grouping.clone()),
+            filelist_header_comment: Some(config.filelist_header_comment),
+            max_results_per_directory: Some(config.max_results_per_directory),
+            dir_trailing_slash: Some(config.dir_trailing_slash),
+            exclude_root_filelist: Some(config.exclude_root_filelist),
+            prefix_match: Some(config.prefix_match),
+            match_tail_components: Some(config.match_tail_components),
+            eager_partial_search: Some(config.eager_partial_search),
+            match_full_path: Some(config.match_full_path),
+            editor_command_template: Some(config.editor_command_template.clone()),
+            min_query_length: Some(config.min_query_length),
+            restore_last_query: Some(config.restore_last_query),
+            walker_max_entries_per_dir: Some(config.walker_max_entries_per_dir),
+            wrap_navigation: Some(config.wrap_navigation),
+            extension_filter_keeps_dirs: Some(config.extension_filter_keeps_dirs),
             update_feed_url: update_feed_url.map(|_| config.update_feed_url.clone()),
             update_allow_same_version: update_allow_same_version_set
                 .then_some(config.update_allow_same_version),
@@ -549,6 +753,166 @@ fn normalize_runtime_config_file(path: &Path, text: &str, config: &RuntimeConfig
         "tab_pin_moves_to_next_row",
         serde_json::json!(config.tab_pin_moves_to_next_row),
     );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "action_confirm_threshold",
+        serde_json::json!(config.action_confirm_threshold),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "remember_filters_per_source",
+        serde_json::json!(config.remember_filters_per_source),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "walker_throttle_ms",
+        serde_json::json!(config.walker_throttle_ms),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "empty_query_recent_first",
+        serde_json::json!(config.empty_query_recent_first),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "preview_max_file_mb",
+        serde_json::json!(config.preview_max_file_mb),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "query_path_prefix_filter",
+        serde_json::json!(config.query_path_prefix_filter),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "result_type_icons",
+        serde_json::json!(config.result_type_icons),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "rank_by_filename",
+        serde_json::json!(config.rank_by_filename),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "index_batch_size",
+        serde_json::json!(config.index_batch_size),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "index_flush_interval_ms",
+        serde_json::json!(config.index_flush_interval_ms),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "preview_dir_depth",
+        serde_json::json!(config.preview_dir_depth),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "preview_skip_binary",
+        serde_json::json!(config.preview_skip_binary),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "fold_diacritics",
+        serde_json::json!(config.fold_diacritics),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "walker_canonicalize_entries",
+        serde_json::json!(config.walker_canonicalize_entries),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "shift_enter_folder_action",
+        serde_json::json!(config.shift_enter_folder_action),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "sticky_results_scroll",
+        serde_json::json!(config.sticky_results_scroll),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "preview_metadata_details",
+        serde_json::json!(config.preview_metadata_details),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "result_kind_grouping",
+        serde_json::json!(config.result_kind_grouping),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "filelist_header_comment",
+        serde_json::json!(config.filelist_header_comment),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "max_results_per_directory",
+        serde_json::json!(config.max_results_per_directory),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "dir_trailing_slash",
+        serde_json::json!(config.dir_trailing_slash),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "exclude_root_filelist",
+        serde_json::json!(config.exclude_root_filelist),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "prefix_match",
+        serde_json::json!(config.prefix_match),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "match_tail_components",
+        serde_json::json!(config.match_tail_components),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "eager_partial_search",
+        serde_json::json!(config.eager_partial_search),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "match_full_path",
+        serde_json::json!(config.match_full_path),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "editor_command_template",
+        serde_json::json!(config.editor_command_template),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "min_query_length",
+        serde_json::json!(config.min_query_length),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "restore_last_query",
+        serde_json::json!(config.restore_last_query),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "walker_max_entries_per_dir",
+        serde_json::json!(config.walker_max_entries_per_dir),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "wrap_navigation",
+        serde_json::json!(config.wrap_navigation),
+    );
+    changed |= insert_missing_runtime_config_value(
+        root,
+        "extension_filter_keeps_dirs",
+        serde_json::json!(config.extension_filter_keeps_dirs),
+    );
     if let Some(developer) = root
         .get_mut("developer")
         .and_then(|value| value.as_object_mut())
@@ -221,7 +221,197 @@ fn seeds_default_user_config_values_when_missing() {
             .and_then(|value| value.as_bool()),
         Some(false)
     );
-    assert_eq!(saved.len(), 5);
+    assert_eq!(
+        saved
+            .get("action_confirm_threshold")
+            .and_then(|value| value.as_u64()),
+        Some(ACTION_CONFIRM_THRESHOLD_DEFAULT as u64)
+    );
+    assert_eq!(
+        saved
+            .get("remember_filters_per_source")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("walker_throttle_ms")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("empty_query_recent_first")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("preview_max_file_mb")
+            .and_then(|value| value.as_u64()),
+        Some(PREVIEW_MAX_FILE_MB_DEFAULT)
+    );
+    assert_eq!(
+        saved
+            .get("query_path_prefix_filter")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("result_type_icons")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        saved
+            .get("rank_by_filename")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("index_batch_size")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("index_flush_interval_ms")
+            .and_then(|value| value.as_u64()),
+        Some(100)
+    );
+    assert_eq!(
+        saved
+            .get("preview_dir_depth")
+            .and_then(|value| value.as_u64()),
+        Some(1)
+    );
+    assert_eq!(
+        saved
+            .get("preview_skip_binary")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        saved
+            .get("fold_diacritics")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("walker_canonicalize_entries")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("shift_enter_folder_action")
+            .and_then(|value| value.as_str()),
+        Some("open")
+    );
+    assert_eq!(
+        saved
+            .get("sticky_results_scroll")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("preview_metadata_details")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("result_kind_grouping")
+            .and_then(|value| value.as_str()),
+        Some("none")
+    );
+    assert_eq!(
+        saved
+            .get("filelist_header_comment")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("max_results_per_directory")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("dir_trailing_slash")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("exclude_root_filelist")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        saved.get("prefix_match").and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("match_tail_components")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("eager_partial_search")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("match_full_path")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("editor_command_template")
+            .and_then(|value| value.as_str()),
+        Some("")
+    );
+    assert_eq!(
+        saved
+            .get("min_query_length")
+            .and_then(|value| value.as_u64()),
+        Some(1)
+    );
+    assert_eq!(
+        saved
+            .get("restore_last_query")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("walker_max_entries_per_dir")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("wrap_navigation")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("extension_filter_keeps_dirs")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(saved.len(), 37);
 
     let _ = fs::remove_dir_all(&home);
 }
@@ -332,7 +522,14 @@ fn settings_base_dir_uses_platform_specific_settings_directory() {
     let _guard = locked_env();
     let home = test_home("base-dir");
     fs::create_dir_all(&home).expect("create home");
-    let _restore = EnvRestore::capture(&["HOME", "USERPROFILE", "LOCALAPPDATA", "APPDATA"]);
+    let _restore = EnvRestore::capture(&[
+        "HOME",
+        "USERPROFILE",
+        "LOCALAPPDATA",
+        "APPDATA",
+        CONFIG_DIR_ENV,
+    ]);
+    env::remove_var(CONFIG_DIR_ENV);
     env::set_var("HOME", &home);
     env::set_var("USERPROFILE", &home);
     env::set_var("LOCALAPPDATA", &home);
@@ -352,6 +549,33 @@ fn settings_base_dir_uses_platform_specific_settings_directory() {
     let _ = fs::remove_dir_all(&home);
 }
 
+#[test]
+fn settings_base_dir_prefers_config_dir_override_and_is_none_without_home() {
+    let _guard = locked_env();
+    let home = test_home("config-dir-override");
+    let _restore = EnvRestore::capture(&[
+        "HOME",
+        "USERPROFILE",
+        "LOCALAPPDATA",
+        "APPDATA",
+        CONFIG_DIR_ENV,
+    ]);
+    env::remove_var("HOME");
+    env::remove_var("USERPROFILE");
+    env::remove_var("LOCALAPPDATA");
+    env::remove_var("APPDATA");
+    env::set_var(CONFIG_DIR_ENV, "");
+    assert_eq!(settings_base_dir(), None);
+    assert_eq!(runtime_config_file_path(), None);
+
+    env::set_var(CONFIG_DIR_ENV, &home);
+    assert_eq!(settings_base_dir(), Some(home.clone()));
+    assert_eq!(
+        runtime_config_file_path(),
+        Some(runtime_config_file_path_in(&home))
+    );
+}
+
 #[test]
 fn legacy_settings_base_dirs_include_home_directory_for_transition_migration() {
     let _guard = locked_env();
@@ -391,6 +615,11 @@ fn load_runtime_config_from_path_handles_missing_field_defaults() {
     assert_eq!(loaded.walker_max_entries, WALKER_MAX_ENTRIES_DEFAULT);
     assert!(loaded.emacs_keybindings_enabled);
     assert!(!loaded.tab_pin_moves_to_next_row);
+    assert_eq!(
+        loaded.action_confirm_threshold,
+        ACTION_CONFIRM_THRESHOLD_DEFAULT
+    );
+    assert!(!loaded.remember_filters_per_source);
     assert_eq!(loaded.developer, DeveloperRuntimeConfig::default());
 
     let _ = fs::remove_dir_all(&home);
@@ -444,7 +673,197 @@ fn load_runtime_config_adds_missing_user_config_values_to_existing_file() {
             .and_then(|value| value.as_bool()),
         Some(false)
     );
-    assert_eq!(saved.len(), 5);
+    assert_eq!(
+        saved
+            .get("action_confirm_threshold")
+            .and_then(|value| value.as_u64()),
+        Some(ACTION_CONFIRM_THRESHOLD_DEFAULT as u64)
+    );
+    assert_eq!(
+        saved
+            .get("remember_filters_per_source")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("walker_throttle_ms")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("empty_query_recent_first")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("preview_max_file_mb")
+            .and_then(|value| value.as_u64()),
+        Some(PREVIEW_MAX_FILE_MB_DEFAULT)
+    );
+    assert_eq!(
+        saved
+            .get("query_path_prefix_filter")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("result_type_icons")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        saved
+            .get("rank_by_filename")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("index_batch_size")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("index_flush_interval_ms")
+            .and_then(|value| value.as_u64()),
+        Some(100)
+    );
+    assert_eq!(
+        saved
+            .get("preview_dir_depth")
+            .and_then(|value| value.as_u64()),
+        Some(1)
+    );
+    assert_eq!(
+        saved
+            .get("preview_skip_binary")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        saved
+            .get("fold_diacritics")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("walker_canonicalize_entries")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("shift_enter_folder_action")
+            .and_then(|value| value.as_str()),
+        Some("open")
+    );
+    assert_eq!(
+        saved
+            .get("sticky_results_scroll")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("preview_metadata_details")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("result_kind_grouping")
+            .and_then(|value| value.as_str()),
+        Some("none")
+    );
+    assert_eq!(
+        saved
+            .get("filelist_header_comment")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("max_results_per_directory")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("dir_trailing_slash")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("exclude_root_filelist")
+            .and_then(|value| value.as_bool()),
+        Some(true)
+    );
+    assert_eq!(
+        saved.get("prefix_match").and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("match_tail_components")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("eager_partial_search")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("match_full_path")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("editor_command_template")
+            .and_then(|value| value.as_str()),
+        Some("")
+    );
+    assert_eq!(
+        saved
+            .get("min_query_length")
+            .and_then(|value| value.as_u64()),
+        Some(1)
+    );
+    assert_eq!(
+        saved
+            .get("restore_last_query")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("walker_max_entries_per_dir")
+            .and_then(|value| value.as_u64()),
+        Some(0)
+    );
+    assert_eq!(
+        saved
+            .get("wrap_navigation")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(
+        saved
+            .get("extension_filter_keeps_dirs")
+            .and_then(|value| value.as_bool()),
+        Some(false)
+    );
+    assert_eq!(saved.len(), 37);
 
     let _ = fs::remove_dir_all(&home);
 }
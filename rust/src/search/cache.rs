@@ -51,7 +51,8 @@ impl SearchPrefixCache {
         if q.contains(char::is_whitespace) {
             return false;
         }
-        !q.contains(['|', '!', '\'', '^', '$'])
+        // `@ext` widens when more extensions are typed, so it never narrows a cached prefix.
+        !q.contains(['|', '!', '\'', '^', '$', '@'])
     }
 
     pub(crate) fn is_safe_prefix_extension(prefix: &str, query: &str) -> bool {
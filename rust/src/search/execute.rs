@@ -14,6 +14,10 @@ struct SearchChunkResult {
     scored: Vec<SearchCandidateScore>,
 }
 
+fn entry_is_dir(entry: &Entry) -> Option<bool> {
+    entry.kind.and_then(|kind| kind.is_dir)
+}
+
 fn merge_chunk_results(
     mut left: SearchChunkResult,
     mut right: SearchChunkResult,
@@ -36,7 +40,7 @@ pub(super) fn collect_sequential(
             .enumerate()
             .filter_map(|(ordinal, index)| {
                 entries.get(index).and_then(|path| {
-                    evaluate_candidate(path, index, ordinal, compiled, ctx, &matcher)
+                    evaluate_candidate(path, None, index, ordinal, compiled, ctx, &matcher)
                 })
             })
             .collect(),
@@ -44,7 +48,7 @@ pub(super) fn collect_sequential(
             .iter()
             .enumerate()
             .filter_map(|(index, path)| {
-                evaluate_candidate(path, index, index, compiled, ctx, &matcher)
+                evaluate_candidate(path, None, index, index, compiled, ctx, &matcher)
             })
             .collect(),
     };
@@ -65,7 +69,15 @@ pub(super) fn collect_entries_sequential(
             .enumerate()
             .filter_map(|(ordinal, index)| {
                 entries.get(index).and_then(|entry| {
-                    evaluate_candidate(entry.path(), index, ordinal, compiled, ctx, &matcher)
+                    evaluate_candidate(
+                        entry.path(),
+                        entry_is_dir(entry),
+                        index,
+                        ordinal,
+                        compiled,
+                        ctx,
+                        &matcher,
+                    )
                 })
             })
             .collect(),
@@ -73,7 +85,15 @@ pub(super) fn collect_entries_sequential(
             .iter()
             .enumerate()
             .filter_map(|(index, entry)| {
-                evaluate_candidate(entry.path(), index, index, compiled, ctx, &matcher)
+                evaluate_candidate(
+                    entry.path(),
+                    entry_is_dir(entry),
+                    index,
+                    index,
+                    compiled,
+                    ctx,
+                    &matcher,
+                )
             })
             .collect(),
     };
@@ -105,6 +125,7 @@ pub(super) fn collect_parallel(
                             entries.get(index).and_then(|path| {
                                 evaluate_candidate(
                                     path,
+                                    None,
                                     index,
                                     base_ordinal + offset,
                                     compiled,
@@ -128,6 +149,7 @@ pub(super) fn collect_parallel(
                     |(matcher, mut scored), index| {
                         if let Some(item) = evaluate_candidate(
                             entries[index],
+                            None,
                             index,
                             index,
                             compiled,
@@ -173,6 +195,7 @@ pub(super) fn collect_entries_parallel(
                             entries.get(index).and_then(|entry| {
                                 evaluate_candidate(
                                     entry.path(),
+                                    entry_is_dir(entry),
                                     index,
                                     base_ordinal + offset,
                                     compiled,
@@ -196,6 +219,7 @@ pub(super) fn collect_entries_parallel(
                     |(matcher, mut scored), index| {
                         if let Some(item) = evaluate_candidate(
                             entries[index].path(),
+                            entry_is_dir(&entries[index]),
                             index,
                             index,
                             compiled,
@@ -9,29 +9,22 @@ pub(super) struct SearchContext<'a> {
     pub(super) prefer_relative: bool,
 }
 
-pub(super) fn compile_query(
-    query: &str,
-    use_regex: bool,
-    ignore_case: bool,
-) -> Result<CompiledQuery, String> {
-    CompiledQuery::compile(
-        query,
-        QueryOptions {
-            use_regex,
-            ignore_case,
-        },
-    )
+pub(super) fn compile_query(query: &str, options: QueryOptions) -> Result<CompiledQuery, String> {
+    CompiledQuery::compile(query, options)
 }
 
 pub(super) fn evaluate_candidate(
     path: &Path,
+    is_dir: Option<bool>,
     index: usize,
     ordinal: usize,
     compiled: &CompiledQuery,
     ctx: SearchContext<'_>,
     matcher: &SkimMatcherV2,
 ) -> Option<SearchCandidateScore> {
-    let prepared = compiled.prepare_candidate(path, ctx.root, ctx.prefer_relative);
+    let prepared = compiled
+        .prepare_candidate(path, ctx.root, ctx.prefer_relative)
+        .with_is_dir(is_dir);
     compiled
         .evaluate_with_matcher(&prepared, EvidenceLevel::RankOnly, matcher)
         .map(|evaluation| SearchCandidateScore {
@@ -5,6 +5,8 @@ mod match_eval;
 mod rank;
 
 use crate::entry::Entry;
+use crate::path_utils::normalize_windows_path;
+use crate::query::{fold_case, parse_query, QueryOptions, QuerySpec};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
@@ -18,6 +20,7 @@ use execute::{
 use match_eval::{compile_query, SearchContext};
 #[cfg(test)]
 pub(crate) use rank::filter_search_results;
+pub(crate) use rank::per_directory_cap;
 use rank::{
     materialize_scored_entries, scored_indices_to_paths, sort_scored_matches, top_ranked_scores,
 };
@@ -68,21 +71,36 @@ pub(crate) enum SearchResultSortScope {
     AllMatches,
 }
 
-#[allow(clippy::too_many_arguments)]
+/// `rank_search_results` に渡す照合・並び替え条件。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SearchRankOptions {
+    pub(crate) query: QueryOptions,
+    pub(crate) prefer_relative: bool,
+    pub(crate) sort_mode: SearchResultSortMode,
+    pub(crate) sort_scope: SearchResultSortScope,
+    /// 親 directory ごとに残す件数。`limit` で切る前に適用する。0 は無制限。
+    pub(crate) max_results_per_directory: usize,
+}
+
 pub(crate) fn rank_search_results(
     entries: &Arc<Vec<Entry>>,
     query: &str,
     root: &Path,
     limit: usize,
-    use_regex: bool,
-    ignore_case: bool,
-    prefer_relative: bool,
+    options: SearchRankOptions,
     prefix_cache: &mut SearchPrefixCache,
-    sort_mode: SearchResultSortMode,
-    sort_scope: SearchResultSortScope,
 ) -> (SearchResultSet, Option<String>) {
+    let SearchRankOptions {
+        query: query_options,
+        prefer_relative,
+        sort_mode,
+        sort_scope,
+        max_results_per_directory,
+    } = options;
+    let ignore_case = query_options.ignore_case;
     let query_trimmed = query.trim().to_string();
-    let cached_candidates = if use_regex {
+    // Adding a term widens any-term results, so prefix-narrowed candidates would drop matches.
+    let cached_candidates = if query_options.use_regex || query_options.match_any_term {
         None
     } else {
         prefix_cache.lookup_candidates(entries, root, ignore_case, prefer_relative, &query_trimmed)
@@ -110,8 +128,7 @@ pub(crate) fn rank_search_results(
         match try_collect_entry_matches(
             query,
             entries,
-            use_regex,
-            ignore_case,
+            query_options,
             Some(root),
             prefer_relative,
             cached_candidates.as_ref().map(|items| items.as_slice()),
@@ -136,10 +153,16 @@ pub(crate) fn rank_search_results(
             matched_indices,
         );
     }
-    let ranked = match (sort_scope, sort_mode) {
+    // The per-directory cap must see every ranked match, or crowded folders would use up `limit`.
+    let rank_limit = if max_results_per_directory == 0 {
+        limit
+    } else {
+        usize::MAX
+    };
+    let mut ranked = match (sort_scope, sort_mode) {
         (SearchResultSortScope::AllMatches, SearchResultSortMode::NameAsc)
         | (SearchResultSortScope::AllMatches, SearchResultSortMode::NameDesc) => {
-            top_name_sorted_scores(entries, scored_matches.scored, limit, sort_mode)
+            top_name_sorted_scores(entries, scored_matches.scored, rank_limit, sort_mode)
         }
         (
             SearchResultSortScope::AllMatches,
@@ -149,9 +172,17 @@ pub(crate) fn rank_search_results(
             | SearchResultSortMode::CreatedAsc
             | SearchResultSortMode::SizeDesc
             | SearchResultSortMode::SizeAsc,
-        ) => top_metadata_sorted_scores(entries, scored_matches.scored, limit, sort_mode),
-        _ => top_ranked_scores(scored_matches.scored, limit),
+        ) => top_metadata_sorted_scores(entries, scored_matches.scored, rank_limit, sort_mode),
+        _ => top_ranked_scores(scored_matches.scored, rank_limit),
     };
+    if max_results_per_directory > 0 {
+        let mut keep = per_directory_cap(max_results_per_directory);
+        ranked.retain(|item| {
+            entries
+                .get(item.index)
+                .is_some_and(|entry| keep(entry.path()))
+        });
+    }
     let results = scored_indices_to_paths(entries, &ranked, limit);
     (
         SearchResultSet {
@@ -163,6 +194,50 @@ pub(crate) fn rank_search_results(
     )
 }
 
+/// 絶対 path 形式の query に一致する directory 配下の entry だけを返す。
+pub(crate) fn filter_entries_by_path_prefix(
+    entries: &[Entry],
+    query: &str,
+    ignore_case: bool,
+) -> Option<Vec<Entry>> {
+    let query = query.trim();
+    if !query_looks_like_absolute_path(query) {
+        return None;
+    }
+    let prefix = path_prefix_key(Path::new(query), ignore_case);
+    let prefix = prefix.trim_end_matches('/');
+    let filtered = entries
+        .iter()
+        .filter(|entry| {
+            let key = path_prefix_key(entry.path(), ignore_case);
+            key.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    (!filtered.is_empty()).then_some(filtered)
+}
+
+fn query_looks_like_absolute_path(query: &str) -> bool {
+    let bytes = query.as_bytes();
+    let drive_letter = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'/' | b'\\');
+    query.starts_with('/') || query.starts_with('\\') || drive_letter
+}
+
+fn path_prefix_key(path: &Path, ignore_case: bool) -> String {
+    let key = normalize_windows_path(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    if ignore_case {
+        fold_case(&key)
+    } else {
+        key
+    }
+}
+
 fn entry_name_key(entry: &Entry) -> String {
     entry
         .path()
@@ -299,8 +374,7 @@ fn compare_optional_sort_value<T: Ord>(
 fn try_collect_entry_matches(
     query: &str,
     entries: &[Entry],
-    use_regex: bool,
-    ignore_case: bool,
+    query_options: QueryOptions,
     root: Option<&Path>,
     prefer_relative: bool,
     candidate_indices: Option<&[usize]>,
@@ -309,8 +383,7 @@ fn try_collect_entry_matches(
         query,
         entries,
         SearchCollectOptions {
-            use_regex,
-            ignore_case,
+            query_options,
             root,
             prefer_relative,
             candidate_indices,
@@ -332,8 +405,11 @@ pub(crate) fn try_collect_search_matches(
         query,
         entries,
         SearchCollectOptions {
-            use_regex,
-            ignore_case,
+            query_options: QueryOptions {
+                use_regex,
+                ignore_case,
+                ..QueryOptions::default()
+            },
             root,
             prefer_relative,
             candidate_indices,
@@ -352,7 +428,7 @@ fn try_collect_entry_matches_with_mode(
         return Ok(SearchScoredMatches::default());
     }
 
-    let compiled = compile_query(query, options.use_regex, options.ignore_case)?;
+    let compiled = compile_query(query, options.query_options)?;
     let ctx = SearchContext {
         root: options.root,
         prefer_relative: options.prefer_relative,
@@ -374,8 +450,7 @@ fn try_collect_entry_matches_with_mode(
 
 #[derive(Clone, Copy)]
 struct SearchCollectOptions<'a> {
-    use_regex: bool,
-    ignore_case: bool,
+    query_options: QueryOptions,
     root: Option<&'a Path>,
     prefer_relative: bool,
     candidate_indices: Option<&'a [usize]>,
@@ -392,7 +467,7 @@ fn try_collect_search_matches_with_mode(
         return Ok(SearchScoredMatches::default());
     }
 
-    let compiled = compile_query(query, options.use_regex, options.ignore_case)?;
+    let compiled = compile_query(query, options.query_options)?;
     let ctx = SearchContext {
         root: options.root,
         prefer_relative: options.prefer_relative,
@@ -412,6 +487,19 @@ fn try_collect_search_matches_with_mode(
     })
 }
 
+/// query を解析し、regex mode では各 term を事前 compile して最初のエラーを返す。
+/// 検索前の入力検証用で、`search_entries` 系は不正な query でも従来どおり空結果で返る。
+pub fn validate_query(query: &str, use_regex: bool) -> Result<QuerySpec, String> {
+    compile_query(
+        query,
+        QueryOptions {
+            use_regex,
+            ..QueryOptions::default()
+        },
+    )?;
+    Ok(parse_query(query))
+}
+
 pub fn search_entries(
     query: &str,
     entries: &[PathBuf],
@@ -1,6 +1,7 @@
 use super::{IndexedScore, SearchCandidateScore};
 use crate::entry::Entry;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 fn compare_scored_candidates(a: &SearchCandidateScore, b: &SearchCandidateScore) -> Ordering {
@@ -10,6 +11,20 @@ fn compare_scored_candidates(a: &SearchCandidateScore, b: &SearchCandidateScore)
         .then_with(|| a.ordinal.cmp(&b.ordinal))
 }
 
+/// 順位順に渡される path を親 directory ごとに先頭 `max_per_directory` 件だけ通す判定を返す。0 は無制限。
+pub(crate) fn per_directory_cap(max_per_directory: usize) -> impl FnMut(&Path) -> bool {
+    let mut counts: HashMap<PathBuf, usize> = HashMap::new();
+    move |path| {
+        if max_per_directory == 0 {
+            return true;
+        }
+        let parent = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let count = counts.entry(parent).or_insert(0);
+        *count += 1;
+        *count <= max_per_directory
+    }
+}
+
 pub(crate) fn sort_scored_matches(scored: &mut [SearchCandidateScore]) {
     scored.sort_unstable_by(compare_scored_candidates);
 }
@@ -2,6 +2,18 @@ use super::*;
 use crate::ui_model::has_visible_match;
 use std::time::{Duration, Instant};
 
+#[test]
+fn validate_query_reports_invalid_regex_terms_only_in_regex_mode() {
+    let spec = validate_query("src !tmp", true).expect("valid regex query");
+    assert_eq!(spec.include_terms, vec!["src".to_string()]);
+    assert_eq!(spec.exclude_terms, vec!["tmp".to_string()]);
+
+    assert!(validate_query("main (", true).is_err());
+    assert!(validate_query("main !(", true).is_err());
+    assert!(validate_query("main (", false).is_ok());
+    assert!(search_entries("main (", &[PathBuf::from("/tmp/main.rs")], 10, true, true).is_empty());
+}
+
 #[test]
 fn orders_by_score_and_limit() {
     let entries = vec![
@@ -67,12 +79,15 @@ fn tc_155_regression_rank_search_compiles_query_once_per_request() {
         "main",
         Path::new("/tmp"),
         10,
-        false,
-        true,
-        true,
+        SearchRankOptions {
+            query: QueryOptions {
+                ignore_case: true,
+                ..QueryOptions::default()
+            },
+            prefer_relative: true,
+            ..SearchRankOptions::default()
+        },
         &mut cache,
-        SearchResultSortMode::Score,
-        SearchResultSortScope::ShownResults,
     );
 
     assert!(error.is_none());
@@ -80,6 +95,95 @@ fn tc_155_regression_rank_search_compiles_query_once_per_request() {
     assert_eq!(crate::query::query_compile_count(), 1);
 }
 
+#[test]
+fn rank_search_applies_per_directory_cap_before_limit() {
+    let entries = Arc::new(
+        [
+            "/tmp/crowded/note1.txt",
+            "/tmp/crowded/note2.txt",
+            "/tmp/crowded/note3.txt",
+            "/tmp/other/note4.txt",
+            "/tmp/note5.txt",
+        ]
+        .into_iter()
+        .map(|path| Entry::new(PathBuf::from(path), Some(crate::entry::EntryKind::file())))
+        .collect::<Vec<_>>(),
+    );
+    let rank = |max_results_per_directory| {
+        let (result, error) = rank_search_results(
+            &entries,
+            "note",
+            Path::new("/tmp"),
+            3,
+            SearchRankOptions {
+                prefer_relative: true,
+                max_results_per_directory,
+                ..SearchRankOptions::default()
+            },
+            &mut SearchPrefixCache::default(),
+        );
+        assert!(error.is_none());
+        result
+    };
+
+    let uncapped = rank(0);
+    assert_eq!(uncapped.results.len(), 3);
+    assert_eq!(uncapped.total_match_count, 5);
+
+    let capped = rank(1);
+    let mut parents = capped
+        .results
+        .iter()
+        .map(|(path, _)| path.parent().expect("parent").to_path_buf())
+        .collect::<Vec<_>>();
+    parents.sort();
+    assert_eq!(
+        parents,
+        ["/tmp", "/tmp/crowded", "/tmp/other"].map(PathBuf::from)
+    );
+    assert_eq!(capped.total_match_count, 5);
+}
+
+#[test]
+fn extension_filter_uses_entry_kind_to_drop_dotted_folders() {
+    let entries = Arc::new(vec![
+        Entry::new(
+            PathBuf::from("/tmp/etc/conf.d"),
+            Some(crate::entry::EntryKind::dir()),
+        ),
+        Entry::new(
+            PathBuf::from("/tmp/etc/site.d"),
+            Some(crate::entry::EntryKind::file()),
+        ),
+        Entry::new(PathBuf::from("/tmp/etc/extra.d"), None),
+    ]);
+    let mut cache = SearchPrefixCache::default();
+
+    let (result, error) = rank_search_results(
+        &entries,
+        "@d",
+        Path::new("/tmp"),
+        10,
+        SearchRankOptions::default(),
+        &mut cache,
+    );
+
+    assert!(error.is_none());
+    let mut paths = result
+        .results
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            PathBuf::from("/tmp/etc/extra.d"),
+            PathBuf::from("/tmp/etc/site.d")
+        ]
+    );
+}
+
 #[test]
 fn tc_155_regression_authoritative_search_still_applies_exclusion() {
     let entries = vec![
@@ -116,12 +220,16 @@ fn all_matches_name_sort_can_surface_items_outside_score_limited_snapshot() {
         "module",
         Path::new("/tmp"),
         1,
-        false,
-        true,
-        false,
+        SearchRankOptions {
+            query: QueryOptions {
+                ignore_case: true,
+                ..QueryOptions::default()
+            },
+            sort_mode: SearchResultSortMode::NameAsc,
+            sort_scope: SearchResultSortScope::AllMatches,
+            ..SearchRankOptions::default()
+        },
         &mut cache,
-        SearchResultSortMode::NameAsc,
-        SearchResultSortScope::AllMatches,
     );
 
     assert!(error.is_none());
@@ -130,6 +238,31 @@ fn all_matches_name_sort_can_surface_items_outside_score_limited_snapshot() {
     assert_eq!(out.results[0].0, PathBuf::from("/tmp/alpha/module.rs"));
 }
 
+#[test]
+fn path_prefix_filter_keeps_entries_under_absolute_query_directory() {
+    let entries = [
+        "/work/src/main.rs",
+        "/work/src",
+        "/work/srcx/lib.rs",
+        "/other/a.rs",
+    ]
+    .into_iter()
+    .map(|path| Entry::new(PathBuf::from(path), None))
+    .collect::<Vec<_>>();
+
+    let filtered =
+        filter_entries_by_path_prefix(&entries, " /WORK/src/ ", true).expect("prefix matches");
+    let paths = filtered.iter().map(Entry::path).collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        vec![Path::new("/work/src/main.rs"), Path::new("/work/src")]
+    );
+
+    assert!(filter_entries_by_path_prefix(&entries, "/WORK/src", false).is_none());
+    assert!(filter_entries_by_path_prefix(&entries, "/missing", true).is_none());
+    assert!(filter_entries_by_path_prefix(&entries, "work/src", true).is_none());
+}
+
 #[test]
 fn parallel_collection_matches_sequential_ranking() {
     let entries: Vec<PathBuf> = (0..50_000)
@@ -141,8 +274,10 @@ fn parallel_collection_matches_sequential_ranking() {
         "module_123",
         &path_refs,
         SearchCollectOptions {
-            use_regex: false,
-            ignore_case: true,
+            query_options: QueryOptions {
+                ignore_case: true,
+                ..QueryOptions::default()
+            },
             root: None,
             prefer_relative: false,
             candidate_indices: None,
@@ -155,8 +290,10 @@ fn parallel_collection_matches_sequential_ranking() {
         "module_123",
         &path_refs,
         SearchCollectOptions {
-            use_regex: false,
-            ignore_case: true,
+            query_options: QueryOptions {
+                ignore_case: true,
+                ..QueryOptions::default()
+            },
             root: None,
             prefer_relative: false,
             candidate_indices: None,
@@ -226,6 +363,59 @@ fn prioritizes_exact_filename_match() {
     );
 }
 
+#[test]
+fn prefers_basename_stem_match_over_extension_match() {
+    let entries = vec![
+        PathBuf::from("/tmp/src/domain.main"),
+        PathBuf::from("/tmp/src/main.py"),
+    ];
+    let out = search_entries("main", &entries, 10, false, true);
+    assert_eq!(out.len(), 2);
+    assert_eq!(out[0].0, PathBuf::from("/tmp/src/main.py"));
+    assert!(out[0].1 > out[1].1);
+}
+
+#[test]
+fn rank_by_filename_scores_against_basename_without_changing_membership() {
+    let entries = Arc::new(vec![
+        Entry::new(PathBuf::from("/tmp/main/app/domain.rs"), None),
+        Entry::new(PathBuf::from("/tmp/main/x/readme_main_notes.rs"), None),
+    ]);
+    let rank = |rank_by_filename: bool| {
+        let mut cache = SearchPrefixCache::default();
+        let (out, error) = rank_search_results(
+            &entries,
+            "mai",
+            Path::new("/tmp"),
+            10,
+            SearchRankOptions {
+                query: QueryOptions {
+                    ignore_case: true,
+                    rank_by_filename,
+                    ..QueryOptions::default()
+                },
+                ..SearchRankOptions::default()
+            },
+            &mut cache,
+        );
+        assert!(error.is_none());
+        out.results
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>()
+    };
+
+    let by_path = rank(false);
+    let by_filename = rank(true);
+    assert_eq!(by_path.len(), 2);
+    assert_eq!(by_filename.len(), 2);
+    assert_eq!(by_path[0], PathBuf::from("/tmp/main/app/domain.rs"));
+    assert_eq!(
+        by_filename[0],
+        PathBuf::from("/tmp/main/x/readme_main_notes.rs")
+    );
+}
+
 #[test]
 fn hides_non_matching_results() {
     let entries = vec![
@@ -629,12 +819,15 @@ fn perf_search_100k_cold_warm_query_shapes() {
         "module_000",
         &root,
         100,
-        false,
-        true,
-        true,
+        SearchRankOptions {
+            query: QueryOptions {
+                ignore_case: true,
+                ..QueryOptions::default()
+            },
+            prefer_relative: true,
+            ..SearchRankOptions::default()
+        },
         &mut warmup_cache,
-        SearchResultSortMode::Score,
-        SearchResultSortScope::ShownResults,
     );
 
     let shapes = [
@@ -655,6 +848,7 @@ fn perf_search_100k_cold_warm_query_shapes() {
             crate::query::QueryOptions {
                 use_regex,
                 ignore_case: true,
+                ..crate::query::QueryOptions::default()
             },
         )
         .expect("compile perf query");
@@ -670,12 +864,16 @@ fn perf_search_100k_cold_warm_query_shapes() {
                 query,
                 &root,
                 100,
-                use_regex,
-                true,
-                true,
+                SearchRankOptions {
+                    query: QueryOptions {
+                        use_regex,
+                        ignore_case: true,
+                        ..QueryOptions::default()
+                    },
+                    prefer_relative: true,
+                    ..SearchRankOptions::default()
+                },
                 &mut cache,
-                SearchResultSortMode::Score,
-                SearchResultSortScope::ShownResults,
             );
             samples.push(started.elapsed());
             assert!(error.is_none(), "{label}: {error:?}");
@@ -715,12 +913,15 @@ fn perf_search_100k_cold_warm_query_shapes() {
         "module_0999",
         &root,
         100,
-        false,
-        true,
-        true,
+        SearchRankOptions {
+            query: QueryOptions {
+                ignore_case: true,
+                ..QueryOptions::default()
+            },
+            prefer_relative: true,
+            ..SearchRankOptions::default()
+        },
         &mut cold_cache,
-        SearchResultSortMode::Score,
-        SearchResultSortScope::ShownResults,
     );
     assert!(cold_error.is_none());
 
@@ -733,12 +934,15 @@ fn perf_search_100k_cold_warm_query_shapes() {
             "module_099",
             &root,
             100,
-            false,
-            true,
-            true,
+            SearchRankOptions {
+                query: QueryOptions {
+                    ignore_case: true,
+                    ..QueryOptions::default()
+                },
+                prefer_relative: true,
+                ..SearchRankOptions::default()
+            },
             &mut cache,
-            SearchResultSortMode::Score,
-            SearchResultSortScope::ShownResults,
         );
         assert!(seed_error.is_none());
         let started = Instant::now();
@@ -747,12 +951,15 @@ fn perf_search_100k_cold_warm_query_shapes() {
             "module_0999",
             &root,
             100,
-            false,
-            true,
-            true,
+            SearchRankOptions {
+                query: QueryOptions {
+                    ignore_case: true,
+                    ..QueryOptions::default()
+                },
+                prefer_relative: true,
+                ..SearchRankOptions::default()
+            },
             &mut cache,
-            SearchResultSortMode::Score,
-            SearchResultSortScope::ShownResults,
         );
         warm_samples.push(started.elapsed());
         assert!(error.is_none());
@@ -822,3 +1029,38 @@ fn include_token_pipe_still_combines_with_and_tokens() {
     assert!(names.contains(&"bar.txt"));
     assert_eq!(out.len(), 2);
 }
+
+#[test]
+fn regex_mode_applies_regex_to_exclusion_terms() {
+    let entries = vec![
+        PathBuf::from("/tmp/src/main.rs"),
+        PathBuf::from("/tmp/src/main.test.rs"),
+        PathBuf::from("/tmp/src/MAIN.TEST.md"),
+        PathBuf::from("/tmp/src/maintest.rs"),
+    ];
+    let out = search_entries(r"main !\.test\.", &entries, 10, true, true);
+    let mut names = out
+        .iter()
+        .filter_map(|(path, _)| path.file_name().and_then(|s| s.to_str()))
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, vec!["main.rs", "maintest.rs"]);
+}
+
+#[test]
+fn non_regex_mode_keeps_exclusion_terms_literal() {
+    let entries = vec![
+        PathBuf::from("/tmp/src/main.rs"),
+        PathBuf::from("/tmp/src/main.test.rs"),
+    ];
+    let out = search_entries(r"main !\.test\.", &entries, 10, false, true);
+    assert_eq!(out.len(), 2);
+}
+
+#[test]
+fn invalid_regex_exclusion_returns_error_in_try_api() {
+    let entries = vec![PathBuf::from("/tmp/src/main.py")];
+    let err = try_search_entries_with_scope("main ![*", &entries, 10, true, true, None, false)
+        .expect_err("invalid exclusion regex should return error");
+    assert!(err.contains("invalid regex"));
+}
@@ -0,0 +1,158 @@
+use crate::search::search_entries_with_scope;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// `--serve` で常駐させる検索 server。index 済み entry を保持し、行単位の query に答える。
+pub struct SearchServer {
+    entries: Vec<PathBuf>,
+    root: PathBuf,
+    limit: usize,
+    ignore_case: bool,
+}
+
+impl SearchServer {
+    pub fn new(entries: Vec<PathBuf>, root: PathBuf, limit: usize, ignore_case: bool) -> Self {
+        Self {
+            entries,
+            root,
+            limit,
+            ignore_case,
+        }
+    }
+
+    /// query 1 件分の結果を JSON 配列 1 行として返す。空 query は index 順の先頭 `limit` 件。
+    pub fn respond(&self, query: &str) -> String {
+        let query = query.trim();
+        let results = if query.is_empty() {
+            self.entries
+                .iter()
+                .take(self.limit)
+                .map(|path| (path.clone(), 0.0))
+                .collect()
+        } else {
+            search_entries_with_scope(
+                query,
+                &self.entries,
+                self.limit,
+                false,
+                self.ignore_case,
+                Some(&self.root),
+                true,
+            )
+        };
+        let items = results
+            .into_iter()
+            .map(|(path, score)| {
+                serde_json::json!({
+                    "path": path.to_string_lossy(),
+                    "score": score,
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::Value::Array(items).to_string()
+    }
+
+    /// 1 接続分の改行区切り query を EOF まで処理する。
+    pub fn serve_connection<R: BufRead, W: Write>(
+        &self,
+        reader: R,
+        mut writer: W,
+    ) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            writeln!(writer, "{}", self.respond(&line))?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Unix domain socket で接続を待ち受け、process shutdown 要求（SIGINT）まで応答を続ける。
+#[cfg(unix)]
+pub fn serve_unix_socket(server: SearchServer, socket_path: &Path) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    // A socket left behind by a crashed server would make bind fail; never remove other files.
+    if std::fs::symlink_metadata(socket_path).is_ok_and(|meta| meta.file_type().is_socket()) {
+        let _ = std::fs::remove_file(socket_path);
+    }
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("failed to bind {}", socket_path.display()))?;
+    listener
+        .set_nonblocking(true)
+        .context("failed to configure listener")?;
+    tracing::info!(socket = %socket_path.display(), "search server listening");
+    let server = Arc::new(server);
+    while !crate::app::process_shutdown_requested() {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let server = Arc::clone(&server);
+                std::thread::spawn(move || {
+                    let _ = stream.set_nonblocking(false);
+                    let Ok(reader) = stream.try_clone() else {
+                        return;
+                    };
+                    if let Err(err) = server.serve_connection(io::BufReader::new(reader), stream) {
+                        tracing::debug!(error = %err, "search client disconnected");
+                    }
+                });
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Err(err) => {
+                let _ = std::fs::remove_file(socket_path);
+                return Err(err).context("failed to accept search client");
+            }
+        }
+    }
+    let _ = std::fs::remove_file(socket_path);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn serve_unix_socket(_server: SearchServer, socket_path: &Path) -> anyhow::Result<()> {
+    anyhow::bail!(
+        "--serve requires Unix domain sockets and is not supported on this platform: {}",
+        socket_path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serve_connection_answers_each_query_line_with_json_array() {
+        let root = PathBuf::from("/tmp/serve-root");
+        let server = SearchServer::new(
+            vec![root.join("src/main.rs"), root.join("README.md")],
+            root.clone(),
+            10,
+            true,
+        );
+        let mut output = Vec::new();
+
+        server
+            .serve_connection(io::Cursor::new("main\n\nzzzz\n"), &mut output)
+            .expect("serve connection");
+
+        let lines = String::from_utf8(output).expect("utf8");
+        let responses = lines
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("json line"))
+            .collect::<Vec<_>>();
+        assert_eq!(responses.len(), 3);
+        assert_eq!(
+            responses[0][0]["path"],
+            root.join("src/main.rs").to_string_lossy().as_ref()
+        );
+        assert!(responses[0][0]["score"].as_f64().is_some());
+        assert_eq!(responses[1].as_array().map(Vec::len), Some(2));
+        assert_eq!(responses[2], serde_json::json!([]));
+    }
+}
@@ -16,6 +16,7 @@ pub fn match_positions_for_path(
         QueryOptions {
             use_regex,
             ignore_case,
+            ..QueryOptions::default()
         },
     ) else {
         return HashSet::new();
@@ -92,6 +93,20 @@ mod tests {
         assert!(match_positions_for_path(&path, &root, "main zzzz", true, false, true).len() >= 4);
     }
 
+    #[test]
+    fn match_positions_highlight_every_contiguous_occurrence() {
+        let root = PathBuf::from("/tmp");
+        let path = PathBuf::from("/tmp/src/lib/src/mod.rs");
+        let positions = match_positions_for_path(&path, &root, "src", true, false, true);
+        let display = display_path_with_mode(&path, &root, true);
+        let highlighted: Vec<usize> = display
+            .match_indices("src")
+            .flat_map(|(start, _)| start..start + 3)
+            .collect();
+        assert_eq!(highlighted.len(), 6);
+        assert!(highlighted.iter().all(|idx| positions.contains(idx)));
+    }
+
     #[test]
     fn match_positions_support_exact_token_prefix() {
         let root = PathBuf::from("/tmp");
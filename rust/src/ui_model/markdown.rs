@@ -0,0 +1,89 @@
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkdownPreviewLine {
+    Heading { level: u8, text: String },
+    Bullet { indent: usize, text: String },
+    Code(String),
+    Plain(String),
+}
+
+pub fn is_markdown_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+}
+
+pub fn parse_markdown_preview(text: &str) -> Vec<MarkdownPreviewLine> {
+    let mut in_fence = false;
+    text.lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                return MarkdownPreviewLine::Code(line.to_string());
+            }
+            if in_fence {
+                return MarkdownPreviewLine::Code(line.to_string());
+            }
+            parse_markdown_line(line)
+        })
+        .collect()
+}
+
+fn parse_markdown_line(line: &str) -> MarkdownPreviewLine {
+    let trimmed = line.trim_start();
+    let hashes = trimmed.chars().take_while(|ch| *ch == '#').count();
+    if (1..=6).contains(&hashes) {
+        let rest = &trimmed[hashes..];
+        if rest.is_empty() || rest.starts_with(' ') {
+            return MarkdownPreviewLine::Heading {
+                level: hashes as u8,
+                text: rest.trim().trim_end_matches('#').trim_end().to_string(),
+            };
+        }
+    }
+    let indent = line.len() - trimmed.len();
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(text) = trimmed.strip_prefix(marker) {
+            return MarkdownPreviewLine::Bullet {
+                indent: indent / 2,
+                text: text.to_string(),
+            };
+        }
+    }
+    MarkdownPreviewLine::Plain(line.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_markdown_preview_detects_headings_bullets_and_fences() {
+        let lines = parse_markdown_preview("# Title\n  - item\n```\n# not heading\n```\n#tag");
+        assert_eq!(
+            lines,
+            vec![
+                MarkdownPreviewLine::Heading {
+                    level: 1,
+                    text: "Title".to_string(),
+                },
+                MarkdownPreviewLine::Bullet {
+                    indent: 1,
+                    text: "item".to_string(),
+                },
+                MarkdownPreviewLine::Code("```".to_string()),
+                MarkdownPreviewLine::Code("# not heading".to_string()),
+                MarkdownPreviewLine::Code("```".to_string()),
+                MarkdownPreviewLine::Plain("#tag".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn is_markdown_path_matches_extension_case_insensitively() {
+        assert!(is_markdown_path(Path::new("README.md")));
+        assert!(is_markdown_path(Path::new("notes.MarkDown")));
+        assert!(!is_markdown_path(Path::new("main.rs")));
+    }
+}
@@ -1,11 +1,21 @@
 mod display;
 mod highlight;
+mod markdown;
 mod on_demand;
 mod preview;
+mod table;
 
 pub use display::{display_path, display_path_with_mode, normalize_path_for_display};
 pub use highlight::{
     has_visible_match, match_positions_for_path, match_positions_for_path_with_compiled,
 };
+pub use markdown::{is_markdown_path, parse_markdown_preview, MarkdownPreviewLine};
 pub use on_demand::should_skip_preview;
-pub use preview::{build_preview_text, build_preview_text_with_kind};
+pub(crate) use preview::format_system_time;
+pub use preview::{
+    append_metadata_details, build_binary_skipped_preview_text, build_preview_text,
+    build_preview_text_cancellable, build_preview_text_with_kind, build_preview_text_with_limits,
+    build_size_skipped_preview_text, is_probably_binary, number_preview_lines, PREVIEW_MAX_BYTES,
+    PREVIEW_MAX_LINES,
+};
+pub use table::{delimited_table_separator, format_delimited_table, table_preview_header_row};
@@ -5,29 +5,58 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use encoding_rs::{EUC_JP, SHIFT_JIS, UTF_16BE, UTF_16LE, WINDOWS_1252};
 
+use super::table::{delimited_table_separator, format_delimited_table};
 use super::{normalize_path_for_display, should_skip_preview};
 pub fn build_preview_text(path: &Path) -> String {
     build_preview_text_with_kind(path, path.is_dir())
 }
 
+pub const PREVIEW_MAX_LINES: usize = 20;
+pub const PREVIEW_MAX_BYTES: usize = 64 * 1024;
+/// minify 済み JS など 1 行が極端に長い file で preview が詰まらないよう、1 行あたりの表示文字数を制限する。
+pub const PREVIEW_MAX_LINE_CHARS: usize = 2000;
+
 pub fn build_preview_text_with_kind(path: &Path, is_dir: bool) -> String {
-    const PREVIEW_MAX_LINES: usize = 20;
-    const PREVIEW_MAX_BYTES: usize = 64 * 1024;
+    build_preview_text_with_limits(path, is_dir, PREVIEW_MAX_LINES, PREVIEW_MAX_BYTES)
+}
+
+pub fn build_preview_text_with_limits(
+    path: &Path,
+    is_dir: bool,
+    max_lines: usize,
+    max_bytes: usize,
+) -> String {
+    build_preview_text_cancellable(path, is_dir, max_lines, max_bytes, 1, &|| false)
+        .unwrap_or_default()
+}
 
+/// file 本文の読み込み中に `is_cancelled` が true になった場合は `None` を返す。
+/// directory は `dir_depth` が 2 以上なら子 folder の直下も字下げして列挙する。
+pub fn build_preview_text_cancellable(
+    path: &Path,
+    is_dir: bool,
+    max_lines: usize,
+    max_bytes: usize,
+    dir_depth: usize,
+    is_cancelled: &dyn Fn() -> bool,
+) -> Option<String> {
     let normalized_path = normalize_path_for_display(path);
     if !is_dir && should_skip_preview(path, is_dir) {
-        return format!("File: {normalized_path}\n\n<on-demand file: preview skipped>");
+        return Some(format!(
+            "File: {normalized_path}\n\n<on-demand file: preview skipped>"
+        ));
     }
 
     let metadata = std::fs::metadata(path).ok();
     let symlink_metadata = std::fs::symlink_metadata(path).ok();
     if is_dir {
-        return build_directory_preview_text(
+        return Some(build_directory_preview_text(
             path,
             &normalized_path,
             metadata.as_ref(),
             symlink_metadata.as_ref(),
-        );
+            dir_depth,
+        ));
     }
 
     let head = build_entry_header(
@@ -37,16 +66,263 @@ pub fn build_preview_text_with_kind(path: &Path, is_dir: bool) -> String {
         metadata.as_ref(),
         symlink_metadata.as_ref(),
     );
-    match read_preview_lines(path, PREVIEW_MAX_LINES, PREVIEW_MAX_BYTES) {
+    match read_preview_lines(path, max_lines, max_bytes, is_cancelled) {
         Ok(preview) => {
+            let preview = match delimited_table_separator(path) {
+                Some(separator) => format_delimited_table(&preview, separator).unwrap_or(preview),
+                None => preview,
+            };
             if preview.is_empty() {
-                format!("{}\n<empty file>", head)
+                Some(format!("{}\n<empty file>", head))
             } else {
-                format!("{}\n{}", head, preview.join("\n"))
+                Some(format!("{}\n{}", head, preview.join("\n")))
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::Interrupted => None,
+        Err(_) => Some(format!("{}\n<binary or unreadable file>", head)),
+    }
+}
+
+/// `max_file_bytes` を超える file は open せずに skip 表示を返す。
+pub fn build_size_skipped_preview_text(
+    path: &Path,
+    is_dir: bool,
+    max_file_bytes: u64,
+) -> Option<String> {
+    if is_dir {
+        return None;
+    }
+    let size = std::fs::metadata(path).ok()?.len();
+    if size <= max_file_bytes {
+        return None;
+    }
+    Some(format!(
+        "File: {} ({}, preview skipped)",
+        normalize_path_for_display(path),
+        format_file_size(size)
+    ))
+}
+
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// 先頭の数 KB だけを読み、NUL や制御文字の多さから binary file らしいかを判定する。
+pub fn is_probably_binary(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let mut prefix = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    if file
+        .take(BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut prefix)
+        .is_err()
+    {
+        return false;
+    }
+    if prefix.is_empty() || has_utf16_bom(&prefix) {
+        return false;
+    }
+    if prefix.contains(&0) {
+        return true;
+    }
+    let control_bytes = prefix
+        .iter()
+        .filter(|byte| matches!(byte, 0x01..=0x08 | 0x0E..=0x1A | 0x1C..=0x1F | 0x7F))
+        .count();
+    control_bytes.saturating_mul(10) > prefix.len()
+}
+
+/// binary 判定済み file の本文を読まずに header と note だけを返す。
+pub fn build_binary_skipped_preview_text(path: &Path) -> String {
+    let normalized_path = normalize_path_for_display(path);
+    let metadata = std::fs::metadata(path).ok();
+    let symlink_metadata = std::fs::symlink_metadata(path).ok();
+    let head = build_entry_header(
+        path,
+        "File",
+        &normalized_path,
+        metadata.as_ref(),
+        symlink_metadata.as_ref(),
+    );
+    format!("{head}\n<binary>")
+}
+
+const PREVIEW_HEADER_PREFIXES: [&str; 13] = [
+    "File: ",
+    "Action: ",
+    "Size: ",
+    "Created: ",
+    "Updated: ",
+    "Attributes: ",
+    "Target",
+    "Path: ",
+    "Exact Size: ",
+    "Accessed: ",
+    "Permissions: ",
+    "Owner: ",
+    "Group: ",
+];
+
+pub(super) fn preview_header_len(lines: &[&str]) -> usize {
+    if lines
+        .first()
+        .is_some_and(|line| line.starts_with("File: ") || line.starts_with("Action: "))
+    {
+        lines
+            .iter()
+            .take_while(|line| {
+                PREVIEW_HEADER_PREFIXES
+                    .iter()
+                    .any(|prefix| line.starts_with(prefix))
+            })
+            .count()
+    } else {
+        0
+    }
+}
+
+/// 先頭の `File:` / `Action:` などの header 行を除き、本文行へ 1 始まりの行番号を付ける。
+pub fn number_preview_lines(preview: &str) -> String {
+    let lines = preview.lines().collect::<Vec<_>>();
+    let header_len = preview_header_len(&lines);
+    let (header, body) = lines.split_at(header_len);
+    let width = body.len().to_string().len();
+    header
+        .iter()
+        .map(|line| line.to_string())
+        .chain(
+            body.iter()
+                .enumerate()
+                .map(|(index, line)| format!("{:>width$}: {line}", index + 1)),
+        )
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// preview の header 直後へ絶対 path・正確な size・アクセス日時・権限などの詳細 metadata を差し込む。
+/// platform で取得できない項目は出力しない。
+pub fn append_metadata_details(path: &Path, preview: &str) -> String {
+    let details = metadata_detail_lines(path);
+    if details.is_empty() {
+        return preview.to_string();
+    }
+    let lines = preview.lines().collect::<Vec<_>>();
+    let (header, body) = lines.split_at(preview_header_len(&lines).max(1).min(lines.len()));
+    header
+        .iter()
+        .map(|line| line.to_string())
+        .chain(details)
+        .chain(body.iter().map(|line| line.to_string()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn metadata_detail_lines(path: &Path) -> Vec<String> {
+    let absolute = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut lines = vec![format!("Path: {}", normalize_path_for_display(&absolute))];
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return lines;
+    };
+    if metadata.is_file() {
+        lines.push(format!("Exact Size: {} bytes", metadata.len()));
+    }
+    if let Some(accessed) = metadata.accessed().ok().and_then(format_system_time) {
+        lines.push(format!("Accessed: {accessed}"));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let owner = unix_account_name(UnixAccountDatabase::Passwd, metadata.uid());
+        let group = unix_account_name(UnixAccountDatabase::Group, metadata.gid());
+        lines.push(format!(
+            "Permissions: {} ({:04o}) {}:{}",
+            unix_mode_string(metadata.mode()),
+            metadata.mode() & 0o7777,
+            owner
+                .as_deref()
+                .map_or_else(|| metadata.uid().to_string(), str::to_string),
+            group
+                .as_deref()
+                .map_or_else(|| metadata.gid().to_string(), str::to_string)
+        ));
+        lines.push(match owner {
+            Some(name) => format!("Owner: {name} (uid {})", metadata.uid()),
+            None => format!("Owner: uid {}", metadata.uid()),
+        });
+        lines.push(match group {
+            Some(name) => format!("Group: {name} (gid {})", metadata.gid()),
+            None => format!("Group: gid {}", metadata.gid()),
+        });
+    }
+    lines
+}
+
+#[cfg(unix)]
+#[derive(Clone, Copy)]
+enum UnixAccountDatabase {
+    Passwd,
+    Group,
+}
+
+/// uid/gid を `/etc/passwd` / `/etc/group` から名前へ解決する。各 file は process 内で 1 回だけ読む。
+#[cfg(unix)]
+fn unix_account_name(database: UnixAccountDatabase, id: u32) -> Option<String> {
+    use std::collections::HashMap;
+    use std::sync::OnceLock;
+
+    static PASSWD: OnceLock<HashMap<u32, String>> = OnceLock::new();
+    static GROUP: OnceLock<HashMap<u32, String>> = OnceLock::new();
+    let (cache, file) = match database {
+        UnixAccountDatabase::Passwd => (&PASSWD, "/etc/passwd"),
+        UnixAccountDatabase::Group => (&GROUP, "/etc/group"),
+    };
+    cache
+        .get_or_init(|| {
+            std::fs::read_to_string(file)
+                .map(|text| parse_unix_account_names(&text))
+                .unwrap_or_default()
+        })
+        .get(&id)
+        .cloned()
+}
+
+/// `name:password:id:...` 形式の行から id と name の対応を作る。同じ id は先に現れた名前を使う。
+#[cfg(unix)]
+fn parse_unix_account_names(text: &str) -> std::collections::HashMap<u32, String> {
+    let mut names = std::collections::HashMap::new();
+    for line in text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(':');
+        let (Some(name), Some(_), Some(id)) = (fields.next(), fields.next(), fields.next()) else {
+            continue;
+        };
+        if let Ok(id) = id.parse::<u32>() {
+            if !name.is_empty() {
+                names.entry(id).or_insert_with(|| name.to_string());
             }
         }
-        Err(_) => format!("{}\n<binary or unreadable file>", head),
     }
+    names
+}
+
+#[cfg(unix)]
+fn unix_mode_string(mode: u32) -> String {
+    const BITS: [(u32, char); 9] = [
+        (0o400, 'r'),
+        (0o200, 'w'),
+        (0o100, 'x'),
+        (0o040, 'r'),
+        (0o020, 'w'),
+        (0o010, 'x'),
+        (0o004, 'r'),
+        (0o002, 'w'),
+        (0o001, 'x'),
+    ];
+    BITS.iter()
+        .map(|(bit, ch)| if mode & bit != 0 { *ch } else { '-' })
+        .collect()
 }
 
 fn format_file_size(bytes: u64) -> String {
@@ -68,11 +344,30 @@ fn read_preview_lines(
     path: &Path,
     max_lines: usize,
     max_bytes: usize,
+    is_cancelled: &dyn Fn() -> bool,
 ) -> std::io::Result<Vec<String>> {
+    const READ_CHUNK_BYTES: usize = 8192;
+
+    let cancelled = || std::io::Error::new(std::io::ErrorKind::Interrupted, "preview cancelled");
+    if is_cancelled() {
+        return Err(cancelled());
+    }
     let mut file = File::open(path)?;
-    let mut bytes = Vec::with_capacity(max_bytes.min(8192));
-    let mut handle = (&mut file).take(max_bytes as u64);
-    handle.read_to_end(&mut bytes)?;
+    let mut bytes = Vec::with_capacity(max_bytes.min(READ_CHUNK_BYTES));
+    let mut chunk = [0u8; READ_CHUNK_BYTES];
+    while bytes.len() < max_bytes {
+        if is_cancelled() {
+            return Err(cancelled());
+        }
+        let want = (max_bytes - bytes.len()).min(READ_CHUNK_BYTES);
+        let read = match file.read(&mut chunk[..want]) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        bytes.extend_from_slice(&chunk[..read]);
+    }
     decode_preview_lines(&bytes, max_lines).ok_or_else(|| {
         std::io::Error::new(std::io::ErrorKind::InvalidData, "preview decode failed")
     })
@@ -98,18 +393,41 @@ fn decode_preview_lines(bytes: &[u8], max_lines: usize) -> Option<Vec<String>> {
 }
 
 fn preview_decoding_candidates(bytes: &[u8]) -> Vec<Option<String>> {
-    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
-        return vec![decode_utf8_preview(&bytes[3..])];
+    if let Some(body) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return vec![decode_utf8_preview(body)];
     }
-    if bytes.starts_with(&[0xFF, 0xFE]) {
-        return vec![decode_with_encoding(&bytes[2..], UTF_16LE)];
+    if let Some(body) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return vec![decode_with_encoding(
+            trim_incomplete_utf16_tail(body, true),
+            UTF_16LE,
+        )];
     }
-    if bytes.starts_with(&[0xFE, 0xFF]) {
-        return vec![decode_with_encoding(&bytes[2..], UTF_16BE)];
+    if let Some(body) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return vec![decode_with_encoding(
+            trim_incomplete_utf16_tail(body, false),
+            UTF_16BE,
+        )];
     }
     Vec::new()
 }
 
+/// byte 上限で切れた末尾の端数 byte と、対になる low surrogate を失った high surrogate を除く。
+fn trim_incomplete_utf16_tail(bytes: &[u8], little_endian: bool) -> &[u8] {
+    let mut end = bytes.len() & !1;
+    if end >= 2 {
+        let pair = [bytes[end - 2], bytes[end - 1]];
+        let unit = if little_endian {
+            u16::from_le_bytes(pair)
+        } else {
+            u16::from_be_bytes(pair)
+        };
+        if (0xD800..0xDC00).contains(&unit) {
+            end -= 2;
+        }
+    }
+    &bytes[..end]
+}
+
 fn preview_fallback_decoders(bytes: &[u8]) -> Vec<Option<String>> {
     #[cfg(windows)]
     {
@@ -130,7 +448,14 @@ fn preview_fallback_decoders(bytes: &[u8]) -> Vec<Option<String>> {
 }
 
 fn decode_utf8_preview(bytes: &[u8]) -> Option<String> {
-    std::str::from_utf8(bytes).ok().map(|text| text.to_string())
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text.to_string()),
+        // byte 上限で multi-byte 文字の途中が切れた場合は、完結している部分までを使う。
+        Err(err) if err.error_len().is_none() => std::str::from_utf8(&bytes[..err.valid_up_to()])
+            .ok()
+            .map(|text| text.to_string()),
+        Err(_) => None,
+    }
 }
 
 fn decode_with_encoding(bytes: &[u8], encoding: &'static encoding_rs::Encoding) -> Option<String> {
@@ -149,10 +474,18 @@ fn split_preview_lines(decoded: &str, max_lines: usize) -> Vec<String> {
     decoded
         .lines()
         .take(max_lines)
-        .map(|line| line.trim_end_matches('\r').to_string())
+        .map(|line| truncate_preview_line(line.trim_end_matches('\r')))
         .collect()
 }
 
+/// `PREVIEW_MAX_LINE_CHARS` を超える行は上限位置で切り、末尾に `…` を付ける。
+fn truncate_preview_line(line: &str) -> String {
+    match line.char_indices().nth(PREVIEW_MAX_LINE_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
 fn looks_like_binary(bytes: &[u8]) -> bool {
     bytes.contains(&0) && !has_utf16_bom(bytes)
 }
@@ -181,12 +514,11 @@ fn build_directory_preview_text(
     normalized_path: &str,
     metadata: Option<&Metadata>,
     symlink_metadata: Option<&Metadata>,
+    dir_depth: usize,
 ) -> String {
     const MAX_LINES: usize = 24;
-    const MAX_NAME_CHARS: usize = 80;
 
-    let read = std::fs::read_dir(path);
-    let Ok(iter) = read else {
+    let Some(entries) = sorted_directory_entries(path) else {
         return format!(
             "{}\nChildren: <unavailable>",
             build_entry_header(
@@ -199,14 +531,6 @@ fn build_directory_preview_text(
         );
     };
 
-    let mut entries: Vec<_> = iter.flatten().collect();
-    entries.sort_by_key(|e| {
-        e.file_name()
-            .to_string_lossy()
-            .to_string()
-            .to_ascii_lowercase()
-    });
-
     let total = entries.len();
     let header = build_entry_header(
         path,
@@ -219,24 +543,64 @@ fn build_directory_preview_text(
         return format!("{header}\nChildren: 0\n<empty>");
     }
 
+    let two_levels = dir_depth >= 2;
     let mut lines = Vec::new();
-    for entry in entries.iter().take(MAX_LINES) {
-        let name = entry.file_name().to_string_lossy().to_string();
-        let short = truncate_chars(&name, MAX_NAME_CHARS);
+    let mut shown = 0usize;
+    for entry in &entries {
+        if lines.len() >= MAX_LINES {
+            break;
+        }
         let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
-        let marker = if is_dir { "[D]" } else { "[F]" };
-        lines.push(format!("{} {}", marker, short));
+        lines.push(directory_child_line(entry, is_dir, ""));
+        shown += 1;
+        if !two_levels || !is_dir {
+            continue;
+        }
+        let children = sorted_directory_entries(&entry.path()).unwrap_or_default();
+        let remaining = MAX_LINES.saturating_sub(lines.len());
+        for child in children.iter().take(remaining) {
+            let child_is_dir = child.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            lines.push(directory_child_line(child, child_is_dir, "    "));
+        }
+        if children.len() > remaining {
+            lines.push(format!("    ... ({} more)", children.len() - remaining));
+        }
     }
-    if total > MAX_LINES {
-        lines.push(format!("... ({} more)", total - MAX_LINES));
+    if total > shown {
+        lines.push(format!("... ({} more)", total - shown));
     }
 
+    let scope = if two_levels {
+        "two levels"
+    } else {
+        "direct children only"
+    };
     format!(
-        "{header}\nChildren: {total}\nScope: direct children only\n\n{}",
+        "{header}\nChildren: {total}\nScope: {scope}\n\n{}",
         lines.join("\n")
     )
 }
 
+fn sorted_directory_entries(path: &Path) -> Option<Vec<std::fs::DirEntry>> {
+    let mut entries: Vec<_> = std::fs::read_dir(path).ok()?.flatten().collect();
+    entries.sort_by_key(|e| {
+        e.file_name()
+            .to_string_lossy()
+            .to_string()
+            .to_ascii_lowercase()
+    });
+    Some(entries)
+}
+
+fn directory_child_line(entry: &std::fs::DirEntry, is_dir: bool, indent: &str) -> String {
+    const MAX_NAME_CHARS: usize = 80;
+
+    let name = entry.file_name().to_string_lossy().to_string();
+    let short = truncate_chars(&name, MAX_NAME_CHARS);
+    let marker = if is_dir { "[D]" } else { "[F]" };
+    format!("{indent}{} {}", marker, short)
+}
+
 fn build_entry_header(
     path: &Path,
     kind: &str,
@@ -299,7 +663,7 @@ fn metadata_attributes(metadata: &Metadata) -> Vec<&'static str> {
     attributes
 }
 
-fn format_system_time(time: SystemTime) -> Option<String> {
+pub(crate) fn format_system_time(time: SystemTime) -> Option<String> {
     let seconds = match time.duration_since(UNIX_EPOCH) {
         Ok(duration) => i64::try_from(duration.as_secs()).ok()?,
         Err(error) => {
@@ -381,6 +745,41 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn build_preview_text_truncates_pathologically_long_lines() {
+        let root = test_root("preview-long-line");
+        fs::create_dir_all(&root).expect("create dir");
+        let file = root.join("bundle.min.js");
+        let long_line = "a".repeat(PREVIEW_MAX_LINE_CHARS + 500);
+        fs::write(&file, format!("{long_line}\nshort\n")).expect("write file");
+
+        let preview = build_preview_text(&file);
+        let expected = format!("{}…", "a".repeat(PREVIEW_MAX_LINE_CHARS));
+        assert!(preview.lines().any(|line| line == expected));
+        assert!(!preview.contains(&long_line));
+        assert!(preview.lines().any(|line| line == "short"));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn build_preview_text_for_directory_lists_two_levels_when_requested() {
+        let root = test_root("preview-dir-depth");
+        fs::create_dir_all(root.join("child").join("deep")).expect("create nested dirs");
+        fs::write(root.join("a.txt"), "x").expect("write file");
+        fs::write(root.join("child").join("b.txt"), "y").expect("write nested file");
+        fs::write(root.join("child").join("deep").join("c.txt"), "z").expect("write deep file");
+
+        let preview =
+            build_preview_text_cancellable(&root, true, 20, 1024, 2, &|| false).expect("preview");
+        assert!(preview.contains("Scope: two levels"), "{preview}");
+        assert!(
+            preview.ends_with("[F] a.txt\n[D] child\n    [F] b.txt\n    [D] deep"),
+            "{preview}"
+        );
+        assert!(!preview.contains("c.txt"), "{preview}");
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn build_preview_text_for_file_contains_content_without_action_policy() {
         let root = test_root("preview-file");
@@ -419,6 +818,82 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn build_preview_text_cancellable_stops_file_reads_once_cancelled() {
+        let root = test_root("preview-cancel");
+        fs::create_dir_all(&root).expect("create dir");
+        let file = root.join("notes.txt");
+        fs::write(&file, "first\nsecond\n").expect("write file");
+
+        assert_eq!(
+            build_preview_text_cancellable(&file, false, 20, 1024, 1, &|| true),
+            None
+        );
+        let preview =
+            build_preview_text_cancellable(&file, false, 20, 1024, 1, &|| false).expect("preview");
+        assert!(preview.contains("second"), "{preview}");
+        assert!(build_preview_text_cancellable(&root, true, 20, 1024, 1, &|| true).is_some());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn build_size_skipped_preview_text_only_skips_files_over_threshold() {
+        let root = test_root("preview-size-skip");
+        fs::create_dir_all(&root).expect("create dir");
+        let file = root.join("large.log");
+        fs::write(&file, vec![b'x'; 2048]).expect("write file");
+
+        let skipped = build_size_skipped_preview_text(&file, false, 1024).expect("skipped");
+        assert!(skipped.starts_with("File: "), "{skipped}");
+        assert!(skipped.ends_with("(2.0 KiB, preview skipped)"), "{skipped}");
+        assert_eq!(build_size_skipped_preview_text(&file, false, 4096), None);
+        assert_eq!(build_size_skipped_preview_text(&root, true, 0), None);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn is_probably_binary_detects_nul_and_control_heavy_prefixes() {
+        let root = test_root("preview-binary-sniff");
+        fs::create_dir_all(&root).expect("create dir");
+        let text = root.join("notes.txt");
+        let nul = root.join("image.bin");
+        let control = root.join("blob.dat");
+        let utf16 = root.join("utf16.txt");
+        fs::write(&text, "plain text\n\twith tabs\n").expect("write text");
+        fs::write(&nul, b"PK\x03\x04\x00\x00data").expect("write nul");
+        fs::write(&control, [0x01u8, 0x02, 0x03, b'a', b'b']).expect("write control");
+        fs::write(&utf16, [0xFFu8, 0xFE, b'a', 0x00]).expect("write utf16");
+
+        assert!(!is_probably_binary(&text));
+        assert!(is_probably_binary(&nul));
+        assert!(is_probably_binary(&control));
+        assert!(!is_probably_binary(&utf16));
+        assert!(!is_probably_binary(&root.join("missing.bin")));
+        let skipped = build_binary_skipped_preview_text(&nul);
+        assert!(skipped.starts_with("File: "), "{skipped}");
+        assert!(skipped.ends_with("\n<binary>"), "{skipped}");
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn number_preview_lines_skips_header_and_pads_numbers() {
+        let preview = format!(
+            "File: /tmp/a.rs\nSize: 10 B\nUpdated: 2026-01-01 00:00:00\n{}",
+            (1..=10)
+                .map(|index| format!("line {index}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+
+        let numbered = number_preview_lines(&preview);
+        let lines = numbered.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "File: /tmp/a.rs");
+        assert_eq!(lines[2], "Updated: 2026-01-01 00:00:00");
+        assert_eq!(lines[3], " 1: line 1");
+        assert_eq!(lines[12], "10: line 10");
+        assert_eq!(number_preview_lines("Size: 1\nx"), "1: Size: 1\n2: x");
+    }
+
     #[test]
     fn build_preview_text_for_file_includes_updated_metadata() {
         let root = test_root("preview-updated");
@@ -475,6 +950,41 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn parse_unix_account_names_maps_ids_and_skips_malformed_lines() {
+        let names = parse_unix_account_names(
+            "# comment\nroot:x:0:0:root:/root:/bin/sh\nalice:x:1000:1000::/home/alice:/bin/sh\nbroken\nalias:x:1000:1000::/:/bin/sh\nbad:x:nan:0::/:/bin/sh\n",
+        );
+        assert_eq!(names.get(&0).map(String::as_str), Some("root"));
+        assert_eq!(names.get(&1000).map(String::as_str), Some("alice"));
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn append_metadata_details_inserts_details_after_header() {
+        let root = test_root("preview-metadata-details");
+        fs::create_dir_all(&root).expect("create dir");
+        let file = root.join("sample.txt");
+        fs::write(&file, "hello\n").expect("write file");
+
+        let preview = append_metadata_details(&file, &build_preview_text(&file));
+        assert!(preview.contains("\nPath: "), "{preview}");
+        assert!(preview.contains("\nExact Size: 6 bytes"), "{preview}");
+        #[cfg(unix)]
+        {
+            assert!(preview.contains("\nPermissions: "), "{preview}");
+            assert!(preview.contains("\nOwner: "), "{preview}");
+            assert!(preview.contains("uid "), "{preview}");
+            assert!(preview.contains("\nGroup: "), "{preview}");
+            assert!(preview.contains("gid "), "{preview}");
+        }
+        assert!(preview.ends_with("\nhello"), "{preview}");
+        let numbered = number_preview_lines(&preview);
+        assert!(numbered.ends_with("\n1: hello"), "{numbered}");
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn format_system_time_uses_compact_utc_format() {
         let timestamp = UNIX_EPOCH + Duration::from_secs(1_783_020_900);
@@ -581,6 +1091,23 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn build_preview_text_with_limits_reads_more_lines_than_default() {
+        let root = test_root("preview-large-limit");
+        fs::create_dir_all(&root).expect("create dir");
+        let file = root.join("long.txt");
+        let body = (1..=100)
+            .map(|n| format!("line-{n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        fs::write(&file, body).expect("write file");
+
+        let preview = build_preview_text_with_limits(&file, false, 200, 1024 * 1024);
+        assert!(preview.contains("line-100"));
+        assert!(!build_preview_text(&file).contains("line-100"));
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn build_preview_text_limits_lines() {
         let root = test_root("preview-limit-lines");
@@ -630,6 +1157,43 @@ mod tests {
         let _ = fs::remove_dir_all(&root);
     }
 
+    #[test]
+    fn build_preview_text_decodes_bom_text_cut_by_byte_limit() {
+        let root = test_root("preview-bom-truncated");
+        fs::create_dir_all(&root).expect("create dir");
+        let utf16le = root.join("utf16le.txt");
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "hello\r\n😀".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        fs::write(&utf16le, &bytes).expect("write utf16 file");
+        let utf16be = root.join("utf16be.txt");
+        let mut bytes = vec![0xFE, 0xFF];
+        for unit in "hello\r\nworld".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_be_bytes());
+        }
+        fs::write(&utf16be, &bytes).expect("write utf16 file");
+        let utf8 = root.join("utf8.txt");
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice("hello\n日本".as_bytes());
+        fs::write(&utf8, &bytes).expect("write utf8 file");
+
+        // Cut inside the surrogate pair, inside a code unit, and inside a multi-byte char.
+        for (file, max_bytes) in [
+            (&utf16le, 2 + 7 * 2 + 2),
+            (&utf16be, 2 + 9 * 2 + 1),
+            (&utf8, 3 + 6 + 4),
+        ] {
+            let preview = build_preview_text_with_limits(file, false, 20, max_bytes);
+            assert!(preview.contains("hello"), "{preview}");
+            assert!(
+                !preview.contains("<binary or unreadable file>"),
+                "{preview}"
+            );
+        }
+        let _ = fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn build_preview_text_keeps_binary_files_unreadable() {
         let root = test_root("preview-binary");
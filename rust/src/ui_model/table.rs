@@ -0,0 +1,134 @@
+use std::path::Path;
+
+use super::preview::preview_header_len;
+
+/// `.csv` は `,`、`.tsv` は tab を区切り文字として返す。それ以外の拡張子は `None`。
+pub fn delimited_table_separator(path: &Path) -> Option<char> {
+    let ext = path.extension()?.to_str()?;
+    if ext.eq_ignore_ascii_case("csv") {
+        Some(',')
+    } else if ext.eq_ignore_ascii_case("tsv") {
+        Some('\t')
+    } else {
+        None
+    }
+}
+
+/// 区切り文字で 1 行を field に分ける。`"` で囲まれた field 内の区切り文字と `""` を扱い、
+/// 引用符が閉じていない行は `None` を返す。
+fn split_delimited_row(line: &str, separator: char) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(ch),
+            }
+        } else if ch == '"' && field.is_empty() {
+            in_quotes = true;
+        } else if ch == separator {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(ch);
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+/// 区切り文字付きの行を列幅をそろえた表に整形する。解析できない行がある場合や、
+/// どの行も 1 列しかない場合は `None` を返し、呼び出し側は元の text を使う。
+pub fn format_delimited_table(lines: &[String], separator: char) -> Option<Vec<String>> {
+    let rows = lines
+        .iter()
+        .map(|line| split_delimited_row(line, separator))
+        .collect::<Option<Vec<_>>>()?;
+    if rows.iter().all(|row| row.len() < 2) {
+        return None;
+    }
+    let mut widths = Vec::new();
+    for row in &rows {
+        for (column, field) in row.iter().enumerate() {
+            let width = field.chars().count();
+            match widths.get_mut(column) {
+                Some(max) if *max < width => *max = width,
+                Some(_) => {}
+                None => widths.push(width),
+            }
+        }
+    }
+    Some(
+        rows.iter()
+            .map(|row| {
+                let cells = row
+                    .iter()
+                    .enumerate()
+                    .map(|(column, field)| format!("{field:<width$}", width = widths[column]))
+                    .collect::<Vec<_>>();
+                cells.join(" │ ").trim_end().to_string()
+            })
+            .collect(),
+    )
+}
+
+/// 表 preview の本文先頭 (列見出し) の行番号を返す。
+pub fn table_preview_header_row(preview: &str) -> usize {
+    preview_header_len(&preview.lines().collect::<Vec<_>>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn format_delimited_table_aligns_columns_and_handles_quoted_commas() {
+        let table = format_delimited_table(
+            &lines("name,city,qty\n\"Doe, Jane\",Tokyo,3\nBob,\"Say \"\"hi\"\"\",12"),
+            ',',
+        )
+        .expect("table");
+        assert_eq!(
+            table,
+            vec![
+                "name      │ city     │ qty",
+                "Doe, Jane │ Tokyo    │ 3",
+                "Bob       │ Say \"hi\" │ 12",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_delimited_table_falls_back_on_unterminated_quotes_or_single_column() {
+        assert!(format_delimited_table(&lines("a,\"b\nc,d"), ',').is_none());
+        assert!(format_delimited_table(&lines("just\ntext"), ',').is_none());
+        assert_eq!(
+            format_delimited_table(&lines("a\tb\nlong\tc"), '\t'),
+            Some(vec!["a    │ b".to_string(), "long │ c".to_string()])
+        );
+    }
+
+    #[test]
+    fn delimited_table_separator_detects_csv_and_tsv() {
+        assert_eq!(delimited_table_separator(Path::new("data.CSV")), Some(','));
+        assert_eq!(delimited_table_separator(Path::new("data.tsv")), Some('\t'));
+        assert_eq!(delimited_table_separator(Path::new("data.txt")), None);
+        assert_eq!(
+            table_preview_header_row("File: /tmp/a.csv\nSize: 1 B\nname │ qty\nx    │ 1"),
+            2
+        );
+    }
+}
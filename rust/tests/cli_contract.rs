@@ -90,6 +90,85 @@ fn cli_outputs_at_most_limit_lines_for_empty_query() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn cli_offset_pages_through_results_without_overlap() {
+    let root = test_root("offset");
+    fs::create_dir_all(&root).expect("create root");
+    for idx in 0..5 {
+        fs::write(root.join(format!("item-{idx}.txt")), "x").expect("write file");
+    }
+    let run = |query: &str, offset: &str, limit: &str| {
+        let output = cli_command("offset")
+            .args([
+                "--cli",
+                query,
+                "--root",
+                root.to_string_lossy().as_ref(),
+                "--offset",
+                offset,
+                "--limit",
+                limit,
+            ])
+            .output()
+            .expect("run cli");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    for query in ["", "item"] {
+        let all = run(query, "0", "5");
+        assert_eq!(all.len(), 5);
+        let mut paged = run(query, "0", "2");
+        paged.extend(run(query, "2", "2"));
+        paged.extend(run(query, "4", "2"));
+        assert_eq!(paged, all);
+        assert!(run(query, "5", "2").is_empty());
+    }
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn cli_diff_filelist_reports_added_and_removed_paths() {
+    let root = test_root("diff-filelist");
+    fs::create_dir_all(&root).expect("create root");
+    let old = root.join("old.txt");
+    let new = root.join("new.txt");
+    fs::write(&old, "kept.txt\nremoved.txt\n").expect("write old filelist");
+    fs::write(&new, "kept.txt\nadded.txt\n").expect("write new filelist");
+
+    let output = cli_command("diff-filelist")
+        .args([
+            "--cli",
+            "--root",
+            root.to_string_lossy().as_ref(),
+            "--diff-filelist",
+            old.to_string_lossy().as_ref(),
+            new.to_string_lossy().as_ref(),
+        ])
+        .output()
+        .expect("run cli");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        vec![
+            format!("+ {}", root.join("added.txt").display()),
+            format!("- {}", root.join("removed.txt").display()),
+        ]
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("diff: added=1, removed=1"));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn cli_does_not_cap_limit_to_1000() {
     let root = test_root("limit-over-1000");
@@ -176,6 +255,66 @@ fn cli_formats_scored_output_for_query() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[cfg(unix)]
+#[test]
+fn serve_answers_queries_over_unix_socket_until_sigint() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::{Duration, Instant};
+
+    let root = test_root("serve-root");
+    fs::create_dir_all(root.join("src")).expect("create root");
+    fs::write(root.join("src").join("main.rs"), "fn main() {}").expect("write main");
+    fs::write(root.join("readme.md"), "readme").expect("write readme");
+    let socket = test_root("serve-socket");
+
+    let mut child = cli_command("serve")
+        .args([
+            "--serve",
+            socket.to_string_lossy().as_ref(),
+            "--root",
+            root.to_string_lossy().as_ref(),
+        ])
+        .spawn()
+        .expect("spawn server");
+    let deadline = Instant::now() + Duration::from_secs(20);
+    let stream = loop {
+        match UnixStream::connect(&socket) {
+            Ok(stream) => break stream,
+            Err(_) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            Err(err) => {
+                let _ = child.kill();
+                panic!("server did not start listening: {err}");
+            }
+        }
+    };
+    let mut writer = stream.try_clone().expect("clone stream");
+    let mut reader = BufReader::new(stream);
+    let mut responses = Vec::new();
+    for query in ["main\n", "zzzz\n"] {
+        writer.write_all(query.as_bytes()).expect("send query");
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("read response");
+        responses.push(line);
+    }
+    drop(writer);
+    drop(reader);
+
+    let status = Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .expect("send SIGINT");
+    assert!(status.success());
+    let exit = child.wait().expect("wait server");
+
+    assert!(responses[0].starts_with("[{"));
+    assert!(responses[0].contains("main.rs"));
+    assert_eq!(responses[1].trim(), "[]");
+    assert!(exit.success());
+    assert!(!socket.exists());
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn cli_returns_empty_stdout_when_no_matches() {
     let root = test_root("no-match");
@@ -201,6 +340,38 @@ fn cli_returns_empty_stdout_when_no_matches() {
     let _ = fs::remove_dir_all(&root);
 }
 
+#[test]
+fn cli_stats_flag_reports_timings_on_stderr_and_keeps_stdout_clean() {
+    let root = test_root("stats");
+    fs::create_dir_all(&root).expect("create root");
+    fs::write(root.join("main.rs"), "fn main() {}").expect("write main");
+    fs::write(root.join("lib.rs"), "").expect("write lib");
+
+    let output = cli_command("stats")
+        .args([
+            "--cli",
+            "main",
+            "--stats",
+            "--root",
+            root.to_string_lossy().as_ref(),
+            "--limit",
+            "10",
+        ])
+        .output()
+        .expect("run cli");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1);
+    assert!(stdout.contains("main.rs"));
+    assert!(!stdout.contains("stats:"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("stats: indexed=2 in "));
+    assert!(stderr.contains("matched=1 in "));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
 #[test]
 fn cli_interprets_filelist_paths_for_current_platform() {
     let root = test_root("filelist-platform-interpretation");